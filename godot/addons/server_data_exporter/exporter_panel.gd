@@ -7,6 +7,7 @@ extends Control
 ##   2. Spawn points to server/spawn_points.json
 ##   3. Obstacles to server/obstacles.json
 ##   4. Spawn areas to server/spawn_areas.json
+##   5. Patrol paths to server/patrol_paths.json
 
 # Zone configuration - maps zone_id to scene path and empire name
 const ZONE_CONFIG = {
@@ -30,6 +31,7 @@ var _export_heightmaps_btn: Button
 var _export_spawns_btn: Button
 var _export_obstacles_btn: Button
 var _export_spawn_areas_btn: Button
+var _export_patrol_paths_btn: Button
 var _zone_checkboxes: Dictionary = {}
 
 # Export state
@@ -149,6 +151,12 @@ func _build_ui() -> void:
 	_export_spawn_areas_btn.pressed.connect(_on_export_spawn_areas_pressed)
 	btn_container2.add_child(_export_spawn_areas_btn)
 	
+	_export_patrol_paths_btn = Button.new()
+	_export_patrol_paths_btn.text = "Patrol Paths"
+	_export_patrol_paths_btn.size_flags_horizontal = Control.SIZE_EXPAND_FILL
+	_export_patrol_paths_btn.pressed.connect(_on_export_patrol_paths_pressed)
+	btn_container2.add_child(_export_patrol_paths_btn)
+	
 	vbox.add_child(_create_separator())
 	
 	# Progress bar
@@ -197,6 +205,8 @@ func _set_buttons_enabled(enabled: bool) -> void:
 		_export_obstacles_btn.disabled = not enabled
 	if _export_spawn_areas_btn:
 		_export_spawn_areas_btn.disabled = not enabled
+	if _export_patrol_paths_btn:
+		_export_patrol_paths_btn.disabled = not enabled
 
 
 func _set_status(text: String) -> void:
@@ -235,27 +245,33 @@ func _on_export_all_pressed() -> void:
 	await get_tree().process_frame
 	
 	# Step 1: Heightmaps (0-40%)
-	_append_status("[color=yellow]Step 1/4: Heightmaps[/color]\n")
+	_append_status("[color=yellow]Step 1/5: Heightmaps[/color]\n")
 	await _export_heightmaps(server_path, selected_zones)
 	
 	# Step 2: Spawn Points (40-55%)
 	_progress_bar.value = 40
-	_append_status("\n[color=yellow]Step 2/4: Spawn Points[/color]\n")
+	_append_status("\n[color=yellow]Step 2/5: Spawn Points[/color]\n")
 	await get_tree().process_frame
 	await _export_spawn_points(server_path, selected_zones)
 	
 	# Step 3: Obstacles (55-70%)
 	_progress_bar.value = 55
-	_append_status("\n[color=yellow]Step 3/4: Obstacles[/color]\n")
+	_append_status("\n[color=yellow]Step 3/5: Obstacles[/color]\n")
 	await get_tree().process_frame
 	await _export_obstacles(server_path, selected_zones)
 	
-	# Step 4: Spawn Areas (70-100%)
+	# Step 4: Spawn Areas (70-85%)
 	_progress_bar.value = 70
-	_append_status("\n[color=yellow]Step 4/4: Spawn Areas[/color]\n")
+	_append_status("\n[color=yellow]Step 4/5: Spawn Areas[/color]\n")
 	await get_tree().process_frame
 	await _export_spawn_areas(server_path, selected_zones)
 	
+	# Step 5: Patrol Paths (85-100%)
+	_progress_bar.value = 85
+	_append_status("\n[color=yellow]Step 5/5: Patrol Paths[/color]\n")
+	await get_tree().process_frame
+	await _export_patrol_paths(server_path, selected_zones)
+	
 	_progress_bar.value = 100
 	_append_status("\n[color=lime]=============================")
 	_append_status("\n        Export Complete!")
@@ -362,6 +378,29 @@ func _on_export_spawn_areas_pressed() -> void:
 	
 	_is_exporting = false
 	_set_buttons_enabled(true)
+
+
+func _on_export_patrol_paths_pressed() -> void:
+	if _is_exporting:
+		return
+	
+	var selected_zones := _get_selected_zones()
+	if selected_zones.is_empty():
+		_set_status("[color=red]No zones selected![/color]")
+		return
+	
+	_is_exporting = true
+	_set_buttons_enabled(false)
+	
+	_set_status("[color=cyan]Exporting patrol paths...[/color]\n\n")
+	await get_tree().process_frame
+	
+	await _export_patrol_paths(_get_server_path(), selected_zones)
+	
+	_append_status("\n[color=lime]Patrol paths export complete![/color]")
+	
+	_is_exporting = false
+	_set_buttons_enabled(true)
 #endregion
 
 
@@ -943,3 +982,86 @@ func _extract_spawn_areas(node: Node) -> Array:
 	
 	return spawn_areas
 #endregion
+
+
+#region Patrol Paths Export
+func _export_patrol_paths(server_path: String, zones: Array) -> void:
+	var all_zones = {}
+	
+	for zone_id in zones:
+		var config = ZONE_CONFIG[zone_id]
+		var scene_path: String = config.scene
+		
+		_append_status("  Zone %d... " % zone_id)
+		
+		var scene = load(scene_path)
+		if scene == null:
+			_append_status("[color=red]not found[/color]\n")
+			continue
+		
+		var root = scene.instantiate()
+		var patrol_paths = _extract_patrol_paths(root, Transform3D.IDENTITY)
+		root.queue_free()
+		
+		all_zones[str(zone_id)] = patrol_paths
+		_append_status("[color=lime]%d path(s)[/color]\n" % patrol_paths.size())
+	
+	# Save to JSON
+	var json_string = JSON.stringify(all_zones, "  ")
+	
+	# Save to godot project
+	var godot_path = "res://exported_patrol_paths.json"
+	var file = FileAccess.open(godot_path, FileAccess.WRITE)
+	if file:
+		file.store_string(json_string)
+		file.close()
+	
+	# Save to server
+	var server_file_path = server_path.path_join("patrol_paths.json")
+	var server_file = FileAccess.open(server_file_path, FileAccess.WRITE)
+	if server_file:
+		server_file.store_string(json_string)
+		server_file.close()
+		_append_status("  Saved to server/patrol_paths.json\n")
+
+
+## Path3D nodes named "PatrolPath..." become patrol routes.
+## Members and timing come from node metadata:
+##   enemies      - comma-separated enemy template ids, front to back
+##   looped       - walk back to the start after the last point (default true)
+##   pause        - seconds to stand at each point (default 0)
+##   respawn_time - seconds before a dead member respawns (default 60)
+func _extract_patrol_paths(node: Node, parent_transform: Transform3D) -> Array:
+	var patrol_paths = []
+	
+	var global_transform = parent_transform
+	if node is Node3D:
+		global_transform = parent_transform * node.transform
+	
+	if node is Path3D and "PatrolPath" in (node.name as String) and node.curve != null:
+		var waypoints = []
+		for i in node.curve.point_count:
+			var pos: Vector3 = global_transform * node.curve.get_point_position(i)
+			waypoints.append([pos.x, pos.y, pos.z])
+		
+		var enemies = []
+		for template_id in str(node.get_meta("enemies", "")).split(",", false):
+			enemies.append(template_id.strip_edges())
+		
+		if waypoints.is_empty() or enemies.is_empty():
+			push_warning("Patrol path %s needs points and an 'enemies' meta" % node.name)
+		else:
+			patrol_paths.append({
+				"name": (node.name as String).replace("PatrolPath", "").strip_edges(),
+				"waypoints": waypoints,
+				"looped": node.get_meta("looped", true),
+				"pause": float(node.get_meta("pause", 0.0)),
+				"enemies": enemies,
+				"respawn_time": float(node.get_meta("respawn_time", 60.0)),
+			})
+	
+	for child in node.get_children():
+		patrol_paths.append_array(_extract_patrol_paths(child, global_transform))
+	
+	return patrol_paths
+#endregion
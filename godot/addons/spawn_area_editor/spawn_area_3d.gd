@@ -174,6 +174,8 @@ func to_dict() -> Dictionary:
 				"weight": config.spawn_weight,
 				"min_level": config.min_level,
 				"max_level": config.max_level,
+				"time_of_day": config.get_time_of_day_names(),
+				"weather": config.get_weather_names(),
 			})
 	
 	# Convert polygon vertices to global coordinates
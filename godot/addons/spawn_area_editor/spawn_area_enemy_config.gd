@@ -7,8 +7,12 @@ extends Resource
 ## and the level range for spawned enemies.
 
 ## Enemy type to spawn
-## 0 = Goblin, 1 = Wolf, 2 = Skeleton, 3 = Mutant
-@export_enum("Goblin:0", "Wolf:1", "Skeleton:2", "Mutant:3") var enemy_type: int = 0
+## 0 = Goblin, 1 = Wolf, 2 = Skeleton, 3 = Mutant, 4 = Skeleton Archer, 5 = Goblin Shaman
+@export_enum("Goblin:0", "Wolf:1", "Skeleton:2", "Mutant:3", "Skeleton Archer:4", "Goblin Shaman:5") var enemy_type: int = 0
+
+## Enemy template id from the server's enemy_templates.json.
+## Overrides enemy_type when set (for mobs without a built-in entry above).
+@export var template_id: String = ""
 
 ## Relative spawn weight. Higher values = more likely to spawn this type.
 ## Example: weight=3 means 3x more likely than weight=1
@@ -20,6 +24,15 @@ extends Resource
 ## Maximum level for spawned enemies of this type
 @export_range(1, 100) var max_level: int = 5
 
+## Times of day this enemy spawns at (none ticked = any time)
+@export_flags("Dawn", "Day", "Dusk", "Night") var time_of_day: int = 0
+
+## Weather this enemy spawns in (none ticked = any weather)
+@export_flags("Clear", "Rain", "Fog", "Storm") var weather: int = 0
+
+const TIME_OF_DAY_NAMES := ["Dawn", "Day", "Dusk", "Night"]
+const WEATHER_NAMES := ["Clear", "Rain", "Fog", "Storm"]
+
 
 func _init() -> void:
 	resource_name = "EnemyConfig"
@@ -27,14 +40,36 @@ func _init() -> void:
 
 ## Get the enemy type as a string for export
 func get_enemy_type_string() -> String:
+	if template_id != "":
+		return template_id
 	match enemy_type:
 		0: return "Goblin"
 		1: return "Wolf"
 		2: return "Skeleton"
 		3: return "Mutant"
+		4: return "SkeletonArcher"
+		5: return "GoblinShaman"
 		_: return "Goblin"
 
 
+## Get the ticked times of day as names for export
+func get_time_of_day_names() -> Array:
+	return _flag_names(time_of_day, TIME_OF_DAY_NAMES)
+
+
+## Get the ticked weather states as names for export
+func get_weather_names() -> Array:
+	return _flag_names(weather, WEATHER_NAMES)
+
+
+func _flag_names(flags: int, names: Array) -> Array:
+	var result := []
+	for i in names.size():
+		if flags & (1 << i):
+			result.append(names[i])
+	return result
+
+
 ## Validate that min_level <= max_level
 func _validate_property(property: Dictionary) -> void:
 	if property.name == "max_level" and max_level < min_level:
@@ -38,12 +38,21 @@ var attack_cooldown: float = 0.0
 ## Whether we're moving to attack (out of range, moving closer)
 var is_moving_to_attack: bool = false
 
-## Pending attack target ID (attack in progress, waiting for hit point)
-var pending_attack_target_id: int = -1
+## Whether the server's swing timer is running on our target
+var server_swinging: bool = false
+
+## Whether an attack was sent and we're waiting for the server to start swinging
+var attack_requested: bool = false
+
+## Seconds between swings, as reported by the server
+var swing_interval: float = BASE_ATTACK_DURATION
 
 ## Attack range
 const ATTACK_RANGE: float = 3.0
 
+## Attack range with a bow (server allows 15)
+const RANGED_ATTACK_RANGE: float = 14.0
+
 ## Base attack animation duration
 const BASE_ATTACK_DURATION: float = 1.53
 
@@ -55,6 +64,12 @@ func _ready() -> void:
 		push_warning("CombatController: Could not find player")
 		return
 	
+	# The server owns the swing timer; swing animations follow its start/stop
+	if player.has_signal("auto_attack_started"):
+		player.auto_attack_started.connect(_on_auto_attack_started)
+	if player.has_signal("auto_attack_stopped"):
+		player.auto_attack_stopped.connect(_on_auto_attack_stopped)
+	
 	# Find animation controller
 	await get_tree().process_frame
 	_find_animation_controller()
@@ -81,9 +96,6 @@ func _find_animation_controller() -> void:
 			if animation_controller.has_signal("attack_finished"):
 				if not animation_controller.attack_finished.is_connected(_on_attack_animation_finished):
 					animation_controller.attack_finished.connect(_on_attack_animation_finished)
-			if animation_controller.has_signal("attack_hit"):
-				if not animation_controller.attack_hit.is_connected(_on_attack_hit):
-					animation_controller.attack_hit.connect(_on_attack_hit)
 		else:
 			print("CombatController: CharacterModel found but no AnimationController inside")
 	else:
@@ -120,12 +132,10 @@ func _process(delta: float) -> void:
 	if player and player.has_method("is_player_dead") and player.is_player_dead():
 		return
 	
-	# Update cooldown
+	# Update cooldown (mirrors the server's swing timer)
 	if attack_cooldown > 0:
 		attack_cooldown -= delta
-		var attack_speed := _get_attack_speed()
-		var total_cooldown := BASE_ATTACK_DURATION / attack_speed
-		var progress := 1.0 - (attack_cooldown / total_cooldown)
+		var progress := 1.0 - (attack_cooldown / swing_interval)
 		emit_signal("attack_cooldown_updated", clamp(progress, 0.0, 1.0))
 	
 	# Process auto-attack
@@ -140,8 +150,7 @@ func _process(delta: float) -> void:
 ## Start attacking a target (called when left-clicking an enemy)
 func attack_enemy(target_id: int, target_node: Node3D) -> void:
 	print("CombatController: attack_enemy called - target_id=%d" % target_id)
-	attack_target_id = target_id
-	attack_target_node = target_node
+	set_target(target_id, target_node)
 	
 	# Ensure we have a valid animation controller
 	_ensure_animation_controller()
@@ -170,18 +179,21 @@ func start_auto_attack() -> void:
 	auto_attack_active = true
 	emit_signal("auto_attack_changed", true)
 	
-	# Try to attack immediately if cooldown is ready
-	if attack_cooldown <= 0:
-		_perform_attack()
+	_request_attack()
 
 
 ## Stop auto-attack
 func stop_auto_attack() -> void:
+	# Server owns the swing timer - tell it to stop swinging
+	if auto_attack_active and player and player.has_method("stop_attack"):
+		player.stop_attack()
+
 	auto_attack_active = false
 	is_moving_to_attack = false
+	server_swinging = false
+	attack_requested = false
 	attack_target_id = -1
 	attack_target_node = null
-	pending_attack_target_id = -1  # Clear pending attack
 	
 	# Cancel click movement if we were moving to attack
 	if click_movement_controller:
@@ -208,6 +220,9 @@ func is_auto_attacking() -> bool:
 
 ## Set attack target without starting attack
 func set_target(target_id: int, target_node: Node3D) -> void:
+	if target_id != attack_target_id:
+		server_swinging = false
+		attack_requested = false
 	attack_target_id = target_id
 	attack_target_node = target_node
 
@@ -232,9 +247,11 @@ func _process_auto_attack() -> void:
 		_move_toward_target()
 		return
 	
-	# In range and cooldown ready - attack
-	if attack_cooldown <= 0:
-		_perform_attack()
+	# In range - ask the server to swing, then play swings on its timer
+	if not server_swinging:
+		_request_attack()
+	elif attack_cooldown <= 0:
+		_play_swing()
 
 
 ## Process move-to-attack logic
@@ -249,9 +266,7 @@ func _process_move_to_attack() -> void:
 		# Cancel click movement if active
 		if click_movement_controller:
 			click_movement_controller.cancel_movement()
-		# Attack immediately
-		if attack_cooldown <= 0:
-			_perform_attack()
+		_request_attack()
 	else:
 		# Keep moving toward target
 		_move_toward_target()
@@ -269,61 +284,57 @@ func _move_toward_target() -> void:
 		var direction: Vector3 = (target_pos - player_pos).normalized()
 		
 		# Move to a position within attack range
-		var destination: Vector3 = target_pos - direction * (ATTACK_RANGE - 0.5)
+		var destination: Vector3 = target_pos - direction * (_get_attack_range() - 0.5)
 		click_movement_controller.move_to(destination)
 
 
-## Perform a single attack
-func _perform_attack() -> void:
-	print("CombatController: _perform_attack called")
+## Ask the server to start swinging at the target
+## The server confirms with auto_attack_started, re-sending the same target is harmless
+func _request_attack() -> void:
+	if attack_requested or server_swinging:
+		return
 	
 	if attack_target_id == -1 or not player:
-		print("CombatController: No target or player, aborting attack")
 		return
 	
 	# Verify target is valid
 	if attack_target_node == null or not is_instance_valid(attack_target_node):
-		print("CombatController: Target node invalid, stopping auto-attack")
 		stop_auto_attack()
 		return
 	
 	# Verify range
 	if not _is_in_attack_range():
-		print("CombatController: Target out of range, moving to attack")
-		# Start moving to target
 		is_moving_to_attack = true
 		_move_toward_target()
 		return
 	
-	# Face the target
 	_face_target()
 	
-	# Get attack speed
-	var attack_speed := _get_attack_speed()
-	print("CombatController: Attack speed = %.2f" % attack_speed)
+	if player.has_method("attack_target"):
+		player.attack_target(attack_target_id)
+		attack_requested = true
+
+
+## Play one swing animation, timed to the server's swing interval
+func _play_swing() -> void:
+	if attack_target_node == null or not is_instance_valid(attack_target_node):
+		return
 	
-	# Store pending attack target (damage will be dealt when animation hits)
-	pending_attack_target_id = attack_target_id
+	_face_target()
+	attack_cooldown = swing_interval
 	
-	# Ensure animation controller is available (re-find if necessary)
 	if not _ensure_animation_controller():
-		push_warning("CombatController: Cannot attack - no animation controller")
+		push_warning("CombatController: Cannot swing - no animation controller")
 		return
 	
-	# Play attack animation (this will trigger attack_hit signal at hit point)
+	# Stretch the animation to fill exactly one swing
 	if animation_controller.has_method("play_attack_animation"):
-		print("CombatController: Playing attack animation")
-		animation_controller.play_attack_animation(attack_speed)
-	else:
-		push_warning("CombatController: AnimationController missing play_attack_animation method")
+		animation_controller.play_attack_animation(BASE_ATTACK_DURATION / swing_interval)
 	
 	# Set animation state on player (for network sync)
 	if player.has_method("set_animation_state"):
 		player.set_animation_state(4)  # 4 = Attacking
 	
-	# Start cooldown
-	attack_cooldown = BASE_ATTACK_DURATION / attack_speed
-	
 	emit_signal("attack_performed", attack_target_id)
 
 
@@ -348,50 +359,53 @@ func _is_in_attack_range() -> bool:
 		return false
 	
 	var distance: float = player.global_position.distance_to(attack_target_node.global_position)
-	return distance <= ATTACK_RANGE
+	return distance <= _get_attack_range()
 
 
-## Get attack speed from player
-func _get_attack_speed() -> float:
-	if player and player.has_method("get_attack_speed"):
-		return player.get_attack_speed()
-	return 1.0
+## Get attack range for the equipped weapon
+func _get_attack_range() -> float:
+	if player and player.has_method("has_ranged_weapon") and player.has_ranged_weapon():
+		return RANGED_ATTACK_RANGE
+	return ATTACK_RANGE
 
 
-## Called when attack animation reaches hit point (damage should be dealt now)
-func _on_attack_hit() -> void:
-	print("CombatController: _on_attack_hit signal received")
-	
-	# Verify we have a pending attack
-	if pending_attack_target_id == -1:
-		print("CombatController: No pending attack target")
+## Called when the server starts swinging (swings once right away if its timer is ready)
+func _on_auto_attack_started(target_id: int, interval: float) -> void:
+	if target_id != attack_target_id:
 		return
 	
-	# Verify target is still valid
-	if attack_target_node == null or not is_instance_valid(attack_target_node):
-		print("CombatController: Target node no longer valid")
-		pending_attack_target_id = -1
-		return
+	attack_requested = false
+	server_swinging = true
+	swing_interval = max(interval, 0.1)
 	
-	# Verify still in range (target might have moved)
-	if not _is_in_attack_range():
-		print("CombatController: Target moved out of range")
-		pending_attack_target_id = -1
+	if attack_cooldown <= 0:
+		_play_swing()
+
+
+## Called when the server stops swinging (target died, out of range, stopped...)
+func _on_auto_attack_stopped(target_id: int, reason: String) -> void:
+	if target_id != attack_target_id:
 		return
 	
-	# Send attack to server NOW (at the hit point of the animation)
-	if player.has_method("attack_target"):
-		print("CombatController: Sending attack to server for target %d" % pending_attack_target_id)
-		player.attack_target(pending_attack_target_id)
+	server_swinging = false
+	attack_requested = false
+	cancel_current_attack()
+	
+	# Keep chasing a target that only got away; ask again once in range
+	if auto_attack_active and (reason == "Out of range" or reason == "No line of sight"):
+		is_moving_to_attack = true
+		_move_toward_target()
+		return
 	
-	pending_attack_target_id = -1
+	# The server already stopped, so don't send a stop back
+	auto_attack_active = false
+	stop_auto_attack()
+	if reason != "Stopped":
+		emit_signal("combat_message", reason, "info")
 
 
 ## Called when attack animation finishes
 func _on_attack_animation_finished() -> void:
-	# Clear any pending attack that didn't fire
-	pending_attack_target_id = -1
-	
 	# Reset animation state to idle (will be overridden by movement if moving)
 	if player.has_method("set_animation_state"):
 		player.set_animation_state(0)  # 0 = Idle
@@ -402,5 +416,4 @@ func _on_destination_reached() -> void:
 	# If we were moving to attack, check if we can attack now
 	if is_moving_to_attack and _is_in_attack_range():
 		is_moving_to_attack = false
-		if attack_cooldown <= 0:
-			_perform_attack()
+		_request_attack()
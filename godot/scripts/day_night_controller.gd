@@ -6,6 +6,12 @@ class_name DayNightController
 ## Signal emitted when time of day changes significantly
 signal time_of_day_changed(time_name: String, sun_elevation: float)
 
+## Signal emitted when the server reports different weather
+signal weather_changed(weather_name: String)
+
+## Weather names by server id (mmo_shared::Weather)
+const WEATHER_NAMES := ["Clear", "Rain", "Fog", "Storm"]
+
 ## Berlin, Germany coordinates (default, overridden by server)
 var latitude: float = 52.5
 var longitude: float = 13.4
@@ -13,6 +19,12 @@ var longitude: float = 13.4
 ## Current server Unix timestamp (UTC)
 var server_timestamp: int = 0
 
+## Local system time when the last sync arrived
+var sync_local_time: float = 0.0
+
+## Current weather (index into WEATHER_NAMES)
+var weather: int = 0
+
 ## Local timestamp offset (for smooth interpolation)
 var local_time_offset: float = 0.0
 
@@ -420,12 +432,17 @@ func _exit_tree() -> void:
 
 
 ## Called when server sends time sync
-func on_time_sync(unix_timestamp: int, server_latitude: float, server_longitude: float) -> void:
+func on_time_sync(unix_timestamp: int, server_latitude: float, server_longitude: float, server_weather: int = 0) -> void:
 	server_timestamp = unix_timestamp
+	sync_local_time = Time.get_unix_time_from_system()
 	latitude = server_latitude
 	longitude = server_longitude
 	local_time_offset = 0.0
 	
+	if server_weather != weather:
+		weather = server_weather
+		emit_signal("weather_changed", get_weather_name())
+	
 	# Immediately update calculations
 	_update_celestial_positions()
 
@@ -439,7 +456,14 @@ func set_time_acceleration(enabled: bool) -> void:
 
 ## Get current game time as Unix timestamp
 func get_current_timestamp() -> int:
-	return server_timestamp + int(local_time_offset) + int(Time.get_unix_time_from_system()) - server_timestamp
+	return server_timestamp + int(local_time_offset + Time.get_unix_time_from_system() - sync_local_time)
+
+
+## Get the current weather name
+func get_weather_name() -> String:
+	if weather >= 0 and weather < WEATHER_NAMES.size():
+		return WEATHER_NAMES[weather]
+	return "Clear"
 
 
 ## Get current game time as a formatted string (HH:MM)
@@ -451,8 +475,9 @@ func get_time_string() -> String:
 
 ## Get the effective timestamp accounting for acceleration
 func _get_effective_timestamp() -> int:
+	# The server clock may run ahead of or behind this machine's clock
 	var base_time = server_timestamp
-	var elapsed_since_sync = Time.get_unix_time_from_system() - server_timestamp
+	var elapsed_since_sync = Time.get_unix_time_from_system() - sync_local_time
 	
 	if time_acceleration_enabled:
 		# Apply time multiplier to elapsed time
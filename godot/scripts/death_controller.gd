@@ -45,6 +45,9 @@ func _ready() -> void:
 		player.player_respawned.connect(_on_player_respawned)
 	else:
 		push_warning("DeathController: Player node does not have player_respawned signal")
+	
+	if player.has_signal("death_penalty"):
+		player.death_penalty.connect(_on_death_penalty)
 
 
 ## Find the Player node (CharacterBody3D with player script) in parent hierarchy
@@ -103,6 +106,12 @@ func _on_player_died() -> void:
 	death_dialog.show_dialog()
 
 
+## Called when the server reports the death penalty (arrives right after death)
+func _on_death_penalty(experience_lost: int, durability: int, auto_release_in: float) -> void:
+	if death_dialog:
+		death_dialog.set_penalty(experience_lost, durability, auto_release_in)
+
+
 ## Called when player chooses to respawn at empire spawn
 func _on_respawn_at_spawn() -> void:
 	print("DeathController: Requesting respawn at empire spawn")
@@ -69,8 +69,8 @@ func _process(delta: float) -> void:
 			look_at(global_position - direction.normalized(), Vector3.UP)
 
 
-## Show damage number at position
-func show_damage(world_position: Vector3, amount: int, is_critical: bool = false) -> void:
+## Show damage number at position (suffix is appended, e.g. " (Blocked)")
+func show_damage(world_position: Vector3, amount: int, is_critical: bool = false, suffix: String = "") -> void:
 	start_position = world_position
 	position = world_position
 	
@@ -89,6 +89,7 @@ func show_damage(world_position: Vector3, amount: int, is_critical: bool = false
 		else:
 			label.modulate = normal_color
 			label.font_size = 32
+		label.text += suffix
 		label.modulate.a = 1.0
 	
 	# Reset and start animation
@@ -117,6 +118,26 @@ func show_heal(world_position: Vector3, amount: int) -> void:
 	visible = true
 
 
+## Show text instead of a number (e.g. "Miss", "Dodge")
+func show_text(world_position: Vector3, text: String) -> void:
+	start_position = world_position
+	position = world_position
+	
+	var random_x = randf_range(-horizontal_spread, horizontal_spread)
+	var random_z = randf_range(-horizontal_spread, horizontal_spread)
+	target_offset = Vector3(random_x, rise_distance, random_z)
+	
+	if label:
+		label.text = text
+		label.modulate = normal_color
+		label.font_size = 28
+		label.modulate.a = 1.0
+	
+	elapsed = 0.0
+	is_active = true
+	visible = true
+
+
 ## Check if this damage number is currently animating
 func is_animating() -> bool:
 	return is_active
@@ -0,0 +1,84 @@
+extends Node3D
+class_name ProjectileVisual
+## Client-side projectile (arrow or spell bolt). The server decides hits;
+## this just flies along the server's path until the impact message arrives.
+
+## Arrow color (weapon attacks)
+const COLOR_ARROW := Color(0.55, 0.4, 0.25)
+
+## Spell color (ability projectiles)
+const COLOR_SPELL := Color(0.5, 0.7, 1.0)
+
+## Safety lifetime in case the impact message is lost
+const MAX_LIFETIME: float = 5.0
+
+## Flight velocity (units/sec)
+var velocity: Vector3 = Vector3.ZERO
+
+## Node to follow when homing (null = fly straight)
+var target_node: Node3D = null
+
+## Height offset of the target's center
+var target_height: float = 1.0
+
+var elapsed: float = 0.0
+
+
+## Set up the projectile. `ability_id` 0 means a weapon attack.
+func setup(start_position: Vector3, start_velocity: Vector3, ability_id: int, homing_target: Node3D) -> void:
+	velocity = start_velocity
+	target_node = homing_target
+
+	var mesh_instance = MeshInstance3D.new()
+	var material = StandardMaterial3D.new()
+	if ability_id == 0:
+		var arrow_mesh = CylinderMesh.new()
+		arrow_mesh.top_radius = 0.02
+		arrow_mesh.bottom_radius = 0.02
+		arrow_mesh.height = 0.7
+		mesh_instance.mesh = arrow_mesh
+		# Cylinders point along Y - lay it along the flight direction (-Z after look_at)
+		mesh_instance.rotation.x = PI / 2
+		material.albedo_color = COLOR_ARROW
+	else:
+		var bolt_mesh = SphereMesh.new()
+		bolt_mesh.radius = 0.15
+		bolt_mesh.height = 0.3
+		mesh_instance.mesh = bolt_mesh
+		material.albedo_color = COLOR_SPELL
+		material.emission_enabled = true
+		material.emission = COLOR_SPELL
+		material.emission_energy_multiplier = 2.0
+	mesh_instance.material_override = material
+	add_child(mesh_instance)
+
+	global_position = start_position
+	_face_velocity()
+
+
+func _process(delta: float) -> void:
+	elapsed += delta
+	if elapsed > MAX_LIFETIME:
+		queue_free()
+		return
+
+	# Homing projectiles re-aim at their target every frame
+	if target_node and is_instance_valid(target_node):
+		var aim: Vector3 = target_node.global_position + Vector3(0, target_height, 0)
+		var to_target: Vector3 = aim - global_position
+		if to_target.length_squared() > 0.01:
+			velocity = to_target.normalized() * velocity.length()
+
+	global_position += velocity * delta
+	_face_velocity()
+
+
+## Called when the server reports the impact
+func impact(impact_position: Vector3) -> void:
+	global_position = impact_position
+	queue_free()
+
+
+func _face_velocity() -> void:
+	if velocity.length_squared() > 0.001:
+		look_at(global_position + velocity, Vector3.UP)
@@ -63,6 +63,9 @@ var npcs: Dictionary = {}
 ## Dictionary of world items by entity ID
 var world_items: Dictionary = {}
 
+## Dictionary of projectiles in flight by ID
+var projectiles: Dictionary = {}
+
 ## Damage number pool
 var damage_number_pool: Array[DamageNumber] = []
 const DAMAGE_NUMBER_POOL_SIZE: int = 20
@@ -157,6 +160,26 @@ func _ready() -> void:
 		if local_player.has_signal("time_sync"):
 			local_player.connect("time_sync", _on_time_sync)
 		
+		# Connect enemy ability and heal signals
+		if local_player.has_signal("enemy_ability_used"):
+			local_player.connect("enemy_ability_used", _on_enemy_ability_used)
+		if local_player.has_signal("heal_received"):
+			local_player.connect("heal_received", _on_heal_received)
+		
+		# Connect Metin stone signals (stones live in the enemies dictionary for targeting)
+		if local_player.has_signal("metin_stone_spawned"):
+			local_player.connect("metin_stone_spawned", _on_metin_stone_spawned)
+		if local_player.has_signal("metin_stone_state"):
+			local_player.connect("metin_stone_state", _on_metin_stone_state)
+		if local_player.has_signal("metin_stone_despawned"):
+			local_player.connect("metin_stone_despawned", _on_metin_stone_despawned)
+		
+		# Connect projectile signals
+		if local_player.has_signal("projectile_spawned"):
+			local_player.connect("projectile_spawned", _on_projectile_spawned)
+		if local_player.has_signal("projectile_impact"):
+			local_player.connect("projectile_impact", _on_projectile_impact)
+		
 		# Connect zone change signal to clear entities
 		if local_player.has_signal("zone_change"):
 			local_player.connect("zone_change", _on_zone_change)
@@ -567,11 +590,12 @@ func _remove_remote_player(id: int) -> void:
 		remote_players.erase(id)
 
 
-func _on_enemy_spawned(id: int, enemy_type: int, position: Vector3, health: int, max_health: int, level: int = 1) -> void:
+func _on_enemy_spawned(id: int, enemy_type: int, position: Vector3, health: int, max_health: int, level: int = 1, display_name: String = "") -> void:
 	if enemies.has(id):
 		return
 	
-	var enemy_name = _get_enemy_name(enemy_type)
+	# Templates name their mobs; fall back to the model name
+	var enemy_name = display_name if display_name != "" else _get_enemy_name(enemy_type)
 	print("GameManager: Spawning ", enemy_name, " Lv.", level, " (ID: ", id, ") at ", position)
 	
 	var enemy = _create_enemy(enemy_type)
@@ -616,12 +640,33 @@ func _on_entity_died(entity_id: int, _killer_id: int) -> void:
 	print("GameManager: Entity ", entity_id, " died")
 
 
+## Show the name of an enemy's ability above it
+func _on_enemy_ability_used(enemy_id: int, ability_name: String, _target_id: int) -> void:
+	if not enemies.has(enemy_id):
+		return
+	var caster_pos: Vector3 = enemies[enemy_id]["node"].global_position + Vector3(0, 2.2, 0)
+	_get_damage_number().show_text(caster_pos, ability_name)
+
+
+## Update enemy health bars and show heal numbers for enemies healed by their allies
+func _on_heal_received(_healer_id: int, target_id: int, amount: int, new_health: int) -> void:
+	if not enemies.has(target_id):
+		return
+	var enemy_data = enemies[target_id]
+	enemy_data["health"] = new_health
+	if enemy_data.has("health_bar"):
+		enemy_data["health_bar"].set_health(new_health, enemy_data["max_health"])
+	_get_damage_number().show_heal(enemy_data["node"].global_position + Vector3(0, 1.0, 0), amount)
+
+
 func _get_enemy_name(enemy_type: int) -> String:
 	match enemy_type:
 		0: return "Goblin"
 		1: return "Skeleton"
 		2: return "Mutant"
 		3: return "Wolf"
+		4: return "Skeleton Archer"
+		5: return "Goblin Shaman"
 		_: return "Enemy"
 
 
@@ -711,6 +756,104 @@ func _create_enemy_placeholder(enemy_type: int) -> Node3D:
 	return enemy
 
 
+# =============================================================================
+# Metin Stones
+# =============================================================================
+
+## Stone colour while idle and while calling enemies
+const METIN_STONE_COLOR := Color(0.15, 0.12, 0.2)
+const METIN_STONE_GLOW := Color(0.7, 0.2, 1.0)
+
+
+func _on_metin_stone_spawned(id: int, stone_name: String, position: Vector3, health: int, max_health: int, level: int) -> void:
+	if enemies.has(id):
+		return
+	print("GameManager: Spawning ", stone_name, " Lv.", level, " (ID: ", id, ") at ", position)
+	
+	var stone = _create_metin_stone()
+	
+	var health_bar = WorldHealthBarScene.instantiate() as WorldHealthBar
+	health_bar.position = Vector3(0, 3.2, 0)
+	health_bar.set_entity_name(stone_name + " Lv." + str(level))
+	health_bar.set_health(health, max_health)
+	stone.add_child(health_bar)
+	
+	if enemies_container:
+		enemies_container.add_child(stone)
+	else:
+		add_child(stone)
+	stone.global_position = position
+	
+	# Stones never move, so no interpolation targets
+	enemies[id] = {
+		"id": id,
+		"node": stone,
+		"type": -1,
+		"name": stone_name,
+		"level": level,
+		"health": health,
+		"max_health": max_health,
+		"health_bar": health_bar,
+		"is_metin_stone": true
+	}
+
+
+func _on_metin_stone_state(id: int, health: int, max_health: int, under_attack: bool) -> void:
+	if not enemies.has(id):
+		return
+	var stone_data = enemies[id]
+	stone_data["health"] = health
+	stone_data["max_health"] = max_health
+	stone_data["health_bar"].set_health(health, max_health)
+	
+	# Glow while the stone is calling enemies
+	var stone_node = stone_data["node"] as Node3D
+	if stone_node.has_meta("stone_material"):
+		var material = stone_node.get_meta("stone_material") as StandardMaterial3D
+		material.emission_enabled = under_attack
+		material.emission = METIN_STONE_GLOW
+
+
+func _on_metin_stone_despawned(id: int, destroyed: bool) -> void:
+	print("GameManager: Metin stone ", id, " destroyed" if destroyed else " removed")
+	_remove_enemy(id)
+
+
+## Tall six-sided stone with a collider for click targeting
+func _create_metin_stone() -> Node3D:
+	var stone = StaticBody3D.new()
+	
+	var mesh_instance = MeshInstance3D.new()
+	var mesh = CylinderMesh.new()
+	mesh.top_radius = 0.6
+	mesh.bottom_radius = 0.9
+	mesh.height = 2.8
+	mesh.radial_segments = 6
+	mesh_instance.mesh = mesh
+	mesh_instance.position.y = 1.4
+	
+	var material = StandardMaterial3D.new()
+	material.albedo_color = METIN_STONE_COLOR
+	material.roughness = 0.4
+	material.emission_energy_multiplier = 1.5
+	mesh_instance.material_override = material
+	stone.add_child(mesh_instance)
+	stone.set_meta("stone_material", material)
+	
+	var collision = CollisionShape3D.new()
+	var shape = CylinderShape3D.new()
+	shape.radius = 0.9
+	shape.height = 2.8
+	collision.shape = shape
+	collision.position.y = 1.4
+	stone.add_child(collision)
+	
+	# Same layer as enemies so the minimap excludes it
+	_set_visual_layer_recursive(stone, 2)
+	
+	return stone
+
+
 func _remove_enemy(id: int) -> void:
 	if enemies.has(id):
 		var enemy_data = enemies[id]
@@ -886,7 +1029,7 @@ func _on_chat_received(sender_name: String, content: String) -> void:
 	# Chat UI handles this via its own signal connection
 
 
-func _on_damage_dealt(attacker_id: int, target_id: int, damage: int, is_critical: bool) -> void:
+func _on_damage_dealt(attacker_id: int, target_id: int, damage: int, is_critical: bool, outcome: int = 0) -> void:
 	var crit_str = " (CRIT!)" if is_critical else ""
 	print("Damage: ", attacker_id, " -> ", target_id, " for ", damage, crit_str)
 	
@@ -924,10 +1067,20 @@ func _on_damage_dealt(attacker_id: int, target_id: int, damage: int, is_critical
 		target_found = true
 		# TODO: Update player HUD health
 	
-	# Spawn damage number
+	# Spawn damage number (or miss/dodge text)
 	if target_found:
 		var damage_number = _get_damage_number()
-		damage_number.show_damage(target_pos, damage, is_critical)
+		match outcome:
+			2:
+				damage_number.show_text(target_pos, "Miss")
+			3:
+				damage_number.show_text(target_pos, "Dodge")
+			4:
+				damage_number.show_damage(target_pos, damage, false, " (Blocked)")
+			5:
+				damage_number.show_text(target_pos, "Immune")
+			_:
+				damage_number.show_damage(target_pos, damage, is_critical)
 
 
 ## Update remote player position from world state (with interpolation)
@@ -1061,6 +1214,31 @@ func _on_player_state_updated(id: int, position: Vector3, rotation: float, healt
 # =============================================================================
 
 ## Handle zone change - clear all remote entities since they're in a different zone now
+func _on_projectile_spawned(id: int, _owner_id: int, target_id: int, ability_id: int, position: Vector3, velocity: Vector3, homing: bool) -> void:
+	var target_node: Node3D = null
+	if homing:
+		if enemies.has(target_id):
+			target_node = enemies[target_id]["node"]
+		elif remote_players.has(target_id):
+			target_node = remote_players[target_id]["node"]
+		elif local_player and target_id == local_player.get_player_id():
+			target_node = local_player
+	
+	var projectile = ProjectileVisual.new()
+	effects_container.add_child(projectile)
+	projectile.setup(position, velocity, ability_id, target_node)
+	projectiles[id] = projectile
+
+
+func _on_projectile_impact(id: int, _target_id: int, position: Vector3) -> void:
+	if not projectiles.has(id):
+		return
+	var projectile = projectiles[id]
+	projectiles.erase(id)
+	if is_instance_valid(projectile):
+		projectile.impact(position)
+
+
 func _on_zone_change(_zone_id: int, _zone_name: String, _scene_path: String, _spawn_x: float, _spawn_y: float, _spawn_z: float) -> void:
 	print("GameManager: Zone change detected, clearing remote entities")
 	
@@ -1085,6 +1263,12 @@ func _on_zone_change(_zone_id: int, _zone_name: String, _scene_path: String, _sp
 			world_items[id]["node"].queue_free()
 	world_items.clear()
 	
+	# Clear projectiles in flight
+	for id in projectiles.keys():
+		if is_instance_valid(projectiles[id]):
+			projectiles[id].queue_free()
+	projectiles.clear()
+	
 	# Clear targeting if any
 	var targeting_system = get_tree().get_first_node_in_group("targeting_system")
 	if targeting_system and targeting_system.has_method("clear_target"):
@@ -1098,10 +1282,10 @@ func _on_zone_change(_zone_id: int, _zone_name: String, _scene_path: String, _sp
 # =============================================================================
 
 ## Handle time sync from server
-func _on_time_sync(unix_timestamp: int, latitude: float, longitude: float) -> void:
-	print("GameManager: Received time sync - timestamp: ", unix_timestamp, ", lat: ", latitude, ", lon: ", longitude)
+func _on_time_sync(unix_timestamp: int, latitude: float, longitude: float, weather: int = 0) -> void:
+	print("GameManager: Received time sync - timestamp: ", unix_timestamp, ", lat: ", latitude, ", lon: ", longitude, ", weather: ", weather)
 	if day_night_controller:
-		day_night_controller.on_time_sync(unix_timestamp, latitude, longitude)
+		day_night_controller.on_time_sync(unix_timestamp, latitude, longitude, weather)
 
 
 ## Get the day/night controller reference (for dev menu)
@@ -256,9 +256,10 @@ func _get_nearby_enemies(position: Vector3, radius: float) -> Array:
 	return game_manager.get_nearby_enemies(position, radius)
 
 
-## Handle entity death - clear target if our target died
+## Handle entity death - clear target if our enemy target died
+## Dead player targets stay selected so they can be resurrected
 func _on_entity_died(entity_id: int, _killer_id: int) -> void:
-	if entity_id == current_target_id:
+	if entity_id == current_target_id and current_target_type == "enemy":
 		clear_target()
 		emit_signal("show_message", "Target died", "info")
 
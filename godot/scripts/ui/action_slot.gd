@@ -29,6 +29,7 @@ var mana_cost: int = 0
 var ability_cooldown: float = 0.0
 var ability_range: float = 0.0
 var ability_target_type: String = ""
+var skill_rank_label: String = ""
 var is_on_cooldown: bool = false
 var cooldown_remaining: float = 0.0
 var cooldown_total: float = 0.0
@@ -156,6 +157,7 @@ func set_full_ability_data(data: Dictionary) -> void:
 	ability_cooldown = data.get("cooldown", 0.0)
 	ability_range = data.get("range", 0.0)
 	ability_target_type = data.get("target_type", "")
+	skill_rank_label = data.get("rank_label", "")
 	
 	# Update tooltip
 	_update_tooltip()
@@ -194,6 +196,12 @@ func _update_tooltip() -> void:
 		"ally":
 			lines.append("Requires friendly target")
 	
+	# Skill grade ("-" = not learned yet)
+	if skill_rank_label == "-":
+		lines.append("Not learned - right-click to learn")
+	elif skill_rank_label != "":
+		lines.append("Skill grade: %s (right-click to train)" % skill_rank_label)
+	
 	tooltip_text = "\n".join(lines)
 
 
@@ -0,0 +1,111 @@
+extends Control
+## Boss Frame UI - shows the active boss encounter (health, phase, immunity/enrage)
+## and announces the boss's scripted abilities.
+
+## How long an ability announcement stays up (seconds)
+const ANNOUNCEMENT_DURATION: float = 3.0
+
+## Animation speed for the health bar
+const BAR_LERP_SPEED: float = 8.0
+
+var local_player: Node = null
+
+## Enemy ID of the boss being shown (-1 = none)
+var boss_id: int = -1
+
+var current_health: float = 0.0
+var target_health: float = 0.0
+var max_health: float = 1.0
+var immune: bool = false
+var enraged: bool = false
+
+var announcement: String = ""
+var announcement_time: float = 0.0
+
+@onready var name_label: Label = $Panel/VBoxContainer/Header/BossName
+@onready var phase_label: Label = $Panel/VBoxContainer/Header/Phase
+@onready var health_bar: ProgressBar = $Panel/VBoxContainer/HealthBar
+@onready var health_label: Label = $Panel/VBoxContainer/HealthBar/HealthLabel
+@onready var status_label: Label = $Panel/VBoxContainer/Status
+
+
+func _ready() -> void:
+	visible = false
+	
+	# Wait a frame for the player to be ready
+	await get_tree().process_frame
+	
+	local_player = get_tree().get_first_node_in_group("local_player")
+	if local_player == null:
+		return
+	
+	if local_player.has_signal("boss_updated"):
+		local_player.connect("boss_updated", _on_boss_updated)
+	if local_player.has_signal("boss_ability_used"):
+		local_player.connect("boss_ability_used", _on_boss_ability_used)
+	if local_player.has_signal("boss_encounter_ended"):
+		local_player.connect("boss_encounter_ended", _on_boss_encounter_ended)
+
+
+func _process(delta: float) -> void:
+	if not visible:
+		return
+	
+	current_health = lerpf(current_health, target_health, BAR_LERP_SPEED * delta)
+	health_bar.value = current_health
+	health_label.text = "%d / %d" % [int(current_health), int(max_health)]
+	
+	if announcement_time > 0.0:
+		announcement_time -= delta
+		if announcement_time <= 0.0:
+			_update_status()
+
+
+func _on_boss_updated(enemy_id: int, boss_name: String, phase: int, phase_count: int, phase_name: String, health: int, maximum: int, is_immune: bool, is_enraged: bool) -> void:
+	if enemy_id != boss_id:
+		# New encounter: snap the bar instead of animating from the old boss
+		boss_id = enemy_id
+		current_health = float(health)
+	
+	name_label.text = boss_name
+	phase_label.text = "%s (%d/%d)" % [phase_name, phase + 1, phase_count]
+	max_health = float(maximum)
+	target_health = float(health)
+	health_bar.max_value = max_health
+	immune = is_immune
+	enraged = is_enraged
+	_update_status()
+	visible = true
+
+
+func _on_boss_ability_used(enemy_id: int, ability_name: String, _position: Vector3, radius: float, wind_up: float) -> void:
+	if enemy_id != boss_id:
+		return
+	
+	announcement = "%s uses %s!" % [name_label.text, ability_name]
+	if radius > 0.0 and wind_up > 0.0:
+		announcement += " Get out!"
+	announcement_time = ANNOUNCEMENT_DURATION
+	_update_status()
+
+
+func _on_boss_encounter_ended(enemy_id: int, _defeated: bool) -> void:
+	if enemy_id != boss_id:
+		return
+	
+	boss_id = -1
+	announcement_time = 0.0
+	visible = false
+
+
+func _update_status() -> void:
+	if announcement_time > 0.0:
+		status_label.text = announcement
+		return
+	
+	var tags: Array[String] = []
+	if immune:
+		tags.append("IMMUNE")
+	if enraged:
+		tags.append("ENRAGED")
+	status_label.text = " - ".join(tags)
@@ -33,7 +33,7 @@ var ActionSlotScene = preload("res://scenes/ui/action_slot.tscn")
 ## Action bar ability IDs (8 slots)
 var action_bar_abilities: Array[int] = [-1, -1, -1, -1, -1, -1, -1, -1]
 
-## Ability definitions (loaded once)
+## Ability definitions (replaced by the server's definitions on character select)
 ## Map of ability_id -> { name, description, mana_cost, cooldown, range, target_type }
 var ability_defs: Dictionary = {
 	# Universal abilities
@@ -135,6 +135,9 @@ func _find_player_and_connect() -> void:
 			local_player.connect("stats_updated", _on_stats_updated)
 		
 		# Connect to ability signals
+		if local_player.has_signal("ability_definitions_received"):
+			local_player.connect("ability_definitions_received", _on_ability_definitions_received)
+		
 		if local_player.has_signal("action_bar_received"):
 			local_player.connect("action_bar_received", _on_action_bar_received)
 		
@@ -146,6 +149,27 @@ func _find_player_and_connect() -> void:
 		
 		if local_player.has_signal("ability_used"):
 			local_player.connect("ability_used", _on_ability_used)
+		
+		# Connect to skill signals
+		if local_player.has_signal("skill_list_received"):
+			local_player.connect("skill_list_received", _on_skill_list_received)
+		
+		if local_player.has_signal("skill_updated"):
+			local_player.connect("skill_updated", _on_skill_updated)
+		
+		if local_player.has_signal("skill_upgrade_failed"):
+			local_player.connect("skill_upgrade_failed", _on_skill_upgrade_failed)
+		
+		# Connect to specialization signals
+		if local_player.has_signal("specialization_chosen"):
+			local_player.connect("specialization_chosen", _on_specialization_chosen)
+		
+		if local_player.has_signal("specialization_failed"):
+			local_player.connect("specialization_failed", _on_specialization_failed)
+		
+		# Connect to regeneration updates
+		if local_player.has_signal("resources_updated"):
+			local_player.connect("resources_updated", _on_resources_updated)
 
 
 func _initialize_bars() -> void:
@@ -199,9 +223,15 @@ func _on_action_slot_clicked(slot_number: int) -> void:
 	_use_ability_in_slot(slot_number - 1)
 
 
-func _on_action_slot_right_clicked(_slot_number: int) -> void:
-	# For future: open ability assignment menu
-	pass
+func _on_action_slot_right_clicked(slot_number: int) -> void:
+	# Right-click learns or trains the skill in this slot
+	var slot_index = slot_number - 1
+	if slot_index < 0 or slot_index >= action_bar_abilities.size():
+		return
+	
+	var ability_id = action_bar_abilities[slot_index]
+	if ability_id > 0 and local_player and local_player.has_method("upgrade_skill"):
+		local_player.upgrade_skill(ability_id)
 
 
 ## Use ability in a specific slot (0-indexed)
@@ -223,6 +253,10 @@ func _use_ability_in_slot(slot_index: int) -> void:
 		if ability.target_type == "enemy":
 			if targeting_system and targeting_system.has_target():
 				target_id = targeting_system.get_current_target_id()
+		elif ability.target_type == "ally":
+			# Heal/resurrect the targeted player, otherwise the server uses self
+			if targeting_system and targeting_system.has_target() and targeting_system.current_target_type == "player":
+				target_id = targeting_system.get_current_target_id()
 	
 	# Use the ability
 	if local_player.has_method("use_ability"):
@@ -237,6 +271,15 @@ func _on_health_changed(current: int, maximum: int) -> void:
 	_update_health_label()
 
 
+func _on_resources_updated(_health: int, _max_health: int, mana: int, maximum_mana: int, _in_combat: bool, _resting: bool) -> void:
+	# Health is handled by health_changed; regenerated mana animates towards the new value
+	max_mana = float(maximum_mana)
+	target_mana = float(mana)
+	if mana_bar:
+		mana_bar.max_value = max_mana
+	_update_mana_label()
+
+
 func _on_character_selected(_character_id: int) -> void:
 	# Update all stats from player
 	if local_player:
@@ -260,6 +303,8 @@ func _on_character_selected(_character_id: int) -> void:
 			var level = local_player.get_level()
 			var current_level_xp = _get_xp_within_level(exp, level)
 			set_exp_immediate(current_level_xp, exp_to_next)
+		
+		_check_specialization_available()
 
 
 ## Calculate XP within current level (not total XP)
@@ -292,6 +337,35 @@ func _on_level_up(new_level: int, new_max_health: int, new_max_mana: int, _attac
 	if local_player and local_player.has_method("get_experience_to_next_level"):
 		var xp_to_next = local_player.get_experience_to_next_level()
 		set_exp_immediate(0, xp_to_next)
+	
+	_check_specialization_available()
+
+
+## Open the specialization dialog once the player is eligible
+func _check_specialization_available() -> void:
+	if not local_player or not local_player.has_method("can_choose_specialization"):
+		return
+	if not local_player.can_choose_specialization():
+		return
+	
+	var dialog = get_tree().get_first_node_in_group("specialization_dialog")
+	if dialog and dialog.has_method("show_dialog"):
+		dialog.show_dialog()
+
+
+func _on_specialization_chosen(specialization: int, new_max_health: int, new_max_mana: int, _attack: int, _defense: int) -> void:
+	set_health_immediate(new_max_health, new_max_health)
+	set_mana_immediate(new_max_mana, new_max_mana)
+	
+	var chat_ui = get_tree().get_first_node_in_group("chat_ui")
+	if chat_ui and chat_ui.has_method("add_system_message"):
+		chat_ui.add_system_message("You have chosen the path of %s." % local_player.get_specialization_name(specialization))
+
+
+func _on_specialization_failed(reason: String) -> void:
+	var chat_ui = get_tree().get_first_node_in_group("chat_ui")
+	if chat_ui and chat_ui.has_method("add_command_error_message"):
+		chat_ui.add_command_error_message("Specialization failed: %s" % reason)
 
 
 func _on_stats_updated(level: int, _max_health: int, _max_mana: int, _attack: int, _defense: int, _gold: int, health: int, mana: int) -> void:
@@ -401,6 +475,15 @@ func update_exp(current: int, maximum: int) -> void:
 # ==========================================================================
 
 ## Handle action bar received from server
+func _on_ability_definitions_received(abilities: Array) -> void:
+	ability_defs.clear()
+	for ability in abilities:
+		ability_defs[int(ability["id"])] = ability
+	
+	# Refresh slots with the new names, costs and tooltips
+	_update_action_slots()
+
+
 func _on_action_bar_received(slots: Array) -> void:
 	# Update local action bar abilities
 	for i in range(min(slots.size(), ACTION_SLOT_COUNT)):
@@ -452,6 +535,27 @@ func _on_ability_used(caster_id: int, ability_id: int, _target_id: int) -> void:
 				action_slots[i].trigger_use_flash()
 
 
+## Handle learned skill list received from server
+func _on_skill_list_received(_skills: Dictionary, _skill_points: int) -> void:
+	_update_action_slots()
+
+
+## Handle a skill being learned or trained
+func _on_skill_updated(ability_id: int, _rank: int, skill_points: int) -> void:
+	_update_action_slots()
+	
+	var chat_ui = get_tree().get_first_node_in_group("chat_ui")
+	if chat_ui and chat_ui.has_method("add_system_message") and local_player:
+		var ability_name = ability_defs[ability_id].name if ability_defs.has(ability_id) else "Skill"
+		chat_ui.add_system_message("%s is now %s (%d skill points left)" % [
+			ability_name, local_player.get_skill_rank_label(ability_id), skill_points])
+
+
+## Handle failed skill learn/train attempt
+func _on_skill_upgrade_failed(ability_id: int, reason: String) -> void:
+	_on_ability_failed(ability_id, reason)
+
+
 ## Update action slot UI to reflect current abilities
 func _update_action_slots() -> void:
 	for i in range(action_slots.size()):
@@ -462,7 +566,9 @@ func _update_action_slots() -> void:
 		var ability_id = action_bar_abilities[i] if i < action_bar_abilities.size() else -1
 		
 		if ability_id > 0 and ability_defs.has(ability_id):
-			var ability = ability_defs[ability_id]
+			var ability = ability_defs[ability_id].duplicate()
+			if local_player and local_player.has_method("get_skill_rank_label"):
+				ability["rank_label"] = local_player.get_skill_rank_label(ability_id)
 			slot.set_full_ability_data(ability)
 		else:
 			slot.clear_ability()
@@ -477,6 +583,13 @@ func _input(event: InputEvent) -> void:
 	if _is_chat_focused() or _is_text_input_focused():
 		return
 	
+	# Sit down to rest / stand up
+	if event.is_action_pressed("toggle_sit"):
+		if local_player and local_player.has_method("set_resting"):
+			local_player.set_resting(not local_player.is_resting())
+		get_viewport().set_input_as_handled()
+		return
+	
 	# Check for number key presses (1-8 for action slots)
 	if event is InputEventKey and event.pressed and not event.echo:
 		var key_event = event as InputEventKey
@@ -18,6 +18,17 @@ const EMPIRE_DATA: Dictionary = {
 	2: {"name": "Jinno", "color": Color(0.3, 0.5, 0.9)},     # Blue
 }
 
+## Primary attributes in server order (PrimaryStat enum)
+const ATTRIBUTE_DATA: Array = [
+	{"key": "vitality", "name": "Vitality"},
+	{"key": "intelligence", "name": "Intelligence"},
+	{"key": "strength", "name": "Strength"},
+	{"key": "dexterity", "name": "Dexterity"},
+]
+
+## Gold cost per character level for a paid attribute reset (matches server)
+const RESPEC_GOLD_PER_LEVEL: int = 100
+
 ## Reference to local player
 var local_player: Node = null
 
@@ -40,9 +51,20 @@ var cached_stats: Dictionary = {
 	"attack": 10,
 	"defense": 5,
 	"attack_speed": 1.0,
+	"move_speed": 5.0,
 	"gold": 0,
+	"durability": 100,
+	"max_durability": 100,
+	"repair_cost": 0,
+	"crit_chance": 0.0,
+	"stat_points": 0,
+	"attributes": {},
 }
 
+## Attribute value labels and "+" buttons, keyed by attribute index
+var attribute_value_labels: Array = []
+var attribute_plus_buttons: Array = []
+
 ## UI References
 @onready var panel: Panel = $Panel
 @onready var header: HBoxContainer = $Panel/MarginContainer/VBoxContainer/Header
@@ -60,7 +82,16 @@ var cached_stats: Dictionary = {
 @onready var attack_value: Label = $Panel/MarginContainer/VBoxContainer/StatsSection/StatsVBox/StatsGrid/AttackValue
 @onready var defense_value: Label = $Panel/MarginContainer/VBoxContainer/StatsSection/StatsVBox/StatsGrid/DefenseValue
 @onready var attack_speed_value: Label = $Panel/MarginContainer/VBoxContainer/StatsSection/StatsVBox/StatsGrid/AttackSpeedValue
+@onready var move_speed_value: Label = $Panel/MarginContainer/VBoxContainer/StatsSection/StatsVBox/StatsGrid/MoveSpeedValue
 @onready var gold_value: Label = $Panel/MarginContainer/VBoxContainer/StatsSection/StatsVBox/StatsGrid/GoldValue
+@onready var durability_value: Label = $Panel/MarginContainer/VBoxContainer/StatsSection/StatsVBox/StatsGrid/DurabilityValue
+@onready var repair_button: Button = $Panel/MarginContainer/VBoxContainer/StatsSection/StatsVBox/RepairButton
+
+# Attributes
+@onready var stat_points_label: Label = $Panel/MarginContainer/VBoxContainer/AttributesSection/AttributesVBox/AttributesHeader/StatPointsLabel
+@onready var attributes_grid: GridContainer = $Panel/MarginContainer/VBoxContainer/AttributesSection/AttributesVBox/AttributesGrid
+@onready var crit_chance_label: Label = $Panel/MarginContainer/VBoxContainer/AttributesSection/AttributesVBox/CritChanceLabel
+@onready var reset_button: Button = $Panel/MarginContainer/VBoxContainer/AttributesSection/AttributesVBox/ResetButton
 
 # Experience
 @onready var exp_bar: ProgressBar = $Panel/MarginContainer/VBoxContainer/ExpSection/ExpVBox/ExpBar
@@ -83,6 +114,13 @@ func _ready() -> void:
 	if close_button:
 		close_button.pressed.connect(close_panel)
 	
+	# Build attribute rows and connect reset button
+	_build_attribute_rows()
+	if reset_button:
+		reset_button.pressed.connect(_on_reset_pressed)
+	if repair_button:
+		repair_button.pressed.connect(_on_repair_pressed)
+	
 	# Connect to viewport resize
 	get_tree().root.size_changed.connect(_on_viewport_resized)
 	
@@ -164,6 +202,21 @@ func _find_player_and_connect() -> void:
 		
 		if local_player.has_signal("gold_updated"):
 			local_player.connect("gold_updated", _on_gold_updated)
+		
+		if local_player.has_signal("attributes_updated"):
+			local_player.connect("attributes_updated", _on_attributes_updated)
+		
+		if local_player.has_signal("movement_speed_changed"):
+			local_player.connect("movement_speed_changed", _on_movement_speed_changed)
+		
+		if local_player.has_signal("stat_allocation_failed"):
+			local_player.connect("stat_allocation_failed", _on_stat_allocation_failed)
+		
+		if local_player.has_signal("durability_updated"):
+			local_player.connect("durability_updated", _on_durability_updated)
+		
+		if local_player.has_signal("repair_failed"):
+			local_player.connect("repair_failed", _on_repair_failed)
 
 
 # =============================================================================
@@ -208,12 +261,14 @@ func _on_level_up(new_level: int, max_health: int, max_mana: int, attack: int, d
 	cached_stats["health"] = max_health
 	cached_stats["mana"] = max_mana
 	
-	# Update experience from player
+	# Update experience and stat points from player
 	if local_player:
 		if local_player.has_method("get_experience"):
 			cached_stats["experience"] = local_player.get_experience()
 		if local_player.has_method("get_experience_to_next_level"):
 			cached_stats["experience_to_next_level"] = local_player.get_experience_to_next_level()
+		if local_player.has_method("get_stat_points"):
+			cached_stats["stat_points"] = local_player.get_stat_points()
 	
 	if visible:
 		_update_display()
@@ -242,6 +297,70 @@ func _on_gold_updated(gold: int) -> void:
 		_update_gold_display()
 
 
+func _on_movement_speed_changed(speed: float) -> void:
+	cached_stats["move_speed"] = speed
+	if visible:
+		_update_display()
+
+
+func _on_attributes_updated(attributes: Dictionary, stat_points: int, attack_speed: float, crit_chance: float) -> void:
+	cached_stats["attributes"] = attributes
+	cached_stats["stat_points"] = stat_points
+	cached_stats["attack_speed"] = attack_speed
+	cached_stats["crit_chance"] = crit_chance
+	
+	# Derived stats changed along with the attributes
+	if local_player:
+		if local_player.has_method("get_max_health"):
+			cached_stats["max_health"] = local_player.get_max_health()
+		if local_player.has_method("get_max_mana"):
+			cached_stats["max_mana"] = local_player.get_max_mana()
+		if local_player.has_method("get_mana"):
+			cached_stats["mana"] = local_player.get_mana()
+		if local_player.has_method("get_attack_power"):
+			cached_stats["attack"] = local_player.get_attack_power()
+		if local_player.has_method("get_defense"):
+			cached_stats["defense"] = local_player.get_defense()
+	
+	if visible:
+		_update_display()
+
+
+func _on_stat_allocation_failed(reason: String) -> void:
+	var chat_ui = get_tree().get_first_node_in_group("chat_ui")
+	if chat_ui and chat_ui.has_method("add_command_error_message"):
+		chat_ui.add_command_error_message("Attributes: %s" % reason)
+
+
+func _on_durability_updated(durability: int, max_durability: int, repair_cost: int) -> void:
+	cached_stats["durability"] = durability
+	cached_stats["max_durability"] = max_durability
+	cached_stats["repair_cost"] = repair_cost
+	if visible:
+		_update_display()
+
+
+func _on_repair_failed(reason: String) -> void:
+	var chat_ui = get_tree().get_first_node_in_group("chat_ui")
+	if chat_ui and chat_ui.has_method("add_command_error_message"):
+		chat_ui.add_command_error_message("Repair: %s" % reason)
+
+
+func _on_attribute_plus_pressed(index: int) -> void:
+	if local_player and local_player.has_method("allocate_stat"):
+		local_player.allocate_stat(index, 1)
+
+
+func _on_reset_pressed() -> void:
+	if local_player and local_player.has_method("reset_stats"):
+		local_player.reset_stats()
+
+
+func _on_repair_pressed() -> void:
+	if local_player and local_player.has_method("repair_equipment"):
+		local_player.repair_equipment()
+
+
 # =============================================================================
 # Display Updates
 # =============================================================================
@@ -280,9 +399,19 @@ func _refresh_all_stats() -> void:
 		cached_stats["defense"] = local_player.get_defense()
 	if local_player.has_method("get_attack_speed"):
 		cached_stats["attack_speed"] = local_player.get_attack_speed()
+	if local_player.has_method("get_movement_speed"):
+		cached_stats["move_speed"] = local_player.get_movement_speed()
+	if local_player.has_method("get_durability"):
+		cached_stats["durability"] = local_player.get_durability()
 	if local_player.has_method("get_gold"):
 		cached_stats["gold"] = local_player.get_gold()
 	
+	# Attributes
+	if local_player.has_method("get_attributes"):
+		cached_stats["attributes"] = local_player.get_attributes()
+	if local_player.has_method("get_stat_points"):
+		cached_stats["stat_points"] = local_player.get_stat_points()
+	
 	_update_display()
 
 
@@ -290,6 +419,7 @@ func _update_display() -> void:
 	"""Update all display elements from cached stats."""
 	_update_character_info()
 	_update_stats_display()
+	_update_attributes_display()
 	_update_exp_display()
 
 
@@ -327,6 +457,19 @@ func _update_stats_display() -> void:
 	if attack_speed_value:
 		attack_speed_value.text = "%.2fx" % cached_stats["attack_speed"]
 	
+	if move_speed_value:
+		move_speed_value.text = "%.1f" % cached_stats["move_speed"]
+	
+	if durability_value:
+		durability_value.text = "%d/%d" % [cached_stats["durability"], cached_stats["max_durability"]]
+		# Broken gear is highlighted red
+		durability_value.modulate = Color(1.0, 0.4, 0.4) if cached_stats["durability"] == 0 else Color.WHITE
+	
+	if repair_button:
+		var cost: int = cached_stats["repair_cost"]
+		repair_button.disabled = cost == 0
+		repair_button.text = "Repair Gear (%s gold)" % _format_gold(cost) if cost > 0 else "Repair Gear"
+	
 	_update_gold_display()
 
 
@@ -345,6 +488,50 @@ func _update_gold_display() -> void:
 		gold_value.text = _format_gold(cached_stats["gold"])
 
 
+func _build_attribute_rows() -> void:
+	"""Create a name / value / "+" row per attribute."""
+	if not attributes_grid:
+		return
+	
+	for i in range(ATTRIBUTE_DATA.size()):
+		var name_label = Label.new()
+		name_label.text = ATTRIBUTE_DATA[i]["name"]
+		name_label.size_flags_horizontal = Control.SIZE_EXPAND_FILL
+		attributes_grid.add_child(name_label)
+		
+		var value_label = Label.new()
+		value_label.text = "0"
+		value_label.horizontal_alignment = HORIZONTAL_ALIGNMENT_RIGHT
+		attributes_grid.add_child(value_label)
+		attribute_value_labels.append(value_label)
+		
+		var plus_button = Button.new()
+		plus_button.text = "+"
+		plus_button.custom_minimum_size = Vector2(24, 0)
+		plus_button.focus_mode = Control.FOCUS_NONE
+		plus_button.pressed.connect(_on_attribute_plus_pressed.bind(i))
+		attributes_grid.add_child(plus_button)
+		attribute_plus_buttons.append(plus_button)
+
+
+func _update_attributes_display() -> void:
+	var attributes: Dictionary = cached_stats["attributes"]
+	var stat_points: int = cached_stats["stat_points"]
+	
+	for i in range(attribute_value_labels.size()):
+		attribute_value_labels[i].text = str(attributes.get(ATTRIBUTE_DATA[i]["key"], 0))
+		attribute_plus_buttons[i].disabled = stat_points <= 0
+	
+	if stat_points_label:
+		stat_points_label.text = "Points: %d" % stat_points
+	
+	if crit_chance_label:
+		crit_chance_label.text = "Critical chance: %.1f%%" % (cached_stats["crit_chance"] * 100.0)
+	
+	if reset_button:
+		reset_button.text = "Reset Attributes (%s gold)" % _format_gold(cached_stats["level"] * RESPEC_GOLD_PER_LEVEL)
+
+
 func _update_exp_display() -> void:
 	var current = cached_stats["experience"]
 	var to_next = cached_stats["experience_to_next_level"]
@@ -28,6 +28,16 @@ const SCROLL_STEP: int = 40
 ## Reference to the local player for sending messages
 var local_player: Node = null
 
+## Combat log colors by event kind (Damage, Heal, Buff, Miss, Death, Experience)
+const COMBAT_LOG_COLORS: Array = [
+	Color(1.0, 0.55, 0.45),
+	Color(0.45, 1.0, 0.55),
+	Color(0.55, 0.75, 1.0),
+	Color(0.7, 0.7, 0.7),
+	Color(1.0, 0.3, 0.3),
+	Color(0.4, 1.0, 0.4),
+]
+
 ## Message data structure
 class ChatMessage:
 	var sender: String
@@ -81,6 +91,9 @@ func _ready() -> void:
 			local_player.connect("level_up", _on_level_up)
 		if local_player.has_signal("gold_updated"):
 			local_player.connect("gold_updated", _on_gold_updated)
+		# Streamed combat log events (after /combatlog on)
+		if local_player.has_signal("combat_log_event"):
+			local_player.connect("combat_log_event", _on_combat_log_event)
 	
 	# Connect UI signals
 	if chat_input:
@@ -348,7 +361,9 @@ func _on_command_response(success: bool, message: String) -> void:
 
 func _on_experience_gained(amount: int, _current_xp: int, _xp_to_next: int) -> void:
 	"""Show XP gain notification in chat."""
-	add_xp_message("+" + str(amount) + " XP")
+	# Amount is 0 when only the total changed (death penalty)
+	if amount > 0:
+		add_xp_message("+" + str(amount) + " XP")
 
 
 func _on_level_up(new_level: int, _max_health: int, _max_mana: int, _attack: int, _defense: int) -> void:
@@ -369,6 +384,16 @@ func _on_gold_updated(gold: int) -> void:
 	_last_gold = gold
 
 
+func _on_combat_log_event(text: String, kind: int) -> void:
+	var color = COMBAT_LOG_COLORS[kind] if kind >= 0 and kind < COMBAT_LOG_COLORS.size() else Color.WHITE
+	var msg = ChatMessage.new("", "[Combat] " + text, color, true)
+	messages.append(msg)
+	_trim_history()
+	_refresh_messages()
+	if current_mode == ChatMode.ACTIVE:
+		_scroll_to_bottom()
+
+
 func _on_text_submitted(_text: String) -> void:
 	send_message()
 	# Return to passive mode after sending
@@ -402,6 +427,16 @@ func send_message() -> void:
 		chat_input.text = ""
 		return
 	
+	# Handle client-side /combatlog on|off command
+	var lower = message.to_lower()
+	if lower == "/combatlog on" or lower == "/combatlog off":
+		var enabled = lower.ends_with("on")
+		if local_player and local_player.has_method("set_combat_log_streaming"):
+			local_player.set_combat_log_streaming(enabled)
+			add_command_success_message("Combat log %s." % ("enabled" if enabled else "disabled"))
+		chat_input.text = ""
+		return
+	
 	# Check if connected
 	var is_connected = false
 	if local_player and local_player.has_method("is_connected_to_server"):
@@ -1,6 +1,7 @@
 extends CanvasLayer
 ## Death dialog shown when the player dies.
-## Allows respawning at empire spawn (full HP) or at death location (20% HP).
+## Allows respawning at empire spawn (full HP) or at death location (reduced HP).
+## Shows the death penalty and the time until the server releases the player automatically.
 
 signal respawn_at_spawn  ## Player chose to respawn at empire spawn
 signal revive_here       ## Player chose to revive at death location
@@ -8,11 +9,13 @@ signal revive_here       ## Player chose to revive at death location
 const COUNTDOWN_SECONDS := 3
 
 @onready var countdown_label: Label = $CenterContainer/Panel/VBoxContainer/Countdown
+@onready var penalty_label: Label = $CenterContainer/Panel/VBoxContainer/Penalty
 @onready var restart_button: Button = $CenterContainer/Panel/VBoxContainer/ButtonContainer/RestartAtSpawn
 @onready var revive_button: Button = $CenterContainer/Panel/VBoxContainer/ButtonContainer/ReviveHere
 @onready var countdown_timer: Timer = $CountdownTimer
 
 var _countdown_remaining: int = COUNTDOWN_SECONDS
+var _auto_release_remaining: float = -1.0
 
 
 func _ready() -> void:
@@ -25,6 +28,8 @@ func show_dialog() -> void:
 	visible = true
 	_countdown_remaining = COUNTDOWN_SECONDS
 	countdown_label.text = str(_countdown_remaining)
+	penalty_label.text = ""
+	_auto_release_remaining = -1.0
 	
 	# Disable buttons during countdown
 	restart_button.disabled = true
@@ -37,6 +42,12 @@ func show_dialog() -> void:
 	Input.set_mouse_mode(Input.MOUSE_MODE_VISIBLE)
 
 
+## Show the penalty reported by the server and start the auto-release countdown
+func set_penalty(experience_lost: int, durability: int, auto_release_in: float) -> void:
+	penalty_label.text = "Lost %d XP - Gear durability %d%%" % [experience_lost, durability]
+	_auto_release_remaining = auto_release_in
+
+
 ## Hide the death dialog
 func hide_dialog() -> void:
 	visible = false
@@ -45,16 +56,25 @@ func hide_dialog() -> void:
 
 func _on_countdown_timer_timeout() -> void:
 	_countdown_remaining -= 1
+	if _auto_release_remaining > 0.0:
+		_auto_release_remaining = maxf(_auto_release_remaining - 1.0, 0.0)
 	
 	if _countdown_remaining > 0:
 		countdown_label.text = str(_countdown_remaining)
-	else:
-		countdown_label.text = "Choose your respawn"
-		countdown_timer.stop()
-		
+		return
+	
+	if _countdown_remaining == 0:
 		# Enable buttons
 		restart_button.disabled = false
 		revive_button.disabled = false
+	
+	if _auto_release_remaining >= 0.0:
+		# Keep ticking to show when the server releases us to the spawn
+		var seconds := int(_auto_release_remaining)
+		countdown_label.text = "Auto-release in %d:%02d" % [seconds / 60, seconds % 60]
+	else:
+		countdown_label.text = "Choose your respawn"
+		countdown_timer.stop()
 
 
 func _on_restart_at_spawn_pressed() -> void:
@@ -333,7 +333,7 @@ func _do_quit() -> void:
 # Damage Detection (for countdown cancellation)
 # =============================================================================
 
-func _on_damage_dealt(attacker_id: int, target_id: int, _damage: int, _is_critical: bool) -> void:
+func _on_damage_dealt(attacker_id: int, target_id: int, _damage: int, _is_critical: bool, _outcome: int = 0) -> void:
 	"""Handle damage dealt event - cancel countdown if local player is hit."""
 	if not _countdown_active:
 		return
@@ -0,0 +1,84 @@
+extends Control
+class_name SpecializationDialog
+## Specialization Dialog - Lets the player pick one of their class's two doctrines.
+
+## Signal emitted when dialog is closed
+signal dialog_closed
+
+## Reference to local player
+var local_player: Node = null
+
+## Short description of each specialization (by specialization id)
+const DESCRIPTIONS: Dictionary = {
+	0: "Close combat assassin. Grows in attack.",
+	1: "Fights from range with the bow. Grows in attack and mana.",
+	2: "Relentless attacker. Grows in health and attack.",
+	3: "Unyielding defender. Grows in health and defense.",
+	4: "Fights with enchanted weapons. Grows in health and attack.",
+	5: "Wields dark spells. Grows in mana.",
+	6: "Commands the dragon's fury. Grows in mana.",
+	7: "Heals and protects. Grows in mana and defense.",
+}
+
+## UI References
+@onready var option_container: VBoxContainer = $CenterContainer/Panel/VBox/OptionContainer
+@onready var cancel_button: Button = $CenterContainer/Panel/VBox/CancelButton
+
+
+func _ready() -> void:
+	# Add to group for easy access
+	add_to_group("specialization_dialog")
+	
+	# Register with UIManager for escape key handling
+	UIManager.register_dialog(self)
+	
+	# Start hidden
+	visible = false
+	
+	if cancel_button:
+		cancel_button.pressed.connect(_on_cancel_pressed)
+
+
+func show_dialog() -> void:
+	"""Show the dialog with our class's two specializations."""
+	if not local_player:
+		local_player = get_tree().get_first_node_in_group("local_player")
+		if not local_player:
+			var main = get_tree().current_scene
+			if main:
+				local_player = main.get_node_or_null("Player")
+	
+	if not local_player or not local_player.has_method("get_specialization_options"):
+		return
+	
+	_create_option_buttons()
+	visible = true
+
+
+func close_dialog() -> void:
+	"""Hide the dialog."""
+	visible = false
+	dialog_closed.emit()
+
+
+func _create_option_buttons() -> void:
+	for child in option_container.get_children():
+		child.queue_free()
+	
+	for spec_id in local_player.get_specialization_options():
+		var button = Button.new()
+		button.text = "%s\n%s" % [local_player.get_specialization_name(spec_id), DESCRIPTIONS.get(spec_id, "")]
+		button.custom_minimum_size = Vector2(280, 56)
+		button.size_flags_horizontal = Control.SIZE_EXPAND_FILL
+		button.pressed.connect(_on_specialization_selected.bind(spec_id))
+		option_container.add_child(button)
+
+
+func _on_specialization_selected(spec_id: int) -> void:
+	if local_player and local_player.has_method("choose_specialization"):
+		local_player.choose_specialization(spec_id)
+	close_dialog()
+
+
+func _on_cancel_pressed() -> void:
+	close_dialog()
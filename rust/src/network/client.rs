@@ -8,7 +8,7 @@ use std::time::{Instant, Duration};
 
 use mmo_shared::{
     ClientMessage, ServerMessage, AnimationState,
-    CharacterClass, Gender, Empire,
+    CharacterClass, Gender, Empire, Specialization, PrimaryStat, RespawnType,
     PROTOCOL_VERSION, DEFAULT_PORT,
 };
 
@@ -329,6 +329,12 @@ impl NetworkClient {
         let _ = self.send_message(&msg);
     }
     
+    /// Send stop attack request
+    pub fn send_stop_attack(&mut self) {
+        let msg = ClientMessage::StopAttack;
+        let _ = self.send_message(&msg);
+    }
+    
     /// Send pickup item request
     pub fn send_pickup(&mut self, item_entity_id: u64) {
         let msg = ClientMessage::PickupItem { item_entity_id };
@@ -415,12 +421,16 @@ impl NetworkClient {
     }
     
     /// Send respawn request after death
-    /// respawn_type: 0 = at empire spawn (full health), 1 = at death location (20% health)
-    pub fn send_respawn_request(&mut self, respawn_type: u8) {
+    pub fn send_respawn_request(&mut self, respawn_type: RespawnType) {
         let msg = ClientMessage::RespawnRequest { respawn_type };
         let _ = self.send_message(&msg);
     }
     
+    /// Send gear repair request
+    pub fn send_repair_equipment(&mut self) {
+        let _ = self.send_message(&ClientMessage::RepairEquipment);
+    }
+    
     // =========================================================================
     // Equipment Methods
     // =========================================================================
@@ -472,6 +482,48 @@ impl NetworkClient {
         let msg = ClientMessage::UseAbility { ability_id, target_id };
         let _ = self.send_message(&msg);
     }
+    
+    /// Send cancel cast request
+    pub fn send_cancel_cast(&mut self) {
+        let msg = ClientMessage::CancelCast;
+        let _ = self.send_message(&msg);
+    }
+    
+    /// Send specialization choice
+    pub fn send_choose_specialization(&mut self, specialization: Specialization) {
+        let msg = ClientMessage::ChooseSpecialization { specialization };
+        let _ = self.send_message(&msg);
+    }
+    
+    /// Send stat point allocation
+    pub fn send_allocate_stat(&mut self, stat: PrimaryStat, amount: u32) {
+        let msg = ClientMessage::AllocateStat { stat, amount };
+        let _ = self.send_message(&msg);
+    }
+    
+    /// Send paid stat reset request
+    pub fn send_reset_stats(&mut self) {
+        let msg = ClientMessage::ResetStats;
+        let _ = self.send_message(&msg);
+    }
+    
+    /// Send combat log streaming opt-in/opt-out
+    pub fn send_set_combat_log_streaming(&mut self, enabled: bool) {
+        let msg = ClientMessage::SetCombatLogStreaming { enabled };
+        let _ = self.send_message(&msg);
+    }
+    
+    /// Send sit down / stand up request
+    pub fn send_set_resting(&mut self, resting: bool) {
+        let msg = ClientMessage::SetResting { resting };
+        let _ = self.send_message(&msg);
+    }
+    
+    /// Send skill upgrade request
+    pub fn send_upgrade_skill(&mut self, ability_id: u32) {
+        let msg = ClientMessage::UpgradeSkill { ability_id };
+        let _ = self.send_message(&msg);
+    }
 }
 
 impl Default for NetworkClient {
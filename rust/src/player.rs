@@ -1,7 +1,8 @@
+use std::collections::HashMap;
 use godot::prelude::*;
 use godot::classes::{CharacterBody3D, ICharacterBody3D, Engine, Input};
 
-use mmo_shared::{AnimationState, ServerMessage, InventorySlot, CharacterClass, Gender, Empire};
+use mmo_shared::{AnimationState, ServerMessage, InventorySlot, CharacterClass, Gender, Empire, HitOutcome, TargetType, Specialization, Attributes, PrimaryStat, get_item_definitions, skill_rank_label, BASE_MOVE_SPEED, SPRINT_MULTIPLIER, RespawnType, MAX_DURABILITY};
 use crate::network::{NetworkClient, ConnectionState};
 
 /// Player controller for the MMO.
@@ -57,6 +58,11 @@ pub struct Player {
     /// Character empire
     character_empire: Option<Empire>,
     
+    /// Chosen class specialization (None = not chosen yet)
+    specialization: Option<Specialization>,
+    /// Level at which a specialization can be chosen (sent by the server)
+    specialization_level: u32,
+    
     /// Current zone ID
     current_zone_id: u32,
     
@@ -93,6 +99,27 @@ pub struct Player {
     /// Gold currency
     gold: u64,
     
+    /// Learned skill ranks (ability_id -> rank)
+    skill_ranks: HashMap<u32, u8>,
+    
+    /// Unspent skill points
+    skill_points: u32,
+    
+    /// Primary attributes (VIT/INT/STR/DEX)
+    attributes: Attributes,
+    
+    /// Unspent stat points
+    stat_points: u32,
+    
+    /// Dealt or took damage recently (no regeneration)
+    in_combat: bool,
+    
+    /// Sitting down to rest
+    is_resting: bool,
+    
+    /// Equipped gear condition (0 = broken)
+    durability: u32,
+    
     /// Inventory (20 slots)
     inventory: Vec<Option<InventorySlot>>,
     
@@ -132,9 +159,9 @@ impl ICharacterBody3D for Player {
         godot_print!("Player initialized");
         
         Self {
-            speed: 5.0,
+            speed: BASE_MOVE_SPEED,
             jump_velocity: 4.5,
-            sprint_multiplier: 1.5,
+            sprint_multiplier: SPRINT_MULTIPLIER,
             server_address: "127.0.0.1".into(),
             is_local: true,
             network: None,
@@ -146,6 +173,8 @@ impl ICharacterBody3D for Player {
             character_class: None,
             character_gender: None,
             character_empire: None,
+            specialization: None,
+            specialization_level: 0,
             current_zone_id: 0,
             current_health: 100,
             max_health: 100,
@@ -158,6 +187,13 @@ impl ICharacterBody3D for Player {
             experience: 0,
             experience_to_next_level: 100,
             gold: 0,
+            skill_ranks: HashMap::new(),
+            skill_points: 0,
+            attributes: Attributes::default(),
+            stat_points: 0,
+            in_combat: false,
+            is_resting: false,
+            durability: MAX_DURABILITY,
             inventory: vec![None; 20],
             equipped_weapon_id: None,
             equipped_armor_id: None,
@@ -293,11 +329,12 @@ impl Player {
     
     /// Signal emitted when an enemy spawns
     #[signal]
-    fn enemy_spawned(id: i64, enemy_type: i64, position: Vector3, health: i64, max_health: i64, level: i64);
+    fn enemy_spawned(id: i64, enemy_type: i64, position: Vector3, health: i64, max_health: i64, level: i64, name: GString);
     
     /// Signal emitted when damage is dealt
+    /// outcome: 0=Hit, 1=Critical, 2=Miss, 3=Dodge, 4=Block, 5=Immune
     #[signal]
-    fn damage_dealt(attacker_id: i64, target_id: i64, damage: i64, is_critical: bool);
+    fn damage_dealt(attacker_id: i64, target_id: i64, damage: i64, is_critical: bool, outcome: i64);
     
     /// Signal emitted when inventory is updated
     #[signal]
@@ -353,8 +390,9 @@ impl Player {
     /// unix_timestamp: seconds since Unix epoch (UTC)
     /// latitude: server location latitude for solar calculations
     /// longitude: server location longitude for solar calculations
+    /// weather: 0 = Clear, 1 = Rain, 2 = Fog, 3 = Storm
     #[signal]
-    fn time_sync(unix_timestamp: i64, latitude: f64, longitude: f64);
+    fn time_sync(unix_timestamp: i64, latitude: f64, longitude: f64, weather: i64);
     
     /// Signal emitted when zone changes (on character select or zone transition)
     /// zone_id: zone ID (1-99: Shinsoo, 100-199: Chunjo, 200-299: Jinno, 300+: Neutral)
@@ -417,10 +455,129 @@ impl Player {
     #[signal]
     fn heal_received(healer_id: i64, target_id: i64, amount: i64, new_health: i64);
     
+    /// Signal emitted when ability definitions are received (on character select)
+    /// abilities: Array of dictionaries (id, name, description, mana_cost, cooldown, range, target_type, ...)
+    #[signal]
+    fn ability_definitions_received(abilities: Array<Dictionary>);
+    
     /// Signal emitted when action bar is received (on character select)
     /// slots: Array of 8 ability IDs (-1 for empty)
     #[signal]
     fn action_bar_received(slots: Array<i64>);
+    
+    /// Signal emitted when an entity starts casting or channeling (target_id 0 if none)
+    #[signal]
+    fn cast_started(caster_id: i64, ability_id: i64, target_id: i64, duration: f64, is_channel: bool);
+    
+    /// Signal emitted periodically while casting/channeling
+    #[signal]
+    fn cast_progress(caster_id: i64, ability_id: i64, elapsed: f64, duration: f64);
+    
+    /// Signal emitted when a cast or channel finishes
+    #[signal]
+    fn cast_completed(caster_id: i64, ability_id: i64);
+    
+    /// Signal emitted when a cast or channel is interrupted
+    #[signal]
+    fn cast_interrupted(caster_id: i64, ability_id: i64, reason: GString);
+    
+    /// Signal emitted when the server starts our auto-attack on a target
+    #[signal]
+    fn auto_attack_started(target_id: i64, swing_interval: f64);
+    
+    /// Signal emitted when the server stops our auto-attack
+    #[signal]
+    fn auto_attack_stopped(target_id: i64, reason: GString);
+    
+    /// Signal emitted when a projectile is launched (target_id/ability_id 0 if none)
+    #[signal]
+    fn projectile_spawned(id: i64, owner_id: i64, target_id: i64, ability_id: i64, position: Vector3, velocity: Vector3, homing: bool);
+    
+    /// Signal emitted when a projectile hits something or expires (target_id 0 if nothing was hit)
+    #[signal]
+    fn projectile_impact(id: i64, target_id: i64, position: Vector3);
+    
+    /// Signal emitted when the learned skill list is received (on character select)
+    /// skills: Dictionary of ability_id -> rank
+    #[signal]
+    fn skill_list_received(skills: Dictionary, skill_points: i64);
+    
+    /// Signal emitted when a skill's rank or the unspent skill points change
+    #[signal]
+    fn skill_updated(ability_id: i64, rank: i64, skill_points: i64);
+    
+    /// Signal emitted when learning or training a skill fails
+    #[signal]
+    fn skill_upgrade_failed(ability_id: i64, reason: GString);
+    
+    /// Signal emitted when our specialization is chosen (stats use the new growth)
+    #[signal]
+    fn specialization_chosen(specialization: i64, max_health: i64, max_mana: i64, attack: i64, defense: i64);
+    
+    /// Signal emitted when choosing a specialization fails
+    #[signal]
+    fn specialization_failed(reason: GString);
+    
+    /// Signal emitted when attributes or the stats derived from them change
+    /// attributes: Dictionary (vitality, intelligence, strength, dexterity)
+    #[signal]
+    fn attributes_updated(attributes: Dictionary, stat_points: i64, attack_speed: f64, crit_chance: f64);
+    
+    /// Signal emitted when spending or resetting stat points fails
+    #[signal]
+    fn stat_allocation_failed(reason: GString);
+    
+    /// Signal emitted for each streamed combat log event (kind: 0=Damage, 1=Heal, 2=Buff, 3=Miss, 4=Death, 5=Experience)
+    #[signal]
+    fn combat_log_event(text: GString, kind: i64);
+    
+    /// Signal emitted when the server changes the local player's movement speed
+    #[signal]
+    fn movement_speed_changed(speed: f64);
+    
+    /// Signal emitted when the server reports regenerated health/mana or a combat/rest change
+    #[signal]
+    fn resources_updated(health: i64, max_health: i64, mana: i64, max_mana: i64, in_combat: bool, resting: bool);
+    
+    /// Signal emitted when the local player dies and the death penalty is applied
+    #[signal]
+    fn death_penalty(experience_lost: i64, durability: i64, auto_release_in: f64);
+    
+    /// Signal emitted when gear durability or the repair cost changes
+    #[signal]
+    fn durability_updated(durability: i64, max_durability: i64, repair_cost: i64);
+    
+    /// Signal emitted when a gear repair fails
+    #[signal]
+    fn repair_failed(reason: GString);
+    
+    /// Signal emitted when a boss encounter starts or its phase, health, immunity or enrage changes
+    #[signal]
+    fn boss_updated(enemy_id: i64, name: GString, phase: i64, phase_count: i64, phase_name: GString, health: i64, max_health: i64, immune: bool, enraged: bool);
+    
+    /// Signal emitted when a boss uses a scripted ability (radius 0 = no area telegraph)
+    #[signal]
+    fn boss_ability_used(enemy_id: i64, ability_name: GString, position: Vector3, radius: f64, wind_up: f64);
+    
+    /// Signal emitted when a boss encounter ends (defeated or reset)
+    #[signal]
+    fn boss_encounter_ended(enemy_id: i64, defeated: bool);
+    
+    /// Signal emitted when an enemy casts one of its abilities (target_id 0 = none)
+    #[signal]
+    fn enemy_ability_used(enemy_id: i64, ability_name: GString, target_id: i64);
+    
+    /// Signal emitted when a Metin stone appears
+    #[signal]
+    fn metin_stone_spawned(id: i64, name: GString, position: Vector3, health: i64, max_health: i64, level: i64);
+    
+    /// Signal emitted when a Metin stone's health or combat state changes
+    #[signal]
+    fn metin_stone_state(id: i64, health: i64, max_health: i64, under_attack: bool);
+    
+    /// Signal emitted when a Metin stone is removed (destroyed = broken by players)
+    #[signal]
+    fn metin_stone_despawned(id: i64, destroyed: bool);
 
     // ==========================================================================
     // Auth methods
@@ -476,6 +633,7 @@ impl Player {
         self.character_class = None;
         self.character_gender = None;
         self.character_empire = None;
+        self.specialization = None;
         // Reset zone state - ensures zone will be properly loaded on next login
         self.current_zone_id = 0;
         self.zone_ready = false;
@@ -564,6 +722,38 @@ impl Player {
         self.character_class.map(|c| c.as_u8() as i64).unwrap_or(-1)
     }
     
+    /// Get chosen specialization (-1 = not chosen yet)
+    #[func]
+    fn get_specialization(&self) -> i64 {
+        self.specialization.map(|s| s.as_u8() as i64).unwrap_or(-1)
+    }
+    
+    /// Get the display name of a specialization
+    #[func]
+    fn get_specialization_name(&self, specialization: i64) -> GString {
+        Specialization::from_u8(specialization as u8)
+            .map(|s| GString::from(s.name()))
+            .unwrap_or_default()
+    }
+    
+    /// Get the two specializations available to our class (empty if no character)
+    #[func]
+    fn get_specialization_options(&self) -> Array<i64> {
+        let mut options = Array::new();
+        if let Some(class) = self.character_class {
+            for spec in Specialization::for_class(class) {
+                options.push(spec.as_u8() as i64);
+            }
+        }
+        options
+    }
+    
+    /// Whether we can choose a specialization now
+    #[func]
+    fn can_choose_specialization(&self) -> bool {
+        self.specialization_level > 0 && self.specialization.is_none() && self.level >= self.specialization_level
+    }
+    
     /// Get character gender (0=Male, 1=Female)
     #[func]
     fn get_character_gender(&self) -> i64 {
@@ -602,6 +792,14 @@ impl Player {
         }
     }
     
+    /// Stop auto-attacking on the server
+    #[func]
+    fn stop_attack(&mut self) {
+        if let Some(ref mut network) = self.network {
+            network.send_stop_attack();
+        }
+    }
+    
     /// Pick up an item by entity ID
     #[func]
     fn pickup_item(&mut self, item_entity_id: i64) {
@@ -703,6 +901,42 @@ impl Player {
         self.gold as i64
     }
     
+    /// Get unspent stat points
+    #[func]
+    fn get_stat_points(&self) -> i64 {
+        self.stat_points as i64
+    }
+    
+    /// Get primary attributes as a dictionary (vitality, intelligence, strength, dexterity)
+    #[func]
+    fn get_attributes(&self) -> Dictionary {
+        let mut dict = Dictionary::new();
+        dict.set("vitality", self.attributes.vitality as i64);
+        dict.set("intelligence", self.attributes.intelligence as i64);
+        dict.set("strength", self.attributes.strength as i64);
+        dict.set("dexterity", self.attributes.dexterity as i64);
+        dict
+    }
+    
+    /// Get unspent skill points
+    #[func]
+    fn get_skill_points(&self) -> i64 {
+        self.skill_points as i64
+    }
+    
+    /// Get the rank of a skill (0 = not learned)
+    #[func]
+    fn get_skill_rank(&self, ability_id: i64) -> i64 {
+        self.skill_ranks.get(&(ability_id as u32)).copied().unwrap_or(0) as i64
+    }
+    
+    /// Get the display label of a skill's rank (e.g. "5", "M3", "G10", "P")
+    #[func]
+    fn get_skill_rank_label(&self, ability_id: i64) -> GString {
+        let rank = self.skill_ranks.get(&(ability_id as u32)).copied().unwrap_or(0);
+        GString::from(skill_rank_label(rank).as_str())
+    }
+    
     /// Get attack power
     #[func]
     fn get_attack_power(&self) -> i64 {
@@ -721,6 +955,12 @@ impl Player {
         self.attack_speed as f64
     }
     
+    /// Get current movement speed (set by the server from buffs, slows and gear)
+    #[func]
+    fn get_movement_speed(&self) -> f64 {
+        self.speed as f64
+    }
+    
     /// Get the current animation state as integer
     /// 0=Idle, 1=Walking, 2=Running, 3=Jumping, 4=Attacking, 5=TakingDamage, 6=Dying, 7=Dead
     #[func]
@@ -796,11 +1036,26 @@ impl Player {
     }
     
     /// Request respawn from server
-    /// respawn_type: 0 = at empire spawn (full health), 1 = at death location (20% health)
+    /// respawn_type: 0 = at empire spawn (full health), 1 = at death location (reduced health)
     #[func]
     fn request_respawn(&mut self, respawn_type: i64) {
+        let respawn_type = RespawnType::from_u8(respawn_type as u8).unwrap_or(RespawnType::Town);
         if let Some(ref mut network) = self.network {
-            network.send_respawn_request(respawn_type as u8);
+            network.send_respawn_request(respawn_type);
+        }
+    }
+    
+    /// Get the current gear durability
+    #[func]
+    fn get_durability(&self) -> i64 {
+        self.durability as i64
+    }
+    
+    /// Request a gear repair (costs gold)
+    #[func]
+    fn repair_equipment(&mut self) {
+        if let Some(ref mut network) = self.network {
+            network.send_repair_equipment();
         }
     }
     
@@ -826,6 +1081,16 @@ impl Player {
         self.equipped_weapon_id.map(|id| id as i64).unwrap_or(-1)
     }
     
+    /// Whether the equipped weapon attacks from range (bows)
+    #[func]
+    fn has_ranged_weapon(&self) -> bool {
+        self.equipped_weapon_id
+            .and_then(|weapon_id| get_item_definitions().into_iter().find(|item| item.id == weapon_id))
+            .and_then(|item| item.weapon_stats)
+            .map(|stats| stats.visual_type.is_ranged())
+            .unwrap_or(false)
+    }
+    
     /// Get currently equipped armor item ID (-1 if no armor)
     #[func]
     fn get_equipped_armor_id(&self) -> i64 {
@@ -872,6 +1137,82 @@ impl Player {
         }
     }
     
+    /// Cancel the current cast or channel
+    #[func]
+    fn cancel_cast(&mut self) {
+        if let Some(ref mut network) = self.network {
+            network.send_cancel_cast();
+        }
+    }
+    
+    /// Choose a class specialization (see get_specialization_options)
+    #[func]
+    fn choose_specialization(&mut self, specialization: i64) {
+        let spec = match Specialization::from_u8(specialization as u8) {
+            Some(s) => s,
+            None => return,
+        };
+        if let Some(ref mut network) = self.network {
+            network.send_choose_specialization(spec);
+        }
+    }
+    
+    /// Spend stat points on an attribute (0=VIT, 1=INT, 2=STR, 3=DEX)
+    #[func]
+    fn allocate_stat(&mut self, stat: i64, amount: i64) {
+        let stat = match PrimaryStat::from_u8(stat as u8) {
+            Some(s) => s,
+            None => return,
+        };
+        if let Some(ref mut network) = self.network {
+            network.send_allocate_stat(stat, amount.max(0) as u32);
+        }
+    }
+    
+    /// Reset all spent stat points for gold
+    #[func]
+    fn reset_stats(&mut self) {
+        if let Some(ref mut network) = self.network {
+            network.send_reset_stats();
+        }
+    }
+    
+    /// Sit down to rest (faster regeneration) or stand up
+    #[func]
+    fn set_resting(&mut self, resting: bool) {
+        if let Some(ref mut network) = self.network {
+            network.send_set_resting(resting);
+        }
+    }
+    
+    /// Check if the local player is sitting down to rest
+    #[func]
+    fn is_resting(&self) -> bool {
+        self.is_resting
+    }
+    
+    /// Check if the local player is in combat
+    #[func]
+    fn is_in_combat(&self) -> bool {
+        self.in_combat
+    }
+    
+    /// Opt in or out of streaming combat log events
+    #[func]
+    fn set_combat_log_streaming(&mut self, enabled: bool) {
+        if let Some(ref mut network) = self.network {
+            network.send_set_combat_log_streaming(enabled);
+        }
+    }
+    
+    /// Spend skill points to learn or train a skill
+    #[func]
+    fn upgrade_skill(&mut self, ability_id: i64) {
+        if let Some(ref mut network) = self.network {
+            network.send_upgrade_skill(ability_id as u32);
+        }
+    }
+    
     /// Called by ZoneManager when zone is fully loaded and ready
     /// This enables physics/gravity - prevents falling through void before ground exists
     #[func]
@@ -1118,6 +1459,30 @@ impl Player {
         }
     }
     
+    /// Local player came back to life (released or resurrected)
+    fn on_local_respawn(&mut self, position: [f32; 3], health: u32, max_health: u32) {
+        self.is_dead = false;
+        self.death_position = None;
+        self.current_health = health;
+        self.max_health = max_health;
+        self.animation_state = AnimationState::Idle;
+        
+        let pos = Vector3::new(position[0], position[1], position[2]);
+        self.base_mut().set_position(pos);
+        
+        self.base_mut().emit_signal("player_respawned", &[
+            pos.to_variant(),
+            (health as i64).to_variant(),
+            (max_health as i64).to_variant(),
+        ]);
+        
+        // Also emit health_changed for UI update
+        self.base_mut().emit_signal("health_changed", &[
+            (health as i64).to_variant(),
+            (max_health as i64).to_variant(),
+        ]);
+    }
+    
     /// Handle a message from the server
     fn handle_server_message(&mut self, msg: ServerMessage) {
         match msg {
@@ -1189,6 +1554,8 @@ impl Player {
                 equipped_weapon_id,
                 equipped_armor_id,
                 gold,
+                specialization,
+                specialization_level,
             } => {
                 // Store character info
                 self.character_id = Some(character_id);
@@ -1196,6 +1563,8 @@ impl Player {
                 self.character_class = Some(class);
                 self.character_gender = Some(gender);
                 self.character_empire = Some(empire);
+                self.specialization = specialization;
+                self.specialization_level = specialization_level;
                 self.current_zone_id = zone_id;
                 
                 // Use character_id as player_id for game world
@@ -1359,12 +1728,14 @@ impl Player {
                 ]);
             }
             
-            ServerMessage::DamageEvent { attacker_id, target_id, damage, target_new_health, is_critical } => {
+            ServerMessage::DamageEvent { attacker_id, target_id, damage, target_new_health, outcome } => {
+                let is_critical = outcome == HitOutcome::Critical;
                 self.base_mut().emit_signal("damage_dealt", &[
                     (attacker_id as i64).to_variant(),
                     (target_id as i64).to_variant(),
                     (damage as i64).to_variant(),
                     is_critical.to_variant(),
+                    (outcome.as_u8() as i64).to_variant(),
                 ]);
                 
                 // If we were the target, update our health
@@ -1380,13 +1751,15 @@ impl Player {
                 }
             }
             
-            ServerMessage::EnemySpawn { id, zone_id: _, enemy_type, position, health, max_health, level } => {
+            ServerMessage::EnemySpawn { id, zone_id: _, enemy_type, name, position, health, max_health, level } => {
                 let pos = Vector3::new(position[0], position[1], position[2]);
                 let enemy_type_int = match enemy_type {
                     mmo_shared::EnemyType::Goblin => 0,
                     mmo_shared::EnemyType::Skeleton => 1,
                     mmo_shared::EnemyType::Mutant => 2,
                     mmo_shared::EnemyType::Wolf => 3,
+                    mmo_shared::EnemyType::SkeletonArcher => 4,
+                    mmo_shared::EnemyType::GoblinShaman => 5,
                 };
                 self.base_mut().emit_signal("enemy_spawned", &[
                     (id as i64).to_variant(),
@@ -1395,6 +1768,7 @@ impl Player {
                     (health as i64).to_variant(),
                     (max_health as i64).to_variant(),
                     (level as i64).to_variant(),
+                    GString::from(&name).to_variant(),
                 ]);
             }
             
@@ -1425,35 +1799,115 @@ impl Player {
             }
             
             ServerMessage::PlayerRespawned { position, health, max_health } => {
-                // Local player respawned
-                self.is_dead = false;
-                self.death_position = None;
-                self.current_health = health;
-                self.max_health = max_health;
-                self.animation_state = AnimationState::Idle;
-                
+                self.on_local_respawn(position, health, max_health);
+            }
+            
+            ServerMessage::EntityRespawn { entity_id, position, health }
+                if self.is_dead && Some(entity_id) == self.player_id =>
+            {
+                // Resurrected by another player
+                let max_health = self.max_health;
+                self.on_local_respawn(position, health, max_health);
+            }
+            
+            ServerMessage::EntityRespawn { entity_id, position, health } => {
                 let pos = Vector3::new(position[0], position[1], position[2]);
-                self.base_mut().set_position(pos);
-                
-                self.base_mut().emit_signal("player_respawned", &[
+                self.base_mut().emit_signal("entity_respawned", &[
+                    (entity_id as i64).to_variant(),
                     pos.to_variant(),
                     (health as i64).to_variant(),
+                ]);
+            }
+            
+            ServerMessage::DeathPenalty { experience_lost, durability, auto_release_in } => {
+                self.durability = durability;
+                self.base_mut().emit_signal("death_penalty", &[
+                    (experience_lost as i64).to_variant(),
+                    (durability as i64).to_variant(),
+                    (auto_release_in as f64).to_variant(),
+                ]);
+            }
+            
+            ServerMessage::DurabilityUpdate { durability, max_durability, repair_cost } => {
+                self.durability = durability;
+                self.base_mut().emit_signal("durability_updated", &[
+                    (durability as i64).to_variant(),
+                    (max_durability as i64).to_variant(),
+                    (repair_cost as i64).to_variant(),
+                ]);
+            }
+            
+            ServerMessage::RepairFailed { reason } => {
+                self.base_mut().emit_signal("repair_failed", &[GString::from(&reason).to_variant()]);
+            }
+            
+            ServerMessage::BossUpdate { enemy_id, name, phase, phase_count, phase_name, health, max_health, immune, enraged } => {
+                self.base_mut().emit_signal("boss_updated", &[
+                    (enemy_id as i64).to_variant(),
+                    GString::from(&name).to_variant(),
+                    (phase as i64).to_variant(),
+                    (phase_count as i64).to_variant(),
+                    GString::from(&phase_name).to_variant(),
+                    (health as i64).to_variant(),
                     (max_health as i64).to_variant(),
+                    immune.to_variant(),
+                    enraged.to_variant(),
                 ]);
-                
-                // Also emit health_changed for UI update
-                self.base_mut().emit_signal("health_changed", &[
+            }
+            
+            ServerMessage::BossAbilityUsed { enemy_id, ability_name, position, radius, wind_up } => {
+                self.base_mut().emit_signal("boss_ability_used", &[
+                    (enemy_id as i64).to_variant(),
+                    GString::from(&ability_name).to_variant(),
+                    Vector3::new(position[0], position[1], position[2]).to_variant(),
+                    (radius as f64).to_variant(),
+                    (wind_up as f64).to_variant(),
+                ]);
+            }
+            
+            ServerMessage::BossEncounterEnded { enemy_id, defeated } => {
+                self.base_mut().emit_signal("boss_encounter_ended", &[
+                    (enemy_id as i64).to_variant(),
+                    defeated.to_variant(),
+                ]);
+            }
+            
+            ServerMessage::EnemyAbilityUsed { enemy_id, ability_name, target_id } => {
+                self.base_mut().emit_signal("enemy_ability_used", &[
+                    (enemy_id as i64).to_variant(),
+                    GString::from(&ability_name).to_variant(),
+                    (target_id.unwrap_or(0) as i64).to_variant(),
+                ]);
+            }
+            
+            ServerMessage::MetinStoneSpawn { id, zone_id, name, position, health, max_health, level } => {
+                // Only show stones in our zone
+                if zone_id != self.current_zone_id {
+                    return;
+                }
+                self.base_mut().emit_signal("metin_stone_spawned", &[
+                    (id as i64).to_variant(),
+                    GString::from(&name).to_variant(),
+                    Vector3::new(position[0], position[1], position[2]).to_variant(),
                     (health as i64).to_variant(),
                     (max_health as i64).to_variant(),
+                    (level as i64).to_variant(),
                 ]);
             }
             
-            ServerMessage::EntityRespawn { entity_id, position, health } => {
-                let pos = Vector3::new(position[0], position[1], position[2]);
-                self.base_mut().emit_signal("entity_respawned", &[
-                    (entity_id as i64).to_variant(),
-                    pos.to_variant(),
+            ServerMessage::MetinStoneState { id, health, max_health, under_attack } => {
+                self.base_mut().emit_signal("metin_stone_state", &[
+                    (id as i64).to_variant(),
                     (health as i64).to_variant(),
+                    (max_health as i64).to_variant(),
+                    under_attack.to_variant(),
+                ]);
+            }
+            
+            ServerMessage::MetinStoneDespawn { id, destroyed } => {
+                self.base_mut().emit_signal("metin_stone_despawned", &[
+                    (id as i64).to_variant(),
+                    destroyed.to_variant(),
                 ]);
             }
             
@@ -1466,12 +1920,13 @@ impl Player {
                 ]);
             }
             
-            ServerMessage::TimeSync { unix_timestamp, latitude, longitude } => {
+            ServerMessage::TimeSync { unix_timestamp, latitude, longitude, weather } => {
                 // Emit signal for day/night controller
                 self.base_mut().emit_signal("time_sync", &[
                     unix_timestamp.to_variant(),
                     (latitude as f64).to_variant(),
                     (longitude as f64).to_variant(),
+                    (weather.as_u8() as i64).to_variant(),
                 ]);
             }
             
@@ -1544,8 +1999,10 @@ impl Player {
                 ]);
             }
             
-            ServerMessage::LevelUp { new_level, max_health, max_mana, attack, defense } => {
+            ServerMessage::LevelUp { new_level, max_health, max_mana, attack, defense, skill_points, stat_points } => {
                 self.level = new_level;
+                self.skill_points = skill_points;
+                self.stat_points = stat_points;
                 self.max_health = max_health;
                 self.max_mana = max_mana;
                 self.attack_power = attack;
@@ -1630,6 +2087,160 @@ impl Player {
                 ]);
             }
             
+            ServerMessage::AbilityDefinitions { abilities } => {
+                let mut ability_array = Array::new();
+                for a in abilities {
+                    let target_type = match a.target_type {
+                        TargetType::Enemy => "enemy",
+                        TargetType::SelfOnly => "self",
+                        TargetType::Ally => "ally",
+                        TargetType::None => "none",
+                        TargetType::AreaAroundSelf => "area_self",
+                        TargetType::AreaAroundTarget => "area_target",
+                    };
+                    let mut dict = Dictionary::new();
+                    dict.set("id", a.id as i64);
+                    dict.set("name", GString::from(&a.name));
+                    dict.set("description", GString::from(&a.description));
+                    dict.set("mana_cost", a.mana_cost as i64);
+                    dict.set("cooldown", a.cooldown as f64);
+                    dict.set("cast_time", a.cast_time as f64);
+                    dict.set("channel_duration", a.channel_duration as f64);
+                    dict.set("range", a.range as f64);
+                    dict.set("target_type", GString::from(target_type));
+                    dict.set("class_restriction", a.class_restriction.map(|c| c.as_u8() as i64).unwrap_or(-1));
+                    dict.set("specialization", a.specialization.map(|s| s.as_u8() as i64).unwrap_or(-1));
+                    dict.set("level_requirement", a.level_requirement as i64);
+                    dict.set("icon", GString::from(&a.icon));
+                    ability_array.push(&dict);
+                }
+                self.base_mut().emit_signal("ability_definitions_received", &[ability_array.to_variant()]);
+            }
+            
+            ServerMessage::SkillList { skills, skill_points } => {
+                self.skill_ranks = skills.iter().map(|s| (s.ability_id, s.rank)).collect();
+                self.skill_points = skill_points;
+                
+                let mut dict = Dictionary::new();
+                for skill in &skills {
+                    dict.set(skill.ability_id as i64, skill.rank as i64);
+                }
+                self.base_mut().emit_signal("skill_list_received", &[
+                    dict.to_variant(),
+                    (skill_points as i64).to_variant(),
+                ]);
+            }
+            
+            ServerMessage::SkillUpdate { ability_id, rank, skill_points } => {
+                self.skill_ranks.insert(ability_id, rank);
+                self.skill_points = skill_points;
+                self.base_mut().emit_signal("skill_updated", &[
+                    (ability_id as i64).to_variant(),
+                    (rank as i64).to_variant(),
+                    (skill_points as i64).to_variant(),
+                ]);
+            }
+            
+            ServerMessage::SpecializationChosen { specialization, max_health, max_mana, attack, defense } => {
+                self.specialization = Some(specialization);
+                self.max_health = max_health;
+                self.max_mana = max_mana;
+                self.attack_power = attack;
+                self.defense = defense;
+                // Stats are recalculated at full health and mana
+                self.current_health = max_health;
+                self.current_mana = max_mana;
+                
+                self.base_mut().emit_signal("specialization_chosen", &[
+                    (specialization.as_u8() as i64).to_variant(),
+                    (max_health as i64).to_variant(),
+                    (max_mana as i64).to_variant(),
+                    (attack as i64).to_variant(),
+                    (defense as i64).to_variant(),
+                ]);
+                self.base_mut().emit_signal("health_changed", &[
+                    (max_health as i64).to_variant(),
+                    (max_health as i64).to_variant(),
+                ]);
+            }
+            
+            ServerMessage::AttributesUpdate {
+                attributes, stat_points, max_health, max_mana, attack, defense,
+                attack_speed, crit_chance, health, mana,
+            } => {
+                self.attributes = attributes;
+                self.stat_points = stat_points;
+                self.max_health = max_health;
+                self.max_mana = max_mana;
+                self.attack_power = attack;
+                self.defense = defense;
+                self.attack_speed = attack_speed;
+                self.current_health = health;
+                self.current_mana = mana;
+                
+                let attributes_dict = self.get_attributes();
+                self.base_mut().emit_signal("attributes_updated", &[
+                    attributes_dict.to_variant(),
+                    (stat_points as i64).to_variant(),
+                    (attack_speed as f64).to_variant(),
+                    (crit_chance as f64).to_variant(),
+                ]);
+                self.base_mut().emit_signal("health_changed", &[
+                    (health as i64).to_variant(),
+                    (max_health as i64).to_variant(),
+                ]);
+            }
+            
+            ServerMessage::StatAllocationFailed { reason } => {
+                self.base_mut().emit_signal("stat_allocation_failed", &[GString::from(&reason).to_variant()]);
+            }
+            
+            ServerMessage::ResourceUpdate { health, max_health, mana, max_mana, in_combat, resting } => {
+                self.current_health = health;
+                self.max_health = max_health;
+                self.current_mana = mana;
+                self.max_mana = max_mana;
+                self.in_combat = in_combat;
+                self.is_resting = resting;
+                
+                self.base_mut().emit_signal("resources_updated", &[
+                    (health as i64).to_variant(),
+                    (max_health as i64).to_variant(),
+                    (mana as i64).to_variant(),
+                    (max_mana as i64).to_variant(),
+                    in_combat.to_variant(),
+                    resting.to_variant(),
+                ]);
+                // Also emit health_changed for UI update
+                self.base_mut().emit_signal("health_changed", &[
+                    (health as i64).to_variant(),
+                    (max_health as i64).to_variant(),
+                ]);
+            }
+            
+            ServerMessage::MovementSpeedUpdate { speed } => {
+                self.speed = speed;
+                self.base_mut().emit_signal("movement_speed_changed", &[(speed as f64).to_variant()]);
+            }
+            
+            ServerMessage::CombatLogEvent { entry } => {
+                self.base_mut().emit_signal("combat_log_event", &[
+                    GString::from(&entry.describe()).to_variant(),
+                    (entry.kind as u8 as i64).to_variant(),
+                ]);
+            }
+            
+            ServerMessage::SpecializationFailed { reason } => {
+                self.base_mut().emit_signal("specialization_failed", &[GString::from(&reason).to_variant()]);
+            }
+            
+            ServerMessage::SkillUpgradeFailed { ability_id, reason } => {
+                self.base_mut().emit_signal("skill_upgrade_failed", &[
+                    (ability_id as i64).to_variant(),
+                    GString::from(&reason).to_variant(),
+                ]);
+            }
+            
             ServerMessage::ActionBarUpdate { slots } => {
                 let mut arr = Array::new();
                 for slot in slots.iter() {
@@ -1638,6 +2249,78 @@ impl Player {
                 self.base_mut().emit_signal("action_bar_received", &[arr.to_variant()]);
             }
             
+            ServerMessage::CastStarted { caster_id, ability_id, target_id, duration, is_channel } => {
+                self.base_mut().emit_signal("cast_started", &[
+                    (caster_id as i64).to_variant(),
+                    (ability_id as i64).to_variant(),
+                    (target_id.unwrap_or(0) as i64).to_variant(),
+                    (duration as f64).to_variant(),
+                    is_channel.to_variant(),
+                ]);
+            }
+            
+            ServerMessage::CastProgress { caster_id, ability_id, elapsed, duration } => {
+                self.base_mut().emit_signal("cast_progress", &[
+                    (caster_id as i64).to_variant(),
+                    (ability_id as i64).to_variant(),
+                    (elapsed as f64).to_variant(),
+                    (duration as f64).to_variant(),
+                ]);
+            }
+            
+            ServerMessage::CastCompleted { caster_id, ability_id } => {
+                self.base_mut().emit_signal("cast_completed", &[
+                    (caster_id as i64).to_variant(),
+                    (ability_id as i64).to_variant(),
+                ]);
+            }
+            
+            ServerMessage::CastInterrupted { caster_id, ability_id, reason } => {
+                self.base_mut().emit_signal("cast_interrupted", &[
+                    (caster_id as i64).to_variant(),
+                    (ability_id as i64).to_variant(),
+                    GString::from(&reason).to_variant(),
+                ]);
+            }
+            
+            ServerMessage::AutoAttackStarted { target_id, swing_interval } => {
+                self.base_mut().emit_signal("auto_attack_started", &[
+                    (target_id as i64).to_variant(),
+                    (swing_interval as f64).to_variant(),
+                ]);
+            }
+            
+            ServerMessage::AutoAttackStopped { target_id, reason } => {
+                self.base_mut().emit_signal("auto_attack_stopped", &[
+                    (target_id as i64).to_variant(),
+                    GString::from(&reason).to_variant(),
+                ]);
+            }
+            
+            ServerMessage::ProjectileSpawn { id, zone_id, owner_id, target_id, ability_id, position, velocity, homing } => {
+                // Only show projectiles in our zone
+                if zone_id != self.current_zone_id {
+                    return;
+                }
+                self.base_mut().emit_signal("projectile_spawned", &[
+                    (id as i64).to_variant(),
+                    (owner_id as i64).to_variant(),
+                    (target_id.unwrap_or(0) as i64).to_variant(),
+                    (ability_id.unwrap_or(0) as i64).to_variant(),
+                    Vector3::new(position[0], position[1], position[2]).to_variant(),
+                    Vector3::new(velocity[0], velocity[1], velocity[2]).to_variant(),
+                    homing.to_variant(),
+                ]);
+            }
+            
+            ServerMessage::ProjectileImpact { id, target_id, position } => {
+                self.base_mut().emit_signal("projectile_impact", &[
+                    (id as i64).to_variant(),
+                    (target_id.unwrap_or(0) as i64).to_variant(),
+                    Vector3::new(position[0], position[1], position[2]).to_variant(),
+                ]);
+            }
+            
             ServerMessage::Teleport { position } => {
                 // Server is forcing our position (e.g., /reset or /tp command)
                 let pos = Vector3::new(position[0], position[1], position[2]);
@@ -0,0 +1,71 @@
+-- Abilities Migration
+-- Moves ability definitions into the database so they can be rebalanced without recompiling
+
+-- =============================================================================
+-- Abilities Table: Master ability definitions
+-- =============================================================================
+-- Target types: 0=SelfOnly, 1=Enemy, 2=Ally, 3=None, 4=AreaAroundSelf, 5=AreaAroundTarget
+-- Class restriction: NULL=Any, 0=Ninja, 1=Warrior, 2=Sura, 3=Shaman
+-- Effects use the serialized AbilityEffect format, e.g. [{"Damage": {"base": 5, "attack_scaling": 1.5}}]
+
+CREATE TABLE IF NOT EXISTS abilities (
+    id INTEGER PRIMARY KEY,
+    name VARCHAR(64) NOT NULL,
+    description TEXT NOT NULL,
+    mana_cost INTEGER NOT NULL DEFAULT 0 CHECK (mana_cost >= 0),
+    cooldown REAL NOT NULL DEFAULT 0,
+    cast_time REAL NOT NULL DEFAULT 0,
+    channel_duration REAL NOT NULL DEFAULT 0,
+    projectile_speed REAL NOT NULL DEFAULT 0,
+    range REAL NOT NULL DEFAULT 0,
+    target_type SMALLINT NOT NULL CHECK (target_type >= 0 AND target_type <= 5),
+    class_restriction SMALLINT CHECK (class_restriction IS NULL OR (class_restriction >= 0 AND class_restriction <= 3)),
+    level_requirement INTEGER NOT NULL DEFAULT 1,
+    effects JSONB NOT NULL DEFAULT '[]',
+    icon VARCHAR(64) NOT NULL DEFAULT ''
+);
+
+-- =============================================================================
+-- Seed Data: Abilities
+-- =============================================================================
+
+INSERT INTO abilities (id, name, description, mana_cost, cooldown, cast_time, channel_duration, projectile_speed, range, target_type, class_restriction, level_requirement, effects, icon) VALUES
+-- Universal abilities (IDs 1-10)
+(1, 'Power Strike', 'A powerful melee attack dealing 150% weapon damage.', 10, 6.0, 0, 0, 0, 3.0, 1, NULL, 1,
+    '[{"Damage": {"base": 5, "attack_scaling": 1.5}}]', 'power_strike.png'),
+(2, 'Recuperate', 'Restore 20% of your maximum health over 10 seconds.', 20, 30.0, 0, 0, 0, 0.0, 0, NULL, 1,
+    '[{"HealOverTime": {"heal_per_tick": 0, "interval": 1.0, "duration": 10.0}}]', 'recuperate.png'),
+-- Ninja abilities (IDs 11-20)
+(11, 'Shadow Strike', 'Strike from the shadows, dealing 200% weapon damage with increased critical chance.', 15, 8.0, 0, 0, 0, 3.0, 1, 0, 1,
+    '[{"Damage": {"base": 8, "attack_scaling": 2.0}}]', 'shadow_strike.png'),
+(12, 'Poison Blade', 'Coat your weapon in poison, causing the target to take damage over 8 seconds.', 20, 12.0, 0, 0, 0, 3.0, 1, 0, 5,
+    '[{"Damage": {"base": 5, "attack_scaling": 0.5}}, {"DamageOverTime": {"damage_per_tick": 8, "interval": 2.0, "duration": 8.0}}]', 'poison_blade.png'),
+-- Warrior abilities (IDs 21-30)
+(21, 'Crushing Blow', 'A devastating attack dealing 180% weapon damage and reducing enemy defense.', 20, 10.0, 0, 0, 0, 3.0, 1, 1, 1,
+    '[{"Damage": {"base": 10, "attack_scaling": 1.8}}, {"DebuffDefense": {"amount": 5, "duration": 10.0}}]', 'crushing_blow.png'),
+(22, 'Battle Cry', 'Let out a battle cry, increasing your attack by 20% for 15 seconds.', 25, 45.0, 0, 0, 0, 0.0, 0, 1, 5,
+    '[{"BuffAttack": {"amount": 10, "duration": 15.0}}]', 'battle_cry.png'),
+-- Sura abilities (IDs 31-40)
+(31, 'Dark Slash', 'Channel dark energy into your blade, dealing 170% weapon damage.', 15, 7.0, 0, 0, 0, 3.0, 1, 2, 1,
+    '[{"Damage": {"base": 8, "attack_scaling": 1.7}}]', 'dark_slash.png'),
+(32, 'Life Drain', 'Drain life from your enemy, dealing damage and healing yourself.', 30, 15.0, 0, 0, 0, 5.0, 1, 2, 5,
+    '[{"Damage": {"base": 15, "attack_scaling": 1.0}}, {"Heal": {"base": 20, "health_scaling": 0.1}}]', 'life_drain.png'),
+-- Shaman abilities (IDs 41-50)
+(41, 'Lightning Bolt', 'Hurl a bolt of lightning at your enemy, dealing magic damage.', 20, 5.0, 1.5, 0, 30.0, 15.0, 1, 3, 1,
+    '[{"Damage": {"base": 25, "attack_scaling": 0.8}}]', 'lightning_bolt.png'),
+(42, 'Healing Wave', 'Channel healing energy to restore health over 3 seconds.', 35, 12.0, 0, 3.0, 0, 0.0, 0, 3, 5,
+    '[{"Heal": {"base": 17, "health_scaling": 0.05}}]', 'healing_wave.png')
+ON CONFLICT (id) DO UPDATE SET
+    name = EXCLUDED.name,
+    description = EXCLUDED.description,
+    mana_cost = EXCLUDED.mana_cost,
+    cooldown = EXCLUDED.cooldown,
+    cast_time = EXCLUDED.cast_time,
+    channel_duration = EXCLUDED.channel_duration,
+    projectile_speed = EXCLUDED.projectile_speed,
+    range = EXCLUDED.range,
+    target_type = EXCLUDED.target_type,
+    class_restriction = EXCLUDED.class_restriction,
+    level_requirement = EXCLUDED.level_requirement,
+    effects = EXCLUDED.effects,
+    icon = EXCLUDED.icon;
@@ -0,0 +1,30 @@
+-- Skills Migration
+-- Adds per-character skill ranks and unspent skill points
+
+-- =============================================================================
+-- Character Skills Table: Learned skills and their rank
+-- =============================================================================
+-- Rank: 1-19 = Normal, 20-29 = Master (M1-M10), 30-39 = Grand Master (G1-G10), 40 = Perfect Master
+
+CREATE TABLE IF NOT EXISTS character_skills (
+    character_id BIGINT NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    ability_id INTEGER NOT NULL REFERENCES abilities(id) ON DELETE CASCADE,
+    rank SMALLINT NOT NULL DEFAULT 1 CHECK (rank >= 1 AND rank <= 40),
+    PRIMARY KEY (character_id, ability_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_character_skills_character ON character_skills(character_id);
+
+-- =============================================================================
+-- Add skill_points column to player_state table
+-- =============================================================================
+
+-- Added without a default so rows from before this migration stay NULL until backfilled;
+-- re-running the file finds no NULLs and leaves spent points alone
+ALTER TABLE player_state ADD COLUMN IF NOT EXISTS skill_points INTEGER;
+
+-- Existing characters get the points they would have earned so far (1 per level up)
+UPDATE player_state SET skill_points = GREATEST(level - 1, 0) WHERE skill_points IS NULL;
+
+ALTER TABLE player_state ALTER COLUMN skill_points SET DEFAULT 0;
+ALTER TABLE player_state ALTER COLUMN skill_points SET NOT NULL;
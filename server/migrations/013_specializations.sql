@@ -0,0 +1,62 @@
+-- Specializations Migration
+-- Adds a per-character class specialization (Metin2-style doctrine) and specialization-only abilities
+
+-- =============================================================================
+-- Add specialization column to characters table
+-- =============================================================================
+-- Specialization values: NULL=Not chosen yet
+--   0=Blade Fight, 1=Archery (Ninja)
+--   2=Body, 3=Mental (Warrior)
+--   4=Weaponry, 5=Black Magic (Sura)
+--   6=Dragon Force, 7=Healing Force (Shaman)
+
+ALTER TABLE characters ADD COLUMN IF NOT EXISTS specialization SMALLINT
+    CHECK (specialization IS NULL OR (specialization >= 0 AND specialization <= 7));
+
+-- =============================================================================
+-- Add specialization restriction to abilities table
+-- =============================================================================
+
+ALTER TABLE abilities ADD COLUMN IF NOT EXISTS specialization SMALLINT
+    CHECK (specialization IS NULL OR (specialization >= 0 AND specialization <= 7));
+
+-- =============================================================================
+-- Seed Data: Specialization Abilities
+-- =============================================================================
+
+INSERT INTO abilities (id, name, description, mana_cost, cooldown, cast_time, channel_duration, projectile_speed, range, target_type, class_restriction, specialization, level_requirement, effects, icon) VALUES
+-- Ninja
+(13, 'Ambush', 'Blade Fight: leap at your enemy from hiding, dealing 240% weapon damage.', 25, 10.0, 0, 0, 0, 3.0, 1, 0, 0, 5,
+    '[{"Damage": {"base": 12, "attack_scaling": 2.4}}]', 'ambush.png'),
+(14, 'Fire Arrow', 'Archery: loose a burning arrow that sets the target on fire.', 20, 6.0, 1.0, 0, 35.0, 18.0, 1, 0, 1, 5,
+    '[{"Damage": {"base": 10, "attack_scaling": 1.6}}, {"DamageOverTime": {"damage_per_tick": 5, "interval": 1.0, "duration": 5.0}}]', 'fire_arrow.png'),
+-- Warrior
+(23, 'Sword Spin', 'Body Warrior: a whirling strike that also quickens your attacks for 8 seconds.', 25, 9.0, 0, 0, 0, 3.0, 1, 1, 2, 5,
+    '[{"Damage": {"base": 15, "attack_scaling": 2.0}}, {"BuffAttackSpeed": {"multiplier": 1.2, "duration": 8.0}}]', 'sword_spin.png'),
+(24, 'Strong Body', 'Mental Warrior: harden your body, increasing defense for 20 seconds.', 30, 40.0, 0, 0, 0, 0.0, 0, 1, 3, 5,
+    '[{"BuffDefense": {"amount": 15, "duration": 20.0}}]', 'strong_body.png'),
+-- Sura
+(33, 'Enchanted Blade', 'Weaponry: bind dark power to your weapon, increasing attack for 20 seconds.', 25, 40.0, 0, 0, 0, 0.0, 0, 2, 4, 5,
+    '[{"BuffAttack": {"amount": 12, "duration": 20.0}}]', 'enchanted_blade.png'),
+(34, 'Dark Orb', 'Black Magic: hurl an orb of dark energy at your enemy.', 30, 6.0, 1.5, 0, 25.0, 15.0, 1, 2, 5, 5,
+    '[{"Damage": {"base": 30, "attack_scaling": 1.0}}]', 'dark_orb.png'),
+-- Shaman
+(43, 'Dragon''s Roar', 'Dragon Force: call the dragon''s fury down on your enemy.', 30, 8.0, 1.0, 0, 0, 12.0, 1, 3, 6, 5,
+    '[{"Damage": {"base": 35, "attack_scaling": 0.7}}]', 'dragons_roar.png'),
+(44, 'Blessing', 'Healing Force: heal yourself instantly and keep healing for 6 seconds.', 40, 20.0, 0, 0, 0, 0.0, 0, 3, 7, 5,
+    '[{"Heal": {"base": 30, "health_scaling": 0.1}}, {"HealOverTime": {"heal_per_tick": 6, "interval": 1.0, "duration": 6.0}}]', 'blessing.png')
+ON CONFLICT (id) DO UPDATE SET
+    name = EXCLUDED.name,
+    description = EXCLUDED.description,
+    mana_cost = EXCLUDED.mana_cost,
+    cooldown = EXCLUDED.cooldown,
+    cast_time = EXCLUDED.cast_time,
+    channel_duration = EXCLUDED.channel_duration,
+    projectile_speed = EXCLUDED.projectile_speed,
+    range = EXCLUDED.range,
+    target_type = EXCLUDED.target_type,
+    class_restriction = EXCLUDED.class_restriction,
+    specialization = EXCLUDED.specialization,
+    level_requirement = EXCLUDED.level_requirement,
+    effects = EXCLUDED.effects,
+    icon = EXCLUDED.icon;
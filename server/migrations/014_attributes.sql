@@ -0,0 +1,36 @@
+-- Attributes Migration
+-- Adds allocatable primary attributes (VIT/INT/STR/DEX) and stat points to player_state
+
+-- =============================================================================
+-- Add attribute columns to player_state table
+-- =============================================================================
+-- Attributes start at class values and grow with spent stat points (max 90 each)
+
+ALTER TABLE player_state ADD COLUMN IF NOT EXISTS vitality INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE player_state ADD COLUMN IF NOT EXISTS intelligence INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE player_state ADD COLUMN IF NOT EXISTS strength INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE player_state ADD COLUMN IF NOT EXISTS dexterity INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE player_state ADD COLUMN IF NOT EXISTS stat_points INTEGER NOT NULL DEFAULT 0;
+
+-- Existing characters start at their class values (0=Ninja, 1=Warrior, 2=Sura, 3=Shaman)
+UPDATE player_state s SET
+    vitality = CASE c.class WHEN 0 THEN 4 WHEN 1 THEN 4 WHEN 2 THEN 3 ELSE 3 END,
+    intelligence = CASE c.class WHEN 0 THEN 3 WHEN 1 THEN 3 WHEN 2 THEN 5 ELSE 6 END,
+    strength = CASE c.class WHEN 0 THEN 4 WHEN 1 THEN 6 WHEN 2 THEN 5 ELSE 3 END,
+    dexterity = CASE c.class WHEN 0 THEN 6 WHEN 1 THEN 3 WHEN 2 THEN 3 ELSE 4 END,
+    -- They get the points they would have earned so far (3 per level up)
+    stat_points = GREATEST(s.level - 1, 0) * 3
+FROM characters c
+WHERE c.id = s.character_id AND s.vitality = 0;
+
+-- =============================================================================
+-- Scroll of Forgetting: free attribute reset
+-- =============================================================================
+-- item_type 0 = Consumable, rarity 2 = Rare
+
+INSERT INTO items (id, name, description, item_type, rarity, max_stack, effects)
+VALUES (101, 'Scroll of Forgetting', 'Refunds all spent stat points.', 0, 2, 5, '[{"ResetStats": true}]')
+ON CONFLICT (id) DO UPDATE SET
+    name = EXCLUDED.name,
+    description = EXCLUDED.description,
+    effects = EXCLUDED.effects;
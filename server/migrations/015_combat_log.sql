@@ -0,0 +1,36 @@
+-- Combat Log Migration
+-- Adds a rolling store of combat events for balance debugging
+
+-- =============================================================================
+-- Combat Log Table: Every damage, heal, buff, miss, death and XP award
+-- involving at least one player. Rows older than the retention window are
+-- pruned by the server.
+-- =============================================================================
+-- Kind: Damage, Heal, Buff, Miss, Death, Experience
+-- Outcome: Hit, Critical, Miss, Dodge, Block (NULL for non-attack events)
+
+CREATE TABLE IF NOT EXISTS combat_log (
+    id BIGSERIAL PRIMARY KEY,
+    -- Unix timestamp in milliseconds
+    occurred_at_ms BIGINT NOT NULL,
+    kind VARCHAR(16) NOT NULL,
+    source_character_id BIGINT REFERENCES characters(id) ON DELETE SET NULL,
+    source_name VARCHAR(64),
+    source_is_player BOOLEAN NOT NULL DEFAULT FALSE,
+    target_character_id BIGINT REFERENCES characters(id) ON DELETE SET NULL,
+    target_name VARCHAR(64) NOT NULL,
+    target_is_player BOOLEAN NOT NULL DEFAULT FALSE,
+    ability_id INTEGER,
+    item_id INTEGER,
+    amount INTEGER NOT NULL DEFAULT 0,
+    outcome VARCHAR(16),
+    -- Mitigation breakdown (damage events only)
+    raw_damage INTEGER,
+    after_outcome INTEGER,
+    armor_mitigated INTEGER,
+    overkill INTEGER
+);
+
+CREATE INDEX IF NOT EXISTS idx_combat_log_source ON combat_log(source_character_id, occurred_at_ms);
+CREATE INDEX IF NOT EXISTS idx_combat_log_target ON combat_log(target_character_id, occurred_at_ms);
+CREATE INDEX IF NOT EXISTS idx_combat_log_time ON combat_log(occurred_at_ms);
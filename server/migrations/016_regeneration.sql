@@ -0,0 +1,16 @@
+-- Regeneration Migration
+-- High-tier armor grants passive out-of-combat health/mana regeneration
+
+-- =============================================================================
+-- Armor regeneration effects (amount per second while equipped)
+-- =============================================================================
+-- Ninja/Warrior armor: HealthRegen, Sura/Shaman armor: ManaRegen
+
+UPDATE items SET effects = '[{"HealthRegen": 1}]' WHERE id = 204; -- Phantom Armor
+UPDATE items SET effects = '[{"HealthRegen": 2}]' WHERE id = 205; -- Eclipse Raiment
+UPDATE items SET effects = '[{"HealthRegen": 2}]' WHERE id = 214; -- Champion's Aegis
+UPDATE items SET effects = '[{"HealthRegen": 3}]' WHERE id = 215; -- Warlord's Regalia
+UPDATE items SET effects = '[{"ManaRegen": 1}]' WHERE id = 224;   -- Abyssal Armor
+UPDATE items SET effects = '[{"ManaRegen": 2}]' WHERE id = 225;   -- Netherworld Vestments
+UPDATE items SET effects = '[{"ManaRegen": 2}]' WHERE id = 234;   -- Elder's Regalia
+UPDATE items SET effects = '[{"ManaRegen": 3}]' WHERE id = 235;   -- Sacred Spirit Vestments
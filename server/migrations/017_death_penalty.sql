@@ -0,0 +1,33 @@
+-- Death Penalty Migration
+-- Adds gear durability (lost on death, repaired for gold) and the Shaman resurrect ability
+
+-- =============================================================================
+-- Add equipment durability to player_state table
+-- =============================================================================
+-- 100 = fully repaired, 0 = broken (equipment stats reduced)
+
+ALTER TABLE player_state ADD COLUMN IF NOT EXISTS equipment_durability INTEGER NOT NULL DEFAULT 100
+    CHECK (equipment_durability >= 0 AND equipment_durability <= 100);
+
+-- =============================================================================
+-- Seed Data: Resurrect Ability
+-- =============================================================================
+
+INSERT INTO abilities (id, name, description, mana_cost, cooldown, cast_time, channel_duration, projectile_speed, range, target_type, class_restriction, specialization, level_requirement, effects, icon) VALUES
+(45, 'Spirit Recall', 'Call a fallen ally''s spirit back, reviving them where they fell.', 50, 60.0, 3.0, 0, 0, 10.0, 2, 3, NULL, 5,
+    '[{"Resurrect": {"health_percent": 0.3}}]', 'spirit_recall.png')
+ON CONFLICT (id) DO UPDATE SET
+    name = EXCLUDED.name,
+    description = EXCLUDED.description,
+    mana_cost = EXCLUDED.mana_cost,
+    cooldown = EXCLUDED.cooldown,
+    cast_time = EXCLUDED.cast_time,
+    channel_duration = EXCLUDED.channel_duration,
+    projectile_speed = EXCLUDED.projectile_speed,
+    range = EXCLUDED.range,
+    target_type = EXCLUDED.target_type,
+    class_restriction = EXCLUDED.class_restriction,
+    specialization = EXCLUDED.specialization,
+    level_requirement = EXCLUDED.level_requirement,
+    effects = EXCLUDED.effects,
+    icon = EXCLUDED.icon;
@@ -0,0 +1,378 @@
+//! Enemy AI.
+//!
+//! Every enemy runs a small state machine. Each tick it first gathers a
+//! `Perception` (closest player in aggro range, distance from spawn, health),
+//! then rules that apply in any state (leash, fleeing) may force a state change,
+//! then the current state runs and either acts or hands over to another state.
+//! States live in `states.rs` and only see the enemy and its perception, so each
+//! one can be tested on its own. What a mob type does with them is set by the
+//! `behaviour` section of its template. Enemies of the same faction pull each
+//! other into fights (`social.rs`), and melee enemies on the same player spread
+//! out around it (`surround.rs`). Out of combat an enemy stands, wanders its
+//! spawn area or walks a patrol route (`Roam`).
+
+mod states;
+pub mod social;
+pub mod surround;
+
+use log::debug;
+use serde::Deserialize;
+use crate::entities::ServerEnemy;
+use crate::navigation::Obstacle;
+
+/// How many state changes an enemy may go through in a single tick
+const MAX_TRANSITIONS_PER_TICK: usize = 4;
+
+fn default_flee_duration() -> f32 {
+    4.0
+}
+
+fn default_home_radius() -> f32 {
+    5.0
+}
+
+fn default_assist_radius() -> f32 {
+    10.0
+}
+
+fn default_help_radius() -> f32 {
+    20.0
+}
+
+fn default_wander_radius() -> f32 {
+    8.0
+}
+
+/// Per-template behaviour settings
+#[derive(Debug, Clone, Deserialize)]
+pub struct Behaviour {
+    /// Run from the target once below this fraction of max health (0 = fights to the death)
+    #[serde(default)]
+    pub flee_below_health: f32,
+    /// Seconds a flee lasts before turning back to fight
+    #[serde(default = "default_flee_duration")]
+    pub flee_duration: f32,
+    /// Idle enemies walk back once further than this from spawn (units)
+    #[serde(default = "default_home_radius")]
+    pub home_radius: f32,
+    /// Enemies of the same faction join each other's fights (None = fights alone)
+    #[serde(default)]
+    pub faction: Option<String>,
+    /// Idle allies this close join when the enemy engages or is attacked (units)
+    #[serde(default = "default_assist_radius")]
+    pub assist_radius: f32,
+    /// Flee towards the closest idle ally and bring it along instead of running straight away
+    #[serde(default)]
+    pub flee_for_help: bool,
+    /// How far away a fleeing enemy looks for help (units)
+    #[serde(default = "default_help_radius")]
+    pub help_radius: f32,
+    /// How far from spawn it strolls inside its spawn area (0 = stands still)
+    #[serde(default = "default_wander_radius")]
+    pub wander_radius: f32,
+}
+
+impl Default for Behaviour {
+    fn default() -> Self {
+        Self {
+            flee_below_health: 0.0,
+            flee_duration: default_flee_duration(),
+            home_radius: default_home_radius(),
+            faction: None,
+            assist_radius: default_assist_radius(),
+            flee_for_help: false,
+            help_radius: default_help_radius(),
+            wander_radius: default_wander_radius(),
+        }
+    }
+}
+
+impl Behaviour {
+    /// Check for values the state machine can't work with
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..1.0).contains(&self.flee_below_health) {
+            return Err(format!("flee_below_health {} must be in 0.0-1.0", self.flee_below_health));
+        }
+        if self.flee_below_health > 0.0 && self.flee_duration <= 0.0 {
+            return Err("flee_duration must be positive".to_string());
+        }
+        if self.flee_for_help && self.faction.is_none() {
+            return Err("flee_for_help needs a faction to call".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// AI states
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AiState {
+    /// No target, drifting back near spawn
+    Idle,
+    /// Closing in on the target
+    Chase,
+    /// In range: attacking, holding or backing off to the preferred distance
+    Attack,
+    /// Running from the target at low health (seconds left)
+    Flee { remaining: f32 },
+    /// Leashed: walking home and healing up, ignores players
+    Evade,
+}
+
+/// How an enemy passes the time when nobody is around
+#[derive(Debug, Clone, Default)]
+pub enum Roam {
+    /// Stands at its post
+    #[default]
+    Stand,
+    /// Strolls to random points near spawn inside its spawn area polygon (XZ)
+    Wander { polygon: Vec<[f32; 2]> },
+    /// Walks an authored route, looping or turning back at the ends
+    Patrol { waypoints: Vec<[f32; 3]>, looped: bool, pause: f32 },
+}
+
+/// AI memory carried between ticks
+#[derive(Debug, Clone)]
+pub struct AiMemory {
+    pub state: AiState,
+    /// Where leash distance is measured from and evading returns to
+    /// (spawn, or the last point on its patrol route)
+    pub home: [f32; 3],
+    pub roam: Roam,
+    /// Point it is walking to while roaming
+    pub roam_goal: Option<[f32; 3]>,
+    /// Seconds left standing around before the next roam goal
+    pub roam_wait: f32,
+    /// Seconds spent walking to the current roam goal
+    pub roam_elapsed: f32,
+    /// Next patrol waypoint
+    pub waypoint: usize,
+    /// Walking a non-looped route backwards
+    pub reversing: bool,
+    /// Already fled once this fight
+    pub has_fled: bool,
+    /// Player this enemy was pulled onto (attacked by, or called by an ally)
+    pub called_target: Option<u64>,
+    /// Fight this enemy was pulled into (id of the enemy that started it)
+    pub pack: Option<u64>,
+    /// Where to stand around the target (melee enemies, assigned every tick)
+    pub attack_slot: Option<[f32; 3]>,
+}
+
+impl AiMemory {
+    pub fn new(home: [f32; 3]) -> Self {
+        Self {
+            state: AiState::Idle,
+            home,
+            roam: Roam::Stand,
+            roam_goal: None,
+            roam_wait: 0.0,
+            roam_elapsed: 0.0,
+            waypoint: 0,
+            reversing: false,
+            has_fled: false,
+            called_target: None,
+            pack: None,
+            attack_slot: None,
+        }
+    }
+}
+
+/// Another enemy as its neighbours see it
+#[derive(Debug, Clone)]
+pub struct Neighbour {
+    pub id: u64,
+    pub position: [f32; 3],
+    pub faction: Option<String>,
+    /// Alive, not evading and not fighting anyone
+    pub idle: bool,
+    /// Collision radius (units)
+    pub radius: f32,
+}
+
+/// A player the enemy is aware of
+#[derive(Debug, Clone, Copy)]
+pub struct TargetInfo {
+    pub id: u64,
+    pub position: [f32; 3],
+    pub distance: f32,
+    /// Close enough to auto-attack (ranged enemies also need a clear shot)
+    pub in_attack_range: bool,
+}
+
+/// What the enemy knows about its surroundings this tick
+#[derive(Debug, Clone, Default)]
+pub struct Perception {
+    /// Player it was pulled onto, otherwise the closest player in aggro range
+    pub target: Option<TargetInfo>,
+    /// Closest idle ally to run to (only looked for by enemies that flee for help)
+    pub help: Option<[f32; 3]>,
+    /// Distance from where the enemy would evade to
+    pub distance_from_home: f32,
+    /// Current health as a fraction of max health
+    pub health_fraction: f32,
+}
+
+impl Perception {
+    /// Look around for players and allies and check the enemy's own state
+    pub fn gather(
+        enemy: &ServerEnemy,
+        player_positions: &[(u64, [f32; 3])],
+        neighbours: &[Neighbour],
+        obstacles: &[Obstacle],
+    ) -> Self {
+        let called = enemy.ai.called_target
+            .and_then(|id| player_positions.iter().find(|(player_id, _)| *player_id == id))
+            .map(|(id, position)| (*id, *position, enemy.distance_to(*position)));
+        let closest = || player_positions.iter()
+            .map(|(id, position)| (*id, *position, enemy.distance_to(*position)))
+            .filter(|(_, _, distance)| *distance <= enemy.template.aggro_range)
+            .min_by(|a, b| a.2.total_cmp(&b.2));
+        let target = called.or_else(closest)
+            .map(|(id, position, distance)| TargetInfo {
+                id,
+                position,
+                distance,
+                in_attack_range: distance <= enemy.template.attack_range
+                    && (!enemy.is_ranged() || enemy.can_see(position, obstacles)),
+            });
+
+        let behaviour = &enemy.template.behaviour;
+        let help = if behaviour.flee_for_help {
+            neighbours.iter()
+                .filter(|n| n.id != enemy.id && n.idle && n.faction.is_some() && n.faction == behaviour.faction)
+                .map(|n| (n.position, enemy.distance_to(n.position)))
+                .filter(|(_, distance)| *distance <= behaviour.help_radius)
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(position, _)| position)
+        } else {
+            None
+        };
+
+        Self {
+            target,
+            help,
+            distance_from_home: enemy.distance_to(enemy.ai.home),
+            health_fraction: enemy.health as f32 / enemy.max_health.max(1) as f32,
+        }
+    }
+}
+
+/// Result of running a state for one tick
+#[derive(Debug, PartialEq)]
+pub enum Transition {
+    /// Stay in the current state; Some((player_id, damage)) when it auto-attacked
+    Stay(Option<(u64, u32)>),
+    /// Switch to another state and run it right away
+    Switch(AiState),
+}
+
+/// Move the enemy into a state and run its enter actions
+pub fn enter(enemy: &mut ServerEnemy, state: AiState) {
+    match state {
+        AiState::Evade => {
+            enemy.target_id = None;
+            enemy.ai.called_target = None;
+            enemy.ai.pack = None;
+            enemy.nav_state.clear_path();
+        }
+        AiState::Flee { .. } => {
+            enemy.ai.has_fled = true;
+            enemy.nav_state.clear_path();
+        }
+        AiState::Idle => {
+            enemy.ai.has_fled = false;
+            enemy.ai.called_target = None;
+            enemy.ai.pack = None;
+        }
+        AiState::Chase | AiState::Attack => {}
+    }
+    enemy.ai.state = state;
+}
+
+/// Rules that override whatever the current state is doing
+fn forced_transition(enemy: &ServerEnemy, perception: &Perception) -> Option<AiState> {
+    if enemy.ai.state == AiState::Evade {
+        return None;
+    }
+
+    if perception.distance_from_home > enemy.leash_range {
+        debug!("[ENEMY {}] Leash triggered at dist={:.1}, returning to spawn", enemy.id, perception.distance_from_home);
+        return Some(AiState::Evade);
+    }
+
+    let behaviour = &enemy.template.behaviour;
+    let fighting = matches!(enemy.ai.state, AiState::Chase | AiState::Attack);
+    if fighting && !enemy.ai.has_fled && perception.health_fraction < behaviour.flee_below_health {
+        debug!("[ENEMY {}] Fleeing at {:.0}% health", enemy.id, perception.health_fraction * 100.0);
+        return Some(AiState::Flee { remaining: behaviour.flee_duration });
+    }
+
+    None
+}
+
+/// Run one tick of the enemy's AI
+/// Returns Some((target_player_id, damage)) if the enemy attacks this tick
+pub fn think(enemy: &mut ServerEnemy, perception: &Perception, delta: f32, obstacles: &[Obstacle]) -> Option<(u64, u32)> {
+    if let Some(state) = forced_transition(enemy, perception) {
+        enter(enemy, state);
+    }
+
+    // Evading enemies ignore players, everyone else locks on to the closest one
+    if enemy.ai.state != AiState::Evade {
+        let target_id = perception.target.map(|t| t.id);
+        if let Some(target) = perception.target.filter(|_| target_id != enemy.target_id) {
+            debug!("[ENEMY {}] Started chasing player {} at ({:.2}, {:.2}), my pos=({:.2}, {:.2})",
+                enemy.id, target.id, target.position[0], target.position[2],
+                enemy.position[0], enemy.position[2]);
+        }
+        enemy.target_id = target_id;
+    }
+
+    for _ in 0..MAX_TRANSITIONS_PER_TICK {
+        match states::run(enemy, perception, delta, obstacles) {
+            Transition::Stay(attack) => return attack,
+            Transition::Switch(next) => enter(enemy, next),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::enemy_template::EnemyTemplate;
+
+    /// Level 1 enemy at the origin from template JSON
+    pub(super) fn enemy_from_json(json: &str) -> ServerEnemy {
+        let template: EnemyTemplate = serde_json::from_str(json).unwrap();
+        ServerEnemy::new(1, 1, "test", template, [0.0, 0.0, 0.0], 1)
+    }
+
+    #[test]
+    fn test_leash_and_flee_override_current_state() {
+        let mut enemy = enemy_from_json(r#"{
+            "name": "Goblin", "model": "Goblin", "health": 100, "attack": 5,
+            "min_level": 1, "max_level": 1, "leash_range": 20.0,
+            "behaviour": { "flee_below_health": 0.3 }
+        }"#);
+        let target = TargetInfo { id: 7, position: [5.0, 0.0, 0.0], distance: 5.0, in_attack_range: false };
+        let mut perception = Perception { target: Some(target), distance_from_home: 0.0, health_fraction: 1.0, ..Default::default() };
+
+        // Healthy: keeps chasing
+        enemy.ai.state = AiState::Chase;
+        assert_eq!(forced_transition(&enemy, &perception), None);
+
+        // Hurt: runs, but only once per fight
+        perception.health_fraction = 0.2;
+        assert!(matches!(forced_transition(&enemy, &perception), Some(AiState::Flee { .. })));
+        enemy.ai.has_fled = true;
+        assert_eq!(forced_transition(&enemy, &perception), None);
+
+        // Past the leash: goes home no matter what
+        perception.distance_from_home = 25.0;
+        assert_eq!(forced_transition(&enemy, &perception), Some(AiState::Evade));
+        think(&mut enemy, &perception, 0.05, &[]);
+        assert_eq!(enemy.ai.state, AiState::Evade);
+        assert_eq!(enemy.target_id, None);
+    }
+}
@@ -0,0 +1,142 @@
+//! Social aggro.
+//!
+//! When an enemy engages a player, gets hit, or runs past its friends while
+//! fleeing for help, idle enemies of the same faction within its assist radius
+//! join in. Everyone pulled into that fight forms a pack: they all go for the
+//! player that started it, and when one of them leashes they all evade.
+
+use std::collections::HashMap;
+use log::debug;
+use crate::entities::ServerEnemy;
+use crate::navigation::{SpatialGrid, Vec2, SPATIAL_CELL_SIZE};
+use super::Neighbour;
+
+/// The enemies of one zone, indexed by position
+#[derive(Debug)]
+pub struct ZoneNeighbours {
+    list: Vec<Neighbour>,
+    /// Indices into `list`
+    grid: SpatialGrid<usize>,
+}
+
+impl ZoneNeighbours {
+    /// Copy the enemies within `radius` of a position into `out`
+    pub fn near(&self, position: [f32; 3], radius: f32, out: &mut Vec<Neighbour>) {
+        out.extend(self.grid.query(Vec2::from_3d(position), radius).map(|index| self.list[index].clone()));
+    }
+}
+
+/// Snapshot of every living enemy's position, faction and availability, grouped by zone
+pub fn neighbours_by_zone(enemies: &HashMap<u64, ServerEnemy>) -> HashMap<u32, ZoneNeighbours> {
+    let mut by_zone: HashMap<u32, Vec<Neighbour>> = HashMap::new();
+    for enemy in enemies.values().filter(|e| e.health > 0) {
+        by_zone.entry(enemy.zone_id).or_default().push(Neighbour {
+            id: enemy.id,
+            position: enemy.position,
+            faction: enemy.template.behaviour.faction.clone(),
+            idle: !enemy.is_evading() && enemy.target_id.is_none(),
+            radius: enemy.template.radius,
+        });
+    }
+    by_zone.into_iter()
+        .map(|(zone_id, list)| {
+            let grid = SpatialGrid::from_items(
+                SPATIAL_CELL_SIZE,
+                list.iter().enumerate().map(|(index, n)| (index, Vec2::from_3d(n.position))),
+            );
+            (zone_id, ZoneNeighbours { list, grid })
+        })
+        .collect()
+}
+
+/// Pull an enemy onto a player and bring its idle allies along
+/// Returns how many allies joined
+pub fn pull(enemies: &mut HashMap<u64, ServerEnemy>, enemy_id: u64, player_id: u64) -> usize {
+    let Some(puller) = enemies.get_mut(&enemy_id) else {
+        return 0;
+    };
+    if puller.health == 0 || puller.is_evading() {
+        return 0;
+    }
+
+    puller.target_id.get_or_insert(player_id);
+    puller.ai.called_target.get_or_insert(player_id);
+    let pack = *puller.ai.pack.get_or_insert(enemy_id);
+    let target = puller.ai.called_target.unwrap_or(player_id);
+
+    let Some(faction) = puller.template.behaviour.faction.clone() else {
+        return 0;
+    };
+    let zone_id = puller.zone_id;
+    let center = Vec2::from_3d(puller.position);
+    let radius = puller.template.behaviour.assist_radius;
+
+    let mut joined = 0;
+    for ally in enemies.values_mut() {
+        let available = ally.id != enemy_id
+            && ally.zone_id == zone_id
+            && ally.health > 0
+            && !ally.is_evading()
+            && ally.target_id.is_none()
+            && ally.template.behaviour.faction.as_ref() == Some(&faction);
+        if available && Vec2::from_3d(ally.position).distance_to(center) <= radius {
+            ally.target_id = Some(target);
+            ally.ai.called_target = Some(target);
+            ally.ai.pack = Some(pack);
+            joined += 1;
+        }
+    }
+
+    if joined > 0 {
+        debug!("[ENEMY {}] Pulled {} {} allies onto player {}", enemy_id, joined, faction, target);
+    }
+    joined
+}
+
+/// Send every member of a pack home
+pub fn evade_together(enemies: &mut HashMap<u64, ServerEnemy>, pack: u64) {
+    for enemy in enemies.values_mut() {
+        if enemy.ai.pack == Some(pack) && enemy.health > 0 && !enemy.is_evading() {
+            enemy.start_evading();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::tests::enemy_from_json;
+
+    fn wolf(id: u64, x: f32) -> ServerEnemy {
+        let mut enemy = enemy_from_json(r#"{
+            "name": "Wolf", "model": "Wolf", "health": 65, "attack": 10,
+            "min_level": 1, "max_level": 1,
+            "behaviour": { "faction": "wolves", "assist_radius": 10.0 }
+        }"#);
+        enemy.id = id;
+        enemy.position = [x, 0.0, 0.0];
+        enemy
+    }
+
+    #[test]
+    fn test_pack_joins_shares_target_and_evades_together() {
+        let mut enemies: HashMap<u64, ServerEnemy> = [wolf(1, 0.0), wolf(2, 6.0), wolf(3, 30.0)]
+            .into_iter()
+            .map(|e| (e.id, e))
+            .collect();
+
+        // Only the wolf within the assist radius joins, on the same player
+        assert_eq!(pull(&mut enemies, 1, 7), 1);
+        assert_eq!(enemies[&2].ai.called_target, Some(7));
+        assert_eq!(enemies[&2].ai.pack, Some(1));
+        assert_eq!(enemies[&3].target_id, None);
+
+        // A second attacker doesn't steal the pack's target
+        pull(&mut enemies, 2, 8);
+        assert_eq!(enemies[&2].ai.called_target, Some(7));
+
+        evade_together(&mut enemies, 1);
+        assert!(enemies[&1].is_evading() && enemies[&2].is_evading());
+        assert!(!enemies[&3].is_evading());
+    }
+}
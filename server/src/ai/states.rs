@@ -0,0 +1,415 @@
+//! Enemy AI states.
+//!
+//! One function per state. Each runs a single tick for an enemy that is in that
+//! state and returns whether it stays (and maybe attacked) or hands over.
+
+use log::debug;
+use rand::Rng;
+use mmo_shared::AnimationState;
+use crate::entities::ServerEnemy;
+use crate::navigation::{Obstacle, Vec2};
+use crate::world::spawn_area::point_in_polygon;
+use super::{AiState, Perception, Roam, Transition};
+
+/// Enemy return speed (faster when evading back to spawn)
+const ENEMY_RETURN_SPEED: f32 = 5.0;
+
+/// How close to spawn point before enemy is considered "home"
+const HOME_THRESHOLD: f32 = 2.0;
+
+/// Health regeneration rate when evading (per second, as fraction of max health)
+const EVADE_REGEN_RATE: f32 = 0.2;
+
+/// How far a kiting or fleeing enemy steps back at a time (units)
+const RETREAT_STEP: f32 = 2.0;
+
+/// Kiting enemies hold their ground instead of backing past this fraction of the leash range
+const KITE_LEASH_FRACTION: f32 = 0.8;
+
+/// Roaming enemies stroll at this fraction of their movement speed
+const ROAM_SPEED_FACTOR: f32 = 0.5;
+
+/// How close to a roam goal counts as arrived (units)
+const ROAM_ARRIVE_DISTANCE: f32 = 0.5;
+
+/// Give up on a roam goal that takes longer than this to reach (seconds)
+const ROAM_GIVE_UP_TIME: f32 = 15.0;
+
+/// Seconds a wandering enemy stands around between strolls
+const WANDER_PAUSE: (f32, f32) = (3.0, 8.0);
+
+/// Random points tried when looking for a wander goal inside the spawn area
+const WANDER_ATTEMPTS: usize = 10;
+
+/// How close to its attack slot a melee enemy counts as in place (units)
+const SLOT_ARRIVE_DISTANCE: f32 = 0.3;
+
+/// Run the enemy's current state for one tick
+pub(super) fn run(enemy: &mut ServerEnemy, perception: &Perception, delta: f32, obstacles: &[Obstacle]) -> Transition {
+    match enemy.ai.state {
+        AiState::Idle => idle(enemy, perception, delta, obstacles),
+        AiState::Chase => chase(enemy, perception, delta, obstacles),
+        AiState::Attack => attack(enemy, perception, delta, obstacles),
+        AiState::Flee { remaining } => flee(enemy, perception, remaining, delta, obstacles),
+        AiState::Evade => evade(enemy, perception, delta, obstacles),
+    }
+}
+
+/// No target: roam, or walk back if wandered off and otherwise stand around
+fn idle(enemy: &mut ServerEnemy, perception: &Perception, delta: f32, obstacles: &[Obstacle]) -> Transition {
+    if perception.target.is_some() {
+        return Transition::Switch(AiState::Chase);
+    }
+
+    let roams = match enemy.ai.roam {
+        Roam::Stand => false,
+        Roam::Wander { .. } => enemy.template.behaviour.wander_radius > 0.0,
+        Roam::Patrol { .. } => true,
+    };
+    if roams {
+        roam(enemy, delta, obstacles);
+    } else if perception.distance_from_home > enemy.template.behaviour.home_radius {
+        enemy.move_towards_with_avoidance(enemy.ai.home, delta, obstacles);
+        enemy.animation_state = AnimationState::Walking;
+    } else {
+        enemy.nav_state.clear_path();
+        enemy.animation_state = AnimationState::Idle;
+    }
+    Transition::Stay(None)
+}
+
+/// Close in on the target (or its attack slot) until it is in attack range
+fn chase(enemy: &mut ServerEnemy, perception: &Perception, delta: f32, obstacles: &[Obstacle]) -> Transition {
+    let Some(target) = perception.target else {
+        return Transition::Switch(AiState::Idle);
+    };
+    if target.in_attack_range {
+        return Transition::Switch(AiState::Attack);
+    }
+
+    // Melee enemies head for their spot around the target
+    let goal = enemy.ai.attack_slot.unwrap_or(target.position);
+    if enemy.distance_to(goal) <= SLOT_ARRIVE_DISTANCE {
+        // Waiting on an outer ring for room to attack
+        enemy.face_towards(target.position);
+        enemy.nav_state.clear_path();
+        enemy.animation_state = AnimationState::Idle;
+        return Transition::Stay(None);
+    }
+
+    enemy.face_towards(goal);
+    enemy.move_towards_with_avoidance(goal, delta, obstacles);
+    enemy.animation_state = AnimationState::Walking;
+    Transition::Stay(None)
+}
+
+/// Auto-attack whenever the cooldown allows; in between, ranged enemies back off
+/// to their preferred distance, melee enemies move to their slot, and everyone
+/// else holds position
+fn attack(enemy: &mut ServerEnemy, perception: &Perception, delta: f32, obstacles: &[Obstacle]) -> Transition {
+    let Some(target) = perception.target else {
+        return Transition::Switch(AiState::Idle);
+    };
+    if !target.in_attack_range {
+        return Transition::Switch(AiState::Chase);
+    }
+
+    enemy.face_towards(target.position);
+
+    if enemy.attack_cooldown <= 0.0 {
+        // Attack (also point-blank, so kiting enemies still fight back when cornered)
+        enemy.animation_state = AnimationState::Attacking;
+        enemy.nav_state.clear_path();
+        enemy.attack_cooldown = enemy.template.attack_cooldown;
+        return Transition::Stay(Some((target.id, enemy.attack_power)));
+    }
+
+    let slot = enemy.ai.attack_slot.filter(|slot| enemy.distance_to(*slot) > SLOT_ARRIVE_DISTANCE);
+    if target.distance < enemy.template.preferred_distance && kite_from(enemy, target.position, delta, obstacles) {
+        enemy.face_towards(target.position);
+        enemy.animation_state = AnimationState::Walking;
+    } else if let Some(slot) = slot {
+        // Step round to its own side of the target between swings
+        enemy.move_towards_with_avoidance(slot, delta, obstacles);
+        enemy.face_towards(target.position);
+        enemy.animation_state = AnimationState::Walking;
+    } else {
+        // Wait for the attack to recharge
+        enemy.animation_state = AnimationState::Attacking;
+        enemy.nav_state.clear_path();
+    }
+    Transition::Stay(None)
+}
+
+/// Run away from the target (or to the closest ally for help), then turn back to fight
+fn flee(enemy: &mut ServerEnemy, perception: &Perception, remaining: f32, delta: f32, obstacles: &[Obstacle]) -> Transition {
+    let Some(target) = perception.target else {
+        return Transition::Switch(AiState::Idle);
+    };
+    if remaining <= 0.0 {
+        return Transition::Switch(AiState::Chase);
+    }
+
+    enemy.ai.state = AiState::Flee { remaining: remaining - delta };
+    if let Some(ally) = perception.help {
+        enemy.move_towards_with_avoidance(ally, delta, obstacles);
+    } else if let Some(retreat) = retreat_point(enemy, target.position) {
+        enemy.move_towards_with_avoidance([retreat.x, enemy.position[1], retreat.z], delta, obstacles);
+    }
+    enemy.animation_state = AnimationState::Walking;
+    Transition::Stay(None)
+}
+
+/// Walk home ignoring players, healing on the way and fully once there
+fn evade(enemy: &mut ServerEnemy, perception: &Perception, delta: f32, obstacles: &[Obstacle]) -> Transition {
+    let regen_amount = (enemy.max_health as f32 * EVADE_REGEN_RATE * delta) as u32;
+    enemy.health = (enemy.health + regen_amount).min(enemy.max_health);
+
+    if perception.distance_from_home < HOME_THRESHOLD {
+        enemy.health = enemy.max_health;
+        enemy.nav_state.clear_path();
+        debug!("[ENEMY {}] Returned home, health restored to {}", enemy.id, enemy.health);
+        return Transition::Switch(AiState::Idle);
+    }
+
+    enemy.move_towards_with_speed(enemy.ai.home, delta, obstacles, ENEMY_RETURN_SPEED);
+    enemy.animation_state = AnimationState::Walking;
+    Transition::Stay(None)
+}
+
+/// Stroll to the next wander point or waypoint, pausing at each
+fn roam(enemy: &mut ServerEnemy, delta: f32, obstacles: &[Obstacle]) {
+    if enemy.ai.roam_wait > 0.0 {
+        enemy.ai.roam_wait -= delta;
+        enemy.nav_state.clear_path();
+        enemy.animation_state = AnimationState::Idle;
+        return;
+    }
+
+    let Some(goal) = enemy.ai.roam_goal.or_else(|| next_roam_goal(enemy)) else {
+        enemy.animation_state = AnimationState::Idle;
+        return;
+    };
+    if enemy.ai.roam_goal.is_none() {
+        enemy.ai.roam_goal = Some(goal);
+        enemy.ai.roam_elapsed = 0.0;
+    }
+
+    enemy.ai.roam_elapsed += delta;
+    if enemy.distance_to(goal) <= ROAM_ARRIVE_DISTANCE || enemy.ai.roam_elapsed > ROAM_GIVE_UP_TIME {
+        arrive_at_roam_goal(enemy);
+        enemy.nav_state.clear_path();
+        enemy.animation_state = AnimationState::Idle;
+        return;
+    }
+
+    let speed = enemy.get_movement_speed() * ROAM_SPEED_FACTOR;
+    enemy.move_towards_with_speed(goal, delta, obstacles, speed);
+    enemy.animation_state = AnimationState::Walking;
+
+    // Patrollers leash and evade relative to where they are on the route
+    if matches!(enemy.ai.roam, Roam::Patrol { .. }) {
+        enemy.ai.home = enemy.position;
+    }
+}
+
+/// Pick where to stroll to next
+fn next_roam_goal(enemy: &ServerEnemy) -> Option<[f32; 3]> {
+    match &enemy.ai.roam {
+        Roam::Stand => None,
+        Roam::Wander { polygon } => {
+            let mut rng = rand::thread_rng();
+            let radius = enemy.template.behaviour.wander_radius;
+            let center = enemy.spawn_position;
+            (0..WANDER_ATTEMPTS)
+                .map(|_| {
+                    let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+                    let distance = radius * rng.gen::<f32>().sqrt();
+                    [center[0] + angle.sin() * distance, center[1], center[2] + angle.cos() * distance]
+                })
+                .find(|point| point_in_polygon(polygon, point[0], point[2]))
+        }
+        Roam::Patrol { waypoints, .. } => waypoints.get(enemy.ai.waypoint).copied(),
+    }
+}
+
+/// Reached (or gave up on) a roam goal: pause and line up the next one
+fn arrive_at_roam_goal(enemy: &mut ServerEnemy) {
+    enemy.ai.roam_goal = None;
+    match &enemy.ai.roam {
+        Roam::Stand => {}
+        Roam::Wander { .. } => {
+            enemy.ai.roam_wait = rand::thread_rng().gen_range(WANDER_PAUSE.0..WANDER_PAUSE.1);
+        }
+        Roam::Patrol { waypoints, looped, pause } => {
+            enemy.ai.roam_wait = *pause;
+            let last = waypoints.len().saturating_sub(1);
+            let current = enemy.ai.waypoint;
+            enemy.ai.waypoint = if last == 0 {
+                0
+            } else if *looped {
+                (current + 1) % waypoints.len()
+            } else if enemy.ai.reversing {
+                if current == 0 {
+                    enemy.ai.reversing = false;
+                    1
+                } else {
+                    current - 1
+                }
+            } else if current >= last {
+                enemy.ai.reversing = true;
+                last - 1
+            } else {
+                current + 1
+            };
+        }
+    }
+}
+
+/// One step directly away from a threat (None when standing on top of it)
+fn retreat_point(enemy: &ServerEnemy, threat: [f32; 3]) -> Option<Vec2> {
+    let away = (Vec2::from_3d(enemy.position) - Vec2::from_3d(threat)).normalized();
+    if away.length_squared() < 0.0001 {
+        return None;
+    }
+    Some(Vec2::from_3d(enemy.position) + away * RETREAT_STEP)
+}
+
+/// Step away from a threat, unless that would drag the enemy towards its leash
+/// Returns true if the enemy moved
+fn kite_from(enemy: &mut ServerEnemy, threat: [f32; 3], delta: f32, obstacles: &[Obstacle]) -> bool {
+    let Some(retreat) = retreat_point(enemy, threat) else {
+        return false;
+    };
+    if retreat.distance_to(Vec2::from_3d(enemy.ai.home)) > enemy.leash_range * KITE_LEASH_FRACTION {
+        return false;
+    }
+
+    let before = enemy.position;
+    enemy.move_towards_with_avoidance([retreat.x, enemy.position[1], retreat.z], delta, obstacles);
+    enemy.position != before
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::TargetInfo;
+    use crate::ai::tests::enemy_from_json;
+
+    fn goblin() -> ServerEnemy {
+        enemy_from_json(r#"{
+            "name": "Goblin", "model": "Goblin", "health": 50, "attack": 8,
+            "min_level": 1, "max_level": 1
+        }"#)
+    }
+
+    fn sees(id: u64, position: [f32; 3], in_attack_range: bool) -> Perception {
+        let distance = Vec2::from_3d(position).length();
+        Perception {
+            target: Some(TargetInfo { id, position, distance, in_attack_range }),
+            health_fraction: 1.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_idle_wanders_home_until_it_sees_someone() {
+        let mut enemy = goblin();
+        enemy.position = [8.0, 0.0, 0.0];
+        let alone = Perception { distance_from_home: 8.0, health_fraction: 1.0, ..Default::default() };
+
+        assert_eq!(idle(&mut enemy, &alone, 0.1, &[]), Transition::Stay(None));
+        assert!(enemy.position[0] < 8.0);
+        assert_eq!(idle(&mut enemy, &sees(7, [3.0, 0.0, 0.0], false), 0.1, &[]), Transition::Switch(AiState::Chase));
+    }
+
+    #[test]
+    fn test_chase_closes_in_then_hands_over() {
+        let mut enemy = goblin();
+
+        assert_eq!(chase(&mut enemy, &sees(7, [6.0, 0.0, 0.0], false), 0.1, &[]), Transition::Stay(None));
+        assert!(enemy.position[0] > 0.0);
+        assert_eq!(enemy.animation_state, AnimationState::Walking);
+
+        assert_eq!(chase(&mut enemy, &sees(7, [1.5, 0.0, 0.0], true), 0.1, &[]), Transition::Switch(AiState::Attack));
+        assert_eq!(chase(&mut enemy, &Perception::default(), 0.1, &[]), Transition::Switch(AiState::Idle));
+    }
+
+    #[test]
+    fn test_melee_attack_hits_then_holds() {
+        let mut enemy = goblin();
+        let perception = sees(7, [1.5, 0.0, 0.0], true);
+
+        assert_eq!(attack(&mut enemy, &perception, 0.1, &[]), Transition::Stay(Some((7, 8))));
+        assert_eq!(attack(&mut enemy, &perception, 0.1, &[]), Transition::Stay(None));
+        assert_eq!(enemy.position, [0.0, 0.0, 0.0]);
+        assert_eq!(attack(&mut enemy, &sees(7, [4.0, 0.0, 0.0], false), 0.1, &[]), Transition::Switch(AiState::Chase));
+    }
+
+    #[test]
+    fn test_flee_runs_away_then_fights_again() {
+        let mut enemy = goblin();
+        let perception = sees(7, [2.0, 0.0, 0.0], true);
+
+        assert_eq!(flee(&mut enemy, &perception, 1.0, 0.1, &[]), Transition::Stay(None));
+        assert!(enemy.position[0] < 0.0);
+        assert!(matches!(enemy.ai.state, AiState::Flee { remaining } if remaining < 1.0));
+        assert_eq!(flee(&mut enemy, &perception, 0.0, 0.1, &[]), Transition::Switch(AiState::Chase));
+
+        // With an ally around it runs there instead
+        let z_before = enemy.position[2];
+        let with_help = Perception { help: Some([0.0, 0.0, 10.0]), ..perception };
+        flee(&mut enemy, &with_help, 1.0, 0.1, &[]);
+        assert!(enemy.position[2] > z_before);
+    }
+
+    #[test]
+    fn test_evade_heals_on_the_way_home() {
+        let mut enemy = goblin();
+        enemy.position = [10.0, 0.0, 0.0];
+        enemy.health = 10;
+
+        let away = Perception { distance_from_home: 10.0, health_fraction: 0.2, ..Default::default() };
+        assert_eq!(evade(&mut enemy, &away, 0.5, &[]), Transition::Stay(None));
+        assert_eq!(enemy.health, 15);
+        assert!(enemy.position[0] < 10.0);
+
+        let home = Perception { distance_from_home: 1.0, health_fraction: 0.3, ..Default::default() };
+        assert_eq!(evade(&mut enemy, &home, 0.5, &[]), Transition::Switch(AiState::Idle));
+        assert_eq!(enemy.health, enemy.max_health);
+    }
+
+    #[test]
+    fn test_wander_stays_inside_the_area() {
+        let mut enemy = goblin();
+        let polygon = vec![[-3.0, -3.0], [3.0, -3.0], [3.0, 3.0], [-3.0, 3.0]];
+        enemy.ai.roam = Roam::Wander { polygon: polygon.clone() };
+
+        // Points outside the polygon are rejected (the enemy retries next tick)
+        for goal in (0..50).filter_map(|_| next_roam_goal(&enemy)) {
+            assert!(point_in_polygon(&polygon, goal[0], goal[2]));
+        }
+    }
+
+    #[test]
+    fn test_patrol_turns_around_at_the_ends() {
+        let mut enemy = goblin();
+        enemy.ai.roam = Roam::Patrol {
+            waypoints: vec![[0.0, 0.0, 0.0], [5.0, 0.0, 0.0], [10.0, 0.0, 0.0]],
+            looped: false,
+            pause: 0.0,
+        };
+
+        let mut visited = Vec::new();
+        for _ in 0..5 {
+            arrive_at_roam_goal(&mut enemy);
+            visited.push(enemy.ai.waypoint);
+        }
+        assert_eq!(visited, vec![1, 2, 1, 0, 1]);
+
+        // Walking the route drags home along
+        idle(&mut enemy, &Perception::default(), 0.5, &[]);
+        assert!(enemy.position[0] > 0.0);
+        assert_eq!(enemy.ai.home, enemy.position);
+    }
+}
@@ -0,0 +1,141 @@
+//! Attack slots.
+//!
+//! Melee enemies fighting the same player spread out around it instead of all
+//! running for the side closest to them. Every tick a player's melee attackers
+//! are spaced evenly on a ring just inside attack range, in the order they
+//! already stand around the player so nobody has to cross over. When the ring
+//! is full, the rest wait on a ring further out.
+
+use std::collections::HashMap;
+use std::f32::consts::TAU;
+use crate::entities::ServerEnemy;
+use crate::navigation::Vec2;
+use super::AiState;
+
+/// Slots sit at this fraction of the attack range from the target
+const SLOT_RANGE_FRACTION: f32 = 0.8;
+
+/// Room per slot on a ring, as a multiple of the widest attacker
+const SLOT_SPACING: f32 = 1.2;
+
+/// Give every melee enemy that is fighting a player a spot around it
+/// Everyone else has their slot cleared
+pub fn assign_attack_slots(
+    enemies: &mut HashMap<u64, ServerEnemy>,
+    target_position: impl Fn(u64) -> Option<[f32; 3]>,
+) {
+    let mut attackers: HashMap<u64, Vec<u64>> = HashMap::new();
+    for enemy in enemies.values_mut() {
+        enemy.ai.attack_slot = None;
+        let fighting = matches!(enemy.ai.state, AiState::Chase | AiState::Attack);
+        if let Some(target_id) = enemy.target_id.filter(|_| fighting && enemy.health > 0 && !enemy.is_ranged()) {
+            attackers.entry(target_id).or_default().push(enemy.id);
+        }
+    }
+
+    for (target_id, mut ids) in attackers {
+        let Some(target) = target_position(target_id) else {
+            continue;
+        };
+        let center = Vec2::from_3d(target);
+        let distance = |id: &u64| Vec2::from_3d(enemies[id].position).distance_to(center);
+        ids.sort_by(|a, b| distance(a).total_cmp(&distance(b)));
+
+        let inner = ids.iter().map(|id| enemies[id].template.attack_range).fold(f32::MAX, f32::min) * SLOT_RANGE_FRACTION;
+        let width = ids.iter().map(|id| enemies[id].template.radius).fold(0.0, f32::max) * 2.0 * SLOT_SPACING;
+
+        // Closest attackers take the inner ring, the rest queue further out
+        let mut ring_distance = inner;
+        let mut remaining = ids.as_slice();
+        while !remaining.is_empty() {
+            let capacity = ((TAU * ring_distance / width.max(0.01)) as usize).max(1);
+            let (ring, rest) = remaining.split_at(capacity.min(remaining.len()));
+            for (id, slot) in spread(enemies, ring, center, ring_distance) {
+                if let Some(enemy) = enemies.get_mut(&id) {
+                    enemy.ai.attack_slot = Some([slot.x, target[1], slot.z]);
+                }
+            }
+            remaining = rest;
+            ring_distance += width;
+        }
+    }
+}
+
+/// Evenly spaced points on a ring, turned to fit where the enemies already are
+fn spread(enemies: &HashMap<u64, ServerEnemy>, ids: &[u64], center: Vec2, radius: f32) -> Vec<(u64, Vec2)> {
+    // atan2(x, z): 0 = +Z, matching the rotation convention
+    let angle_of = |id: &u64| {
+        let offset = Vec2::from_3d(enemies[id].position) - center;
+        offset.x.atan2(offset.z)
+    };
+    let mut by_angle: Vec<(u64, f32)> = ids.iter().map(|id| (*id, angle_of(id))).collect();
+    by_angle.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    // Rotate the ring by the mean offset between each enemy and its slot
+    let spacing = TAU / by_angle.len() as f32;
+    let (sin, cos) = by_angle.iter().enumerate()
+        .map(|(i, (_, angle))| angle - i as f32 * spacing)
+        .fold((0.0, 0.0), |(sin, cos), offset| (sin + offset.sin(), cos + offset.cos()));
+    let start = sin.atan2(cos);
+
+    by_angle.into_iter().enumerate()
+        .map(|(i, (id, _))| {
+            let angle = start + i as f32 * spacing;
+            (id, center + Vec2::new(angle.sin(), angle.cos()) * radius)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::tests::enemy_from_json;
+
+    fn wolf(id: u64, x: f32, z: f32) -> ServerEnemy {
+        let mut enemy = enemy_from_json(r#"{
+            "name": "Wolf", "model": "Wolf", "health": 50, "attack": 8, "min_level": 1, "max_level": 1
+        }"#);
+        enemy.id = id;
+        enemy.position = [x, 0.0, z];
+        enemy.target_id = Some(1);
+        enemy.ai.state = AiState::Chase;
+        enemy
+    }
+
+    #[test]
+    fn test_pack_spreads_around_the_target() {
+        // Three wolves coming from the same side
+        let mut enemies: HashMap<u64, ServerEnemy> = [(10, 9.0, 0.5), (11, 10.0, 0.0), (12, 9.5, -0.5)]
+            .into_iter()
+            .map(|(id, x, z)| (id, wolf(id, x, z)))
+            .collect();
+        assign_attack_slots(&mut enemies, |_| Some([0.0, 0.0, 0.0]));
+
+        let slots: Vec<Vec2> = enemies.values().map(|e| Vec2::from_3d(e.ai.attack_slot.unwrap())).collect();
+        for slot in &slots {
+            assert!(slot.length() < enemies[&10].template.attack_range);
+        }
+        for (i, a) in slots.iter().enumerate() {
+            for b in &slots[i + 1..] {
+                assert!(a.distance_to(*b) > 2.0, "slots too close: {:?} {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_overflow_waits_on_an_outer_ring() {
+        let mut enemies: HashMap<u64, ServerEnemy> = (0..12)
+            .map(|i| {
+                let angle = i as f32 * 0.5;
+                (i, wolf(i, angle.sin() * (5.0 + i as f32), angle.cos() * (5.0 + i as f32)))
+            })
+            .collect();
+        assign_attack_slots(&mut enemies, |_| Some([0.0, 0.0, 0.0]));
+
+        let range = enemies[&0].template.attack_range;
+        let inner = enemies.values().filter(|e| Vec2::from_3d(e.ai.attack_slot.unwrap()).length() < range).count();
+        assert!(inner > 0 && inner < 12);
+        // The closest ones get to fight
+        assert!(Vec2::from_3d(enemies[&0].ai.attack_slot.unwrap()).length() < range);
+    }
+}
@@ -0,0 +1,187 @@
+//! Combat resolution: hit, miss, dodge, block and critical strikes.
+//!
+//! Every attack (player auto-attacks, enemy attacks and ability damage) is
+//! resolved with a single roll against an attack table built from the
+//! attacker's and defender's combat profiles and their level difference:
+//!
+//! ```text
+//! [ miss | dodge | block | critical | hit ]
+//! ```
+//!
+//! The resulting damage is the raw damage before armor mitigation; the
+//! defender's `take_damage` still applies defense afterwards.
+
+use mmo_shared::HitOutcome;
+use rand::Rng;
+
+/// Base chance to miss an equal-level target
+const BASE_MISS_CHANCE: f32 = 0.05;
+
+/// Additional miss chance per level the defender is above the attacker
+const MISS_CHANCE_PER_LEVEL: f32 = 0.01;
+
+/// Maximum miss chance (heavily out-leveled attackers)
+const MAX_MISS_CHANCE: f32 = 0.5;
+
+/// Crit chance lost per level the defender is above the attacker
+const CRIT_CHANCE_PER_LEVEL: f32 = 0.01;
+
+/// Maximum dodge chance
+const MAX_DODGE_CHANCE: f32 = 0.4;
+
+/// Maximum block chance
+const MAX_BLOCK_CHANCE: f32 = 0.5;
+
+/// Fraction of damage removed by a successful block
+pub const BLOCK_DAMAGE_REDUCTION: f32 = 0.5;
+
+/// Base block chance when a shield is equipped
+pub const SHIELD_BLOCK_CHANCE: f32 = 0.1;
+
+/// Default critical hit damage multiplier
+pub const DEFAULT_CRIT_MULTIPLIER: f32 = 2.0;
+
+/// Combat-relevant stats of an attacker or defender
+#[derive(Debug, Clone, Copy)]
+pub struct CombatProfile {
+    pub level: u32,
+    /// Chance to land a critical hit (0.0 - 1.0)
+    pub crit_chance: f32,
+    /// Damage multiplier on critical hits
+    pub crit_multiplier: f32,
+    /// Chance to dodge incoming attacks (0.0 - 1.0)
+    pub dodge_chance: f32,
+    /// Chance to block incoming attacks (0 = no shield)
+    pub block_chance: f32,
+}
+
+/// Result of resolving a single attack
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HitResult {
+    pub outcome: HitOutcome,
+    /// Damage after the outcome is applied (0 for miss/dodge)
+    pub damage: u32,
+}
+
+/// Chances for each row of the attack table, in roll order
+#[derive(Debug, Clone, Copy)]
+pub struct AttackTable {
+    pub miss: f32,
+    pub dodge: f32,
+    pub block: f32,
+    pub crit: f32,
+}
+
+impl AttackTable {
+    /// Build the attack table for an attacker against a defender
+    pub fn new(attacker: &CombatProfile, defender: &CombatProfile) -> Self {
+        // Positive when the defender out-levels the attacker
+        let level_diff = defender.level as f32 - attacker.level as f32;
+
+        Self {
+            miss: (BASE_MISS_CHANCE + level_diff * MISS_CHANCE_PER_LEVEL).clamp(0.0, MAX_MISS_CHANCE),
+            dodge: defender.dodge_chance.clamp(0.0, MAX_DODGE_CHANCE),
+            block: defender.block_chance.clamp(0.0, MAX_BLOCK_CHANCE),
+            crit: (attacker.crit_chance - level_diff * CRIT_CHANCE_PER_LEVEL).clamp(0.0, 1.0),
+        }
+    }
+
+    /// Look up the outcome for a roll in [0, 1)
+    pub fn outcome_for_roll(&self, roll: f32) -> HitOutcome {
+        let mut threshold = self.miss;
+        if roll < threshold {
+            return HitOutcome::Miss;
+        }
+        threshold += self.dodge;
+        if roll < threshold {
+            return HitOutcome::Dodge;
+        }
+        threshold += self.block;
+        if roll < threshold {
+            return HitOutcome::Block;
+        }
+        threshold += self.crit;
+        if roll < threshold {
+            return HitOutcome::Critical;
+        }
+        HitOutcome::Hit
+    }
+}
+
+/// Apply an outcome to raw damage
+pub fn apply_outcome(outcome: HitOutcome, damage: u32, crit_multiplier: f32) -> u32 {
+    match outcome {
+        HitOutcome::Hit => damage,
+        HitOutcome::Critical => (damage as f32 * crit_multiplier) as u32,
+        HitOutcome::Block => (damage as f32 * (1.0 - BLOCK_DAMAGE_REDUCTION)) as u32,
+        HitOutcome::Miss | HitOutcome::Dodge | HitOutcome::Immune => 0,
+    }
+}
+
+/// Resolve an attack from attacker to defender for the given raw damage
+pub fn resolve_hit(attacker: &CombatProfile, defender: &CombatProfile, damage: u32) -> HitResult {
+    let roll = rand::thread_rng().gen_range(0.0..1.0);
+    let outcome = AttackTable::new(attacker, defender).outcome_for_roll(roll);
+
+    HitResult {
+        outcome,
+        damage: apply_outcome(outcome, damage, attacker.crit_multiplier),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(level: u32) -> CombatProfile {
+        CombatProfile {
+            level,
+            crit_chance: 0.1,
+            crit_multiplier: 2.0,
+            dodge_chance: 0.05,
+            block_chance: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_attack_table_order() {
+        let table = AttackTable::new(&profile(5), &profile(5));
+
+        // miss 0.05, dodge 0.05, block 0.0, crit 0.1
+        assert_eq!(table.outcome_for_roll(0.01), HitOutcome::Miss);
+        assert_eq!(table.outcome_for_roll(0.07), HitOutcome::Dodge);
+        assert_eq!(table.outcome_for_roll(0.15), HitOutcome::Critical);
+        assert_eq!(table.outcome_for_roll(0.5), HitOutcome::Hit);
+    }
+
+    #[test]
+    fn test_level_difference() {
+        let even = AttackTable::new(&profile(5), &profile(5));
+        let higher_defender = AttackTable::new(&profile(5), &profile(10));
+        let lower_defender = AttackTable::new(&profile(15), &profile(5));
+
+        assert!(higher_defender.miss > even.miss);
+        assert!(higher_defender.crit < even.crit);
+        assert_eq!(lower_defender.miss, 0.0);
+        assert!(lower_defender.crit > even.crit);
+    }
+
+    #[test]
+    fn test_block_only_with_shield() {
+        let mut shielded = profile(5);
+        shielded.block_chance = SHIELD_BLOCK_CHANCE;
+
+        assert_eq!(AttackTable::new(&profile(5), &profile(5)).block, 0.0);
+        assert_eq!(AttackTable::new(&profile(5), &shielded).outcome_for_roll(0.15), HitOutcome::Block);
+    }
+
+    #[test]
+    fn test_apply_outcome() {
+        assert_eq!(apply_outcome(HitOutcome::Hit, 100, 2.0), 100);
+        assert_eq!(apply_outcome(HitOutcome::Critical, 100, 2.0), 200);
+        assert_eq!(apply_outcome(HitOutcome::Block, 100, 2.0), 50);
+        assert_eq!(apply_outcome(HitOutcome::Miss, 100, 2.0), 0);
+        assert_eq!(apply_outcome(HitOutcome::Dodge, 100, 2.0), 0);
+        assert_eq!(apply_outcome(HitOutcome::Immune, 100, 2.0), 0);
+    }
+}
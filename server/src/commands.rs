@@ -3,6 +3,20 @@
 use mmo_shared::{ServerMessage, get_item_definitions};
 use crate::entities::ServerPlayer;
 use crate::world::GameWorld;
+use crate::world::combat_log::now_ms;
+use crate::persistence::COMBAT_LOG_RETENTION_DAYS;
+
+/// Default time range for /combathistory (minutes)
+const DEFAULT_COMBAT_HISTORY_MINUTES: u64 = 10;
+
+/// Combat history lookup requested by an admin (run by the server against the database)
+pub struct CombatLogQuery {
+    pub character_name: String,
+    /// Start of the time range (unix ms)
+    pub from_ms: u64,
+    /// End of the time range (unix ms)
+    pub to_ms: u64,
+}
 
 /// Result of executing a command
 pub struct CommandResult {
@@ -18,6 +32,8 @@ pub struct CommandResult {
     pub inventory_update: Option<ServerMessage>,
     /// Optional Teleport message if position changed
     pub teleport: Option<ServerMessage>,
+    /// Optional combat history lookup to run
+    pub combat_log_query: Option<CombatLogQuery>,
 }
 
 impl CommandResult {
@@ -29,6 +45,7 @@ impl CommandResult {
             gold_update: None,
             inventory_update: None,
             teleport: None,
+            combat_log_query: None,
         }
     }
     
@@ -40,6 +57,7 @@ impl CommandResult {
             gold_update: None,
             inventory_update: None,
             teleport: None,
+            combat_log_query: None,
         }
     }
     
@@ -75,6 +93,11 @@ impl CommandResult {
         self.teleport = Some(ServerMessage::Teleport { position });
         self
     }
+    
+    pub fn with_combat_log_query(mut self, query: CombatLogQuery) -> Self {
+        self.combat_log_query = Some(query);
+        self
+    }
 }
 
 /// Parse and execute a chat command
@@ -177,6 +200,27 @@ pub fn parse_and_execute(
                 cmd_reset(player_id, world)
             }
         }
+        "combathistory" => {
+            if !is_admin {
+                CommandResult::error("This command requires admin privileges")
+            } else {
+                cmd_combat_history(args)
+            }
+        }
+        "event" => {
+            if !is_admin {
+                CommandResult::error("This command requires admin privileges")
+            } else {
+                cmd_event(args, world)
+            }
+        }
+        "reload" => {
+            if !is_admin {
+                CommandResult::error("This command requires admin privileges")
+            } else {
+                cmd_reload(args, world)
+            }
+        }
         
         _ => CommandResult::error(format!("Unknown command: /{}", command)),
     })
@@ -192,6 +236,7 @@ fn cmd_help(is_admin: bool) -> CommandResult {
     help.push_str("  /items [page] - List items with IDs\n");
     help.push_str("  /pos - Show your current position\n");
     help.push_str("  /clear - Clear chat (client-side)\n");
+    help.push_str("  /combatlog on|off - Stream your combat log to chat (client-side)\n");
     
     if is_admin {
         help.push_str("\nAdmin commands:\n");
@@ -205,6 +250,9 @@ fn cmd_help(is_admin: bool) -> CommandResult {
         help.push_str("  /item get <id> [qty] - Add item to inventory\n");
         help.push_str("  /tp <x> <y> <z> - Teleport to coordinates\n");
         help.push_str("  /reset - Reset position to zone spawn point\n");
+        help.push_str("  /combathistory <character> [minutes] [until_minutes_ago] - Query combat history\n");
+        help.push_str("  /event list|start <id>|cancel <id> - Manage world events\n");
+        help.push_str("  /reload spawns|obstacles|heightmaps|all - Reload level data exported from Godot\n");
     }
     
     CommandResult::success(help)
@@ -528,3 +576,81 @@ fn cmd_reset(player_id: u64, world: &mut GameWorld) -> CommandResult {
         CommandResult::error("Player not found")
     }
 }
+
+fn cmd_combat_history(args: &[&str]) -> CommandResult {
+    const USAGE: &str = "Usage: /combathistory <character> [minutes] [until_minutes_ago]";
+    
+    if args.is_empty() {
+        return CommandResult::error(USAGE);
+    }
+    
+    let max_minutes = COMBAT_LOG_RETENTION_DAYS * 24 * 60;
+    
+    let minutes: u64 = match args.get(1) {
+        Some(arg) => match arg.parse() {
+            Ok(m) if m >= 1 && m <= max_minutes => m,
+            _ => return CommandResult::error(format!("Minutes must be between 1 and {}", max_minutes)),
+        },
+        None => DEFAULT_COMBAT_HISTORY_MINUTES,
+    };
+    
+    let until_minutes_ago: u64 = match args.get(2) {
+        Some(arg) => match arg.parse() {
+            Ok(m) if m <= max_minutes => m,
+            _ => return CommandResult::error(USAGE),
+        },
+        None => 0,
+    };
+    
+    let to_ms = now_ms().saturating_sub(until_minutes_ago * 60 * 1000);
+    let from_ms = to_ms.saturating_sub(minutes * 60 * 1000);
+    
+    CommandResult::success(format!(
+        "Searching combat history of '{}' ({} min, ending {} min ago)...",
+        args[0], minutes, until_minutes_ago
+    )).with_combat_log_query(CombatLogQuery {
+        character_name: args[0].to_string(),
+        from_ms,
+        to_ms,
+    })
+}
+
+fn cmd_event(args: &[&str], world: &mut GameWorld) -> CommandResult {
+    const USAGE: &str = "Usage: /event list | /event start <id> | /event cancel <id>";
+    
+    match (args.first().copied(), args.get(1)) {
+        (Some("list"), _) => {
+            let lines = world.describe_world_events();
+            if lines.is_empty() {
+                return CommandResult::success("No world events loaded");
+            }
+            CommandResult::success(format!("World events:\n  {}", lines.join("\n  ")))
+        }
+        (Some("start"), Some(id)) => match world.start_world_event(id) {
+            Ok(name) => CommandResult::success(format!("Starting {}", name)),
+            Err(e) => CommandResult::error(e),
+        },
+        (Some("cancel"), Some(id)) => match world.cancel_world_event(id) {
+            Ok(name) => CommandResult::success(format!("Cancelling {}", name)),
+            Err(e) => CommandResult::error(e),
+        },
+        _ => CommandResult::error(USAGE),
+    }
+}
+
+fn cmd_reload(args: &[&str], world: &mut GameWorld) -> CommandResult {
+    const USAGE: &str = "Usage: /reload spawns|obstacles|heightmaps|all";
+    
+    let (spawn_areas, obstacles, heightmaps) = match args.first().copied() {
+        Some("spawns") => (true, false, false),
+        Some("obstacles") => (false, true, false),
+        Some("heightmaps") => (false, false, true),
+        Some("all") => (true, true, true),
+        _ => return CommandResult::error(USAGE),
+    };
+    
+    match world.reload_level_data(spawn_areas, obstacles, heightmaps) {
+        Ok(summary) => CommandResult::success(summary),
+        Err(e) => CommandResult::error(format!("Reload failed, nothing was changed: {}", e)),
+    }
+}
@@ -1,34 +1,14 @@
-//! Server-side enemy entity with basic AI.
+//! Server-side enemy entity. Decisions are made by the state machine in `ai`.
 
+use std::sync::Arc;
 use log::{debug, trace};
-use mmo_shared::{AnimationState, EnemyType};
+use mmo_shared::{AnimationState, EnemyType, HitOutcome};
+use crate::ai::{self, AiMemory, AiState, Neighbour, Perception};
+use crate::combat::{CombatProfile, HitResult};
 use crate::navigation::{
-    Obstacle, Vec2, NavigationState, navigate_toward, ENEMY_RADIUS,
+    separation, Body, NavMesh, Obstacle, Vec2, NavigationState, navigate_toward, has_line_of_sight,
 };
-
-/// Enemy aggro range - how far an enemy will detect a player
-const AGGRO_RANGE: f32 = 10.0;
-
-/// Enemy attack range - how close the enemy needs to be to attack
-const ATTACK_RANGE: f32 = 2.0;
-
-/// Enemy attack cooldown in seconds
-const ATTACK_COOLDOWN: f32 = 2.0;
-
-/// Enemy movement speed
-const ENEMY_SPEED: f32 = 3.0;
-
-/// Enemy return speed (faster when evading back to spawn)
-const ENEMY_RETURN_SPEED: f32 = 5.0;
-
-/// Default leash range - how far from spawn before enemy gives up chase
-const DEFAULT_LEASH_RANGE: f32 = 25.0;
-
-/// How close to spawn point before enemy is considered "home"
-const HOME_THRESHOLD: f32 = 2.0;
-
-/// Health regeneration rate when evading (per second, as fraction of max health)
-const EVADE_REGEN_RATE: f32 = 0.2;
+use crate::world::enemy_template::EnemyTemplate;
 
 /// Server-side enemy state
 #[derive(Debug)]
@@ -36,6 +16,9 @@ pub struct ServerEnemy {
     pub id: u64,
     /// Zone this enemy belongs to
     pub zone_id: u32,
+    /// Template this enemy was spawned from
+    pub template_id: String,
+    /// Model the client renders
     pub enemy_type: EnemyType,
     pub position: [f32; 3],
     pub spawn_position: [f32; 3],
@@ -50,194 +33,176 @@ pub struct ServerEnemy {
     pub leash_range: f32,
     /// Navigation state for pathfinding
     pub nav_state: NavigationState,
-    /// Whether the enemy is evading (returning to spawn after leash)
-    pub is_evading: bool,
+    /// Nav-mesh of the zone (None = steer and grid A* only)
+    pub navmesh: Option<Arc<NavMesh>>,
+    /// AI state and memory
+    pub ai: AiMemory,
+    /// Active slow (speed multiplier, remaining seconds)
+    pub slow: Option<(f32, f32)>,
+    /// Takes no damage (boss immunity windows)
+    pub is_immune: bool,
+    /// Stats, ranges, abilities and loot of the mob type
+    pub template: EnemyTemplate,
+    /// Remaining cooldown per template ability (seconds)
+    pub ability_cooldowns: Vec<f32>,
 }
 
 impl ServerEnemy {
-    pub fn new(id: u64, zone_id: u32, enemy_type: EnemyType, position: [f32; 3]) -> Self {
-        // Stats and level range based on enemy type
-        let (health, attack_power, min_level, max_level) = match enemy_type {
-            EnemyType::Goblin => (50, 8, 1, 3),       // Weakest enemy
-            EnemyType::Wolf => (65, 10, 2, 4),        // Pack predator, early-mid enemy
-            EnemyType::Skeleton => (80, 14, 3, 5),    // Undead warrior
-            EnemyType::Mutant => (150, 25, 5, 8),     // Elite enemy, very dangerous
-        };
-        
-        // Random level within range
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
-        let level = rng.gen_range(min_level..=max_level);
-        
-        // Scale health and attack power by level
-        let level_multiplier = 1.0 + (level as f32 - 1.0) * 0.15;
-        let scaled_health = (health as f32 * level_multiplier) as u32;
-        let scaled_attack = (attack_power as f32 * level_multiplier) as u32;
+    /// Create an enemy from its template at a level
+    pub fn new(id: u64, zone_id: u32, template_id: &str, template: EnemyTemplate, position: [f32; 3], level: u8) -> Self {
+        let (health, attack_power) = template.stats_for_level(level);
+        let ability_cooldowns = template.abilities.iter().map(|a| a.initial_delay).collect();
         
         Self {
             id,
             zone_id,
-            enemy_type,
+            template_id: template_id.to_string(),
+            enemy_type: template.model,
             position,
             spawn_position: position,
             rotation: 0.0,
-            health: scaled_health,
-            max_health: scaled_health,
+            health,
+            max_health: health,
             level,
-            attack_power: scaled_attack,
+            attack_power,
             animation_state: AnimationState::Idle,
             target_id: None,
             attack_cooldown: 0.0,
-            leash_range: DEFAULT_LEASH_RANGE,
+            leash_range: template.leash_range,
             nav_state: NavigationState::new(),
-            is_evading: false,
+            navmesh: None,
+            ai: AiMemory::new(position),
+            slow: None,
+            is_immune: false,
+            template,
+            ability_cooldowns,
         }
     }
     
-    /// Get the collision radius for this enemy type
-    fn get_radius(&self) -> f32 {
-        match self.enemy_type {
-            EnemyType::Mutant => ENEMY_RADIUS * 1.5, // Larger enemy
-            _ => ENEMY_RADIUS,
+    /// Display name of the mob type
+    pub fn name(&self) -> &str {
+        &self.template.name
+    }
+    
+    /// Whether auto-attacks fire projectiles
+    pub fn is_ranged(&self) -> bool {
+        self.template.projectile_speed > 0.0
+    }
+    
+    /// Whether the enemy is evading (returning to spawn after leash)
+    pub fn is_evading(&self) -> bool {
+        self.ai.state == AiState::Evade
+    }
+    
+    /// Drop the target and walk home
+    pub fn start_evading(&mut self) {
+        ai::enter(self, AiState::Evade);
+    }
+    
+    /// Put every ability back on its initial delay (out of combat)
+    fn reset_ability_cooldowns(&mut self) {
+        self.ability_cooldowns = self.template.abilities.iter().map(|a| a.initial_delay).collect();
+    }
+    
+    /// Check for a clear line of sight to a position
+    pub fn can_see(&self, target: [f32; 3], obstacles: &[Obstacle]) -> bool {
+        has_line_of_sight(Vec2::from_3d(self.position), Vec2::from_3d(target), obstacles)
+    }
+    
+    /// Get combat stats for hit resolution (crit, dodge, block)
+    pub fn combat_profile(&self) -> CombatProfile {
+        CombatProfile {
+            level: self.level as u32,
+            crit_chance: self.template.crit_chance,
+            crit_multiplier: 1.5,
+            dodge_chance: self.template.dodge_chance,
+            block_chance: 0.0,
         }
     }
     
+    /// Turn a resolved hit into an immune hit while immunity is active
+    pub fn apply_immunity(&self, hit: HitResult) -> HitResult {
+        if self.is_immune {
+            HitResult { outcome: HitOutcome::Immune, damage: 0 }
+        } else {
+            hit
+        }
+    }
+    
+    /// Apply a slow (keeps the stronger slow if one is already active)
+    pub fn apply_slow(&mut self, multiplier: f32, duration: f32) {
+        if self.slow.is_none_or(|(current, _)| multiplier <= current) {
+            self.slow = Some((multiplier, duration));
+        }
+    }
+    
+    /// Current movement speed from the template and active slows (units per second)
+    pub fn get_movement_speed(&self) -> f32 {
+        let multiplier = self.slow.map(|(mult, _)| mult).unwrap_or(1.0);
+        self.template.move_speed * multiplier
+    }
+    
     /// Update enemy AI with obstacle awareness
     /// Returns Some((target_player_id, damage)) if enemy attacks this frame
     pub fn update(
         &mut self,
         delta: f32,
         player_positions: &[(u64, [f32; 3])],
+        neighbours: &[Neighbour],
         obstacles: &[Obstacle],
     ) -> Option<(u64, u32)> {
-        // Log occasionally when we have a target
-        static UPDATE_COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
-        let count = UPDATE_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-        
         // Update attack cooldown
         if self.attack_cooldown > 0.0 {
             self.attack_cooldown -= delta;
         }
         
-        // If dead, do nothing
-        if self.health == 0 {
-            self.animation_state = AnimationState::Dead;
-            return None;
+        // Tick down slows
+        if let Some((multiplier, remaining)) = self.slow {
+            self.slow = (remaining > delta).then_some((multiplier, remaining - delta));
         }
         
-        let dist_from_spawn = self.distance_to(self.spawn_position);
-        
-        // Handle evading state (returning to spawn)
-        if self.is_evading {
-            // Regenerate health while evading
-            let regen_amount = (self.max_health as f32 * EVADE_REGEN_RATE * delta) as u32;
-            self.health = (self.health + regen_amount).min(self.max_health);
-            
-            // Check if we're home
-            if dist_from_spawn < HOME_THRESHOLD {
-                self.is_evading = false;
-                self.health = self.max_health; // Full heal when reaching spawn
-                self.animation_state = AnimationState::Idle;
-                self.nav_state.clear_path();
-                debug!("[ENEMY {}] Returned home, health restored to {}", self.id, self.health);
-                return None;
+        // Ability cooldowns only run in combat
+        if self.target_id.is_some() {
+            for cooldown in &mut self.ability_cooldowns {
+                *cooldown -= delta;
             }
-            
-            // Move back to spawn faster
-            self.move_towards_with_speed(self.spawn_position, delta, obstacles, ENEMY_RETURN_SPEED);
-            self.animation_state = AnimationState::Walking;
-            return None;
+        } else {
+            self.reset_ability_cooldowns();
         }
         
-        // Check leash - if too far from spawn, start evading
-        if dist_from_spawn > self.leash_range {
-            self.is_evading = true;
-            self.target_id = None;
-            self.nav_state.clear_path();
-            debug!("[ENEMY {}] Leash triggered at dist={:.1}, returning to spawn", self.id, dist_from_spawn);
+        // If dead, do nothing
+        if self.health == 0 {
+            self.animation_state = AnimationState::Dead;
             return None;
         }
         
-        // Find closest player in aggro range
-        let mut closest_player: Option<(u64, f32)> = None;
-        for (player_id, player_pos) in player_positions {
-            let dist = self.distance_to(*player_pos);
-            if dist <= AGGRO_RANGE {
-                if closest_player.is_none() || dist < closest_player.unwrap().1 {
-                    closest_player = Some((*player_id, dist));
-                }
-            }
-        }
-        
-        // Update target based on aggro
-        if let Some((player_id, dist)) = closest_player {
-            let was_already_targeting = self.target_id == Some(player_id);
-            self.target_id = Some(player_id);
-            
-            // Get target position
-            if let Some((_, target_pos)) = player_positions.iter().find(|(id, _)| *id == player_id) {
-                // Log when we start chasing a new target
-                if !was_already_targeting {
-                    debug!("[ENEMY {}] Started chasing player {} at ({:.2}, {:.2}), my pos=({:.2}, {:.2}), {} obstacles",
-                        self.id, player_id, target_pos[0], target_pos[2], 
-                        self.position[0], self.position[2], obstacles.len());
-                }
-                
-                // Log chase status every ~2 seconds (40 ticks at 20 tick/s)
-                if count % 40 == 0 && dist > ATTACK_RANGE {
-                    debug!("[ENEMY {}] Chasing player {} dist={:.2}, spawn_dist={:.2}, my_pos=({:.2}, {:.2})",
-                        self.id, player_id, dist, dist_from_spawn, self.position[0], self.position[2]);
-                }
-                
-                // Always face the target when we have one
-                self.face_towards(*target_pos);
-                
-                if dist <= ATTACK_RANGE {
-                    // In attack range - try to attack
-                    self.animation_state = AnimationState::Attacking;
-                    // Clear path when attacking (we've reached the target)
-                    self.nav_state.clear_path();
-                    
-                    // Attack if cooldown is ready
-                    if self.attack_cooldown <= 0.0 {
-                        self.attack_cooldown = ATTACK_COOLDOWN;
-                        // Return attack event with damage
-                        return Some((player_id, self.attack_power));
-                    }
-                } else {
-                    // Chase player with obstacle avoidance
-                    self.move_towards_with_avoidance(*target_pos, delta, obstacles);
-                    self.animation_state = AnimationState::Walking;
-                }
-            }
-        } else {
-            // No target - idle or patrol
-            self.target_id = None;
-            self.animation_state = AnimationState::Idle;
-            
-            // Simple patrol: return towards spawn if far
-            if dist_from_spawn > 5.0 {
-                self.move_towards_with_avoidance(self.spawn_position, delta, obstacles);
-                self.animation_state = AnimationState::Walking;
-            } else {
-                // Clear path when idle near spawn
-                self.nav_state.clear_path();
-            }
-        }
-        
-        None
+        let perception = Perception::gather(self, player_positions, neighbours, obstacles);
+        let attack = ai::think(self, &perception, delta, obstacles);
+        self.keep_apart(neighbours, obstacles, delta);
+        attack
+    }
+    
+    /// Step out of other enemies it overlaps, no faster than it can walk
+    fn keep_apart(&mut self, neighbours: &[Neighbour], obstacles: &[Obstacle], delta: f32) {
+        let body = Body { id: self.id, position: Vec2::from_3d(self.position), radius: self.template.radius };
+        let others: Vec<Body> = neighbours.iter()
+            .map(|n| Body { id: n.id, position: Vec2::from_3d(n.position), radius: n.radius })
+            .collect();
+        let separated = separation::separate(body, &others, obstacles, self.get_movement_speed() * delta);
+        self.position[0] = separated.x;
+        self.position[2] = separated.z;
     }
     
     /// Move towards a target position with obstacle avoidance
-    fn move_towards_with_avoidance(&mut self, target: [f32; 3], delta: f32, obstacles: &[Obstacle]) {
-        self.move_towards_with_speed(target, delta, obstacles, ENEMY_SPEED);
+    pub(crate) fn move_towards_with_avoidance(&mut self, target: [f32; 3], delta: f32, obstacles: &[Obstacle]) {
+        self.move_towards_with_speed(target, delta, obstacles, self.get_movement_speed());
     }
     
     /// Move towards a target position with obstacle avoidance at a custom speed
-    fn move_towards_with_speed(&mut self, target: [f32; 3], delta: f32, obstacles: &[Obstacle], speed: f32) {
+    pub(crate) fn move_towards_with_speed(&mut self, target: [f32; 3], delta: f32, obstacles: &[Obstacle], speed: f32) {
         let current_pos = Vec2::from_3d(self.position);
         let target_pos = Vec2::new(target[0], target[2]);
-        let enemy_radius = self.get_radius();
+        let enemy_radius = self.template.radius;
         
         let old_pos = self.position;
         
@@ -245,6 +210,7 @@ impl ServerEnemy {
             current_pos,
             target_pos,
             obstacles,
+            self.navmesh.as_deref(),
             &mut self.nav_state,
             speed,
             delta,
@@ -278,7 +244,7 @@ impl ServerEnemy {
         let dist = (dx * dx + dz * dz).sqrt();
         
         if dist > 0.1 {
-            let move_dist = ENEMY_SPEED * delta;
+            let move_dist = self.get_movement_speed() * delta;
             let ratio = (move_dist / dist).min(1.0);
             
             self.position[0] += dx * ratio;
@@ -291,14 +257,14 @@ impl ServerEnemy {
     }
     
     /// Calculate distance to a position
-    fn distance_to(&self, target: [f32; 3]) -> f32 {
+    pub(crate) fn distance_to(&self, target: [f32; 3]) -> f32 {
         let dx = target[0] - self.position[0];
         let dz = target[2] - self.position[2];
         (dx * dx + dz * dz).sqrt()
     }
     
     /// Update rotation to face a target position
-    fn face_towards(&mut self, target: [f32; 3]) {
+    pub(crate) fn face_towards(&mut self, target: [f32; 3]) {
         let dx = target[0] - self.position[0];
         let dz = target[2] - self.position[2];
         if dx.abs() > 0.01 || dz.abs() > 0.01 {
@@ -317,3 +283,43 @@ impl ServerEnemy {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::navigation::BoxObstacle;
+
+    fn archer() -> ServerEnemy {
+        let template: EnemyTemplate = serde_json::from_str(r#"{
+            "name": "Archer", "model": "SkeletonArcher", "health": 60, "attack": 12,
+            "min_level": 1, "max_level": 1,
+            "attack_range": 12.0, "preferred_distance": 8.0, "projectile_speed": 15.0
+        }"#).unwrap();
+        ServerEnemy::new(1, 1, "archer", template, [0.0, 0.0, 0.0], 1)
+    }
+
+    #[test]
+    fn test_ranged_enemy_shoots_then_backs_off() {
+        let mut enemy = archer();
+        let players = [(7, [4.0, 0.0, 0.0])];
+
+        // In range with a clear shot: fires from where it stands
+        assert_eq!(enemy.update(0.05, &players, &[], &[]).map(|(id, _)| id), Some(7));
+        assert_eq!(enemy.position, [0.0, 0.0, 0.0]);
+
+        // While reloading it steps away from a player inside its preferred distance
+        assert!(enemy.update(0.05, &players, &[], &[]).is_none());
+        assert!(enemy.position[0] < 0.0);
+    }
+
+    #[test]
+    fn test_ranged_enemy_needs_line_of_sight() {
+        let mut enemy = archer();
+        let players = [(7, [10.0, 0.0, 0.0])];
+        let wall = [Obstacle::Box(BoxObstacle::from_corners(4.0, -3.0, 5.0, 3.0))];
+
+        // Target is in range but behind a wall: move instead of shooting
+        assert!(enemy.update(0.05, &players, &[], &wall).is_none());
+        assert_eq!(enemy.animation_state, AnimationState::Walking);
+    }
+}
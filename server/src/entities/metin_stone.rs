@@ -0,0 +1,185 @@
+//! Server-side Metin stone entity.
+
+use mmo_shared::ServerMessage;
+use crate::combat::CombatProfile;
+use crate::world::metin_stone::MetinStoneTemplate;
+
+/// Seconds after the last hit that a stone stops calling enemies
+const UNDER_ATTACK_DURATION: f32 = 10.0;
+
+/// How often changing stone health is re-broadcast (seconds)
+const STATE_UPDATE_INTERVAL: f32 = 0.5;
+
+/// Destructible stone that calls waves of enemies while it is attacked
+#[derive(Debug)]
+pub struct ServerMetinStone {
+    pub id: u64,
+    pub zone_id: u32,
+    /// Index into the stone manager's templates
+    pub template: usize,
+    /// Spawn area the stone was placed in
+    pub spawn_area: String,
+    pub name: String,
+    pub level: u8,
+    pub position: [f32; 3],
+    pub health: u32,
+    pub max_health: u32,
+    pub defense: u32,
+    /// Enemies the stone called that are still alive
+    pub linked_enemies: Vec<u64>,
+    /// Player who hit the stone last (waves go after them)
+    pub last_attacker: Option<u64>,
+    /// Seconds since the last hit
+    since_hit: f32,
+    /// Seconds until the next wave while under attack
+    wave_timer: f32,
+    state_timer: f32,
+    /// State last broadcast (health, under attack)
+    last_sent: (u32, bool),
+}
+
+impl ServerMetinStone {
+    pub fn new(id: u64, zone_id: u32, index: usize, template: &MetinStoneTemplate, spawn_area: &str, position: [f32; 3]) -> Self {
+        Self {
+            id,
+            zone_id,
+            template: index,
+            spawn_area: spawn_area.to_string(),
+            name: template.name.clone(),
+            level: template.level,
+            position,
+            health: template.max_health,
+            max_health: template.max_health,
+            defense: template.defense,
+            linked_enemies: Vec::new(),
+            last_attacker: None,
+            since_hit: UNDER_ATTACK_DURATION,
+            wave_timer: 0.0,
+            state_timer: 0.0,
+            last_sent: (template.max_health, false),
+        }
+    }
+
+    pub fn is_destroyed(&self) -> bool {
+        self.health == 0
+    }
+
+    /// Hit within the last few seconds
+    pub fn is_under_attack(&self) -> bool {
+        self.since_hit < UNDER_ATTACK_DURATION
+    }
+
+    /// Stones never dodge, block or crit
+    pub fn combat_profile(&self) -> CombatProfile {
+        CombatProfile {
+            level: self.level as u32,
+            crit_chance: 0.0,
+            crit_multiplier: 1.0,
+            dodge_chance: 0.0,
+            block_chance: 0.0,
+        }
+    }
+
+    /// Apply a hit after defense (every hit that lands chips at least 1)
+    /// The first hit of a fight calls a wave right away
+    /// Returns the damage dealt
+    pub fn take_damage(&mut self, attacker_id: u64, damage: u32) -> u32 {
+        if damage == 0 || self.is_destroyed() {
+            return 0;
+        }
+        if !self.is_under_attack() {
+            self.wave_timer = 0.0;
+        }
+        self.since_hit = 0.0;
+        self.last_attacker = Some(attacker_id);
+
+        let dealt = damage.saturating_sub(self.defense / 2).max(1).min(self.health);
+        self.health -= dealt;
+        dealt
+    }
+
+    /// Advance the attack and wave timers
+    /// Returns true when a wave should be called
+    pub fn update(&mut self, delta: f32, wave_interval: f32) -> bool {
+        self.since_hit += delta;
+        if !self.is_under_attack() || self.is_destroyed() {
+            return false;
+        }
+        self.wave_timer -= delta;
+        if self.wave_timer > 0.0 {
+            return false;
+        }
+        self.wave_timer = wave_interval;
+        true
+    }
+
+    /// State message if health or combat state changed (health changes are throttled)
+    pub fn take_state_update(&mut self, delta: f32) -> Option<ServerMessage> {
+        self.state_timer -= delta;
+        let state = (self.health, self.is_under_attack());
+        let combat_changed = state.1 != self.last_sent.1;
+        let health_due = state.0 != self.last_sent.0 && self.state_timer <= 0.0;
+        if !combat_changed && !health_due {
+            return None;
+        }
+        self.state_timer = STATE_UPDATE_INTERVAL;
+        self.last_sent = state;
+        Some(ServerMessage::MetinStoneState {
+            id: self.id,
+            health: self.health,
+            max_health: self.max_health,
+            under_attack: state.1,
+        })
+    }
+
+    pub fn spawn_message(&self) -> ServerMessage {
+        ServerMessage::MetinStoneSpawn {
+            id: self.id,
+            zone_id: self.zone_id,
+            name: self.name.clone(),
+            position: self.position,
+            health: self.health,
+            max_health: self.max_health,
+            level: self.level,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stone() -> ServerMetinStone {
+        let template: MetinStoneTemplate = serde_json::from_str(r#"{
+            "id": "test", "name": "Stone of Battle", "zone_id": 1, "level": 5,
+            "max_health": 100, "defense": 10, "respawn_time": 60.0, "wave_interval": 5.0,
+            "wave": [{ "enemy_type": "Goblin", "count": 2 }]
+        }"#).unwrap();
+        ServerMetinStone::new(50000, 1, 0, &template, "meadow", [0.0; 3])
+    }
+
+    #[test]
+    fn test_waves_only_while_under_attack() {
+        let mut stone = stone();
+        assert!(!stone.update(1.0, 5.0));
+
+        // First hit calls a wave right away, then one every interval
+        assert_eq!(stone.take_damage(1, 20), 15);
+        assert!(stone.update(0.1, 5.0));
+        assert!(!stone.update(4.0, 5.0));
+        assert!(stone.update(1.0, 5.0));
+
+        // Left alone it calms down
+        assert!(!stone.update(UNDER_ATTACK_DURATION, 5.0));
+        assert!(!stone.is_under_attack());
+    }
+
+    #[test]
+    fn test_every_hit_chips_the_stone() {
+        let mut stone = stone();
+        assert_eq!(stone.take_damage(1, 3), 1);
+        assert_eq!(stone.take_damage(1, 500), 99);
+        assert!(stone.is_destroyed());
+        assert_eq!(stone.take_damage(1, 10), 0);
+    }
+}
@@ -4,8 +4,12 @@ pub mod player;
 mod enemy;
 mod item;
 mod npc;
+mod metin_stone;
+mod projectile;
 
-pub use player::{ServerPlayer, ActiveBuff, BuffEffect, MAX_LEVEL};
+pub use player::{ServerPlayer, ActiveBuff, BuffEffect, CastEvent, ARROW_SPEED, MAX_LEVEL};
 pub use enemy::ServerEnemy;
 pub use item::WorldItem;
 pub use npc::ServerNpc;
+pub use metin_stone::ServerMetinStone;
+pub use projectile::{ServerProjectile, ProjectilePayload, ProjectileImpact, PROJECTILE_HEIGHT};
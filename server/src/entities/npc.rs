@@ -1,6 +1,15 @@
 //! Server-side NPC entity.
 
+use std::sync::Arc;
 use mmo_shared::{AnimationState, NpcType};
+use crate::navigation::{NavMesh, Obstacle, Vec2, NavigationState, navigate_toward, ENEMY_RADIUS};
+use crate::world::npc_schedule::NpcSchedule;
+
+/// NPC walking speed between schedule stops (units per second)
+const NPC_WALK_SPEED: f32 = 2.0;
+
+/// How close to a stop counts as arrived (units)
+const STOP_ARRIVE_DISTANCE: f32 = 0.3;
 
 /// Server-side NPC state
 #[derive(Debug)]
@@ -13,6 +22,17 @@ pub struct ServerNpc {
     pub spawn_position: [f32; 3],
     pub rotation: f32,
     pub animation_state: AnimationState,
+    /// Zone NPC spawn this NPC came from
+    pub spawn_id: Option<i32>,
+    /// Stops the NPC walks between (None = stands at spawn)
+    pub schedule: Option<NpcSchedule>,
+    /// Stop the NPC is at or walking to
+    current_stop: usize,
+    /// Seconds left at the current stop (0 = walking)
+    wait_remaining: f32,
+    nav_state: NavigationState,
+    /// Nav-mesh of the zone (None = steer and grid A* only)
+    pub navmesh: Option<Arc<NavMesh>>,
 }
 
 impl ServerNpc {
@@ -25,13 +45,83 @@ impl ServerNpc {
             spawn_position: position,
             rotation,
             animation_state: AnimationState::Idle,
+            spawn_id: None,
+            schedule: None,
+            current_stop: 0,
+            wait_remaining: 0.0,
+            nav_state: NavigationState::new(),
+            navmesh: None,
+        }
+    }
+
+    /// Switch to a rebuilt nav-mesh, dropping the route planned on the old one
+    pub fn set_navmesh(&mut self, navmesh: Option<Arc<NavMesh>>) {
+        self.navmesh = navmesh;
+        self.nav_state = NavigationState::new();
+    }
+
+    /// Whether the NPC moves around (clients need its position every tick)
+    pub fn is_roaming(&self) -> bool {
+        self.schedule.is_some()
+    }
+
+    /// Follow the schedule: walk to the current stop, wait there, then move on
+    pub fn update(&mut self, delta: f32, obstacles: &[Obstacle]) {
+        let Some(schedule) = &self.schedule else {
+            // Static NPC - always idle
+            self.animation_state = AnimationState::Idle;
+            return;
+        };
+        let stop = &schedule.stops[self.current_stop];
+
+        if self.wait_remaining > 0.0 {
+            self.wait_remaining -= delta;
+            if self.wait_remaining <= 0.0 {
+                self.current_stop = (self.current_stop + 1) % schedule.stops.len();
+            }
+            self.animation_state = AnimationState::Idle;
+            return;
+        }
+
+        let here = Vec2::from_3d(self.position);
+        let target = Vec2::from_3d(stop.position);
+        if here.distance_to(target) <= STOP_ARRIVE_DISTANCE {
+            self.wait_remaining = stop.wait.max(delta);
+            self.rotation = stop.rotation;
+            self.nav_state.clear_path();
+            self.animation_state = AnimationState::Idle;
+            return;
         }
+
+        let nav_result = navigate_toward(here, target, obstacles, self.navmesh.as_deref(), &mut self.nav_state, NPC_WALK_SPEED, delta, ENEMY_RADIUS);
+        self.position[0] = nav_result.new_position.x;
+        self.position[2] = nav_result.new_position.z;
+        self.rotation = nav_result.rotation;
+        self.animation_state = AnimationState::Walking;
     }
-    
-    /// Update NPC (for future roaming behavior)
-    /// Currently NPCs are static and just idle
-    pub fn update(&mut self, _delta: f32) {
-        // Static NPC - always idle
-        self.animation_state = AnimationState::Idle;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_walks_waits_and_loops() {
+        let mut npc = ServerNpc::new(1, 1, NpcType::OldMan, [0.0, 0.0, 0.0], 0.0);
+        npc.schedule = Some(serde_json::from_str(r#"{ "stops": [
+            { "position": [0.0, 0.0, 0.0], "rotation": 1.0, "wait": 1.0 },
+            { "position": [2.0, 0.0, 0.0], "wait": 1.0 }
+        ] }"#).unwrap());
+
+        // Already at the first stop: faces its direction and waits
+        npc.update(0.1, &[]);
+        assert_eq!(npc.rotation, 1.0);
+        npc.update(1.0, &[]);
+        assert_eq!(npc.current_stop, 1);
+
+        // Walks over to the second one
+        npc.update(0.5, &[]);
+        assert_eq!(npc.animation_state, AnimationState::Walking);
+        assert!(npc.position[0] > 0.0);
     }
 }
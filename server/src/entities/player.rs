@@ -1,7 +1,14 @@
 //! Server-side player entity.
 
 use mmo_shared::{AnimationState, InventorySlot, ItemEffect, ItemDef, ItemType, CharacterClass, Gender, Empire, get_item_definitions, get_item_slot_size, AbilityEffect, ArmorStats};
+use mmo_shared::{AbilityDef, SkillGrade, SkillRank, Specialization, MAX_SKILL_RANK, SKILL_POINTS_PER_LEVEL};
+use mmo_shared::{Attributes, PrimaryStat, MAX_ATTRIBUTE_VALUE, STAT_POINTS_PER_LEVEL, BASE_MOVE_SPEED, SPRINT_MULTIPLIER, MAX_DURABILITY};
+use rand::Rng;
 use std::collections::HashMap;
+use std::time::Instant;
+
+use crate::combat::{CombatProfile, DEFAULT_CRIT_MULTIPLIER, SHIELD_BLOCK_CHANCE};
+use crate::world::specialization::DEFAULT_SPECIALIZATION_LEVEL;
 
 /// Maximum inventory slots
 const INVENTORY_SIZE: usize = 20;
@@ -39,15 +46,130 @@ pub enum BuffEffect {
     SpeedMultiplier(f32),
     /// Heal over time (heal per tick, tick interval, time until next tick)
     HealOverTime { heal_per_tick: u32, interval: f32, next_tick: f32 },
-    /// Damage over time (damage per tick, tick interval, time until next tick)
-    DamageOverTime { damage_per_tick: u32, interval: f32, next_tick: f32 },
+    /// Damage over time (entity that applied it, damage per tick, tick interval, time until next tick)
+    DamageOverTime { source_id: u64, damage_per_tick: u32, interval: f32, next_tick: f32 },
     /// Stunned (can't move or attack)
     Stunned,
+    /// Resurrection sickness (damage and defense multiplier)
+    ResurrectionSickness(f32),
+}
+
+/// Ability currently being cast or channeled by a player
+#[derive(Debug, Clone)]
+pub struct ActiveCast {
+    /// Ability being cast
+    pub ability_id: u32,
+    /// Validated target at cast start
+    pub target_id: Option<u64>,
+    /// True while channeling (cast time already elapsed)
+    pub is_channel: bool,
+    /// Time elapsed in the current phase (seconds)
+    pub elapsed: f32,
+    /// Duration of the current phase (seconds)
+    pub duration: f32,
+    /// Channel pulses already applied
+    pub pulses_done: u32,
+    /// Position when the cast started (moving away interrupts)
+    pub start_position: [f32; 3],
+    /// Damage taken since the cast started (too much interrupts)
+    pub damage_taken: u32,
+    /// Time until the next progress update is sent
+    pub next_progress: f32,
+}
+
+/// Events produced when advancing an active cast
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CastEvent {
+    /// Periodic progress update (elapsed, duration)
+    Progress(f32, f32),
+    /// Cast time elapsed - the ability should resolve now
+    CastFinished,
+    /// A channel pulse is due - apply the ability effects again
+    ChannelPulse,
+    /// Channel duration elapsed
+    ChannelFinished,
 }
 
+/// Horizontal distance a caster may drift before the cast is interrupted
+pub const CAST_MOVE_TOLERANCE: f32 = 0.5;
+
+/// Damage taken during a cast (as fraction of max health) that interrupts it
+pub const CAST_INTERRUPT_DAMAGE_PERCENT: f32 = 0.2;
+
+/// Seconds between channel pulses
+pub const CHANNEL_PULSE_INTERVAL: f32 = 1.0;
+
+/// Seconds between cast progress updates
+const CAST_PROGRESS_INTERVAL: f32 = 0.5;
+
+/// Seconds between auto-attack swings at 1.0 attack speed (matches the attack animation)
+pub const BASE_ATTACK_INTERVAL: f32 = 1.53;
+
+/// Maximum distance for melee auto-attacks
+pub const MELEE_ATTACK_RANGE: f32 = 5.0;
+
+/// Maximum distance for bow auto-attacks
+pub const BOW_ATTACK_RANGE: f32 = 15.0;
+
+/// Arrow speed for bow auto-attacks (units per second)
+pub const ARROW_SPEED: f32 = 25.0;
+
+/// Global cooldown triggered by abilities; blocks other abilities and auto-attack swings
+pub const GLOBAL_COOLDOWN: f32 = 1.0;
+
 /// Maximum level cap
 pub const MAX_LEVEL: u32 = 99;
 
+/// Seconds without dealing or taking damage before a player leaves combat
+pub const COMBAT_TIMEOUT: f32 = 6.0;
+
+/// Regeneration multiplier while sitting down to rest
+pub const RESTING_REGEN_MULTIPLIER: f32 = 3.0;
+
+/// Flat health regeneration per player level (per second)
+const HEALTH_REGEN_PER_LEVEL: f32 = 0.1;
+
+/// Flat mana regeneration per player level (per second)
+const MANA_REGEN_PER_LEVEL: f32 = 0.05;
+
+/// Minimum seconds between resource updates sent to the client
+const RESOURCE_UPDATE_INTERVAL: f32 = 1.0;
+
+/// Slowest movement speed multiplier from stacked slows
+const MIN_MOVE_SPEED_MULTIPLIER: f32 = 0.3;
+
+/// Fastest movement speed multiplier from stacked buffs and gear
+const MAX_MOVE_SPEED_MULTIPLIER: f32 = 2.0;
+
+/// Duration of timed speed boosts from consumables (seconds)
+const SPEED_ITEM_DURATION: f32 = 30.0;
+
+/// Extra speed allowed by movement validation to absorb network jitter
+const MOVE_VALIDATION_TOLERANCE: f32 = 1.25;
+
+/// Distance always allowed per update regardless of speed (units)
+const MOVE_VALIDATION_SLACK: f32 = 0.5;
+
+/// Longest idle period that builds up movement allowance (seconds)
+const MAX_MOVE_ALLOWANCE_TIME: f32 = 1.0;
+
+/// Weapon damage and armor defense multiplier once gear durability reaches 0
+const BROKEN_GEAR_EFFECTIVENESS: f32 = 0.5;
+
+/// Repair cost per missing durability point, per character level
+const REPAIR_GOLD_PER_POINT_PER_LEVEL: u64 = 2;
+
+/// Health, mana and combat/rest state as last reported to the client
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResourceSnapshot {
+    pub health: u32,
+    pub max_health: u32,
+    pub mana: u32,
+    pub max_mana: u32,
+    pub in_combat: bool,
+    pub resting: bool,
+}
+
 /// Server-side player state
 #[derive(Debug)]
 pub struct ServerPlayer {
@@ -101,6 +223,46 @@ pub struct ServerPlayer {
     pub next_buff_id: u32,
     /// Action bar (8 slots, each containing an optional ability ID)
     pub action_bar: [Option<u32>; 8],
+    /// Ability currently being cast or channeled (None = not casting)
+    pub active_cast: Option<ActiveCast>,
+    /// Current auto-attack target (None = not auto-attacking)
+    pub auto_attack_target: Option<u64>,
+    /// Time until the next auto-attack swing is ready (seconds)
+    pub swing_timer: f32,
+    /// Remaining global cooldown (seconds)
+    pub global_cooldown: f32,
+    /// Learned skills (ability_id -> rank, see SkillGrade)
+    pub skill_ranks: HashMap<u32, u8>,
+    /// Unspent skill points
+    pub skill_points: u32,
+    /// Chosen class specialization (None = not chosen yet)
+    pub specialization: Option<Specialization>,
+    /// Level at which a specialization can be chosen (see SpecializationConfig)
+    pub specialization_level: u32,
+    /// Primary attributes (VIT/INT/STR/DEX)
+    pub attributes: Attributes,
+    /// Unspent stat points
+    pub stat_points: u32,
+    /// Time until the player drops out of combat (seconds, 0 = out of combat)
+    pub combat_timer: f32,
+    /// Sitting down to rest (faster regeneration)
+    pub is_resting: bool,
+    /// Fractional health/mana regenerated but not yet applied
+    pub regen_remainder: [f32; 2],
+    /// Time until the next throttled resource update may be sent (seconds)
+    pub resource_update_timer: f32,
+    /// Last resource state sent to the client
+    pub last_sent_resources: Option<ResourceSnapshot>,
+    /// Last movement speed sent to the client (None = not sent yet)
+    pub last_sent_move_speed: Option<f32>,
+    /// Distance the player may still cover before position updates are rejected
+    pub move_allowance: f32,
+    /// When the last position update was received
+    pub last_move_update: Option<Instant>,
+    /// Durability of equipped gear (0 = broken, MAX_DURABILITY = fully repaired)
+    pub equipment_durability: u32,
+    /// Time until a dead player is released automatically (None = alive or penalty not applied yet)
+    pub release_timer: Option<f32>,
 }
 
 impl ServerPlayer {
@@ -115,17 +277,31 @@ impl ServerPlayer {
         }
     }
     
-    /// Calculate stats for a given class and level
+    /// Calculate stats for a given class, specialization and level
+    /// Levels after specialization_level use the specialization's growth
     /// Returns (max_health, max_mana, attack, defense)
-    pub fn calculate_stats_for_level(class: CharacterClass, level: u32) -> (u32, u32, u32, u32) {
+    pub fn calculate_stats_for_level(
+        class: CharacterClass,
+        specialization: Option<Specialization>,
+        specialization_level: u32,
+        level: u32,
+    ) -> (u32, u32, u32, u32) {
         let (base_hp, base_mp, base_atk, base_def) = Self::base_stats_for_class(class);
-        let level_bonus = level.saturating_sub(1);
+        
+        let (base_levels, spec_levels, (hp_growth, mp_growth, atk_growth, def_growth)) = match specialization {
+            Some(spec) if level > specialization_level => (
+                specialization_level.saturating_sub(1),
+                level - specialization_level,
+                spec.stat_growth_per_level(),
+            ),
+            _ => (level.saturating_sub(1), 0, (0, 0, 0, 0)),
+        };
         
         (
-            base_hp + level_bonus * 5,    // +5 HP per level
-            base_mp + level_bonus * 3,    // +3 MP per level
-            base_atk + level_bonus * 2,   // +2 ATK per level
-            base_def + level_bonus * 1,   // +1 DEF per level
+            base_hp + base_levels * 5 + spec_levels * hp_growth,    // +5 HP per level
+            base_mp + base_levels * 3 + spec_levels * mp_growth,    // +3 MP per level
+            base_atk + base_levels * 2 + spec_levels * atk_growth,  // +2 ATK per level
+            base_def + base_levels + spec_levels * def_growth,      // +1 DEF per level
         )
     }
     
@@ -188,6 +364,8 @@ impl ServerPlayer {
             let xp_needed = Self::experience_for_level(self.level + 1);
             if self.experience >= xp_needed {
                 self.level += 1;
+                self.skill_points += SKILL_POINTS_PER_LEVEL;
+                self.stat_points += STAT_POINTS_PER_LEVEL;
                 leveled_up = true;
                 log::info!("Player {} leveled up to {}", self.name, self.level);
             } else {
@@ -216,6 +394,11 @@ impl ServerPlayer {
         }
         
         if new_level != old_level {
+            // Only award points for levels gained; lowering XP keeps spent points
+            if new_level > old_level {
+                self.skill_points += (new_level - old_level) * SKILL_POINTS_PER_LEVEL;
+                self.stat_points += (new_level - old_level) * STAT_POINTS_PER_LEVEL;
+            }
             self.level = new_level;
             self.recalculate_stats_for_level();
             Some(new_level)
@@ -226,14 +409,23 @@ impl ServerPlayer {
     
     /// Recalculate stats based on current level, updating max values and healing to full
     pub fn recalculate_stats_for_level(&mut self) {
-        let (max_health, max_mana, attack, defense) = Self::calculate_stats_for_level(self.class, self.level);
-        self.max_health = max_health;
-        self.max_mana = max_mana;
-        self.attack_power = attack;
-        self.defense = defense;
+        self.apply_derived_stats();
         // Heal to full when leveling
-        self.health = max_health;
-        self.mana = max_mana;
+        self.health = self.max_health;
+        self.mana = self.max_mana;
+    }
+    
+    /// Recalculate max health, mana, attack and defense from level and attributes
+    /// Current health and mana are kept (clamped to the new maximums)
+    pub fn apply_derived_stats(&mut self) {
+        let (max_health, max_mana, attack, defense) = Self::calculate_stats_for_level(self.class, self.specialization, self.specialization_level, self.level);
+        let (bonus_health, bonus_mana, bonus_attack, bonus_defense) = self.attribute_bonuses();
+        self.max_health = max_health + bonus_health;
+        self.max_mana = max_mana + bonus_mana;
+        self.attack_power = attack + bonus_attack;
+        self.defense = defense + bonus_defense;
+        self.health = self.health.min(self.max_health);
+        self.mana = self.mana.min(self.max_mana);
     }
     
     /// Create a player with saved state (for persistence)
@@ -292,7 +484,27 @@ impl ServerPlayer {
             ability_cooldowns: HashMap::new(),
             active_buffs: Vec::new(),
             next_buff_id: 1,
-            action_bar: mmo_shared::get_default_action_bar(class),
+            action_bar: mmo_shared::get_default_action_bar(class, None),
+            active_cast: None,
+            auto_attack_target: None,
+            swing_timer: 0.0,
+            global_cooldown: 0.0,
+            skill_ranks: HashMap::new(),
+            skill_points: 0,
+            specialization: None,
+            specialization_level: DEFAULT_SPECIALIZATION_LEVEL,
+            attributes: Attributes::starting_for_class(class),
+            stat_points: 0,
+            combat_timer: 0.0,
+            is_resting: false,
+            regen_remainder: [0.0; 2],
+            resource_update_timer: 0.0,
+            last_sent_resources: None,
+            last_sent_move_speed: None,
+            move_allowance: 0.0,
+            last_move_update: None,
+            equipment_durability: MAX_DURABILITY,
+            release_timer: None,
         }
     }
     
@@ -358,7 +570,27 @@ impl ServerPlayer {
             ability_cooldowns: HashMap::new(),
             active_buffs: Vec::new(),
             next_buff_id: 1,
-            action_bar: mmo_shared::get_default_action_bar(class),
+            action_bar: mmo_shared::get_default_action_bar(class, None),
+            active_cast: None,
+            auto_attack_target: None,
+            swing_timer: 0.0,
+            global_cooldown: 0.0,
+            skill_ranks: HashMap::new(),
+            skill_points: 0,
+            specialization: None,
+            specialization_level: DEFAULT_SPECIALIZATION_LEVEL,
+            attributes: Attributes::starting_for_class(class),
+            stat_points: 0,
+            combat_timer: 0.0,
+            is_resting: false,
+            regen_remainder: [0.0; 2],
+            resource_update_timer: 0.0,
+            last_sent_resources: None,
+            last_sent_move_speed: None,
+            move_allowance: 0.0,
+            last_move_update: None,
+            equipment_durability: MAX_DURABILITY,
+            release_timer: None,
         }
     }
     
@@ -673,18 +905,23 @@ impl ServerPlayer {
     }
     
     /// Use item from inventory
-    pub fn use_item(&mut self, slot: u8) -> Option<()> {
+    /// Returns the effects that were applied
+    pub fn use_item(&mut self, slot: u8, items: &HashMap<u32, ItemDef>) -> Option<Vec<ItemEffect>> {
         let slot_idx = slot as usize;
         if slot_idx >= self.inventory.len() {
             return None;
         }
         
-        let inv_slot = self.inventory[slot_idx].as_mut()?;
-        let item_defs = get_item_definitions();
-        let item_def = item_defs.iter().find(|i| i.id == inv_slot.item_id)?;
+        let item_id = self.inventory[slot_idx].as_ref()?.item_id;
+        let effects = items.get(&item_id)?.effects.clone();
+        
+        // Keep a reset scroll when there is nothing to reset
+        if effects.iter().any(|e| matches!(e, ItemEffect::ResetStats)) && !self.has_allocated_attributes() {
+            return None;
+        }
         
         // Apply effects
-        for effect in &item_def.effects {
+        for effect in &effects {
             match effect {
                 ItemEffect::RestoreHealth(amount) => {
                     self.health = (self.health + amount).min(self.max_health);
@@ -698,19 +935,27 @@ impl ServerPlayer {
                 ItemEffect::IncreaseDefense(amount) => {
                     self.defense += amount;
                 }
-                ItemEffect::IncreaseSpeed(_) => {
-                    // TODO: Implement speed buff
+                ItemEffect::IncreaseSpeed(bonus) => {
+                    self.add_buff(0, BuffEffect::SpeedMultiplier(1.0 + bonus), SPEED_ITEM_DURATION, false);
+                }
+                ItemEffect::ResetStats => {
+                    self.reset_attributes();
+                }
+                ItemEffect::HealthRegen(_) | ItemEffect::ManaRegen(_) => {
+                    // Passive equipment effects, see get_regen_rates
                 }
             }
         }
         
         // Consume item
-        inv_slot.quantity -= 1;
-        if inv_slot.quantity == 0 {
-            self.inventory[slot_idx] = None;
+        if let Some(inv_slot) = self.inventory[slot_idx].as_mut() {
+            inv_slot.quantity -= 1;
+            if inv_slot.quantity == 0 {
+                self.inventory[slot_idx] = None;
+            }
         }
         
-        Some(())
+        Some(effects)
     }
     
     /// Get inventory slots for network update
@@ -726,6 +971,8 @@ impl ServerPlayer {
         }
         let actual_damage = damage.saturating_sub(self.defense / 2);
         self.health = self.health.saturating_sub(actual_damage);
+        self.record_cast_damage(actual_damage);
+        self.enter_combat();
         actual_damage
     }
     
@@ -737,6 +984,8 @@ impl ServerPlayer {
         let total_defense = self.get_total_defense(items);
         let actual_damage = damage.saturating_sub(total_defense / 2);
         self.health = self.health.saturating_sub(actual_damage);
+        self.record_cast_damage(actual_damage);
+        self.enter_combat();
         actual_damage
     }
     
@@ -745,23 +994,70 @@ impl ServerPlayer {
         self.health == 0
     }
     
-    /// Get attack speed (base from class * weapon multiplier)
+    /// Get attack speed (base from class and DEX * weapon multiplier * buff multiplier)
     /// Returns attacks per second multiplier (1.0 = normal, higher = faster)
     pub fn get_attack_speed(&self, items: &HashMap<u32, ItemDef>) -> f32 {
         // Base attack speed from class
-        let base_speed = self.class.base_attack_speed();
+        let base_speed = self.class.base_attack_speed() * self.attribute_attack_speed_multiplier();
+        let buff_multiplier = self.get_buff_attack_speed_multiplier();
         
         // Get weapon attack speed multiplier
         if let Some(weapon_id) = self.equipped_weapon_id {
             if let Some(item) = items.get(&weapon_id) {
                 if let Some(stats) = &item.weapon_stats {
-                    return base_speed * stats.attack_speed;
+                    return base_speed * stats.attack_speed * buff_multiplier;
                 }
             }
         }
         
         // Unarmed is slower
-        base_speed * 0.8
+        base_speed * 0.8 * buff_multiplier
+    }
+    
+    /// Get combat stats for hit resolution (crit, dodge, block)
+    pub fn combat_profile(&self, items: &HashMap<u32, ItemDef>) -> CombatProfile {
+        // Shields grant block chance, sturdier shields block more often
+        let block_chance = match self.equipped_shield_id {
+            Some(shield_id) => {
+                let shield_defense = items.get(&shield_id)
+                    .and_then(|item| item.armor_stats.as_ref())
+                    .map(|stats| stats.defense)
+                    .unwrap_or(0);
+                SHIELD_BLOCK_CHANCE + shield_defense as f32 * 0.005
+            }
+            None => 0.0,
+        };
+        
+        CombatProfile {
+            level: self.level,
+            crit_chance: self.get_crit_chance(),
+            crit_multiplier: DEFAULT_CRIT_MULTIPLIER,
+            dodge_chance: self.class.base_dodge_chance(),
+            block_chance,
+        }
+    }
+    
+    /// Get seconds between auto-attack swings at current attack speed
+    pub fn get_swing_interval(&self, items: &HashMap<u32, ItemDef>) -> f32 {
+        BASE_ATTACK_INTERVAL / self.get_attack_speed(items).max(0.1)
+    }
+    
+    /// Whether the equipped weapon fires projectiles (bows)
+    pub fn has_ranged_weapon(&self, items: &HashMap<u32, ItemDef>) -> bool {
+        self.equipped_weapon_id
+            .and_then(|weapon_id| items.get(&weapon_id))
+            .and_then(|item| item.weapon_stats.as_ref())
+            .map(|stats| stats.visual_type.is_ranged())
+            .unwrap_or(false)
+    }
+    
+    /// Get maximum auto-attack distance for the equipped weapon
+    pub fn get_attack_range(&self, items: &HashMap<u32, ItemDef>) -> f32 {
+        if self.has_ranged_weapon(items) {
+            BOW_ATTACK_RANGE
+        } else {
+            MELEE_ATTACK_RANGE
+        }
     }
     
     /// Calculate attack damage based on equipped weapon
@@ -770,13 +1066,14 @@ impl ServerPlayer {
         if let Some(weapon_id) = self.equipped_weapon_id {
             if let Some(item) = items.get(&weapon_id) {
                 if let Some(stats) = &item.weapon_stats {
-                    return stats.damage + (self.attack_power / 2);
+                    let weapon_damage = (stats.damage as f32 * self.gear_effectiveness()) as u32;
+                    return ((weapon_damage + self.attack_power / 2) as f32 * self.get_sickness_multiplier()) as u32;
                 }
             }
         }
         
         // Unarmed: reduced damage
-        self.attack_power / 2
+        ((self.attack_power / 2) as f32 * self.get_sickness_multiplier()) as u32
     }
     
     /// Try to equip a weapon from inventory
@@ -975,9 +1272,10 @@ impl ServerPlayer {
     /// Get total defense (base + armor bonus + buff bonus)
     pub fn get_total_defense(&self, items: &HashMap<u32, ItemDef>) -> u32 {
         let base = self.defense;
-        let armor_bonus = self.get_armor_defense_bonus(items);
+        let armor_bonus = (self.get_armor_defense_bonus(items) as f32 * self.gear_effectiveness()) as u32;
         let buff_bonus = self.get_buff_defense_bonus();
-        ((base as i32 + armor_bonus as i32 + buff_bonus).max(0)) as u32
+        let total = (base as i32 + armor_bonus as i32 + buff_bonus).max(0) as u32;
+        (total as f32 * self.get_sickness_multiplier()) as u32
     }
     
     /// Get total max health (base + armor bonus)
@@ -985,6 +1283,19 @@ impl ServerPlayer {
         self.max_health + self.get_armor_hp_bonus(items)
     }
     
+    /// Item IDs in every occupied equipment slot
+    pub fn equipped_item_ids(&self) -> impl Iterator<Item = u32> {
+        [
+            self.equipped_weapon_id,
+            self.equipped_armor_id,
+            self.equipped_helmet_id,
+            self.equipped_shield_id,
+            self.equipped_boots_id,
+            self.equipped_necklace_id,
+            self.equipped_ring_id,
+        ].into_iter().flatten()
+    }
+    
     // ==========================================================================
     // Ability System
     // ==========================================================================
@@ -1010,6 +1321,18 @@ impl ServerPlayer {
             *cd -= delta;
             *cd > 0.0
         });
+        self.global_cooldown = (self.global_cooldown - delta).max(0.0);
+        self.swing_timer = (self.swing_timer - delta).max(0.0);
+    }
+    
+    /// Check if the global cooldown is active
+    pub fn is_on_global_cooldown(&self) -> bool {
+        self.global_cooldown > 0.0
+    }
+    
+    /// Start the global cooldown
+    pub fn start_global_cooldown(&mut self) {
+        self.global_cooldown = self.global_cooldown.max(GLOBAL_COOLDOWN);
     }
     
     /// Consume mana (returns true if successful)
@@ -1049,11 +1372,12 @@ impl ServerPlayer {
         }
     }
     
-    /// Update buffs and return events (expired buff IDs, DOT damage, HOT heals)
-    /// Returns (expired_buff_ids, dot_damage, hot_heal)
-    pub fn update_buffs(&mut self, delta: f32) -> (Vec<u32>, u32, u32) {
+    /// Update buffs and return events (expired buff IDs, DOT ticks, HOT heals)
+    /// Returns (expired_buff_ids, dot_ticks as (source_id, damage), hot_heal)
+    /// DOT damage is not applied here so the caller can attribute it to its source
+    pub fn update_buffs(&mut self, delta: f32) -> (Vec<u32>, Vec<(u64, u32)>, u32) {
         let mut expired = Vec::new();
-        let mut total_dot_damage = 0u32;
+        let mut dot_ticks = Vec::new();
         let mut total_hot_heal = 0u32;
         
         for buff in &mut self.active_buffs {
@@ -1061,10 +1385,10 @@ impl ServerPlayer {
             
             // Handle tick-based effects
             match &mut buff.effect {
-                BuffEffect::DamageOverTime { damage_per_tick, interval, next_tick } => {
+                BuffEffect::DamageOverTime { source_id, damage_per_tick, interval, next_tick } => {
                     *next_tick -= delta;
                     while *next_tick <= 0.0 && buff.remaining > 0.0 {
-                        total_dot_damage += *damage_per_tick;
+                        dot_ticks.push((*source_id, *damage_per_tick));
                         *next_tick += *interval;
                     }
                 }
@@ -1091,7 +1415,7 @@ impl ServerPlayer {
             self.health = (self.health + total_hot_heal).min(self.max_health);
         }
         
-        (expired, total_dot_damage, total_hot_heal)
+        (expired, dot_ticks, total_hot_heal)
     }
     
     /// Get total attack bonus from buffs
@@ -1129,17 +1453,949 @@ impl ServerPlayer {
         self.active_buffs.iter().any(|b| matches!(b.effect, BuffEffect::Stunned))
     }
     
+    /// Get damage and defense multiplier from resurrection sickness (1.0 if not sick)
+    pub fn get_sickness_multiplier(&self) -> f32 {
+        self.active_buffs.iter().filter_map(|b| {
+            match &b.effect {
+                BuffEffect::ResurrectionSickness(mult) => Some(*mult),
+                _ => None,
+            }
+        }).fold(1.0, f32::min)
+    }
+    
+    /// Get combined movement speed multiplier from buffs and slows
+    pub fn get_buff_speed_multiplier(&self) -> f32 {
+        self.active_buffs.iter().filter_map(|b| {
+            match &b.effect {
+                BuffEffect::SpeedMultiplier(mult) => Some(*mult),
+                _ => None,
+            }
+        }).product::<f32>()
+    }
+    
     /// Calculate ability damage based on effect and player stats
     pub fn calculate_ability_damage(&self, base: u32, attack_scaling: f32, items: &HashMap<u32, ItemDef>) -> u32 {
         let weapon_damage = self.calculate_attack_damage(items);
         let attack_bonus = self.get_buff_attack_bonus();
         let total_attack = (self.attack_power as i32 + attack_bonus).max(0) as u32;
         
-        base + ((weapon_damage as f32 + total_attack as f32 / 2.0) * attack_scaling) as u32
+        let damage = base + ((weapon_damage as f32 + total_attack as f32 / 2.0) * attack_scaling) as u32;
+        (damage as f32 * self.get_sickness_multiplier()) as u32
     }
     
     /// Calculate heal amount based on effect
     pub fn calculate_heal_amount(&self, base: u32, health_scaling: f32) -> u32 {
         base + (self.max_health as f32 * health_scaling) as u32
     }
+    
+    // ==========================================================================
+    // Attributes
+    // ==========================================================================
+    
+    /// Attribute points spent above the class's starting values
+    fn allocated_attributes(&self) -> Attributes {
+        let start = Attributes::starting_for_class(self.class);
+        Attributes {
+            vitality: self.attributes.vitality.saturating_sub(start.vitality),
+            intelligence: self.attributes.intelligence.saturating_sub(start.intelligence),
+            strength: self.attributes.strength.saturating_sub(start.strength),
+            dexterity: self.attributes.dexterity.saturating_sub(start.dexterity),
+        }
+    }
+    
+    /// Whether any stat points are spent (and could be refunded)
+    pub fn has_allocated_attributes(&self) -> bool {
+        self.allocated_attributes().total() > 0
+    }
+    
+    /// Stat bonuses from allocated attribute points
+    /// Returns (max_health, max_mana, attack, defense)
+    pub fn attribute_bonuses(&self) -> (u32, u32, u32, u32) {
+        let allocated = self.allocated_attributes();
+        (
+            allocated.vitality * 10,      // +10 HP per VIT
+            allocated.intelligence * 6,   // +6 MP per INT
+            allocated.strength * 2,       // +2 ATK per STR
+            allocated.vitality / 2,       // +1 DEF per 2 VIT
+        )
+    }
+    
+    /// Attack speed multiplier from dexterity (+0.5% per allocated DEX)
+    pub fn attribute_attack_speed_multiplier(&self) -> f32 {
+        1.0 + self.allocated_attributes().dexterity as f32 * 0.005
+    }
+    
+    /// Critical hit chance including dexterity (+0.25% per allocated DEX)
+    pub fn get_crit_chance(&self) -> f32 {
+        self.class.base_crit_chance() + self.allocated_attributes().dexterity as f32 * 0.0025
+    }
+    
+    /// Spend stat points on a primary attribute
+    pub fn allocate_stat(&mut self, stat: PrimaryStat, amount: u32) -> Result<(), &'static str> {
+        if amount == 0 {
+            return Err("Nothing to allocate");
+        }
+        if self.stat_points < amount {
+            return Err("Not enough stat points");
+        }
+        if self.attributes.get(stat) + amount > MAX_ATTRIBUTE_VALUE {
+            return Err("Attribute would exceed its maximum");
+        }
+        
+        *self.attributes.get_mut(stat) += amount;
+        self.stat_points -= amount;
+        self.apply_derived_stats();
+        Ok(())
+    }
+    
+    /// Refund all spent stat points and return to the class's starting attributes
+    /// Returns the number of points refunded
+    pub fn reset_attributes(&mut self) -> u32 {
+        let refunded = self.allocated_attributes().total();
+        self.stat_points += refunded;
+        self.attributes = Attributes::starting_for_class(self.class);
+        self.apply_derived_stats();
+        refunded
+    }
+    
+    // ==========================================================================
+    // Movement
+    // ==========================================================================
+    
+    /// Movement speed bonus from equipped gear (0.1 = +10%)
+    pub fn get_gear_speed_bonus(&self, items: &HashMap<u32, ItemDef>) -> f32 {
+        self.equipped_item_ids().filter_map(|id| items.get(&id))
+            .flat_map(|item| item.effects.iter())
+            .filter_map(|effect| match effect {
+                ItemEffect::IncreaseSpeed(bonus) => Some(*bonus),
+                _ => None,
+            })
+            .sum()
+    }
+    
+    /// Current movement speed from base speed, gear, buffs and slows (units per second)
+    /// Stunned and dead players can't move
+    pub fn get_movement_speed(&self, items: &HashMap<u32, ItemDef>) -> f32 {
+        if self.is_dead() || self.is_stunned() {
+            return 0.0;
+        }
+        
+        let multiplier = (1.0 + self.get_gear_speed_bonus(items)) * self.get_buff_speed_multiplier();
+        BASE_MOVE_SPEED * multiplier.clamp(MIN_MOVE_SPEED_MULTIPLIER, MAX_MOVE_SPEED_MULTIPLIER)
+    }
+    
+    /// Take the movement speed to send to the client if it changed since the last update
+    pub fn take_movement_speed_update(&mut self, items: &HashMap<u32, ItemDef>) -> Option<f32> {
+        let speed = self.get_movement_speed(items);
+        if self.last_sent_move_speed == Some(speed) {
+            return None;
+        }
+        self.last_sent_move_speed = Some(speed);
+        Some(speed)
+    }
+    
+    /// Check a client-reported position against the player's movement speed
+    /// Returns false if the player moved further than their speed (plus sprinting) allows
+    pub fn validate_movement(&mut self, new_position: [f32; 3], items: &HashMap<u32, ItemDef>) -> bool {
+        let now = Instant::now();
+        let elapsed = self.last_move_update
+            .map(|t| now.duration_since(t).as_secs_f32())
+            .unwrap_or(0.0);
+        self.last_move_update = Some(now);
+        
+        // Allowance builds up over time so bunched-up packets aren't rejected
+        let max_speed = self.get_movement_speed(items) * SPRINT_MULTIPLIER * MOVE_VALIDATION_TOLERANCE;
+        self.move_allowance = (self.move_allowance + max_speed * elapsed).min(max_speed * MAX_MOVE_ALLOWANCE_TIME);
+        
+        let dx = new_position[0] - self.position[0];
+        let dz = new_position[2] - self.position[2];
+        let distance = (dx * dx + dz * dz).sqrt();
+        if distance > self.move_allowance + MOVE_VALIDATION_SLACK {
+            return false;
+        }
+        
+        self.move_allowance = (self.move_allowance - distance).max(0.0);
+        true
+    }
+    
+    // ==========================================================================
+    // Death
+    // ==========================================================================
+    
+    /// Apply the death penalty: lose experience (never below the current level) and gear durability
+    /// Returns the experience lost
+    pub fn apply_death_penalty(&mut self, experience_loss_percent: f32, durability_loss: u32) -> u32 {
+        let level_floor = Self::experience_for_level(self.level);
+        let max_loss = (self.get_experience_to_next_level() as f32 * experience_loss_percent / 100.0) as u32;
+        let experience_lost = max_loss.min(self.experience.saturating_sub(level_floor));
+        self.experience -= experience_lost;
+        self.equipment_durability = self.equipment_durability.saturating_sub(durability_loss);
+        experience_lost
+    }
+    
+    /// Bring a dead player back at a position with the given health
+    pub fn revive(&mut self, position: [f32; 3], health: u32) {
+        self.position = position;
+        self.health = health.clamp(1, self.max_health);
+        self.animation_state = AnimationState::Idle;
+        self.death_announced = false;  // Reset for next death
+        self.release_timer = None;
+        self.move_allowance = 0.0;
+    }
+    
+    /// Weapon damage and armor defense multiplier from gear durability
+    pub fn gear_effectiveness(&self) -> f32 {
+        if self.equipment_durability == 0 {
+            BROKEN_GEAR_EFFECTIVENESS
+        } else {
+            1.0
+        }
+    }
+    
+    /// Gold needed to fully repair equipped gear
+    pub fn repair_cost(&self) -> u64 {
+        (MAX_DURABILITY - self.equipment_durability.min(MAX_DURABILITY)) as u64
+            * self.level as u64
+            * REPAIR_GOLD_PER_POINT_PER_LEVEL
+    }
+    
+    /// Repair equipped gear for gold
+    /// Returns the gold spent
+    pub fn repair_equipment(&mut self) -> Result<u64, &'static str> {
+        if self.equipment_durability >= MAX_DURABILITY {
+            return Err("Your gear doesn't need repairs");
+        }
+        let cost = self.repair_cost();
+        if self.gold < cost {
+            return Err("Not enough gold");
+        }
+        self.gold -= cost;
+        self.equipment_durability = MAX_DURABILITY;
+        Ok(cost)
+    }
+    
+    // ==========================================================================
+    // Regeneration
+    // ==========================================================================
+    
+    /// Mark the player as in combat (dealt or took damage); stands up if resting
+    pub fn enter_combat(&mut self) {
+        self.combat_timer = COMBAT_TIMEOUT;
+        self.is_resting = false;
+    }
+    
+    /// Check if the player dealt or took damage recently
+    pub fn is_in_combat(&self) -> bool {
+        self.combat_timer > 0.0
+    }
+    
+    /// Sit down to rest or stand up again
+    pub fn set_resting(&mut self, resting: bool) -> Result<(), &'static str> {
+        if resting {
+            if self.is_dead() {
+                return Err("You are dead");
+            }
+            if self.is_in_combat() {
+                return Err("You can't rest while in combat");
+            }
+            if self.is_casting() {
+                return Err("You can't rest while casting");
+            }
+            self.stop_auto_attack();
+        }
+        self.is_resting = resting;
+        Ok(())
+    }
+    
+    /// Passive regeneration from equipped gear
+    /// Returns (health, mana) per second
+    pub fn get_gear_regen(&self, items: &HashMap<u32, ItemDef>) -> (u32, u32) {
+        let mut health = 0;
+        let mut mana = 0;
+        for item in self.equipped_item_ids().filter_map(|id| items.get(&id)) {
+            for effect in &item.effects {
+                match effect {
+                    ItemEffect::HealthRegen(amount) => health += amount,
+                    ItemEffect::ManaRegen(amount) => mana += amount,
+                    _ => {}
+                }
+            }
+        }
+        (health, mana)
+    }
+    
+    /// Current regeneration from class, level and gear (zero while in combat or dead)
+    /// Returns (health, mana) per second
+    pub fn get_regen_rates(&self, items: &HashMap<u32, ItemDef>) -> (f32, f32) {
+        if self.is_dead() || self.is_in_combat() {
+            return (0.0, 0.0);
+        }
+        
+        let (gear_health, gear_mana) = self.get_gear_regen(items);
+        let health = self.max_health as f32 * self.class.base_health_regen()
+            + self.level as f32 * HEALTH_REGEN_PER_LEVEL
+            + gear_health as f32;
+        let mana = self.max_mana as f32 * self.class.base_mana_regen()
+            + self.level as f32 * MANA_REGEN_PER_LEVEL
+            + gear_mana as f32;
+        
+        let multiplier = if self.is_resting { RESTING_REGEN_MULTIPLIER } else { 1.0 };
+        (health * multiplier, mana * multiplier)
+    }
+    
+    /// Advance the combat timer and regenerate health/mana
+    pub fn update_regeneration(&mut self, delta: f32, items: &HashMap<u32, ItemDef>) {
+        self.combat_timer = (self.combat_timer - delta).max(0.0);
+        self.resource_update_timer = (self.resource_update_timer - delta).max(0.0);
+        
+        if self.is_dead() {
+            self.is_resting = false;
+        }
+        
+        let (health_rate, mana_rate) = self.get_regen_rates(items);
+        if health_rate <= 0.0 && mana_rate <= 0.0 {
+            self.regen_remainder = [0.0; 2];
+            return;
+        }
+        
+        self.health = Self::regenerate(self.health, self.max_health, health_rate * delta, &mut self.regen_remainder[0]);
+        self.mana = Self::regenerate(self.mana, self.max_mana, mana_rate * delta, &mut self.regen_remainder[1]);
+    }
+    
+    /// Add regenerated points to a resource, carrying the fractional part over
+    fn regenerate(current: u32, max: u32, amount: f32, remainder: &mut f32) -> u32 {
+        if current >= max {
+            *remainder = 0.0;
+            return max;
+        }
+        *remainder += amount;
+        let whole = *remainder as u32;
+        *remainder -= whole as f32;
+        (current + whole).min(max)
+    }
+    
+    /// Current resource state for client updates
+    pub fn resource_snapshot(&self) -> ResourceSnapshot {
+        ResourceSnapshot {
+            health: self.health,
+            max_health: self.max_health,
+            mana: self.mana,
+            max_mana: self.max_mana,
+            in_combat: self.is_in_combat(),
+            resting: self.is_resting,
+        }
+    }
+    
+    /// Take the resource state to send to the client, if any
+    /// Combat/rest changes are sent immediately, value changes at most once per RESOURCE_UPDATE_INTERVAL
+    pub fn take_resource_update(&mut self) -> Option<ResourceSnapshot> {
+        let snapshot = self.resource_snapshot();
+        let state_changed = match self.last_sent_resources {
+            Some(last) if last == snapshot => return None,
+            Some(last) => last.in_combat != snapshot.in_combat || last.resting != snapshot.resting,
+            None => true,
+        };
+        if !state_changed && self.resource_update_timer > 0.0 {
+            return None;
+        }
+        
+        self.resource_update_timer = RESOURCE_UPDATE_INTERVAL;
+        self.last_sent_resources = Some(snapshot);
+        Some(snapshot)
+    }
+    
+    // ==========================================================================
+    // Specialization
+    // ==========================================================================
+    
+    /// Set the specialization loaded from the database (stats are stored separately)
+    pub fn set_specialization(&mut self, specialization: Option<Specialization>) {
+        self.specialization = specialization;
+        self.action_bar = mmo_shared::get_default_action_bar(self.class, specialization);
+    }
+    
+    /// Choose a specialization for the first time, switching to its stat growth
+    pub fn choose_specialization(&mut self, specialization: Specialization) -> Result<(), &'static str> {
+        if self.specialization.is_some() {
+            return Err("Specialization already chosen");
+        }
+        if specialization.class() != self.class {
+            return Err("Specialization belongs to another class");
+        }
+        if self.level < self.specialization_level {
+            return Err("Level too low to choose a specialization");
+        }
+        
+        self.set_specialization(Some(specialization));
+        self.recalculate_stats_for_level();
+        Ok(())
+    }
+    
+    // ==========================================================================
+    // Skills
+    // ==========================================================================
+    
+    /// Get the rank of a skill (0 = not learned)
+    pub fn get_skill_rank(&self, ability_id: u32) -> u8 {
+        *self.skill_ranks.get(&ability_id).unwrap_or(&0)
+    }
+    
+    /// Learn a skill or train it to the next rank
+    /// Returns Ok(true) if the rank went up, Ok(false) if training failed (points are still spent)
+    pub fn try_upgrade_skill(&mut self, ability: &AbilityDef) -> Result<bool, &'static str> {
+        if let Some(required_class) = ability.class_restriction {
+            if self.class != required_class {
+                return Err("Wrong class for this skill");
+            }
+        }
+        if !ability.is_available_to(self.class, self.specialization) {
+            return Err("Requires a different specialization");
+        }
+        if self.level < ability.level_requirement {
+            return Err("Level too low to learn this skill");
+        }
+        
+        let rank = self.get_skill_rank(ability.id);
+        if rank >= MAX_SKILL_RANK {
+            return Err("Skill is already at Perfect Master");
+        }
+        
+        let rule = SkillGrade::from_rank(rank).upgrade_rule().ok_or("Skill cannot be trained further")?;
+        if self.level < rule.min_level {
+            return Err("Level too low to train this grade");
+        }
+        if self.skill_points < rule.point_cost {
+            return Err("Not enough skill points");
+        }
+        
+        self.skill_points -= rule.point_cost;
+        
+        if rand::thread_rng().gen_range(0.0..1.0) >= rule.success_chance {
+            return Ok(false);
+        }
+        
+        self.skill_ranks.insert(ability.id, rank + 1);
+        Ok(true)
+    }
+    
+    /// Get learned skills for the protocol, sorted by ability ID
+    pub fn get_skill_list(&self) -> Vec<SkillRank> {
+        let mut skills: Vec<SkillRank> = self.skill_ranks
+            .iter()
+            .map(|(&ability_id, &rank)| SkillRank { ability_id, rank })
+            .collect();
+        skills.sort_by_key(|s| s.ability_id);
+        skills
+    }
+    
+    // ==========================================================================
+    // Auto-Attack
+    // ==========================================================================
+    
+    /// Start or switch auto-attack target
+    /// Returns true if the target changed
+    pub fn start_auto_attack(&mut self, target_id: u64) -> bool {
+        if self.auto_attack_target == Some(target_id) {
+            return false;
+        }
+        // Switching targets keeps the swing timer (no swing reset exploit)
+        self.auto_attack_target = Some(target_id);
+        true
+    }
+    
+    /// Stop auto-attacking, returning the previous target
+    pub fn stop_auto_attack(&mut self) -> Option<u64> {
+        self.auto_attack_target.take()
+    }
+    
+    /// Check if the next swing may fire (swing timer and global cooldown elapsed)
+    pub fn is_swing_ready(&self) -> bool {
+        self.swing_timer <= 0.0 && self.global_cooldown <= 0.0
+    }
+    
+    // ==========================================================================
+    // Casting
+    // ==========================================================================
+    
+    /// Check if the player is casting or channeling
+    pub fn is_casting(&self) -> bool {
+        self.active_cast.is_some()
+    }
+    
+    /// Start casting (or channeling, if is_channel) an ability
+    pub fn start_cast(&mut self, ability_id: u32, target_id: Option<u64>, duration: f32, is_channel: bool) {
+        self.active_cast = Some(ActiveCast {
+            ability_id,
+            target_id,
+            is_channel,
+            elapsed: 0.0,
+            duration,
+            pulses_done: 0,
+            start_position: self.position,
+            damage_taken: 0,
+            next_progress: CAST_PROGRESS_INTERVAL,
+        });
+    }
+    
+    /// Stop casting, returning the cast that was active
+    pub fn cancel_cast(&mut self) -> Option<ActiveCast> {
+        self.active_cast.take()
+    }
+    
+    /// Track damage taken while casting (for interrupt threshold)
+    fn record_cast_damage(&mut self, damage: u32) {
+        if let Some(cast) = &mut self.active_cast {
+            cast.damage_taken += damage;
+        }
+    }
+    
+    /// Check whether the active cast should be interrupted
+    /// Returns the interrupt reason, or None if the cast may continue
+    pub fn check_cast_interrupt(&self) -> Option<&'static str> {
+        let cast = self.active_cast.as_ref()?;
+        
+        if self.is_dead() {
+            return Some("Died");
+        }
+        
+        if self.is_stunned() {
+            return Some("Stunned");
+        }
+        
+        let dx = self.position[0] - cast.start_position[0];
+        let dz = self.position[2] - cast.start_position[2];
+        if dx * dx + dz * dz > CAST_MOVE_TOLERANCE * CAST_MOVE_TOLERANCE {
+            return Some("Moved");
+        }
+        
+        let damage_threshold = (self.max_health as f32 * CAST_INTERRUPT_DAMAGE_PERCENT) as u32;
+        if cast.damage_taken > 0 && cast.damage_taken >= damage_threshold {
+            return Some("Took too much damage");
+        }
+        
+        None
+    }
+    
+    /// Advance the active cast by delta seconds and return the resulting events
+    /// The cast is NOT cleared on completion - the caller handles that
+    pub fn update_cast(&mut self, delta: f32) -> Vec<CastEvent> {
+        let mut events = Vec::new();
+        let cast = match &mut self.active_cast {
+            Some(c) => c,
+            None => return events,
+        };
+        
+        cast.elapsed += delta;
+        let finished = cast.elapsed >= cast.duration;
+        
+        if cast.is_channel {
+            // Pulse once per interval, always landing the final pulse at the end
+            let total_pulses = ((cast.duration / CHANNEL_PULSE_INTERVAL).round() as u32).max(1);
+            let due = if finished {
+                total_pulses
+            } else {
+                ((cast.elapsed / CHANNEL_PULSE_INTERVAL) as u32).min(total_pulses)
+            };
+            while cast.pulses_done < due {
+                cast.pulses_done += 1;
+                events.push(CastEvent::ChannelPulse);
+            }
+        }
+        
+        if finished {
+            events.push(if cast.is_channel {
+                CastEvent::ChannelFinished
+            } else {
+                CastEvent::CastFinished
+            });
+            return events;
+        }
+        
+        cast.next_progress -= delta;
+        if cast.next_progress <= 0.0 {
+            cast.next_progress += CAST_PROGRESS_INTERVAL;
+            events.push(CastEvent::Progress(cast.elapsed, cast.duration));
+        }
+        
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(class: CharacterClass) -> ServerPlayer {
+        let (health, mana, attack, defense) = ServerPlayer::calculate_stats_for_level(class, None, DEFAULT_SPECIALIZATION_LEVEL, 1);
+        ServerPlayer::with_state(
+            1, "Tester".into(), class, Gender::Male, Empire::Blue, 1, [0.0; 3], 0.0,
+            health, health, mana, mana, attack, defense,
+            vec![None; INVENTORY_SIZE], None, 1, 0, 0,
+        )
+    }
+
+    fn items() -> HashMap<u32, ItemDef> {
+        get_item_definitions().into_iter().map(|i| (i.id, i)).collect()
+    }
+
+    #[test]
+    fn test_allocate_stat_spends_points_and_applies_bonuses() {
+        let mut player = player(CharacterClass::Warrior);
+        let base_health = player.max_health;
+        let base_defense = player.defense;
+        player.stat_points = 5;
+
+        assert!(player.allocate_stat(PrimaryStat::Vitality, 0).is_err());
+        assert!(player.allocate_stat(PrimaryStat::Vitality, 6).is_err());
+        assert_eq!(player.stat_points, 5);
+
+        player.allocate_stat(PrimaryStat::Vitality, 3).unwrap();
+        assert_eq!(player.stat_points, 2);
+        assert_eq!(player.max_health, base_health + 30);
+        assert_eq!(player.defense, base_defense + 1);
+        assert_eq!(player.attribute_bonuses(), (30, 0, 0, 1));
+    }
+
+    #[test]
+    fn test_allocate_stat_respects_the_cap() {
+        let mut player = player(CharacterClass::Warrior);
+        player.attributes.strength = MAX_ATTRIBUTE_VALUE - 1;
+        player.stat_points = 5;
+
+        assert!(player.allocate_stat(PrimaryStat::Strength, 2).is_err());
+        player.allocate_stat(PrimaryStat::Strength, 1).unwrap();
+        assert!(player.allocate_stat(PrimaryStat::Strength, 1).is_err());
+        assert_eq!(player.stat_points, 4);
+    }
+
+    #[test]
+    fn test_reset_attributes_refunds_every_spent_point() {
+        let mut player = player(CharacterClass::Sura);
+        let base = (player.max_health, player.max_mana, player.attack_power, player.defense);
+        player.stat_points = 6;
+        player.allocate_stat(PrimaryStat::Vitality, 2).unwrap();
+        player.allocate_stat(PrimaryStat::Intelligence, 3).unwrap();
+
+        assert_eq!(player.reset_attributes(), 5);
+        assert_eq!(player.stat_points, 6);
+        assert_eq!(player.attributes, Attributes::starting_for_class(CharacterClass::Sura));
+        assert_eq!((player.max_health, player.max_mana, player.attack_power, player.defense), base);
+        assert_eq!(player.reset_attributes(), 0);
+    }
+
+    #[test]
+    fn test_scroll_of_forgetting_is_kept_when_nothing_is_spent() {
+        let items = items();
+        let mut player = player(CharacterClass::Ninja);
+        player.add_to_inventory(101, 1);
+
+        assert!(player.use_item(0, &items).is_none());
+        assert_eq!(player.inventory[0].as_ref().map(|s| s.quantity), Some(1));
+
+        player.stat_points = 1;
+        player.allocate_stat(PrimaryStat::Dexterity, 1).unwrap();
+        assert!(player.use_item(0, &items).is_some());
+        assert_eq!(player.stat_points, 1);
+        assert!(player.inventory[0].is_none());
+    }
+
+    #[test]
+    fn test_death_penalty_never_drops_below_the_current_level() {
+        let mut player = player(CharacterClass::Warrior);
+        player.level = 3;
+        // 400 XP reaches level 3, 500 more reaches level 4
+        player.experience = 450;
+
+        assert_eq!(player.apply_death_penalty(5.0, 10), 25);
+        assert_eq!(player.experience, 425);
+        assert_eq!(player.apply_death_penalty(20.0, 10), 25);
+        assert_eq!(player.experience, 400);
+        assert_eq!(player.apply_death_penalty(20.0, 10), 0);
+        assert_eq!(player.level, 3);
+    }
+
+    #[test]
+    fn test_durability_loss_breaks_gear_and_raises_repair_cost() {
+        let mut player = player(CharacterClass::Warrior);
+        player.level = 10;
+        assert_eq!(player.repair_cost(), 0);
+        assert!(player.repair_equipment().is_err());
+
+        player.apply_death_penalty(0.0, 30);
+        assert_eq!(player.equipment_durability, MAX_DURABILITY - 30);
+        assert_eq!(player.gear_effectiveness(), 1.0);
+        assert_eq!(player.repair_cost(), 30 * 10 * REPAIR_GOLD_PER_POINT_PER_LEVEL);
+
+        player.apply_death_penalty(0.0, MAX_DURABILITY);
+        assert_eq!(player.equipment_durability, 0);
+        assert_eq!(player.gear_effectiveness(), BROKEN_GEAR_EFFECTIVENESS);
+
+        let cost = player.repair_cost();
+        player.gold = cost - 1;
+        assert!(player.repair_equipment().is_err());
+        player.gold = cost;
+        assert_eq!(player.repair_equipment(), Ok(cost));
+        assert_eq!((player.gold, player.equipment_durability), (0, MAX_DURABILITY));
+    }
+
+    #[test]
+    fn test_revive_restores_clamped_health() {
+        let mut player = player(CharacterClass::Shaman);
+        player.health = 0;
+        player.death_announced = true;
+        player.release_timer = Some(10.0);
+
+        player.revive([5.0, 0.0, 5.0], 0);
+        assert_eq!(player.health, 1);
+        assert!(!player.is_dead() && !player.death_announced);
+        assert_eq!(player.release_timer, None);
+        assert_eq!(player.position, [5.0, 0.0, 5.0]);
+
+        player.revive([5.0, 0.0, 5.0], player.max_health * 2);
+        assert_eq!(player.health, player.max_health);
+    }
+
+    /// Fastest a player may move per second, sprinting included
+    fn max_move_speed(player: &ServerPlayer, items: &HashMap<u32, ItemDef>) -> f32 {
+        player.get_movement_speed(items) * SPRINT_MULTIPLIER * MOVE_VALIDATION_TOLERANCE
+    }
+
+    #[test]
+    fn test_movement_speed_from_gear_and_buffs() {
+        let mut items = items();
+        let mut boots = items[&204].clone();
+        boots.id = 900;
+        boots.effects = vec![ItemEffect::IncreaseSpeed(0.2)];
+        items.insert(900, boots);
+
+        let mut player = player(CharacterClass::Ninja);
+        assert_eq!(player.get_movement_speed(&items), BASE_MOVE_SPEED);
+        player.equipped_boots_id = Some(900);
+        assert_eq!(player.get_gear_speed_bonus(&items), 0.2);
+        assert_eq!(player.equipped_item_ids().collect::<Vec<_>>(), vec![900]);
+
+        // Slows can't push below the floor
+        player.add_buff(1, BuffEffect::SpeedMultiplier(0.1), 5.0, true);
+        assert_eq!(player.get_movement_speed(&items), BASE_MOVE_SPEED * MIN_MOVE_SPEED_MULTIPLIER);
+
+        player.add_buff(2, BuffEffect::Stunned, 5.0, true);
+        assert_eq!(player.get_movement_speed(&items), 0.0);
+        player.active_buffs.clear();
+        player.health = 0;
+        assert_eq!(player.get_movement_speed(&items), 0.0);
+    }
+
+    #[test]
+    fn test_validate_movement_allows_only_the_slack_without_elapsed_time() {
+        let items = items();
+        let mut player = player(CharacterClass::Warrior);
+
+        assert!(player.validate_movement([MOVE_VALIDATION_SLACK * 0.8, 0.0, 0.0], &items));
+        assert!(!player.validate_movement([MOVE_VALIDATION_SLACK * 4.0, 0.0, 0.0], &items));
+    }
+
+    #[test]
+    fn test_validate_movement_allowance_is_capped() {
+        let items = items();
+        let mut player = player(CharacterClass::Warrior);
+        let max_step = max_move_speed(&player, &items) * MAX_MOVE_ALLOWANCE_TIME;
+
+        // Ten quiet seconds only buy one second of movement
+        player.last_move_update = Some(Instant::now() - std::time::Duration::from_secs(10));
+        assert!(!player.validate_movement([max_step + MOVE_VALIDATION_SLACK + 1.0, 0.0, 0.0], &items));
+
+        player.last_move_update = Some(Instant::now() - std::time::Duration::from_secs(10));
+        assert!(player.validate_movement([max_step + MOVE_VALIDATION_SLACK * 0.5, 0.0, 0.0], &items));
+        assert_eq!(player.move_allowance, 0.0);
+
+        // The bucket is spent, a second jump right after is rejected
+        player.position = [max_step, 0.0, 0.0];
+        assert!(!player.validate_movement([max_step * 1.5, 0.0, 0.0], &items));
+    }
+
+    #[test]
+    fn test_validate_movement_rejects_stunned_and_dead_players() {
+        let items = items();
+        for stunned in [true, false] {
+            let mut player = player(CharacterClass::Warrior);
+            if stunned {
+                player.add_buff(1, BuffEffect::Stunned, 5.0, true);
+            } else {
+                player.health = 0;
+            }
+            player.last_move_update = Some(Instant::now() - std::time::Duration::from_secs(1));
+            assert!(!player.validate_movement([1.0, 0.0, 0.0], &items));
+        }
+    }
+
+    #[test]
+    fn test_regenerate_carries_the_fraction() {
+        let mut remainder = 0.0;
+        assert_eq!(ServerPlayer::regenerate(10, 100, 0.4, &mut remainder), 10);
+        assert_eq!(ServerPlayer::regenerate(10, 100, 0.4, &mut remainder), 10);
+        assert_eq!(ServerPlayer::regenerate(10, 100, 0.4, &mut remainder), 11);
+        assert!((remainder - 0.2).abs() < 0.001);
+
+        // Never past the maximum, and a full bar drops the carry
+        assert_eq!(ServerPlayer::regenerate(99, 100, 5.0, &mut remainder), 100);
+        assert_eq!(ServerPlayer::regenerate(100, 100, 0.5, &mut remainder), 100);
+        assert_eq!(remainder, 0.0);
+    }
+
+    #[test]
+    fn test_no_regeneration_in_combat() {
+        let items = items();
+        let mut player = player(CharacterClass::Warrior);
+        player.health = 10;
+        player.regen_remainder = [0.5, 0.5];
+        player.enter_combat();
+
+        player.update_regeneration(1.0, &items);
+        assert_eq!(player.health, 10);
+        assert_eq!(player.regen_remainder, [0.0; 2]);
+
+        player.update_regeneration(COMBAT_TIMEOUT, &items);
+        assert!(!player.is_in_combat());
+        player.update_regeneration(10.0, &items);
+        assert!(player.health > 10);
+    }
+
+    #[test]
+    fn test_resource_updates_are_throttled() {
+        let items = items();
+        let mut player = player(CharacterClass::Warrior);
+        assert!(player.take_resource_update().is_some());
+        assert!(player.take_resource_update().is_none());
+
+        // Value changes wait for the interval
+        player.health -= 10;
+        assert!(player.take_resource_update().is_none());
+
+        // Entering combat goes out right away
+        player.enter_combat();
+        let update = player.take_resource_update().unwrap();
+        assert!(update.in_combat);
+        assert_eq!(update.health, player.health);
+
+        player.health -= 5;
+        assert!(player.take_resource_update().is_none());
+        player.update_regeneration(RESOURCE_UPDATE_INTERVAL, &items);
+        assert_eq!(player.take_resource_update().map(|u| u.health), Some(player.health));
+        assert!(player.take_resource_update().is_none());
+    }
+
+    fn ability(id: u32) -> AbilityDef {
+        mmo_shared::get_fallback_abilities().into_iter().find(|a| a.id == id).unwrap()
+    }
+
+    #[test]
+    fn test_upgrade_skill_checks_class_level_and_points() {
+        let mut player = player(CharacterClass::Warrior);
+        let crushing_blow = ability(21);
+
+        assert_eq!(player.try_upgrade_skill(&crushing_blow), Err("Not enough skill points"));
+        player.skill_points = 2;
+        assert_eq!(player.try_upgrade_skill(&ability(11)), Err("Wrong class for this skill"));
+
+        let sword_spin = AbilityDef { specialization: Some(Specialization::Body), ..crushing_blow.clone() };
+        assert_eq!(player.try_upgrade_skill(&sword_spin), Err("Requires a different specialization"));
+
+        let later = AbilityDef { level_requirement: 5, ..crushing_blow.clone() };
+        assert_eq!(player.try_upgrade_skill(&later), Err("Level too low to learn this skill"));
+
+        // Normal ranks always succeed
+        assert_eq!(player.try_upgrade_skill(&crushing_blow), Ok(true));
+        assert_eq!(player.try_upgrade_skill(&crushing_blow), Ok(true));
+        assert_eq!(player.get_skill_rank(21), 2);
+        assert_eq!(player.skill_points, 0);
+    }
+
+    #[test]
+    fn test_upgrade_skill_grade_rules() {
+        let mut player = player(CharacterClass::Warrior);
+        let crushing_blow = ability(21);
+        player.skill_points = 10;
+
+        // Master training needs level 20 and may fail, but always costs its points
+        player.skill_ranks.insert(21, 20);
+        assert_eq!(player.try_upgrade_skill(&crushing_blow), Err("Level too low to train this grade"));
+        player.level = 20;
+        let upgraded = player.try_upgrade_skill(&crushing_blow).unwrap();
+        assert_eq!(player.skill_points, 9);
+        assert_eq!(player.get_skill_rank(21), if upgraded { 21 } else { 20 });
+
+        // Grand Master costs two points per attempt
+        player.level = 30;
+        player.skill_ranks.insert(21, 30);
+        player.try_upgrade_skill(&crushing_blow).unwrap();
+        assert_eq!(player.skill_points, 7);
+
+        player.skill_ranks.insert(21, MAX_SKILL_RANK);
+        assert_eq!(player.try_upgrade_skill(&crushing_blow), Err("Skill is already at Perfect Master"));
+        assert_eq!(player.skill_points, 7);
+    }
+
+    #[test]
+    fn test_cast_reports_progress_then_finishes() {
+        let mut player = player(CharacterClass::Shaman);
+        assert!(player.update_cast(1.0).is_empty());
+
+        player.start_cast(41, Some(7), 1.5, false);
+        assert!(player.update_cast(0.4).is_empty());
+        assert!(matches!(player.update_cast(0.2)[..], [CastEvent::Progress(elapsed, 1.5)] if (elapsed - 0.6).abs() < 0.001));
+        assert_eq!(player.update_cast(1.0), vec![CastEvent::CastFinished]);
+
+        // Finishing leaves clearing the cast to the caller
+        assert_eq!(player.cancel_cast().and_then(|c| c.target_id), Some(7));
+        assert!(!player.is_casting());
+    }
+
+    #[test]
+    fn test_channel_pulses_every_interval_and_lands_the_last_one() {
+        let mut player = player(CharacterClass::Shaman);
+        let pulses = |events: &[CastEvent]| events.iter().filter(|e| **e == CastEvent::ChannelPulse).count();
+        player.start_cast(42, None, 3.0 * CHANNEL_PULSE_INTERVAL, true);
+
+        assert_eq!(pulses(&player.update_cast(CHANNEL_PULSE_INTERVAL * 0.5)), 0);
+        assert_eq!(pulses(&player.update_cast(CHANNEL_PULSE_INTERVAL * 0.6)), 1);
+
+        // A long tick catches up on every missed pulse before finishing
+        let events = player.update_cast(CHANNEL_PULSE_INTERVAL * 10.0);
+        assert_eq!(pulses(&events), 2);
+        assert_eq!(events.last(), Some(&CastEvent::ChannelFinished));
+        assert_eq!(player.active_cast.as_ref().map(|c| c.pulses_done), Some(3));
+    }
+
+    #[test]
+    fn test_cast_interrupted_by_moving() {
+        let mut player = player(CharacterClass::Shaman);
+        assert_eq!(player.check_cast_interrupt(), None);
+
+        player.start_cast(41, None, 1.5, false);
+        player.position = [CAST_MOVE_TOLERANCE * 0.8, 0.0, 0.0];
+        assert_eq!(player.check_cast_interrupt(), None);
+        player.position = [CAST_MOVE_TOLERANCE * 0.8, 0.0, CAST_MOVE_TOLERANCE * 0.8];
+        assert_eq!(player.check_cast_interrupt(), Some("Moved"));
+    }
+
+    #[test]
+    fn test_cast_interrupted_by_damage_stun_or_death() {
+        let mut player = player(CharacterClass::Warrior);
+        let threshold = (player.max_health as f32 * CAST_INTERRUPT_DAMAGE_PERCENT) as u32;
+        let armor = player.defense / 2;
+        player.start_cast(21, None, 1.5, false);
+
+        // Damage adds up over the cast, after armor
+        player.take_damage(threshold - 1 + armor);
+        assert_eq!(player.check_cast_interrupt(), None);
+        player.take_damage(1 + armor);
+        assert_eq!(player.check_cast_interrupt(), Some("Took too much damage"));
+
+        player.start_cast(21, None, 1.5, false);
+        assert_eq!(player.check_cast_interrupt(), None);
+        player.add_buff(1, BuffEffect::Stunned, 2.0, true);
+        assert_eq!(player.check_cast_interrupt(), Some("Stunned"));
+        player.health = 0;
+        assert_eq!(player.check_cast_interrupt(), Some("Died"));
+    }
 }
@@ -0,0 +1,164 @@
+//! Server-side projectile entity (arrows, spell bolts).
+
+use crate::navigation::{self, Obstacle, Vec2};
+
+/// Height above the ground that projectiles fly at (roughly chest height)
+pub const PROJECTILE_HEIGHT: f32 = 1.0;
+
+/// Distance at which a projectile counts as having reached its target
+const PROJECTILE_HIT_RADIUS: f32 = 0.8;
+
+/// What a projectile applies when it reaches its target
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProjectilePayload {
+    /// Weapon auto-attack (bows)
+    Attack,
+    /// Ability effects
+    Ability(u32),
+}
+
+/// Why a projectile stopped flying
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProjectileImpact {
+    /// Reached its target
+    Target,
+    /// Blocked by an obstacle
+    Obstacle,
+    /// Flew its maximum distance without hitting anything
+    Expired,
+}
+
+/// A projectile in flight
+#[derive(Debug)]
+pub struct ServerProjectile {
+    pub id: u64,
+    /// Zone this projectile is flying in
+    pub zone_id: u32,
+    /// Player or enemy that launched it
+    pub owner_id: u64,
+    pub target_id: Option<u64>,
+    pub payload: ProjectilePayload,
+    pub position: [f32; 3],
+    /// Normalized flight direction
+    pub direction: [f32; 3],
+    /// Speed in units per second
+    pub speed: f32,
+    /// Re-aims at the target every tick (otherwise flies straight)
+    pub homing: bool,
+    pub max_distance: f32,
+    pub traveled: f32,
+}
+
+impl ServerProjectile {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: u64,
+        zone_id: u32,
+        owner_id: u64,
+        target_id: Option<u64>,
+        payload: ProjectilePayload,
+        position: [f32; 3],
+        aim_position: [f32; 3],
+        speed: f32,
+        homing: bool,
+        max_distance: f32,
+    ) -> Self {
+        Self {
+            id,
+            zone_id,
+            owner_id,
+            target_id,
+            payload,
+            position,
+            direction: direction_to(position, aim_position),
+            speed,
+            homing,
+            max_distance,
+            traveled: 0.0,
+        }
+    }
+
+    /// Current velocity (units per second)
+    pub fn velocity(&self) -> [f32; 3] {
+        [
+            self.direction[0] * self.speed,
+            self.direction[1] * self.speed,
+            self.direction[2] * self.speed,
+        ]
+    }
+
+    /// Advance the projectile by one tick.
+    /// `target_position` is the target's current position at projectile height (None if it's gone).
+    pub fn update(&mut self, delta: f32, target_position: Option<[f32; 3]>, obstacles: &[Obstacle]) -> Option<ProjectileImpact> {
+        if self.homing {
+            if let Some(target) = target_position {
+                self.direction = direction_to(self.position, target);
+            }
+        }
+
+        let step = self.speed * delta;
+        let start = self.position;
+        let end = [
+            start[0] + self.direction[0] * step,
+            start[1] + self.direction[1] * step,
+            start[2] + self.direction[2] * step,
+        ];
+
+        // Obstacles are 2D, so test the flight path on the ground plane
+        if let Some(t) = navigation::raycast_obstacles(Vec2::from_3d(start), Vec2::from_3d(end), obstacles) {
+            self.position = lerp(start, end, t);
+            return Some(ProjectileImpact::Obstacle);
+        }
+
+        // Check the whole segment so fast projectiles can't tunnel through their target
+        if let Some(target) = target_position {
+            let closest = closest_point_on_segment(start, end, target);
+            if distance(closest, target) <= PROJECTILE_HIT_RADIUS {
+                self.position = closest;
+                return Some(ProjectileImpact::Target);
+            }
+        }
+
+        self.position = end;
+        self.traveled += step;
+        if self.traveled >= self.max_distance {
+            return Some(ProjectileImpact::Expired);
+        }
+
+        None
+    }
+}
+
+fn direction_to(from: [f32; 3], to: [f32; 3]) -> [f32; 3] {
+    let d = [to[0] - from[0], to[1] - from[1], to[2] - from[2]];
+    let len = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt();
+    if len > 0.0001 {
+        [d[0] / len, d[1] / len, d[2] / len]
+    } else {
+        [0.0, 0.0, 1.0]
+    }
+}
+
+fn distance(a: [f32; 3], b: [f32; 3]) -> f32 {
+    let d = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+    (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt()
+}
+
+fn lerp(a: [f32; 3], b: [f32; 3], t: f32) -> [f32; 3] {
+    [
+        a[0] + (b[0] - a[0]) * t,
+        a[1] + (b[1] - a[1]) * t,
+        a[2] + (b[2] - a[2]) * t,
+    ]
+}
+
+fn closest_point_on_segment(start: [f32; 3], end: [f32; 3], point: [f32; 3]) -> [f32; 3] {
+    let seg = [end[0] - start[0], end[1] - start[1], end[2] - start[2]];
+    let len_sq = seg[0] * seg[0] + seg[1] * seg[1] + seg[2] * seg[2];
+    if len_sq < 0.0001 {
+        return start;
+    }
+    let to_point = [point[0] - start[0], point[1] - start[1], point[2] - start[2]];
+    let t = ((to_point[0] * seg[0] + to_point[1] * seg[1] + to_point[2] * seg[2]) / len_sq).clamp(0.0, 1.0);
+    lerp(start, end, t)
+}
@@ -2,19 +2,22 @@
 //! 
 //! A simple authoritative game server for the WoW-like MMO prototype.
 
+mod ai;
 mod network;
 mod world;
 mod entities;
 mod persistence;
+pub mod combat;
 pub mod commands;
 pub mod navigation;
 
+use std::collections::HashMap;
 use std::time::{Duration, Instant};
 use log::{info, error};
-use mmo_shared::{DEFAULT_PORT, SERVER_TICK_RATE};
+use mmo_shared::{AbilityDef, DEFAULT_PORT, SERVER_TICK_RATE};
 
 use crate::network::Server;
-use crate::world::{GameWorld, ZoneManager, SpawnAreaManager};
+use crate::world::{GameWorld, ZoneManager, SpawnAreaManager, JsonConfig, DeathPenaltyConfig, SpecializationConfig, BossManager, EnemyTemplates, PatrolManager, NpcSchedules, WorldClock, EventManager, MetinStoneManager};
 use crate::persistence::{PersistenceHandle, Database};
 
 /// Database URL (matches docker-compose.yml)
@@ -51,9 +54,20 @@ async fn load_zones_from_db(db: &Database) -> ZoneManager {
     // Initialize heightmaps for terrain height queries
     zone_manager.init_heightmaps();
     
+    // Build nav-meshes from heightmaps and obstacles (cached in navmesh/)
+    zone_manager.init_navmeshes(Some("navmesh"));
+    
     zone_manager
 }
 
+/// Starter abilities keyed by id, for when the database can't provide any
+fn fallback_abilities() -> HashMap<u32, AbilityDef> {
+    mmo_shared::get_fallback_abilities()
+        .into_iter()
+        .map(|a| (a.id, a))
+        .collect()
+}
+
 /// Load spawn areas from JSON file
 fn load_spawn_areas() -> SpawnAreaManager {
     let mut spawn_area_manager = SpawnAreaManager::new();
@@ -72,6 +86,100 @@ fn load_spawn_areas() -> SpawnAreaManager {
     spawn_area_manager
 }
 
+/// Load a JSON config next to the binary, falling back to its defaults
+fn load_config<T: JsonConfig>() -> T {
+    match T::load_from_json(T::FILE) {
+        Ok(config) => {
+            info!("Loaded {}: {:?}", T::FILE, config);
+            config
+        }
+        Err(e) => {
+            info!("Using defaults for {}: {}", T::FILE, e);
+            T::default()
+        }
+    }
+}
+
+/// Load boss encounters from JSON file
+fn load_bosses() -> BossManager {
+    let mut boss_manager = BossManager::new();
+    
+    if let Err(e) = boss_manager.load_from_json("bosses.json") {
+        info!("No bosses loaded: {}", e);
+    }
+    
+    boss_manager
+}
+
+/// Load enemy combat templates from JSON file
+fn load_enemy_templates() -> EnemyTemplates {
+    let mut templates = EnemyTemplates::new();
+    
+    if let Err(e) = templates.load_from_json("enemy_templates.json") {
+        error!("No enemy templates loaded, no enemies will spawn: {}", e);
+    }
+    
+    templates
+}
+
+/// Load patrol routes from exported JSON
+fn load_patrols() -> PatrolManager {
+    let mut patrols = PatrolManager::new();
+    
+    if let Err(e) = patrols.load_from_json("patrol_paths.json") {
+        info!("No patrol paths loaded: {}", e);
+    }
+    
+    patrols
+}
+
+/// Load NPC schedules from JSON file
+fn load_npc_schedules() -> NpcSchedules {
+    let mut schedules = NpcSchedules::new();
+    
+    if let Err(e) = schedules.load_from_json("npc_schedules.json") {
+        info!("No NPC schedules loaded, NPCs stand at their spawns: {}", e);
+    }
+    
+    schedules
+}
+
+/// Load world clock and weather settings from JSON file
+fn load_world_clock() -> WorldClock {
+    match WorldClock::load_from_json("world_clock.json") {
+        Ok(clock) => {
+            info!("World clock loaded, weather is {}", clock.weather().name());
+            clock
+        }
+        Err(e) => {
+            info!("Using default world clock (Berlin, always clear): {}", e);
+            WorldClock::default()
+        }
+    }
+}
+
+/// Load scheduled world events from JSON file
+fn load_world_events() -> EventManager {
+    let mut events = EventManager::new();
+    
+    if let Err(e) = events.load_from_json("world_events.json") {
+        info!("No world events loaded: {}", e);
+    }
+    
+    events
+}
+
+/// Load Metin stone templates from JSON file
+fn load_metin_stones() -> MetinStoneManager {
+    let mut stones = MetinStoneManager::new();
+    
+    if let Err(e) = stones.load_from_json("metin_stones.json") {
+        info!("No metin stones loaded: {}", e);
+    }
+    
+    stones
+}
+
 /// Redis URL (matches docker-compose.yml)
 const REDIS_URL: &str = "redis://localhost:6380";
 
@@ -100,8 +208,8 @@ async fn main() {
         }
     };
     
-    // Load items and zones from database
-    let (items, zone_manager) = match Database::connect(DATABASE_URL).await {
+    // Load items, abilities and zones from database
+    let (items, abilities, zone_manager) = match Database::connect(DATABASE_URL).await {
         Ok(db) => {
             // Load items
             let items = match db.load_all_items().await {
@@ -119,28 +227,58 @@ async fn main() {
                 }
             };
             
+            // Load abilities
+            let abilities = match db.load_all_abilities().await {
+                Ok(abilities) if !abilities.is_empty() => {
+                    info!("Loaded {} abilities from database", abilities.len());
+                    abilities
+                }
+                Ok(_) => {
+                    error!("No abilities in database, run the migrations");
+                    error!("Using fallback starter abilities");
+                    fallback_abilities()
+                }
+                Err(e) => {
+                    error!("Failed to load abilities from database: {}", e);
+                    error!("Using fallback starter abilities");
+                    fallback_abilities()
+                }
+            };
+            
             // Load zones
             let zone_manager = load_zones_from_db(&db).await;
             
-            (items, zone_manager)
+            (items, abilities, zone_manager)
         }
         Err(e) => {
             error!("Failed to connect to database: {}", e);
-            error!("Using fallback hardcoded items and zones");
+            error!("Using fallback hardcoded items, starter abilities and zones");
             let items = mmo_shared::get_item_definitions()
                 .into_iter()
                 .map(|i| (i.id, i))
                 .collect();
             let zone_manager = ZoneManager::with_defaults();
-            (items, zone_manager)
+            (items, fallback_abilities(), zone_manager)
         }
     };
     
     // Load spawn areas from exported JSON
     let spawn_area_manager = load_spawn_areas();
     
-    // Create the game world with loaded items, zones, and spawn areas
-    let mut world = GameWorld::with_spawn_areas(items, zone_manager, spawn_area_manager);
+    // Create the game world with loaded items, abilities, zones, and spawn areas
+    let enemy_templates = load_enemy_templates();
+    if let Err(e) = spawn_area_manager.validate(|id| enemy_templates.get(id).is_some()) {
+        error!("Spawn areas: {} (the area will keep retrying that enemy)", e);
+    }
+    let mut world = GameWorld::with_spawn_areas(items, abilities, zone_manager, spawn_area_manager, enemy_templates);
+    world.set_death_penalty_config(load_config::<DeathPenaltyConfig>());
+    world.set_specialization_config(load_config::<SpecializationConfig>());
+    world.set_boss_manager(load_bosses());
+    world.set_patrol_manager(load_patrols());
+    world.set_npc_schedules(&load_npc_schedules());
+    world.set_world_clock(load_world_clock());
+    world.set_event_manager(load_world_events());
+    world.set_metin_stone_manager(load_metin_stones());
     
     // Create the network server
     let mut server = match Server::new(DEFAULT_PORT, persistence.clone()).await {
@@ -179,10 +317,25 @@ async fn main() {
             server.queue_broadcasts(world_messages);
         }
         
-        // Update player abilities (cooldowns, buffs/debuffs)
-        let ability_updates = world.update_player_abilities(delta);
-        if !ability_updates.is_empty() {
-            server.queue_player_ability_updates(ability_updates);
+        // Zone announcements and per-player messages (world events)
+        let zone_messages = world.drain_zone_messages();
+        if !zone_messages.is_empty() {
+            server.queue_zone_broadcasts(zone_messages, &world);
+        }
+        for (player_id, messages) in world.drain_player_messages() {
+            server.queue_messages_for_player(player_id, messages);
+        }
+        
+        // Update player abilities (cooldowns, buffs/debuffs, casts)
+        let (ability_updates, ability_broadcasts) = world.update_player_abilities(delta);
+        if !ability_updates.is_empty() || !ability_broadcasts.is_empty() {
+            server.queue_player_ability_updates(ability_updates, ability_broadcasts);
+        }
+        
+        // Stream and persist combat log events from this tick
+        let combat_events = world.drain_combat_log();
+        if !combat_events.is_empty() {
+            server.dispatch_combat_log(combat_events);
         }
         
         // Send world state to all clients
@@ -195,10 +348,11 @@ async fn main() {
         if last_save.elapsed() >= save_interval {
             if let Some(ref persistence) = persistence {
                 server.save_all_players(&world, persistence);
+                persistence.prune_combat_log();
                 info!("Periodic save complete");
             }
             // Broadcast time sync to all clients (for day/night cycle)
-            server.broadcast_time_sync().await;
+            server.broadcast_time_sync(&world).await;
             last_save = Instant::now();
         }
         
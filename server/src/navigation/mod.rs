@@ -3,15 +3,30 @@
 //! This module provides:
 //! - Obstacle definitions (circles, boxes)
 //! - Collision detection
+//! - Raycasts and line-of-sight checks
 //! - Context-based steering for obstacle avoidance
+//! - Nav-mesh paths over terrain (see `navmesh`)
+//! - Spatial indexes for proximity queries (see `spatial`)
+//! - Separation between moving enemies (see `separation`)
 //! - A* pathfinding fallback
 
+pub mod navmesh;
+pub mod spatial;
+pub mod separation;
+
 use std::f32::consts::PI;
 use log::{debug, trace};
 
+pub use navmesh::{NavMesh, Fingerprint};
+pub use spatial::{SpatialGrid, ObstacleGrid, SPATIAL_CELL_SIZE};
+pub use separation::Body;
+
 /// Radius used for enemy collision detection
 pub const ENEMY_RADIUS: f32 = 0.6;
 
+/// Obstacles further away than this can't affect a tick of steering or a fallback grid path
+pub const LOCAL_NAV_RADIUS: f32 = 16.0;
+
 /// Number of directions to sample for context steering
 const STEERING_DIRECTIONS: usize = 16;
 
@@ -21,6 +36,12 @@ const STEERING_LOOKAHEAD: f32 = 3.0;
 /// Minimum distance to maintain from obstacles
 const OBSTACLE_MARGIN: f32 = 0.3;
 
+/// How far the target may move before a nav-mesh route is planned again
+const REPATH_DISTANCE: f32 = 3.0;
+
+/// How close to a waypoint counts as reached
+const WAYPOINT_REACHED: f32 = 1.0;
+
 // ============================================================================
 // Obstacle Types
 // ============================================================================
@@ -282,6 +303,80 @@ pub fn resolve_collision(pos: Vec2, radius: f32, obstacles: &[Obstacle]) -> Vec2
     result
 }
 
+// ============================================================================
+// Raycasting / Line of Sight
+// ============================================================================
+
+/// Fraction along the segment `from -> to` where it first enters a circular obstacle
+fn segment_circle_intersection(from: Vec2, to: Vec2, obstacle: &CircleObstacle) -> Option<f32> {
+    let d = to - from;
+    let f = from - obstacle.center;
+
+    let a = d.dot(d);
+    let c = f.dot(f) - obstacle.radius * obstacle.radius;
+    if c <= 0.0 {
+        // Segment starts inside the obstacle
+        return Some(0.0);
+    }
+    if a < 0.0001 {
+        return None;
+    }
+
+    let b = 2.0 * f.dot(d);
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let t = (-b - discriminant.sqrt()) / (2.0 * a);
+    (0.0..=1.0).contains(&t).then_some(t)
+}
+
+/// Fraction along the segment `from -> to` where it first enters an AABB obstacle (slab method)
+fn segment_aabb_intersection(from: Vec2, to: Vec2, obstacle: &BoxObstacle) -> Option<f32> {
+    let d = to - from;
+    let mut t_min: f32 = 0.0;
+    let mut t_max: f32 = 1.0;
+
+    for (origin, dir, min, max) in [
+        (from.x, d.x, obstacle.min.x, obstacle.max.x),
+        (from.z, d.z, obstacle.min.z, obstacle.max.z),
+    ] {
+        if dir.abs() < 0.0001 {
+            // Parallel to this slab - must already be inside it
+            if origin < min || origin > max {
+                return None;
+            }
+        } else {
+            let t1 = (min - origin) / dir;
+            let t2 = (max - origin) / dir;
+            t_min = t_min.max(t1.min(t2));
+            t_max = t_max.min(t1.max(t2));
+            if t_min > t_max {
+                return None;
+            }
+        }
+    }
+
+    Some(t_min)
+}
+
+/// Cast a ray from `from` to `to` and return the fraction (0..1) of the first obstacle hit
+pub fn raycast_obstacles(from: Vec2, to: Vec2, obstacles: &[Obstacle]) -> Option<f32> {
+    obstacles
+        .iter()
+        .filter_map(|obstacle| match obstacle {
+            Obstacle::Circle(c) => segment_circle_intersection(from, to, c),
+            Obstacle::Box(b) => segment_aabb_intersection(from, to, b),
+        })
+        .min_by(|a, b| a.total_cmp(b))
+}
+
+/// Check if there is an unobstructed straight line between two points
+pub fn has_line_of_sight(from: Vec2, to: Vec2, obstacles: &[Obstacle]) -> bool {
+    raycast_obstacles(from, to, obstacles).is_none()
+}
+
 // ============================================================================
 // Context-Based Steering
 // ============================================================================
@@ -527,6 +622,12 @@ pub struct NavigationState {
     pub stuck_time: f32,
     /// Last position (for stuck detection)
     pub last_position: Option<Vec2>,
+    /// Corners of the current nav-mesh route
+    pub mesh_path: Vec<Vec2>,
+    /// Current corner index
+    pub mesh_path_index: usize,
+    /// Target the nav-mesh route was planned for
+    pub mesh_goal: Option<Vec2>,
 }
 
 impl NavigationState {
@@ -538,6 +639,9 @@ impl NavigationState {
     pub fn clear_path(&mut self) {
         self.path.clear();
         self.path_index = 0;
+        self.mesh_path.clear();
+        self.mesh_path_index = 0;
+        self.mesh_goal = None;
     }
 
     /// Get the current waypoint target, if any
@@ -551,18 +655,46 @@ impl NavigationState {
             self.path_index += 1;
         }
     }
+
+    /// Next corner of the nav-mesh route to `target` (None = head straight there)
+    /// Plans a new route when the target has moved too far from the old one
+    fn mesh_waypoint(&mut self, mesh: &NavMesh, current_pos: Vec2, target_pos: Vec2) -> Option<Vec2> {
+        if self.mesh_goal.is_none_or(|goal| goal.distance_to(target_pos) > REPATH_DISTANCE) {
+            self.mesh_goal = Some(target_pos);
+            self.mesh_path_index = 0;
+            self.mesh_path = if mesh.is_clear(current_pos, target_pos) {
+                Vec::new()
+            } else {
+                mesh.find_path(current_pos, target_pos).unwrap_or_default()
+            };
+            if !self.mesh_path.is_empty() {
+                debug!("[NAV] Nav-mesh route with {} corners", self.mesh_path.len());
+            }
+        }
+
+        while let Some(&corner) = self.mesh_path.get(self.mesh_path_index) {
+            if corner.distance_to(current_pos) > WAYPOINT_REACHED {
+                return Some(corner);
+            }
+            self.mesh_path_index += 1;
+        }
+        None
+    }
 }
 
 /// Calculate the next position for an entity navigating toward a target
 /// 
 /// This is the main entry point for navigation. It:
-/// 1. Uses context steering to try to move directly toward the target
-/// 2. If that fails, falls back to A* pathfinding
-/// 3. Handles collision resolution to prevent clipping through obstacles
+/// 1. With a nav-mesh, heads for the next corner of a route around steep terrain and obstacles
+/// 2. Uses context steering to try to move directly toward the target
+/// 3. If that fails, falls back to A* pathfinding
+/// 4. Handles collision resolution to prevent clipping through obstacles
+#[allow(clippy::too_many_arguments)]
 pub fn navigate_toward(
     current_pos: Vec2,
     target_pos: Vec2,
     obstacles: &[Obstacle],
+    mesh: Option<&NavMesh>,
     nav_state: &mut NavigationState,
     speed: f32,
     delta: f32,
@@ -584,6 +716,11 @@ pub fn navigate_toward(
         };
     }
 
+    // Long routes: steer toward the next nav-mesh corner instead of the target itself
+    if let Some(corner) = mesh.and_then(|mesh| nav_state.mesh_waypoint(mesh, current_pos, target_pos)) {
+        return navigate_toward(current_pos, corner, obstacles, None, nav_state, speed, delta, enemy_radius);
+    }
+
     // First, try context steering (faster and smoother)
     if let Some(direction) = calculate_steering_direction(current_pos, target_pos, obstacles, enemy_radius) {
         let move_dist = (speed * delta).min(target_dist);
@@ -666,7 +803,7 @@ pub fn navigate_toward(
         if waypoint_dist < 1.0 {
             nav_state.advance_waypoint();
             // Recurse to move toward next waypoint
-            return navigate_toward(current_pos, target_pos, obstacles, nav_state, speed, delta, enemy_radius);
+            return navigate_toward(current_pos, target_pos, obstacles, None, nav_state, speed, delta, enemy_radius);
         }
 
         let direction = to_waypoint.normalized();
@@ -729,6 +866,29 @@ mod tests {
         assert!(path.is_some());
     }
     
+    #[test]
+    fn test_line_of_sight() {
+        let obstacles = vec![
+            Obstacle::Box(BoxObstacle::from_center(10.0, -8.0, 4.0, 5.0)),
+            Obstacle::Circle(CircleObstacle::new(0.0, 10.0, 1.0)),
+        ];
+
+        // Straight through the building
+        assert!(!has_line_of_sight(Vec2::new(20.0, -8.0), Vec2::new(0.0, -8.0), &obstacles));
+        // Box entered at x = 14 -> 30% of the way
+        let t = raycast_obstacles(Vec2::new(20.0, -8.0), Vec2::new(0.0, -8.0), &obstacles).unwrap();
+        assert!((t - 0.3).abs() < 0.001);
+
+        // Through the pillar
+        assert!(!has_line_of_sight(Vec2::new(-5.0, 10.0), Vec2::new(5.0, 10.0), &obstacles));
+
+        // Clear lines
+        assert!(has_line_of_sight(Vec2::new(0.0, 0.0), Vec2::new(5.0, 0.0), &obstacles));
+        assert!(has_line_of_sight(Vec2::new(-5.0, 12.0), Vec2::new(5.0, 12.0), &obstacles));
+        // Stops short of the building
+        assert!(has_line_of_sight(Vec2::new(20.0, -8.0), Vec2::new(15.0, -8.0), &obstacles));
+    }
+
     #[test]
     fn test_main_building_collision() {
         // Test collision with the main building obstacle as defined in zones
@@ -0,0 +1,695 @@
+//! Navigation mesh.
+//!
+//! Built per zone from the terrain heightmap and the zone's obstacles: the zone
+//! is rasterised into walkable cells (too steep, or inside an obstacle grown by
+//! the agent radius, means blocked) and the walkable cells are merged into
+//! rectangles that become the mesh polygons. Path queries run A* over the
+//! polygons and pull the corridor tight with the funnel algorithm, so long
+//! routes come out as a handful of corner waypoints.
+//!
+//! Building a full zone takes a moment, so meshes can be cached on disk and are
+//! only rebuilt when the heightmap or obstacles change.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Mutex;
+use log::debug;
+use serde::{Deserialize, Serialize};
+use super::{Obstacle, Vec2};
+
+/// Edge length of a walkability cell (units)
+pub const NAVMESH_CELL_SIZE: f32 = 2.0;
+
+/// Steepest terrain an agent can walk on (degrees)
+pub const MAX_WALKABLE_SLOPE: f32 = 40.0;
+
+/// Longest polygon side (cells); keeps A* costs between polygon centers honest
+const MAX_POLY_CELLS: usize = 16;
+
+/// How far to look for walkable ground around a blocked start or goal (cells)
+const SNAP_SEARCH_CELLS: i32 = 4;
+
+/// Corridors kept in memory before the cache is flushed
+const MAX_CACHED_CORRIDORS: usize = 4096;
+
+/// Bump when the cache file format or the build rules change
+const NAVMESH_FORMAT_VERSION: u32 = 1;
+
+/// Cell value for blocked cells
+const NO_POLY: u32 = u32::MAX;
+
+/// A mesh polygon: an axis-aligned block of walkable cells
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct CellRect {
+    x: usize,
+    z: usize,
+    w: usize,
+    h: usize,
+}
+
+/// Shared edge between two neighbouring polygons
+#[derive(Debug, Clone, Copy)]
+struct Portal {
+    to: u32,
+    a: Vec2,
+    b: Vec2,
+}
+
+/// On-disk cache format
+#[derive(Serialize, Deserialize)]
+struct NavMeshFile {
+    version: u32,
+    /// Hash of the inputs the mesh was built from
+    fingerprint: u64,
+    origin: [f32; 2],
+    cell_size: f32,
+    cols: usize,
+    rows: usize,
+    polys: Vec<CellRect>,
+}
+
+/// Hash of the inputs a cached mesh was built from
+/// FNV-1a over little-endian bytes, so it stays the same across toolchains and platforms
+#[derive(Debug, Clone, Copy)]
+pub struct Fingerprint(u64);
+
+impl Fingerprint {
+    pub fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    pub fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    pub fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    pub fn write_f32(&mut self, value: f32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    /// Hash obstacles by shape and exact coordinates
+    pub fn write_obstacles(&mut self, obstacles: &[Obstacle]) {
+        self.write_u64(obstacles.len() as u64);
+        for obstacle in obstacles {
+            match obstacle {
+                Obstacle::Circle(c) => {
+                    self.write(&[0]);
+                    for value in [c.center.x, c.center.z, c.radius] {
+                        self.write_f32(value);
+                    }
+                }
+                Obstacle::Box(b) => {
+                    self.write(&[1]);
+                    for value in [b.min.x, b.min.z, b.max.x, b.max.z] {
+                        self.write_f32(value);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+impl Default for Fingerprint {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Polygon corridors by (start, goal) polygon, None = unreachable
+type CorridorCache = HashMap<(u32, u32), Option<Vec<u32>>>;
+
+/// Open-list entry for the polygon A* search (lowest cost first)
+struct OpenPoly {
+    cost: f32,
+    poly: u32,
+}
+
+impl PartialEq for OpenPoly {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost
+    }
+}
+
+impl Eq for OpenPoly {}
+
+impl PartialOrd for OpenPoly {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenPoly {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+/// Walkable area of a zone
+pub struct NavMesh {
+    /// World position of the grid's minimum corner
+    origin: Vec2,
+    cell_size: f32,
+    cols: usize,
+    rows: usize,
+    polys: Vec<CellRect>,
+    /// Polygon covering each cell (row-major, NO_POLY = blocked)
+    cells: Vec<u32>,
+    /// Portals out of each polygon
+    portals: Vec<Vec<Portal>>,
+    /// Corridors found so far
+    corridors: Mutex<CorridorCache>,
+}
+
+impl std::fmt::Debug for NavMesh {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NavMesh")
+            .field("cols", &self.cols)
+            .field("rows", &self.rows)
+            .field("polys", &self.polys.len())
+            .finish()
+    }
+}
+
+impl NavMesh {
+    /// Build a mesh covering `min`..`max` (x, z)
+    /// `height_at` samples the terrain; obstacles are grown by `agent_radius`
+    pub fn build(
+        min: Vec2,
+        max: Vec2,
+        height_at: impl Fn(f32, f32) -> f32,
+        obstacles: &[Obstacle],
+        agent_radius: f32,
+    ) -> Self {
+        let cell = NAVMESH_CELL_SIZE;
+        let cols = ((max.x - min.x) / cell).ceil().max(1.0) as usize;
+        let rows = ((max.z - min.z) / cell).ceil().max(1.0) as usize;
+        let mut walkable = vec![true; cols * rows];
+
+        // Steep terrain
+        let max_gradient = MAX_WALKABLE_SLOPE.to_radians().tan();
+        let half = cell * 0.5;
+        for z in 0..rows {
+            for x in 0..cols {
+                let center = cell_center(min, cell, x, z);
+                let dx = (height_at(center.x + half, center.z) - height_at(center.x - half, center.z)) / cell;
+                let dz = (height_at(center.x, center.z + half) - height_at(center.x, center.z - half)) / cell;
+                if dx * dx + dz * dz > max_gradient * max_gradient {
+                    walkable[z * cols + x] = false;
+                }
+            }
+        }
+
+        // Obstacles, grown by the agent radius (a cell is blocked if any part of it is
+        // too close, so agents can stand anywhere on the mesh, edges and corners included)
+        for obstacle in obstacles {
+            let (lo, hi) = match obstacle {
+                Obstacle::Circle(c) => (
+                    Vec2::new(c.center.x - c.radius, c.center.z - c.radius),
+                    Vec2::new(c.center.x + c.radius, c.center.z + c.radius),
+                ),
+                Obstacle::Box(b) => (b.min, b.max),
+            };
+            let lo = Vec2::new(lo.x - agent_radius, lo.z - agent_radius);
+            let hi = Vec2::new(hi.x + agent_radius, hi.z + agent_radius);
+            let (xs, zs) = cell_span(min, cell, cols, rows, lo, hi);
+            for z in zs {
+                for x in xs.clone() {
+                    let cell_min = Vec2::new(min.x + x as f32 * cell, min.z + z as f32 * cell);
+                    let cell_max = Vec2::new(cell_min.x + cell, cell_min.z + cell);
+                    let clearance = match obstacle {
+                        Obstacle::Circle(c) => rect_distance(cell_min, cell_max, c.center, c.center) - c.radius,
+                        Obstacle::Box(b) => rect_distance(cell_min, cell_max, b.min, b.max),
+                    };
+                    if clearance < agent_radius {
+                        walkable[z * cols + x] = false;
+                    }
+                }
+            }
+        }
+
+        let polys = merge_cells(&walkable, cols, rows);
+        Self::from_polys(min, cell, cols, rows, polys)
+    }
+
+    /// Load a cached mesh, if it was built from the same inputs
+    pub fn load_cached<P: AsRef<Path>>(path: P, fingerprint: u64) -> Result<Self, String> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        let file: NavMeshFile = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse {:?}: {}", path, e))?;
+
+        if file.version != NAVMESH_FORMAT_VERSION || file.cell_size != NAVMESH_CELL_SIZE {
+            return Err(format!("{:?} was built by an older server", path));
+        }
+        if file.fingerprint != fingerprint {
+            return Err(format!("{:?} is out of date", path));
+        }
+        if file.polys.iter().any(|p| p.x + p.w > file.cols || p.z + p.h > file.rows) {
+            return Err(format!("{:?} has polygons outside its grid", path));
+        }
+
+        let origin = Vec2::new(file.origin[0], file.origin[1]);
+        Ok(Self::from_polys(origin, file.cell_size, file.cols, file.rows, file.polys))
+    }
+
+    /// Write the mesh to a cache file
+    pub fn save<P: AsRef<Path>>(&self, path: P, fingerprint: u64) -> Result<(), String> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+        }
+
+        let file = NavMeshFile {
+            version: NAVMESH_FORMAT_VERSION,
+            fingerprint,
+            origin: [self.origin.x, self.origin.z],
+            cell_size: self.cell_size,
+            cols: self.cols,
+            rows: self.rows,
+            polys: self.polys.clone(),
+        };
+        let json = serde_json::to_string(&file)
+            .map_err(|e| format!("Failed to serialize nav-mesh: {}", e))?;
+        std::fs::write(path, json)
+            .map_err(|e| format!("Failed to write {:?}: {}", path, e))
+    }
+
+    fn from_polys(origin: Vec2, cell_size: f32, cols: usize, rows: usize, polys: Vec<CellRect>) -> Self {
+        let mut cells = vec![NO_POLY; cols * rows];
+        for (id, p) in polys.iter().enumerate() {
+            for z in p.z..p.z + p.h {
+                cells[z * cols + p.x..z * cols + p.x + p.w].fill(id as u32);
+            }
+        }
+
+        let mut mesh = Self {
+            origin,
+            cell_size,
+            cols,
+            rows,
+            polys,
+            cells,
+            portals: Vec::new(),
+            corridors: Mutex::new(HashMap::new()),
+        };
+        mesh.portals = (0..mesh.polys.len()).map(|id| mesh.find_portals(id)).collect();
+        mesh
+    }
+
+    /// Number of polygons in the mesh
+    pub fn poly_count(&self) -> usize {
+        self.polys.len()
+    }
+
+    /// Whether an agent can stand at a position
+    pub fn is_walkable(&self, pos: Vec2) -> bool {
+        self.poly_at(pos).is_some()
+    }
+
+    /// Whether the straight line between two points stays on walkable ground
+    pub fn is_clear(&self, from: Vec2, to: Vec2) -> bool {
+        let distance = from.distance_to(to);
+        let steps = (distance / (self.cell_size * 0.5)).ceil().max(1.0) as usize;
+        (0..=steps).all(|i| {
+            let t = i as f32 / steps as f32;
+            self.is_walkable(from + (to - from) * t)
+        })
+    }
+
+    /// Path from start to goal around steep terrain and obstacles
+    /// Returns waypoints (excluding start, including goal), or None when unreachable
+    /// A start or goal on blocked ground is moved to the nearest walkable spot
+    pub fn find_path(&self, start: Vec2, goal: Vec2) -> Option<Vec<Vec2>> {
+        let (start_poly, snapped_start) = self.snap(start)?;
+        let (goal_poly, snapped_goal) = self.snap(goal)?;
+        let corridor = self.corridor(start_poly, goal_poly)?;
+
+        let mut path = Vec::new();
+        if snapped_start.distance_to(start) > 0.01 {
+            path.push(snapped_start);
+        }
+        path.extend(self.funnel(snapped_start, snapped_goal, &corridor));
+        Some(path)
+    }
+
+    fn poly_at(&self, pos: Vec2) -> Option<u32> {
+        let x = ((pos.x - self.origin.x) / self.cell_size).floor();
+        let z = ((pos.z - self.origin.z) / self.cell_size).floor();
+        if x < 0.0 || z < 0.0 || x >= self.cols as f32 || z >= self.rows as f32 {
+            return None;
+        }
+        Some(self.cells[z as usize * self.cols + x as usize]).filter(|&p| p != NO_POLY)
+    }
+
+    /// Polygon under a position, or the nearest walkable cell center close by
+    fn snap(&self, pos: Vec2) -> Option<(u32, Vec2)> {
+        if let Some(poly) = self.poly_at(pos) {
+            return Some((poly, pos));
+        }
+
+        let cx = ((pos.x - self.origin.x) / self.cell_size).floor() as i32;
+        let cz = ((pos.z - self.origin.z) / self.cell_size).floor() as i32;
+        let mut best: Option<(u32, Vec2)> = None;
+        for z in cz - SNAP_SEARCH_CELLS..=cz + SNAP_SEARCH_CELLS {
+            for x in cx - SNAP_SEARCH_CELLS..=cx + SNAP_SEARCH_CELLS {
+                if x < 0 || z < 0 || x >= self.cols as i32 || z >= self.rows as i32 {
+                    continue;
+                }
+                let poly = self.cells[z as usize * self.cols + x as usize];
+                if poly == NO_POLY {
+                    continue;
+                }
+                let center = cell_center(self.origin, self.cell_size, x as usize, z as usize);
+                if best.is_none_or(|(_, b)| center.distance_to(pos) < b.distance_to(pos)) {
+                    best = Some((poly, center));
+                }
+            }
+        }
+        best
+    }
+
+    fn poly_center(&self, poly: u32) -> Vec2 {
+        let p = self.polys[poly as usize];
+        Vec2::new(
+            self.origin.x + (p.x as f32 + p.w as f32 * 0.5) * self.cell_size,
+            self.origin.z + (p.z as f32 + p.h as f32 * 0.5) * self.cell_size,
+        )
+    }
+
+    fn corner(&self, x: usize, z: usize) -> Vec2 {
+        Vec2::new(self.origin.x + x as f32 * self.cell_size, self.origin.z + z as f32 * self.cell_size)
+    }
+
+    /// Portals along all four sides of a polygon
+    fn find_portals(&self, id: usize) -> Vec<Portal> {
+        let p = self.polys[id];
+        let mut portals = Vec::new();
+
+        // Left and right sides (walk along z)
+        for (edge, neighbour) in [(p.x, p.x.checked_sub(1)), (p.x + p.w, Some(p.x + p.w).filter(|&x| x < self.cols))] {
+            if let Some(nx) = neighbour {
+                self.collect_portals(p.z..p.z + p.h, |z| self.cells[z * self.cols + nx], |a, b| (self.corner(edge, a), self.corner(edge, b)), &mut portals);
+            }
+        }
+        // Bottom and top sides (walk along x)
+        for (edge, neighbour) in [(p.z, p.z.checked_sub(1)), (p.z + p.h, Some(p.z + p.h).filter(|&z| z < self.rows))] {
+            if let Some(nz) = neighbour {
+                self.collect_portals(p.x..p.x + p.w, |x| self.cells[nz * self.cols + x], |a, b| (self.corner(a, edge), self.corner(b, edge)), &mut portals);
+            }
+        }
+        portals
+    }
+
+    /// Group a polygon side into runs that border the same neighbour
+    fn collect_portals(
+        &self,
+        side: Range<usize>,
+        neighbour: impl Fn(usize) -> u32,
+        corners: impl Fn(usize, usize) -> (Vec2, Vec2),
+        portals: &mut Vec<Portal>,
+    ) {
+        let mut start = side.start;
+        while start < side.end {
+            let to = neighbour(start);
+            let mut end = start + 1;
+            while end < side.end && neighbour(end) == to {
+                end += 1;
+            }
+            if to != NO_POLY {
+                let (a, b) = corners(start, end);
+                portals.push(Portal { to, a, b });
+            }
+            start = end;
+        }
+    }
+
+    /// Polygons from start to goal (cached)
+    fn corridor(&self, from: u32, to: u32) -> Option<Vec<u32>> {
+        if let Some(cached) = self.corridors.lock().ok().and_then(|c| c.get(&(from, to)).cloned()) {
+            return cached;
+        }
+
+        let corridor = self.search(from, to);
+        if let Ok(mut cache) = self.corridors.lock() {
+            if cache.len() >= MAX_CACHED_CORRIDORS {
+                cache.clear();
+            }
+            cache.insert((from, to), corridor.clone());
+        }
+        corridor
+    }
+
+    /// A* over polygon centers
+    fn search(&self, from: u32, to: u32) -> Option<Vec<u32>> {
+        let goal = self.poly_center(to);
+        let mut costs: HashMap<u32, f32> = HashMap::from([(from, 0.0)]);
+        let mut parents: HashMap<u32, u32> = HashMap::new();
+        let mut open = BinaryHeap::from([OpenPoly { cost: self.poly_center(from).distance_to(goal), poly: from }]);
+
+        while let Some(OpenPoly { poly, .. }) = open.pop() {
+            if poly == to {
+                let mut corridor = vec![to];
+                let mut current = to;
+                while let Some(&parent) = parents.get(&current) {
+                    corridor.push(parent);
+                    current = parent;
+                }
+                corridor.reverse();
+                return Some(corridor);
+            }
+
+            let here = self.poly_center(poly);
+            let cost_here = costs[&poly];
+            for portal in &self.portals[poly as usize] {
+                let next = self.poly_center(portal.to);
+                let cost = cost_here + here.distance_to(next);
+                if costs.get(&portal.to).is_some_and(|&known| known <= cost) {
+                    continue;
+                }
+                costs.insert(portal.to, cost);
+                parents.insert(portal.to, poly);
+                open.push(OpenPoly { cost: cost + next.distance_to(goal), poly: portal.to });
+            }
+        }
+
+        debug!("[NAVMESH] No corridor from polygon {} to {}", from, to);
+        None
+    }
+
+    /// Shortest path through a corridor (simple stupid funnel algorithm)
+    fn funnel(&self, start: Vec2, goal: Vec2, corridor: &[u32]) -> Vec<Vec2> {
+        // Portals as (left, right) seen walking the corridor, bracketed by the end points
+        let mut portals = vec![(start, start)];
+        for pair in corridor.windows(2) {
+            let Some(portal) = self.portals[pair[0] as usize].iter().find(|p| p.to == pair[1]) else {
+                continue;
+            };
+            let from = self.poly_center(pair[0]);
+            let heading = self.poly_center(pair[1]) - from;
+            if cross(heading, portal.a - from) > cross(heading, portal.b - from) {
+                portals.push((portal.a, portal.b));
+            } else {
+                portals.push((portal.b, portal.a));
+            }
+        }
+        portals.push((goal, goal));
+
+        let mut path = Vec::new();
+        let (mut apex, mut left, mut right) = (start, start, start);
+        let (mut left_index, mut right_index) = (0, 0);
+        let mut i = 1;
+        while i < portals.len() {
+            let (next_left, next_right) = portals[i];
+
+            // Narrow the right side, unless it would cross the left (then the left corner is on the path)
+            if triangle_area(apex, right, next_right) <= 0.0 {
+                if same_point(apex, right) || triangle_area(apex, left, next_right) > 0.0 {
+                    right = next_right;
+                    right_index = i;
+                } else {
+                    apex = left;
+                    path.push(apex);
+                    right = apex;
+                    right_index = left_index;
+                    i = left_index + 1;
+                    continue;
+                }
+            }
+
+            // Same for the left side
+            if triangle_area(apex, left, next_left) >= 0.0 {
+                if same_point(apex, left) || triangle_area(apex, right, next_left) < 0.0 {
+                    left = next_left;
+                    left_index = i;
+                } else {
+                    apex = right;
+                    path.push(apex);
+                    left = apex;
+                    left_index = right_index;
+                    i = right_index + 1;
+                    continue;
+                }
+            }
+
+            i += 1;
+        }
+
+        if path.last().is_none_or(|last| !same_point(*last, goal)) {
+            path.push(goal);
+        }
+        path
+    }
+}
+
+/// Merge walkable cells into rectangles, greedily row by row
+fn merge_cells(walkable: &[bool], cols: usize, rows: usize) -> Vec<CellRect> {
+    let mut taken = vec![false; walkable.len()];
+    let mut polys = Vec::new();
+
+    for z in 0..rows {
+        for x in 0..cols {
+            let free = |x: usize, z: usize| walkable[z * cols + x] && !taken[z * cols + x];
+            if !free(x, z) {
+                continue;
+            }
+            let mut w = 1;
+            while w < MAX_POLY_CELLS && x + w < cols && free(x + w, z) {
+                w += 1;
+            }
+            let mut h = 1;
+            while h < MAX_POLY_CELLS && z + h < rows && (x..x + w).all(|cx| free(cx, z + h)) {
+                h += 1;
+            }
+
+            for rz in z..z + h {
+                taken[rz * cols + x..rz * cols + x + w].fill(true);
+            }
+            polys.push(CellRect { x, z, w, h });
+        }
+    }
+    polys
+}
+
+fn cell_center(origin: Vec2, cell_size: f32, x: usize, z: usize) -> Vec2 {
+    Vec2::new(origin.x + (x as f32 + 0.5) * cell_size, origin.z + (z as f32 + 0.5) * cell_size)
+}
+
+/// Cells overlapping a world-space box, clamped to the grid
+fn cell_span(origin: Vec2, cell_size: f32, cols: usize, rows: usize, lo: Vec2, hi: Vec2) -> (Range<usize>, Range<usize>) {
+    let to_cell = |value: f32, start: f32, count: usize| ((value - start) / cell_size).floor().clamp(0.0, count as f32) as usize;
+    let xs = to_cell(lo.x, origin.x, cols)..(to_cell(hi.x, origin.x, cols) + 1).min(cols);
+    let zs = to_cell(lo.z, origin.z, rows)..(to_cell(hi.z, origin.z, rows) + 1).min(rows);
+    (xs, zs)
+}
+
+/// Gap between two axis-aligned rectangles (0 when they overlap)
+fn rect_distance(a_min: Vec2, a_max: Vec2, b_min: Vec2, b_max: Vec2) -> f32 {
+    let dx = (b_min.x - a_max.x).max(a_min.x - b_max.x).max(0.0);
+    let dz = (b_min.z - a_max.z).max(a_min.z - b_max.z).max(0.0);
+    (dx * dx + dz * dz).sqrt()
+}
+
+fn cross(a: Vec2, b: Vec2) -> f32 {
+    a.x * b.z - a.z * b.x
+}
+
+/// Twice the signed area of triangle abc (sign tells which side of ab c is on)
+fn triangle_area(a: Vec2, b: Vec2, c: Vec2) -> f32 {
+    (c.x - a.x) * (b.z - a.z) - (b.x - a.x) * (c.z - a.z)
+}
+
+fn same_point(a: Vec2, b: Vec2) -> bool {
+    a.distance_to(b) < 0.001
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::navigation::BoxObstacle;
+
+    fn flat(_x: f32, _z: f32) -> f32 {
+        0.0
+    }
+
+    #[test]
+    fn test_fingerprint_is_fnv1a() {
+        // Reference values of 64-bit FNV-1a
+        assert_eq!(Fingerprint::new().finish(), 0xcbf2_9ce4_8422_2325);
+        let mut hash = Fingerprint::new();
+        hash.write(b"a");
+        assert_eq!(hash.finish(), 0xaf63_dc4c_8601_ec8c);
+
+        let fingerprint = |min_x| {
+            let mut hash = Fingerprint::new();
+            hash.write_obstacles(&[Obstacle::Box(BoxObstacle::from_corners(min_x, 0.0, 2.0, 2.0))]);
+            hash.finish()
+        };
+        assert_eq!(fingerprint(0.0), fingerprint(0.0));
+        assert_ne!(fingerprint(0.0), fingerprint(0.5));
+    }
+
+    #[test]
+    fn test_path_hugs_the_corner_of_a_wall() {
+        // A wall across the middle with a gap at the top
+        let wall = Obstacle::Box(BoxObstacle::from_corners(19.0, 0.0, 21.0, 30.0));
+        let mesh = NavMesh::build(Vec2::new(0.0, 0.0), Vec2::new(40.0, 40.0), flat, std::slice::from_ref(&wall), 0.6);
+
+        let start = Vec2::new(10.0, 10.0);
+        let goal = Vec2::new(30.0, 10.0);
+        assert!(!mesh.is_clear(start, goal));
+
+        let path = mesh.find_path(start, goal).unwrap();
+        // Up to the wall's end, around it, and down again: just the corners
+        assert!(path.len() <= 4, "path: {:?}", path);
+        assert!(path.iter().any(|p| p.z >= 30.0));
+        let mut from = start;
+        for &point in &path {
+            assert!(crate::navigation::raycast_obstacles(from, point, std::slice::from_ref(&wall)).is_none());
+            from = point;
+        }
+        assert!(same_point(*path.last().unwrap(), goal));
+    }
+
+    #[test]
+    fn test_steep_ridge_is_walked_around() {
+        // A ridge at x = 20 rising 5 units per unit, except near z = 35
+        let ridge = |x: f32, z: f32| if z < 32.0 { (5.0 * (3.0 - (x - 20.0).abs())).max(0.0) } else { 0.0 };
+        let mesh = NavMesh::build(Vec2::new(0.0, 0.0), Vec2::new(40.0, 40.0), ridge, &[], 0.6);
+
+        assert!(!mesh.is_walkable(Vec2::new(19.0, 10.0)));
+        let path = mesh.find_path(Vec2::new(5.0, 10.0), Vec2::new(35.0, 10.0)).unwrap();
+        assert!(path.iter().any(|p| p.z >= 32.0));
+    }
+
+    #[test]
+    fn test_cache_round_trip_and_staleness() {
+        let wall = Obstacle::Box(BoxObstacle::from_corners(9.0, 0.0, 11.0, 15.0));
+        let mesh = NavMesh::build(Vec2::new(0.0, 0.0), Vec2::new(20.0, 20.0), flat, &[wall], 0.6);
+        let path = std::env::temp_dir().join(format!("navmesh_test_{}.json", std::process::id()));
+
+        mesh.save(&path, 42).unwrap();
+        let loaded = NavMesh::load_cached(&path, 42).unwrap();
+        assert_eq!(loaded.poly_count(), mesh.poly_count());
+        assert!(!loaded.is_walkable(Vec2::new(10.0, 5.0)));
+        assert!(NavMesh::load_cached(&path, 43).is_err());
+        std::fs::remove_file(path).ok();
+    }
+}
@@ -0,0 +1,78 @@
+//! Local avoidance between moving enemies.
+//!
+//! Steering and the nav-mesh only know about static obstacles, so enemies
+//! heading for the same spot would end up on top of each other. Every tick each
+//! enemy is pushed out of the ones it overlaps, the smaller body giving way more.
+
+use super::{resolve_collision, Obstacle, Vec2};
+
+/// Fraction of an overlap resolved per tick (crowds settle instead of jittering)
+const SEPARATION_STIFFNESS: f32 = 0.5;
+
+/// Another body to keep clear of
+#[derive(Debug, Clone, Copy)]
+pub struct Body {
+    pub id: u64,
+    pub position: Vec2,
+    pub radius: f32,
+}
+
+/// Where a body ends up after being pushed out of the others it overlaps
+/// The push is capped at `max_step` and never ends inside an obstacle
+pub fn separate(body: Body, others: &[Body], obstacles: &[Obstacle], max_step: f32) -> Vec2 {
+    let mut push = Vec2::new(0.0, 0.0);
+    for other in others.iter().filter(|other| other.id != body.id) {
+        let offset = body.position - other.position;
+        let distance = offset.length();
+        let overlap = body.radius + other.radius - distance;
+        if overlap <= 0.0 {
+            continue;
+        }
+        // Standing exactly on top of each other: split along x, by id so both don't go the same way
+        let direction = if distance > 0.0001 {
+            offset * (1.0 / distance)
+        } else if body.id < other.id {
+            Vec2::new(-1.0, 0.0)
+        } else {
+            Vec2::new(1.0, 0.0)
+        };
+        let share = other.radius / (body.radius + other.radius);
+        push = push + direction * (overlap * share * SEPARATION_STIFFNESS);
+    }
+
+    let length = push.length();
+    if length < 0.001 {
+        return body.position;
+    }
+    if length > max_step {
+        push = push * (max_step / length);
+    }
+    resolve_collision(body.position + push, body.radius, obstacles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stacked_bodies_spread_until_touching() {
+        let mut bodies: Vec<Body> = (0..4)
+            .map(|id| Body { id, position: Vec2::new(0.0, 0.0), radius: 0.6 })
+            .collect();
+        bodies[2].position = Vec2::new(0.1, 0.2);
+        bodies[3].position = Vec2::new(-0.2, 0.1);
+
+        for _ in 0..200 {
+            let snapshot = bodies.clone();
+            for body in &mut bodies {
+                body.position = separate(*body, &snapshot, &[], 0.3);
+            }
+        }
+
+        for a in &bodies {
+            for b in bodies.iter().filter(|b| b.id > a.id) {
+                assert!(a.position.distance_to(b.position) > 1.1, "{} and {} still overlap", a.id, b.id);
+            }
+        }
+    }
+}
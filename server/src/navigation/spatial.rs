@@ -0,0 +1,149 @@
+//! Uniform-grid spatial indexes.
+//!
+//! `SpatialGrid` buckets point entities (players, enemies) by cell and is rebuilt
+//! whenever they have moved. `ObstacleGrid` buckets a zone's static obstacles by
+//! every cell they overlap and is built once. Both answer "what is near here"
+//! without walking everything in the zone.
+
+use std::collections::HashMap;
+use super::{Obstacle, Vec2};
+
+/// Default cell size for entity and obstacle grids (units)
+pub const SPATIAL_CELL_SIZE: f32 = 16.0;
+
+fn cell_of(cell_size: f32, position: Vec2) -> (i32, i32) {
+    ((position.x / cell_size).floor() as i32, (position.z / cell_size).floor() as i32)
+}
+
+/// Cells overlapping the square around a circle
+fn cells_around(cell_size: f32, center: Vec2, radius: f32) -> impl Iterator<Item = (i32, i32)> {
+    let (min_x, min_z) = cell_of(cell_size, Vec2::new(center.x - radius, center.z - radius));
+    let (max_x, max_z) = cell_of(cell_size, Vec2::new(center.x + radius, center.z + radius));
+    (min_z..=max_z).flat_map(move |z| (min_x..=max_x).map(move |x| (x, z)))
+}
+
+/// Point entities bucketed by grid cell
+#[derive(Debug, Clone)]
+pub struct SpatialGrid<T> {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<(T, Vec2)>>,
+    len: usize,
+}
+
+impl<T: Copy> SpatialGrid<T> {
+    pub fn new(cell_size: f32) -> Self {
+        Self { cell_size, cells: HashMap::new(), len: 0 }
+    }
+
+    /// Build a grid from items and their positions
+    pub fn from_items(cell_size: f32, items: impl IntoIterator<Item = (T, Vec2)>) -> Self {
+        let mut grid = Self::new(cell_size);
+        for (item, position) in items {
+            grid.insert(item, position);
+        }
+        grid
+    }
+
+    pub fn insert(&mut self, item: T, position: Vec2) {
+        self.cells.entry(cell_of(self.cell_size, position)).or_default().push((item, position));
+        self.len += 1;
+    }
+
+    /// Number of items in the grid
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Items within `radius` of `center`
+    pub fn query(&self, center: Vec2, radius: f32) -> impl Iterator<Item = T> + '_ {
+        cells_around(self.cell_size, center, radius)
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .filter(move |(_, position)| position.distance_to(center) <= radius)
+            .map(|(item, _)| *item)
+    }
+}
+
+/// Static obstacles bucketed by every cell they overlap
+#[derive(Debug, Clone)]
+pub struct ObstacleGrid {
+    cell_size: f32,
+    /// Indices into the zone's obstacle list
+    cells: HashMap<(i32, i32), Vec<u32>>,
+}
+
+impl ObstacleGrid {
+    pub fn new(obstacles: &[Obstacle], cell_size: f32) -> Self {
+        let mut cells: HashMap<(i32, i32), Vec<u32>> = HashMap::new();
+        for (index, obstacle) in obstacles.iter().enumerate() {
+            let (center, half_extent) = match obstacle {
+                Obstacle::Circle(c) => (c.center, Vec2::new(c.radius, c.radius)),
+                Obstacle::Box(b) => (b.center(), (b.max - b.min) * 0.5),
+            };
+            let (min_x, min_z) = cell_of(cell_size, center - half_extent);
+            let (max_x, max_z) = cell_of(cell_size, center + half_extent);
+            for z in min_z..=max_z {
+                for x in min_x..=max_x {
+                    cells.entry((x, z)).or_default().push(index as u32);
+                }
+            }
+        }
+        Self { cell_size, cells }
+    }
+
+    /// Copy the obstacles that may come within `radius` of `center` into `out`
+    pub fn query(&self, obstacles: &[Obstacle], center: Vec2, radius: f32, out: &mut Vec<Obstacle>) {
+        let mut indices: Vec<u32> = cells_around(self.cell_size, center, radius)
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+        out.extend(indices.into_iter().map(|index| obstacles[index as usize].clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::navigation::{BoxObstacle, CircleObstacle};
+
+    #[test]
+    fn test_grid_queries_match_a_linear_scan() {
+        let points: Vec<(u64, Vec2)> = (0..400)
+            .map(|i| (i, Vec2::new((i % 20) as f32 * 7.3 - 60.0, (i / 20) as f32 * 5.1 - 40.0)))
+            .collect();
+        let grid = SpatialGrid::from_items(SPATIAL_CELL_SIZE, points.iter().copied());
+        assert_eq!(grid.len(), 400);
+
+        for (center, radius) in [(Vec2::new(0.0, 0.0), 10.0), (Vec2::new(-55.0, 30.0), 25.0), (Vec2::new(500.0, 0.0), 5.0)] {
+            let mut found: Vec<u64> = grid.query(center, radius).collect();
+            let mut expected: Vec<u64> = points.iter()
+                .filter(|(_, p)| p.distance_to(center) <= radius)
+                .map(|(id, _)| *id)
+                .collect();
+            found.sort_unstable();
+            expected.sort_unstable();
+            assert_eq!(found, expected);
+        }
+    }
+
+    #[test]
+    fn test_obstacles_spanning_cells_are_found_once() {
+        let obstacles = vec![
+            Obstacle::Box(BoxObstacle::from_corners(-40.0, -2.0, 40.0, 2.0)),
+            Obstacle::Circle(CircleObstacle::new(100.0, 100.0, 1.0)),
+        ];
+        let grid = ObstacleGrid::new(&obstacles, SPATIAL_CELL_SIZE);
+
+        let mut near = Vec::new();
+        grid.query(&obstacles, Vec2::new(0.0, 5.0), 20.0, &mut near);
+        assert_eq!(near.len(), 1);
+        assert!(matches!(near[0], Obstacle::Box(_)));
+    }
+}
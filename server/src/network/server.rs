@@ -4,16 +4,18 @@ use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::net::UdpSocket;
+use tokio::sync::mpsc;
 use log::{info, warn, error};
 
 use mmo_shared::{
     ClientMessage, ServerMessage, PlayerState, EnemyState, NpcState,
     AnimationState, InventorySlot, CharacterClass, Gender, Empire,
-    CharacterInfo, PROTOCOL_VERSION,
+    CharacterInfo, Specialization, Attributes, PrimaryStat, CombatLogEntry, RespawnType,
+    MAX_DURABILITY, PROTOCOL_VERSION,
 };
 
 use crate::world::GameWorld;
-use crate::persistence::{PersistenceHandle, Database, PlayerStateData, InventorySlotData, CharacterEquipment};
+use crate::persistence::{PersistenceHandle, Database, PlayerStateData, InventorySlotData, CharacterEquipment, CombatLogRecord};
 
 /// Maximum packet size
 const MAX_PACKET_SIZE: usize = 1200;
@@ -21,6 +23,22 @@ const MAX_PACKET_SIZE: usize = 1200;
 /// Connection timeout in seconds
 const CONNECTION_TIMEOUT: f32 = 30.0;
 
+/// Maximum number of combat history events returned by /combathistory
+const COMBAT_HISTORY_LIMIT: u32 = 50;
+
+/// Players and enemies further away than this are left out of a client's WorldState (units)
+/// Clients are sent a spawn when something comes into range and a despawn when it leaves
+const INTEREST_RADIUS: f32 = 150.0;
+
+/// Entities in a client's interest radius this tick
+#[derive(Default)]
+struct Interest {
+    /// Spawns for what came into range, despawns for what left it
+    messages: Vec<ServerMessage>,
+    players: std::collections::HashSet<u64>,
+    enemies: std::collections::HashSet<u64>,
+}
+
 /// Connection state - tracks whether client is in character select or in game
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ConnectionState {
@@ -53,6 +71,12 @@ pub struct ClientConnection {
     pub last_zone_id: Option<u32>,
     /// Set of NPC IDs the client already knows about (to avoid resending static NPCs)
     pub known_npcs: std::collections::HashSet<u64>,
+    /// Other players the client was sent a spawn for (within INTEREST_RADIUS)
+    pub visible_players: std::collections::HashSet<u64>,
+    /// Enemies the client was sent a spawn for (within INTEREST_RADIUS)
+    pub visible_enemies: std::collections::HashSet<u64>,
+    /// Whether combat log events involving this player are streamed to the client
+    pub combat_log_streaming: bool,
 }
 
 impl ClientConnection {
@@ -68,6 +92,9 @@ impl ClientConnection {
             is_admin,
             last_zone_id: None,
             known_npcs: std::collections::HashSet::new(),
+            visible_players: std::collections::HashSet::new(),
+            visible_enemies: std::collections::HashSet::new(),
+            combat_log_streaming: false,
         }
     }
     
@@ -76,6 +103,14 @@ impl ClientConnection {
         self.known_npcs.clear();
     }
     
+    /// Forget everything the client was sent (entering the game with a fresh scene)
+    pub fn reset_interest(&mut self) {
+        self.last_zone_id = None;
+        self.known_npcs.clear();
+        self.visible_players.clear();
+        self.visible_enemies.clear();
+    }
+    
     pub fn is_timed_out(&self) -> bool {
         self.last_seen.elapsed().as_secs_f32() > CONNECTION_TIMEOUT
     }
@@ -106,6 +141,9 @@ pub struct Server {
     persistence: Option<PersistenceHandle>,
     /// Database for auth (separate from persistence handle for sync operations)
     database: Option<Database>,
+    /// Responses from slow lookups running outside the game tick (e.g. /combathistory)
+    command_response_tx: mpsc::UnboundedSender<(SocketAddr, ServerMessage)>,
+    command_response_rx: mpsc::UnboundedReceiver<(SocketAddr, ServerMessage)>,
 }
 
 impl Server {
@@ -128,6 +166,8 @@ impl Server {
             None
         };
         
+        let (command_response_tx, command_response_rx) = mpsc::unbounded_channel();
+        
         Ok(Self {
             socket: Arc::new(socket),
             clients: HashMap::new(),
@@ -136,6 +176,8 @@ impl Server {
             broadcast_queue: Vec::new(),
             persistence,
             database,
+            command_response_tx,
+            command_response_rx,
         })
     }
     
@@ -159,6 +201,13 @@ impl Server {
             }
         }
         
+        // Deliver finished background lookups to clients that are still connected
+        while let Ok((addr, msg)) = self.command_response_rx.try_recv() {
+            if let Some(client) = self.clients.get_mut(&addr) {
+                client.outgoing_queue.push(msg);
+            }
+        }
+        
         // Check for timed out clients
         self.check_timeouts(world);
     }
@@ -204,11 +253,14 @@ impl Server {
                 self.handle_player_update(addr, position, rotation, velocity, animation_state, world);
             }
             ClientMessage::ChatMessage { content } => {
-                self.handle_chat(addr, content, world);
+                self.handle_chat(addr, content, world).await;
             }
             ClientMessage::Attack { target_id } => {
                 self.handle_attack(addr, target_id, world);
             }
+            ClientMessage::StopAttack => {
+                self.handle_stop_attack(addr, world);
+            }
             ClientMessage::PickupItem { item_entity_id } => {
                 self.handle_pickup(addr, item_entity_id, world);
             }
@@ -221,6 +273,9 @@ impl Server {
             ClientMessage::RespawnRequest { respawn_type } => {
                 self.handle_respawn(addr, respawn_type, world).await;
             }
+            ClientMessage::RepairEquipment => {
+                self.handle_repair_equipment(addr, world);
+            }
             ClientMessage::EquipItem { inventory_slot } => {
                 self.handle_equip_item(addr, inventory_slot, world).await;
             }
@@ -239,6 +294,27 @@ impl Server {
             ClientMessage::UseAbility { ability_id, target_id } => {
                 self.handle_use_ability(addr, ability_id, target_id, world);
             }
+            ClientMessage::CancelCast => {
+                self.handle_cancel_cast(addr, world);
+            }
+            ClientMessage::UpgradeSkill { ability_id } => {
+                self.handle_upgrade_skill(addr, ability_id, world).await;
+            }
+            ClientMessage::ChooseSpecialization { specialization } => {
+                self.handle_choose_specialization(addr, specialization, world).await;
+            }
+            ClientMessage::AllocateStat { stat, amount } => {
+                self.handle_allocate_stat(addr, stat, amount, world);
+            }
+            ClientMessage::ResetStats => {
+                self.handle_reset_stats(addr, world);
+            }
+            ClientMessage::SetCombatLogStreaming { enabled } => {
+                self.handle_set_combat_log_streaming(addr, enabled);
+            }
+            ClientMessage::SetResting { resting } => {
+                self.handle_set_resting(addr, resting, world);
+            }
         }
     }
     
@@ -523,18 +599,19 @@ impl Server {
             }
         };
         
-        // Load character state, inventory, and equipment
-        let (player_state, inventory_data, equipment) = match (
+        // Load character state, inventory, equipment, and skills
+        let (player_state, inventory_data, equipment, skill_ranks) = match (
             db.load_character_state(character_id as i64).await,
             db.load_character_inventory(character_id as i64).await,
             db.load_character_equipment(character_id as i64).await,
+            db.load_character_skills(character_id as i64).await,
         ) {
-            (Ok(Some(state)), Ok(inv), Ok(equip)) => (state, inv, equip),
-            (Ok(None), Ok(inv), Ok(equip)) => {
+            (Ok(Some(state)), Ok(inv), Ok(equip), Ok(skills)) => (state, inv, equip, skills),
+            (Ok(None), Ok(inv), Ok(equip), Ok(skills)) => {
                 // No state yet - use default for class
-                (PlayerStateData::new_for_class(character.class, character.empire), inv, equip)
+                (PlayerStateData::new_for_class(character.class, character.empire), inv, equip, skills)
             }
-            (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+            (Err(e), _, _, _) | (_, Err(e), _, _) | (_, _, Err(e), _) | (_, _, _, Err(e)) => {
                 error!("Failed to load character state for {}: {}", character_id, e);
                 let msg = ServerMessage::CharacterSelectFailed {
                     reason: "Failed to load character state".to_string(),
@@ -553,6 +630,7 @@ impl Server {
                 gender: character.gender,
                 empire: character.empire,
             };
+            client.reset_interest();
         }
         
         // Determine zone_id - use saved zone or fallback to empire default
@@ -562,11 +640,12 @@ impl Server {
             world.zone_manager.get_default_zone_for_empire(character.empire)
         };
         
-        // If player has 0 health (died and logged out), respawn them at zone spawn with 20% HP
-        // Also reset players who fell through the world (Y < -50)
+        // If player has 0 health (died and logged out), respawn them at zone spawn with the
+        // death spot health fraction. Also reset players who fell through the world (Y < -50)
         let (spawn_position, spawn_health) = if player_state.health <= 0 {
             let zone_spawn = world.zone_manager.get_default_spawn_point(zone_id);
-            let respawn_health = (player_state.max_health as f32 * 0.2).max(1.0) as i32;
+            let health_percent = world.death_penalty_config().death_spot_health_percent;
+            let respawn_health = (player_state.max_health as f32 * health_percent).max(1.0) as i32;
             info!("Character '{}' was dead, respawning at zone {} spawn with {} HP", character.name, zone_id, respawn_health);
             (zone_spawn, respawn_health)
         } else if player_state.position_y < -50.0 {
@@ -603,6 +682,32 @@ impl Server {
             player_state.gold as u64,
         );
         
+        // Apply specialization (also fills its action bar slot) and attributes
+        if let Some(player) = world.get_player_mut(player_id) {
+            player.set_specialization(character.specialization);
+            // States saved before attributes existed start at the class values
+            if player_state.vitality > 0 {
+                player.attributes = Attributes {
+                    vitality: player_state.vitality as u32,
+                    intelligence: player_state.intelligence as u32,
+                    strength: player_state.strength as u32,
+                    dexterity: player_state.dexterity as u32,
+                };
+            }
+            player.stat_points = player_state.stat_points.max(0) as u32;
+            player.equipment_durability = (player_state.equipment_durability.max(0) as u32).min(MAX_DURABILITY);
+            // The saved max health/attack/defense predate these, derive them again
+            player.apply_derived_stats();
+        }
+        
+        // Apply learned skills - new characters start with their class's first skills
+        let granted_skills = world.load_player_skills(player_id, skill_ranks, player_state.skill_points.max(0) as u32);
+        for (ability_id, rank) in granted_skills {
+            if let Err(e) = db.save_character_skill(character_id as i64, ability_id, rank).await {
+                error!("Failed to save skill {} for character {}: {}", ability_id, character_id, e);
+            }
+        }
+        
         info!("Character '{}' (ID: {}) entered game for account '{}'", 
               character.name, character_id, username);
         
@@ -619,6 +724,19 @@ impl Server {
         // Calculate XP to next level
         let experience_to_next_level = ServerPlayer::experience_to_next_level(player_state.level as u32);
         
+        // Stats as derived from level, specialization and attributes
+        let (health, max_health, mana, max_mana, attack, defense) = match world.get_player(player_id) {
+            Some(p) => (p.health, p.max_health, p.mana, p.max_mana, p.attack_power, p.defense),
+            None => (
+                spawn_health as u32,
+                player_state.max_health as u32,
+                player_state.mana as u32,
+                player_state.max_mana as u32,
+                player_state.attack as u32,
+                player_state.defense as u32,
+            ),
+        };
+        
         // Send character selected with full state
         let msg = ServerMessage::CharacterSelected {
             character_id,
@@ -629,20 +747,22 @@ impl Server {
             zone_id,
             position: spawn_position,
             rotation: player_state.rotation,
-            health: spawn_health as u32,
-            max_health: player_state.max_health as u32,
-            mana: player_state.mana as u32,
-            max_mana: player_state.max_mana as u32,
+            health,
+            max_health,
+            mana,
+            max_mana,
             level: player_state.level as u32,
             experience: player_state.experience as u32,
             experience_to_next_level,
-            attack: player_state.attack as u32,
-            defense: player_state.defense as u32,
+            attack,
+            defense,
             attack_speed,
             inventory: inventory_slots,
             equipped_weapon_id: equipment.weapon_id,
             equipped_armor_id: equipment.armor_id,
             gold: player_state.gold as u64,
+            specialization: character.specialization,
+            specialization_level: world.specialization_level(),
         };
         self.send_to(addr, &msg).await;
         
@@ -657,72 +777,43 @@ impl Server {
             self.send_to(addr, &zone_change_msg).await;
         }
         
+        // Send ability definitions so the client can show names, costs and tooltips
+        let abilities_msg = ServerMessage::AbilityDefinitions {
+            abilities: world.get_ability_definitions(),
+        };
+        self.send_to(addr, &abilities_msg).await;
+        
+        // Send attributes and unspent stat points
+        if let Some(attributes_msg) = world.get_player_attributes(player_id) {
+            self.send_to(addr, &attributes_msg).await;
+        }
+        
+        // Send gear durability and repair cost
+        if let Some(durability_msg) = world.get_player_durability(player_id) {
+            self.send_to(addr, &durability_msg).await;
+        }
+        
+        // Send learned skills and unspent skill points
+        if let Some((skills, skill_points)) = world.get_player_skills(player_id) {
+            let skill_list_msg = ServerMessage::SkillList { skills, skill_points };
+            self.send_to(addr, &skill_list_msg).await;
+        }
+        
         // Send action bar (abilities assigned to slots)
         if let Some(action_bar) = world.get_player_action_bar(player_id) {
             let action_bar_msg = ServerMessage::ActionBarUpdate { slots: action_bar };
             self.send_to(addr, &action_bar_msg).await;
         }
         
-        // Send time sync for day/night cycle (Berlin, Germany coordinates)
-        let time_sync_msg = ServerMessage::TimeSync {
-            unix_timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs() as i64,
-            latitude: 52.5,   // Berlin latitude
-            longitude: 13.4,  // Berlin longitude
-        };
+        // Send time sync for day/night cycle and weather
+        let time_sync_msg = world.time_sync();
         self.send_to(addr, &time_sync_msg).await;
         
-        // Notify other players in the SAME ZONE (only those in game)
-        let spawn_msg = ServerMessage::PlayerSpawn {
-            id: player_id,
-            name: character.name.clone(),
-            class: character.class,
-            gender: character.gender,
-            empire: character.empire,
-            zone_id,
-            position: spawn_position,
-            rotation: player_state.rotation,
-        };
-        self.broadcast_to_zone_except(addr, zone_id, spawn_msg, world);
-        
-        // Send existing players and enemies IN THE SAME ZONE to new client
+        // Players and enemies in range are spawned by broadcast_world_state
         let mut messages_for_new_client: Vec<ServerMessage> = Vec::new();
         
-        for (other_addr, other_client) in &self.clients {
-            if *other_addr != addr {
-                if let ConnectionState::InGame { character_name, class, gender, empire, .. } = &other_client.state {
-                    if let Some(player) = world.get_player(other_client.player_id) {
-                        // Only send players in the same zone
-                        if player.zone_id == zone_id {
-                            messages_for_new_client.push(ServerMessage::PlayerSpawn {
-                                id: other_client.player_id,
-                                name: character_name.clone(),
-                                class: *class,
-                                gender: *gender,
-                                empire: *empire,
-                                zone_id: player.zone_id,
-                                position: player.position,
-                                rotation: player.rotation,
-                            });
-                        }
-                    }
-                }
-            }
-        }
-        
-        // Only send enemies in the same zone
-        for enemy in world.get_enemies_in_zone(zone_id) {
-            messages_for_new_client.push(ServerMessage::EnemySpawn {
-                id: enemy.id,
-                zone_id: enemy.zone_id,
-                enemy_type: enemy.enemy_type,
-                position: enemy.position,
-                health: enemy.health,
-                max_health: enemy.max_health,
-                level: enemy.level,
-            });
+        for stone in world.get_metin_stones_in_zone(zone_id) {
+            messages_for_new_client.push(stone.spawn_message());
         }
         
         if let Some(client) = self.clients.get_mut(&addr) {
@@ -824,16 +915,19 @@ impl Server {
         world: &mut GameWorld,
     ) {
         // Only process if client is in game
-        let client = match self.clients.get(&addr) {
+        let client = match self.clients.get_mut(&addr) {
             Some(c) if c.is_in_game() => c,
             _ => return,
         };
         
-        world.update_player_state(client.player_id, position, rotation, velocity, animation_state);
+        // Moves faster than the player's speed are corrected
+        if let Some(correction) = world.update_player_state(client.player_id, position, rotation, velocity, animation_state) {
+            client.outgoing_queue.push(correction);
+        }
     }
     
     /// Handle chat message
-    fn handle_chat(&mut self, addr: SocketAddr, content: String, world: &mut GameWorld) {
+    async fn handle_chat(&mut self, addr: SocketAddr, content: String, world: &mut GameWorld) {
         let (player_id, is_admin, sender_name) = {
             let connection = match self.clients.get(&addr) {
                 Some(c) => c,
@@ -881,6 +975,11 @@ impl Server {
                         client.outgoing_queue.push(teleport_msg);
                     }
                 }
+                
+                // Run combat history lookup if requested (answered once the query finishes)
+                if let Some(query) = result.combat_log_query {
+                    self.query_combat_history(addr, query);
+                }
             }
             return; // Don't broadcast commands to chat
         }
@@ -896,13 +995,33 @@ impl Server {
     
     /// Handle attack request
     fn handle_attack(&mut self, addr: SocketAddr, target_id: u64, world: &mut GameWorld) {
-        let client = match self.clients.get(&addr) {
-            Some(c) if c.is_in_game() => c,
+        let player_id = match self.clients.get(&addr) {
+            Some(c) if c.is_in_game() => c.player_id,
             _ => return,
         };
         
-        if let Some(damage_event) = world.process_attack(client.player_id, target_id) {
-            self.broadcast_to_ingame(damage_event);
+        let (attacker_msgs, broadcast_msgs) = world.start_auto_attack(player_id, target_id);
+        
+        if let Some(client) = self.clients.get_mut(&addr) {
+            client.outgoing_queue.extend(attacker_msgs);
+        }
+        
+        for msg in broadcast_msgs {
+            self.broadcast_to_ingame(msg);
+        }
+    }
+    
+    /// Handle stop attack request
+    fn handle_stop_attack(&mut self, addr: SocketAddr, world: &mut GameWorld) {
+        let player_id = match self.clients.get(&addr) {
+            Some(c) if c.is_in_game() => c.player_id,
+            _ => return,
+        };
+        
+        if let Some(msg) = world.stop_auto_attack(player_id) {
+            if let Some(client) = self.clients.get_mut(&addr) {
+                client.outgoing_queue.push(msg);
+            }
         }
     }
     
@@ -926,6 +1045,169 @@ impl Server {
         }
     }
     
+    /// Handle cast cancel request
+    fn handle_cancel_cast(&mut self, addr: SocketAddr, world: &mut GameWorld) {
+        let player_id = match self.clients.get(&addr) {
+            Some(c) if c.is_in_game() => c.player_id,
+            _ => return,
+        };
+        
+        if let Some(msg) = world.cancel_player_cast(player_id) {
+            self.broadcast_to_ingame(msg);
+        }
+    }
+    
+    /// Handle skill upgrade request
+    async fn handle_upgrade_skill(&mut self, addr: SocketAddr, ability_id: u32, world: &mut GameWorld) {
+        let (player_id, character_id) = match self.clients.get(&addr) {
+            Some(c) => {
+                if let ConnectionState::InGame { character_id, .. } = &c.state {
+                    (c.player_id, *character_id)
+                } else {
+                    return;
+                }
+            }
+            _ => return,
+        };
+        
+        let (messages, new_rank) = world.upgrade_player_skill(player_id, ability_id);
+        
+        // Save the new rank, and the spent points even if training failed
+        if messages.iter().any(|m| matches!(m, ServerMessage::SkillUpdate { .. })) {
+            if let (Some(db), Some(rank)) = (&self.database, new_rank) {
+                if let Err(e) = db.save_character_skill(character_id, ability_id, rank).await {
+                    error!("Failed to save skill {} for character {}: {}", ability_id, character_id, e);
+                }
+            }
+            if let (Some(persistence), Some(player)) = (&self.persistence, world.get_player(player_id)) {
+                let state = player_to_state_data(player);
+                let inventory = player_inventory_to_data(player);
+                persistence.save_character(character_id, state, inventory);
+            }
+        }
+        
+        if let Some(client) = self.clients.get_mut(&addr) {
+            client.outgoing_queue.extend(messages);
+        }
+    }
+    
+    /// Handle combat log streaming opt-in/opt-out
+    fn handle_set_combat_log_streaming(&mut self, addr: SocketAddr, enabled: bool) {
+        if let Some(client) = self.clients.get_mut(&addr) {
+            client.combat_log_streaming = enabled;
+            info!("Player {} {} combat log streaming", client.player_id, if enabled { "enabled" } else { "disabled" });
+        }
+    }
+    
+    /// Look up a character's combat history in the background
+    /// The scan can be slow, so the response is sent from process_incoming once it's done
+    fn query_combat_history(&mut self, addr: SocketAddr, query: crate::commands::CombatLogQuery) {
+        let db = match &self.database {
+            Some(db) => db.clone(),
+            None => {
+                if let Some(client) = self.clients.get_mut(&addr) {
+                    client.outgoing_queue.push(ServerMessage::CommandResponse {
+                        success: false,
+                        message: "Combat history not available (no database)".to_string(),
+                    });
+                }
+                return;
+            }
+        };
+        
+        let responses = self.command_response_tx.clone();
+        tokio::spawn(async move {
+            let response_msg = combat_history_response(&db, query).await;
+            let _ = responses.send((addr, response_msg));
+        });
+    }
+    /// Handle stat point allocation
+    fn handle_allocate_stat(&mut self, addr: SocketAddr, stat: PrimaryStat, amount: u32, world: &mut GameWorld) {
+        let player_id = match self.clients.get(&addr) {
+            Some(c) if c.is_in_game() => c.player_id,
+            _ => return,
+        };
+        
+        if let Some(msg) = world.allocate_player_stat(player_id, stat, amount) {
+            if let Some(client) = self.clients.get_mut(&addr) {
+                client.outgoing_queue.push(msg);
+            }
+        }
+    }
+    
+    /// Handle paid stat reset
+    fn handle_reset_stats(&mut self, addr: SocketAddr, world: &mut GameWorld) {
+        let player_id = match self.clients.get(&addr) {
+            Some(c) if c.is_in_game() => c.player_id,
+            _ => return,
+        };
+        
+        let messages = world.reset_player_stats(player_id);
+        if let Some(client) = self.clients.get_mut(&addr) {
+            client.outgoing_queue.extend(messages);
+        }
+    }
+    
+    /// Handle sitting down to rest or standing up
+    fn handle_set_resting(&mut self, addr: SocketAddr, resting: bool, world: &mut GameWorld) {
+        let player_id = match self.clients.get(&addr) {
+            Some(c) if c.is_in_game() => c.player_id,
+            _ => return,
+        };
+        
+        let msg = match world.set_player_resting(player_id, resting) {
+            Ok(update) => update,
+            Err(reason) => ServerMessage::CommandResponse {
+                success: false,
+                message: reason.to_string(),
+            },
+        };
+        if let Some(client) = self.clients.get_mut(&addr) {
+            client.outgoing_queue.push(msg);
+        }
+    }
+    
+    /// Handle specialization choice
+    async fn handle_choose_specialization(&mut self, addr: SocketAddr, specialization: Specialization, world: &mut GameWorld) {
+        let (player_id, character_id) = match self.clients.get(&addr) {
+            Some(c) => {
+                if let ConnectionState::InGame { character_id, .. } = &c.state {
+                    (c.player_id, *character_id)
+                } else {
+                    return;
+                }
+            }
+            _ => return,
+        };
+        
+        let msg = match world.choose_player_specialization(player_id, specialization) {
+            Some(msg) => msg,
+            None => return,
+        };
+        
+        let chosen = matches!(msg, ServerMessage::SpecializationChosen { .. });
+        let mut messages = vec![msg];
+        if chosen {
+            if let Some(ref db) = self.database {
+                if let Err(e) = db.save_character_specialization(character_id, specialization).await {
+                    error!("Failed to save specialization for character {}: {}", character_id, e);
+                }
+            }
+            // Stats changed with the new growth, save them right away
+            if let (Some(persistence), Some(player)) = (&self.persistence, world.get_player(player_id)) {
+                let state = player_to_state_data(player);
+                let inventory = player_inventory_to_data(player);
+                persistence.save_character(character_id, state, inventory);
+            }
+            if let Some(action_bar) = world.get_player_action_bar(player_id) {
+                messages.push(ServerMessage::ActionBarUpdate { slots: action_bar });
+            }
+        }
+        if let Some(client) = self.clients.get_mut(&addr) {
+            client.outgoing_queue.extend(messages);
+        }
+    }
+    
     /// Handle item pickup
     fn handle_pickup(&mut self, addr: SocketAddr, item_entity_id: u64, world: &mut GameWorld) {
         let player_id = match self.clients.get(&addr) {
@@ -949,9 +1231,9 @@ impl Server {
             _ => return,
         };
         
-        if let Some(inv_msg) = world.use_item(player_id, slot) {
+        if let Some(messages) = world.use_item(player_id, slot) {
             if let Some(client) = self.clients.get_mut(&addr) {
-                client.outgoing_queue.push(inv_msg);
+                client.outgoing_queue.extend(messages);
             }
         }
     }
@@ -971,59 +1253,38 @@ impl Server {
         }
     }
     
-    /// Handle respawn request
-    /// respawn_type: 0 = at empire spawn (full health), 1 = at death location (20% health)
-    async fn handle_respawn(&mut self, addr: SocketAddr, respawn_type: u8, world: &mut GameWorld) {
-        let (player_id, empire) = match self.clients.get(&addr) {
-            Some(c) => {
-                if let ConnectionState::InGame { empire, .. } = &c.state {
-                    (c.player_id, *empire)
-                } else {
-                    return;
-                }
-            }
+    /// Handle respawn request (release to empire spawn or death location)
+    async fn handle_respawn(&mut self, addr: SocketAddr, respawn_type: RespawnType, world: &mut GameWorld) {
+        let player_id = match self.clients.get(&addr) {
+            Some(c) if c.is_in_game() => c.player_id,
             _ => return,
         };
         
-        // Get player's current (death) position and max health
-        let (death_position, max_health) = match world.get_player(player_id) {
-            Some(p) => (p.position, p.max_health),
-            None => return,
-        };
-        
-        // Calculate respawn position and health based on respawn type
-        let (respawn_position, respawn_health) = if respawn_type == 0 {
-            // Respawn at empire spawn with full health
-            (empire.spawn_position(), max_health)
-        } else {
-            // Respawn at death location with 20% health
-            let health_20_percent = (max_health as f32 * 0.2).max(1.0) as u32;
-            (death_position, health_20_percent)
+        match world.release_player(player_id, respawn_type) {
+            Ok((messages, broadcast_msg)) => {
+                if let Some(client) = self.clients.get_mut(&addr) {
+                    client.outgoing_queue.extend(messages);
+                }
+                // Broadcast entity respawn to other players
+                self.broadcast_to_ingame_except(addr, broadcast_msg);
+            }
+            Err(reason) => {
+                warn!("Player {} respawn ({}) rejected: {}", player_id, respawn_type.name(), reason);
+            }
+        }
+    }
+    
+    /// Handle gear repair request
+    fn handle_repair_equipment(&mut self, addr: SocketAddr, world: &mut GameWorld) {
+        let player_id = match self.clients.get(&addr) {
+            Some(c) if c.is_in_game() => c.player_id,
+            _ => return,
         };
         
-        // Respawn the player in the world
-        world.respawn_player(player_id, respawn_position, respawn_health);
-        
-        info!("Player {} respawned (type: {}) at {:?} with {} health", 
-              player_id, respawn_type, respawn_position, respawn_health);
-        
-        // Send respawn response to the player
-        let respawn_msg = ServerMessage::PlayerRespawned {
-            position: respawn_position,
-            health: respawn_health,
-            max_health,
-        };
+        let messages = world.repair_player_equipment(player_id);
         if let Some(client) = self.clients.get_mut(&addr) {
-            client.outgoing_queue.push(respawn_msg);
+            client.outgoing_queue.extend(messages);
         }
-        
-        // Broadcast entity respawn to other players
-        let broadcast_msg = ServerMessage::EntityRespawn {
-            entity_id: player_id,
-            position: respawn_position,
-            health: respawn_health,
-        };
-        self.broadcast_to_ingame_except(addr, broadcast_msg);
     }
     
     /// Handle equip item request
@@ -1275,53 +1536,13 @@ impl Server {
             client.outgoing_queue.push(zone_change_msg);
         }
         
-        // Broadcast PlayerSpawn to NEW zone players (get fresh player data)
-        if let Some(player) = world.get_player(player_id) {
-            let spawn_msg = ServerMessage::PlayerSpawn {
-                id: player_id,
-                name: player.name.clone(),
-                class: player.class,
-                gender: player.gender,
-                empire: player.empire,
-                position: spawn_position,
-                rotation: player.rotation,
-                zone_id,
-            };
-            self.broadcast_to_zone_except(addr, zone_id, spawn_msg, world);
-            
-            // Send existing players in new zone to the teleporting player
-            for other_player in world.get_players_in_zone(zone_id) {
-                if other_player.id != player_id {
-                    let other_spawn_msg = ServerMessage::PlayerSpawn {
-                        id: other_player.id,
-                        name: other_player.name.clone(),
-                        class: other_player.class,
-                        gender: other_player.gender,
-                        empire: other_player.empire,
-                        position: other_player.position,
-                        rotation: other_player.rotation,
-                        zone_id: other_player.zone_id,
-                    };
-                    if let Some(client) = self.clients.get_mut(&addr) {
-                        client.outgoing_queue.push(other_spawn_msg);
-                    }
-                }
-            }
-            
-            // Send existing enemies in new zone to the teleporting player
-            for enemy in world.get_enemies_in_zone(zone_id) {
-                let enemy_spawn_msg = ServerMessage::EnemySpawn {
-                    id: enemy.id,
-                    enemy_type: enemy.enemy_type,
-                    position: enemy.position,
-                    health: enemy.health as u32,
-                    max_health: enemy.max_health as u32,
-                    level: enemy.level,
-                    zone_id: enemy.zone_id,
-                };
-                if let Some(client) = self.clients.get_mut(&addr) {
-                    client.outgoing_queue.push(enemy_spawn_msg);
-                }
+        // Players and enemies in range are spawned by broadcast_world_state
+        
+        // Send Metin stones in new zone to the teleporting player
+        for stone in world.get_metin_stones_in_zone(zone_id) {
+            let stone_spawn_msg = stone.spawn_message();
+            if let Some(client) = self.clients.get_mut(&addr) {
+                client.outgoing_queue.push(stone_spawn_msg);
             }
         }
         
@@ -1370,6 +1591,9 @@ impl Server {
                 is_admin: c.is_admin,
                 last_zone_id: c.last_zone_id,
                 known_npcs: c.known_npcs.clone(),
+                visible_players: c.visible_players.clone(),
+                visible_enemies: c.visible_enemies.clone(),
+                combat_log_streaming: c.combat_log_streaming,
             }))
             .collect();
         
@@ -1400,15 +1624,8 @@ impl Server {
     
     /// Broadcast time sync to all connected in-game clients
     /// Called periodically (every 60 seconds) to keep client time synchronized
-    pub async fn broadcast_time_sync(&self) {
-        let time_sync_msg = ServerMessage::TimeSync {
-            unix_timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs() as i64,
-            latitude: 52.5,   // Berlin latitude
-            longitude: 13.4,  // Berlin longitude
-        };
+    pub async fn broadcast_time_sync(&self, world: &GameWorld) {
+        let time_sync_msg = world.time_sync();
         
         let data = time_sync_msg.serialize();
         
@@ -1424,10 +1641,10 @@ impl Server {
     
     /// Broadcast world state to all connected clients (zone-filtered)
     /// Each client only receives players and enemies in their current zone
-    /// NPCs are only sent once per zone (they're static)
+    /// Standing NPCs are only sent once per zone, NPCs on a schedule every tick
     pub async fn broadcast_world_state(&mut self, world: &GameWorld, tick: u64) {
         // Collect data for each client first to avoid borrow issues
-        let mut client_updates: Vec<(SocketAddr, ServerMessage, Vec<u64>)> = Vec::new();
+        let mut client_updates: Vec<(SocketAddr, ServerMessage, Vec<u64>, Interest)> = Vec::new();
         
         for (addr, client) in &self.clients {
             // Only send to in-game clients
@@ -1435,17 +1652,48 @@ impl Server {
                 continue;
             }
             
-            // Get the player's current zone
-            let player_zone_id = match world.get_player(client.player_id) {
-                Some(p) => p.zone_id,
+            // Get the player's current zone and position
+            let (player_zone_id, player_position) = match world.get_player(client.player_id) {
+                Some(p) => (p.zone_id, p.position),
                 None => continue, // Player not in world, skip
             };
             
             // Check if zone changed - if so, we need to send NPCs
             let zone_changed = client.last_zone_id != Some(player_zone_id);
             
-            // Get players in the same zone
-            let players: Vec<PlayerState> = world.get_players_in_zone(player_zone_id)
+            // Get players within view, always including the client's own player
+            let mut nearby_players = world.get_players_near(player_zone_id, player_position, INTEREST_RADIUS);
+            if !nearby_players.iter().any(|p| p.id == client.player_id) {
+                nearby_players.extend(world.get_player(client.player_id));
+            }
+            
+            // Spawn what came into range and despawn what left it (the client
+            // cleared everything itself on a zone change)
+            let (seen_players, seen_enemies) = if zone_changed {
+                (Default::default(), Default::default())
+            } else {
+                (client.visible_players.clone(), client.visible_enemies.clone())
+            };
+            let mut interest = Interest::default();
+            for p in nearby_players.iter().filter(|p| p.id != client.player_id) {
+                interest.players.insert(p.id);
+                if !seen_players.contains(&p.id) {
+                    interest.messages.push(ServerMessage::PlayerSpawn {
+                        id: p.id,
+                        name: p.name.clone(),
+                        class: p.class,
+                        gender: p.gender,
+                        empire: p.empire,
+                        zone_id: p.zone_id,
+                        position: p.position,
+                        rotation: p.rotation,
+                    });
+                }
+            }
+            interest.messages.extend(seen_players.difference(&interest.players)
+                .map(|id| ServerMessage::PlayerDespawn { id: *id }));
+            
+            let players: Vec<PlayerState> = nearby_players
                 .iter()
                 .map(|p| PlayerState {
                     id: p.id,
@@ -1461,8 +1709,27 @@ impl Server {
                 })
                 .collect();
             
-            // Get enemies in the same zone
-            let enemies: Vec<EnemyState> = world.get_enemies_in_zone(player_zone_id)
+            // Get enemies within view
+            let nearby_enemies = world.get_enemies_near(player_zone_id, player_position, INTEREST_RADIUS);
+            for e in &nearby_enemies {
+                interest.enemies.insert(e.id);
+                if !seen_enemies.contains(&e.id) {
+                    interest.messages.push(ServerMessage::EnemySpawn {
+                        id: e.id,
+                        zone_id: e.zone_id,
+                        enemy_type: e.enemy_type,
+                        name: e.name().to_string(),
+                        position: e.position,
+                        health: e.health,
+                        max_health: e.max_health,
+                        level: e.level,
+                    });
+                }
+            }
+            interest.messages.extend(seen_enemies.difference(&interest.enemies)
+                .map(|id| ServerMessage::EnemyDespawn { id: *id }));
+            
+            let enemies: Vec<EnemyState> = nearby_enemies
                 .iter()
                 .map(|e| EnemyState {
                     id: e.id,
@@ -1478,8 +1745,8 @@ impl Server {
                 })
                 .collect();
             
-            // Only include NPCs that the client doesn't know about yet
-            // (NPCs are static, so we only need to send them once per zone)
+            // Only include NPCs that the client doesn't know about yet, plus
+            // NPCs walking a schedule (standing NPCs only need sending once per zone)
             let mut new_npc_ids: Vec<u64> = Vec::new();
             let npcs: Vec<NpcState> = if zone_changed {
                 // Zone changed - send all NPCs in new zone
@@ -1498,12 +1765,14 @@ impl Server {
                     })
                     .collect()
             } else {
-                // Same zone - only send NPCs client doesn't know about
+                // Same zone - only send new or moving NPCs
                 world.get_npcs_in_zone(player_zone_id)
                     .iter()
-                    .filter(|n| !client.known_npcs.contains(&n.id))
+                    .filter(|n| n.is_roaming() || !client.known_npcs.contains(&n.id))
                     .map(|n| {
-                        new_npc_ids.push(n.id);
+                        if !client.known_npcs.contains(&n.id) {
+                            new_npc_ids.push(n.id);
+                        }
                         NpcState {
                             id: n.id,
                             zone_id: n.zone_id,
@@ -1517,8 +1786,8 @@ impl Server {
             };
             
             // Log when sending NPCs to a new zone
-            if !npcs.is_empty() {
-                info!("Sending {} NPCs to player {} in zone {}", npcs.len(), client.player_id, player_zone_id);
+            if !new_npc_ids.is_empty() {
+                info!("Sending {} NPCs to player {} in zone {}", new_npc_ids.len(), client.player_id, player_zone_id);
             }
             
             let msg = ServerMessage::WorldState {
@@ -1528,11 +1797,11 @@ impl Server {
                 npcs,
             };
             
-            client_updates.push((*addr, msg, new_npc_ids));
+            client_updates.push((*addr, msg, new_npc_ids, interest));
         }
         
         // Now send messages and update client state
-        for (addr, msg, new_npc_ids) in client_updates {
+        for (addr, msg, new_npc_ids, interest) in client_updates {
             let data = msg.serialize();
             
             if let Err(e) = self.socket.send_to(&data, &addr).await {
@@ -1554,6 +1823,11 @@ impl Server {
                 for npc_id in new_npc_ids {
                     client.known_npcs.insert(npc_id);
                 }
+                
+                // Spawns and despawns go out after the zone change queued before them
+                client.outgoing_queue.extend(interest.messages);
+                client.visible_players = interest.players;
+                client.visible_enemies = interest.enemies;
             }
         }
     }
@@ -1635,9 +1909,22 @@ impl Server {
     }
     
     /// Queue messages to broadcast to all clients (in game only)
+    /// Enemy spawns are left to broadcast_world_state, which sends them to clients in range
     pub fn queue_broadcasts(&mut self, messages: Vec<ServerMessage>) {
         for msg in messages {
-            self.broadcast_to_ingame(msg);
+            if !matches!(msg, ServerMessage::EnemySpawn { .. }) {
+                self.broadcast_to_ingame(msg);
+            }
+        }
+    }
+    
+    /// Queue messages for everyone in their zone (zone_id, message)
+    /// Enemy spawns are left to broadcast_world_state, which sends them to clients in range
+    pub fn queue_zone_broadcasts(&mut self, messages: Vec<(u32, ServerMessage)>, world: &GameWorld) {
+        for (zone_id, msg) in messages {
+            if !matches!(msg, ServerMessage::EnemySpawn { .. }) {
+                self.broadcast_to_zone(zone_id, msg, world);
+            }
         }
     }
     
@@ -1652,11 +1939,57 @@ impl Server {
         }
     }
     
-    /// Queue player-specific messages from ability updates
-    pub fn queue_player_ability_updates(&mut self, updates: Vec<(u64, Vec<ServerMessage>)>) {
+    /// Queue player-specific and broadcast messages from ability updates
+    pub fn queue_player_ability_updates(&mut self, updates: Vec<(u64, Vec<ServerMessage>)>, broadcasts: Vec<ServerMessage>) {
         for (player_id, messages) in updates {
             self.queue_messages_for_player(player_id, messages);
         }
+        self.queue_broadcasts(broadcasts);
+    }
+    
+    /// Stream combat log events to opted-in players and append them to the combat history
+    pub fn dispatch_combat_log(&mut self, entries: Vec<CombatLogEntry>) {
+        // Runtime player ID -> character ID, for events involving players
+        let character_ids: HashMap<u64, i64> = self.clients
+            .values()
+            .filter_map(|c| Some((c.player_id, c.character_id()?)))
+            .collect();
+        let character_id_of = |participant: Option<&mmo_shared::CombatParticipant>| {
+            participant
+                .filter(|p| p.is_player)
+                .and_then(|p| character_ids.get(&p.id).copied())
+        };
+        
+        let mut records = Vec::new();
+        for entry in entries {
+            let source_character_id = character_id_of(entry.source.as_ref());
+            let target_character_id = character_id_of(Some(&entry.target));
+            
+            for client in self.clients.values_mut() {
+                if !client.combat_log_streaming || !client.is_in_game() {
+                    continue;
+                }
+                let is_source = entry.source.as_ref().is_some_and(|s| s.is_player && s.id == client.player_id);
+                let is_target = entry.target.is_player && entry.target.id == client.player_id;
+                if is_source || is_target {
+                    client.outgoing_queue.push(ServerMessage::CombatLogEvent { entry: entry.clone() });
+                }
+            }
+            
+            if source_character_id.is_some() || target_character_id.is_some() {
+                records.push(CombatLogRecord {
+                    entry,
+                    source_character_id,
+                    target_character_id,
+                });
+            }
+        }
+        
+        if let Some(persistence) = &self.persistence {
+            if !records.is_empty() {
+                persistence.record_combat_log(records);
+            }
+        }
     }
     
     /// Save all connected players that are in game (called periodically)
@@ -1677,6 +2010,40 @@ impl Server {
 // Helper functions for data conversion
 // =============================================================================
 
+/// Look up a character's combat history and format it as a command response
+async fn combat_history_response(db: &Database, query: crate::commands::CombatLogQuery) -> ServerMessage {
+    match db.query_combat_log(&query.character_name, query.from_ms, query.to_ms, COMBAT_HISTORY_LIMIT).await {
+        Ok(entries) if entries.is_empty() => ServerMessage::CommandResponse {
+            success: true,
+            message: format!("No combat events for '{}' in that time range", query.character_name),
+        },
+        Ok(entries) => {
+            let mut message = format!(
+                "Combat history of '{}' ({} events, oldest first):",
+                query.character_name,
+                entries.len()
+            );
+            for entry in entries.iter().rev() {
+                message.push_str(&format!("\n[{}] {}", format_utc_time(entry.timestamp_ms), entry.describe()));
+            }
+            ServerMessage::CommandResponse { success: true, message }
+        }
+        Err(e) => {
+            error!("Failed to query combat history for '{}': {}", query.character_name, e);
+            ServerMessage::CommandResponse {
+                success: false,
+                message: "Failed to query combat history".to_string(),
+            }
+        }
+    }
+}
+
+/// Format a unix timestamp (ms) as HH:MM:SS UTC
+fn format_utc_time(timestamp_ms: u64) -> String {
+    let secs_of_day = (timestamp_ms / 1000) % 86400;
+    format!("{:02}:{:02}:{:02}", secs_of_day / 3600, (secs_of_day / 60) % 60, secs_of_day % 60)
+}
+
 use crate::entities::ServerPlayer;
 
 fn player_to_state_data(player: &ServerPlayer) -> PlayerStateData {
@@ -1695,6 +2062,13 @@ fn player_to_state_data(player: &ServerPlayer) -> PlayerStateData {
         attack: player.attack_power as i32,
         defense: player.defense as i32,
         gold: player.gold as i64,
+        skill_points: player.skill_points as i32,
+        vitality: player.attributes.vitality as i32,
+        intelligence: player.attributes.intelligence as i32,
+        strength: player.attributes.strength as i32,
+        dexterity: player.attributes.dexterity as i32,
+        stat_points: player.stat_points as i32,
+        equipment_durability: player.equipment_durability as i32,
     }
 }
 
@@ -6,7 +6,7 @@ use argon2::{
     Argon2,
 };
 use serde::{Deserialize, Serialize};
-use mmo_shared::{CharacterClass, Gender, Empire, CharacterInfo, MAX_CHARACTERS_PER_ACCOUNT, ItemDef, ItemType, ItemRarity, ItemEffect, WeaponStats, WeaponVisualType, ArmorStats, get_starter_armor_id};
+use mmo_shared::{CharacterClass, Gender, Empire, CharacterInfo, MAX_CHARACTERS_PER_ACCOUNT, ItemDef, ItemType, ItemRarity, ItemEffect, WeaponStats, WeaponVisualType, ArmorStats, get_starter_armor_id, AbilityDef, AbilityEffect, TargetType, Specialization, Attributes, CombatLogEntry, CombatLogKind, CombatParticipant, DamageBreakdown, HitOutcome, MAX_DURABILITY};
 use std::collections::HashMap;
 
 /// Player account data from the database
@@ -17,6 +17,14 @@ pub struct PlayerData {
     pub password_hash: String,
 }
 
+/// A combat log event with the characters it involves (for the combat history)
+#[derive(Debug, Clone)]
+pub struct CombatLogRecord {
+    pub entry: CombatLogEntry,
+    pub source_character_id: Option<i64>,
+    pub target_character_id: Option<i64>,
+}
+
 /// Character data from the database (full info)
 #[derive(Debug, Clone)]
 pub struct CharacterData {
@@ -27,6 +35,7 @@ pub struct CharacterData {
     pub gender: Gender,
     pub empire: Empire,
     pub level: i32,
+    pub specialization: Option<Specialization>,
 }
 
 /// Player state data for persistence (now per-character)
@@ -46,6 +55,24 @@ pub struct PlayerStateData {
     pub attack: i32,
     pub defense: i32,
     pub gold: i64,
+    #[serde(default)]
+    pub skill_points: i32,
+    #[serde(default)]
+    pub vitality: i32,
+    #[serde(default)]
+    pub intelligence: i32,
+    #[serde(default)]
+    pub strength: i32,
+    #[serde(default)]
+    pub dexterity: i32,
+    #[serde(default)]
+    pub stat_points: i32,
+    #[serde(default = "default_equipment_durability")]
+    pub equipment_durability: i32,
+}
+
+fn default_equipment_durability() -> i32 {
+    MAX_DURABILITY as i32
 }
 
 impl PlayerStateData {
@@ -59,6 +86,7 @@ impl PlayerStateData {
         };
         let spawn = [0.0, 1.0, 0.0]; // Default spawn within zone
         
+        let attributes = Attributes::starting_for_class(class);
         let (health, max_health, mana, max_mana, attack, defense) = match class {
             CharacterClass::Ninja => (80, 80, 40, 40, 12, 4),
             CharacterClass::Warrior => (120, 120, 20, 20, 10, 8),
@@ -81,6 +109,13 @@ impl PlayerStateData {
             attack,
             defense,
             gold: 100, // Starting gold
+            skill_points: 0,
+            vitality: attributes.vitality as i32,
+            intelligence: attributes.intelligence as i32,
+            strength: attributes.strength as i32,
+            dexterity: attributes.dexterity as i32,
+            stat_points: 0,
+            equipment_durability: MAX_DURABILITY as i32,
         }
     }
 }
@@ -102,6 +137,13 @@ impl Default for PlayerStateData {
             attack: 10,
             defense: 5,
             gold: 100,
+            skill_points: 0,
+            vitality: 0,
+            intelligence: 0,
+            strength: 0,
+            dexterity: 0,
+            stat_points: 0,
+            equipment_durability: MAX_DURABILITY as i32,
         }
     }
 }
@@ -381,8 +423,9 @@ impl Database {
         let state = PlayerStateData::new_for_class(class, empire);
         sqlx::query(
             "INSERT INTO player_state (character_id, position_x, position_y, position_z, rotation,
-                                       health, max_health, mana, max_mana, level, experience, attack, defense)
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)"
+                                       health, max_health, mana, max_mana, level, experience, attack, defense,
+                                       vitality, intelligence, strength, dexterity)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)"
         )
             .bind(character_id)
             .bind(state.position_x)
@@ -397,6 +440,10 @@ impl Database {
             .bind(state.experience)
             .bind(state.attack)
             .bind(state.defense)
+            .bind(state.vitality)
+            .bind(state.intelligence)
+            .bind(state.strength)
+            .bind(state.dexterity)
             .execute(&mut *tx)
             .await
             .map_err(|e| CharacterError::Database(e.to_string()))?;
@@ -479,7 +526,8 @@ impl Database {
     /// Get full character data (for select)
     pub async fn get_character(&self, character_id: i64, player_id: i64) -> Result<Option<CharacterData>, sqlx::Error> {
         let row = sqlx::query(
-            "SELECT c.id, c.player_id, c.name, c.class, c.gender, c.empire, COALESCE(s.level, 1) as level
+            "SELECT c.id, c.player_id, c.name, c.class, c.gender, c.empire, c.specialization,
+                    COALESCE(s.level, 1) as level
              FROM characters c
              LEFT JOIN player_state s ON s.character_id = c.id
              WHERE c.id = $1 AND c.player_id = $2"
@@ -493,6 +541,7 @@ impl Database {
             let class_val: i16 = r.get("class");
             let gender_val: i16 = r.get("gender");
             let empire_val: i16 = r.get("empire");
+            let specialization_val: Option<i16> = r.get("specialization");
             
             CharacterData {
                 id: r.get("id"),
@@ -502,10 +551,24 @@ impl Database {
                 gender: Gender::from_u8(gender_val as u8).unwrap_or(Gender::Male),
                 empire: Empire::from_u8(empire_val as u8).unwrap_or(Empire::Red),
                 level: r.get("level"),
+                specialization: specialization_val.and_then(|s| Specialization::from_u8(s as u8)),
             }
         }))
     }
     
+    /// Save a character's chosen specialization
+    pub async fn save_character_specialization(&self, character_id: i64, specialization: Specialization) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE characters SET specialization = $2 WHERE id = $1"
+        )
+            .bind(character_id)
+            .bind(specialization.as_u8() as i16)
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(())
+    }
+    
     // =========================================================================
     // Character State Operations (updated to use character_id)
     // =========================================================================
@@ -515,7 +578,8 @@ impl Database {
         let row = sqlx::query(
             "SELECT zone_id, position_x, position_y, position_z, rotation, 
                     health, max_health, mana, max_mana, 
-                    level, experience, attack, defense, gold
+                    level, experience, attack, defense, gold, skill_points,
+                    vitality, intelligence, strength, dexterity, stat_points, equipment_durability
              FROM player_state WHERE character_id = $1"
         )
             .bind(character_id)
@@ -537,6 +601,13 @@ impl Database {
             attack: r.get("attack"),
             defense: r.get("defense"),
             gold: r.get::<Option<i64>, _>("gold").unwrap_or(0),
+            skill_points: r.get("skill_points"),
+            vitality: r.get("vitality"),
+            intelligence: r.get("intelligence"),
+            strength: r.get("strength"),
+            dexterity: r.get("dexterity"),
+            stat_points: r.get("stat_points"),
+            equipment_durability: r.get("equipment_durability"),
         }))
     }
     
@@ -544,8 +615,10 @@ impl Database {
     pub async fn save_character_state(&self, character_id: i64, state: &PlayerStateData) -> Result<(), sqlx::Error> {
         sqlx::query(
             "INSERT INTO player_state (character_id, zone_id, position_x, position_y, position_z, rotation,
-                                       health, max_health, mana, max_mana, level, experience, attack, defense, gold)
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+                                       health, max_health, mana, max_mana, level, experience, attack, defense, gold,
+                                       skill_points, vitality, intelligence, strength, dexterity, stat_points,
+                                       equipment_durability)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22)
              ON CONFLICT (character_id) DO UPDATE SET
                 zone_id = EXCLUDED.zone_id,
                 position_x = EXCLUDED.position_x,
@@ -560,7 +633,14 @@ impl Database {
                 experience = EXCLUDED.experience,
                 attack = EXCLUDED.attack,
                 defense = EXCLUDED.defense,
-                gold = EXCLUDED.gold"
+                gold = EXCLUDED.gold,
+                skill_points = EXCLUDED.skill_points,
+                vitality = EXCLUDED.vitality,
+                intelligence = EXCLUDED.intelligence,
+                strength = EXCLUDED.strength,
+                dexterity = EXCLUDED.dexterity,
+                stat_points = EXCLUDED.stat_points,
+                equipment_durability = EXCLUDED.equipment_durability"
         )
             .bind(character_id)
             .bind(state.zone_id)
@@ -577,6 +657,13 @@ impl Database {
             .bind(state.attack)
             .bind(state.defense)
             .bind(state.gold)
+            .bind(state.skill_points)
+            .bind(state.vitality)
+            .bind(state.intelligence)
+            .bind(state.strength)
+            .bind(state.dexterity)
+            .bind(state.stat_points)
+            .bind(state.equipment_durability)
             .execute(&self.pool)
             .await?;
         
@@ -769,6 +856,16 @@ impl Database {
                         if let Some(amount) = val.as_f64() {
                             effects.push(ItemEffect::IncreaseSpeed(amount as f32));
                         }
+                    } else if obj.contains_key("ResetStats") {
+                        effects.push(ItemEffect::ResetStats);
+                    } else if let Some(val) = obj.get("HealthRegen") {
+                        if let Some(amount) = val.as_u64() {
+                            effects.push(ItemEffect::HealthRegen(amount as u32));
+                        }
+                    } else if let Some(val) = obj.get("ManaRegen") {
+                        if let Some(amount) = val.as_u64() {
+                            effects.push(ItemEffect::ManaRegen(amount as u32));
+                        }
                     }
                 }
             }
@@ -777,6 +874,240 @@ impl Database {
         effects
     }
     
+    // =========================================================================
+    // Ability Operations
+    // =========================================================================
+    
+    /// Load all abilities from the database
+    pub async fn load_all_abilities(&self) -> Result<HashMap<u32, AbilityDef>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT id, name, description, mana_cost, cooldown, cast_time, channel_duration,
+                    projectile_speed, range, target_type, class_restriction, specialization,
+                    level_requirement, effects, icon
+             FROM abilities"
+        )
+            .fetch_all(&self.pool)
+            .await?;
+        
+        let mut abilities = HashMap::new();
+        
+        for row in rows {
+            let id: i32 = row.get("id");
+            let target_type_val: i16 = row.get("target_type");
+            let class_restriction: Option<i16> = row.get("class_restriction");
+            let specialization: Option<i16> = row.get("specialization");
+            let effects_json: serde_json::Value = row.get("effects");
+            
+            let target_type = match TargetType::from_u8(target_type_val as u8) {
+                Some(t) => t,
+                None => {
+                    log::warn!("Skipping ability {}: unknown target type {}", id, target_type_val);
+                    continue;
+                }
+            };
+            
+            abilities.insert(id as u32, AbilityDef {
+                id: id as u32,
+                name: row.get("name"),
+                description: row.get("description"),
+                mana_cost: row.get::<i32, _>("mana_cost") as u32,
+                cooldown: row.get("cooldown"),
+                cast_time: row.get("cast_time"),
+                channel_duration: row.get("channel_duration"),
+                projectile_speed: row.get("projectile_speed"),
+                range: row.get("range"),
+                target_type,
+                class_restriction: class_restriction.and_then(|c| CharacterClass::from_u8(c as u8)),
+                specialization: specialization.and_then(|s| Specialization::from_u8(s as u8)),
+                level_requirement: row.get::<i32, _>("level_requirement") as u32,
+                effects: Self::parse_ability_effects(id, &effects_json),
+                icon: row.get("icon"),
+            });
+        }
+        
+        Ok(abilities)
+    }
+    
+    /// Parse ability effects from JSON (serialized AbilityEffect values)
+    fn parse_ability_effects(ability_id: i32, json: &serde_json::Value) -> Vec<AbilityEffect> {
+        let mut effects = Vec::new();
+        
+        if let Some(arr) = json.as_array() {
+            for effect in arr {
+                match serde_json::from_value::<AbilityEffect>(effect.clone()) {
+                    Ok(e) => effects.push(e),
+                    Err(e) => log::warn!("Ability {}: skipping invalid effect {}: {}", ability_id, effect, e),
+                }
+            }
+        }
+        
+        effects
+    }
+    
+    // =========================================================================
+    // Skill Operations
+    // =========================================================================
+    
+    /// Load a character's learned skills (ability_id -> rank)
+    pub async fn load_character_skills(&self, character_id: i64) -> Result<HashMap<u32, u8>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT ability_id, rank FROM character_skills WHERE character_id = $1"
+        )
+            .bind(character_id)
+            .fetch_all(&self.pool)
+            .await?;
+        
+        Ok(rows.iter().map(|r| {
+            (r.get::<i32, _>("ability_id") as u32, r.get::<i16, _>("rank") as u8)
+        }).collect())
+    }
+    
+    /// Save a character's rank in a skill
+    pub async fn save_character_skill(&self, character_id: i64, ability_id: u32, rank: u8) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO character_skills (character_id, ability_id, rank)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (character_id, ability_id) DO UPDATE SET rank = EXCLUDED.rank"
+        )
+            .bind(character_id)
+            .bind(ability_id as i32)
+            .bind(rank as i16)
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(())
+    }
+    
+    // =========================================================================
+    // Combat Log Operations
+    // =========================================================================
+    
+    /// Append combat log events to the combat history
+    pub async fn insert_combat_log(&self, records: &[CombatLogRecord]) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        
+        for record in records {
+            let entry = &record.entry;
+            let breakdown = entry.breakdown;
+            
+            sqlx::query(
+                "INSERT INTO combat_log (
+                    occurred_at_ms, kind,
+                    source_character_id, source_name, source_is_player,
+                    target_character_id, target_name, target_is_player,
+                    ability_id, item_id, amount, outcome,
+                    raw_damage, after_outcome, armor_mitigated, overkill
+                 ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)"
+            )
+                .bind(entry.timestamp_ms as i64)
+                .bind(entry.kind.name())
+                .bind(record.source_character_id)
+                .bind(entry.source.as_ref().map(|s| s.name.clone()))
+                .bind(entry.source.as_ref().map(|s| s.is_player).unwrap_or(false))
+                .bind(record.target_character_id)
+                .bind(&entry.target.name)
+                .bind(entry.target.is_player)
+                .bind(entry.ability_id.map(|id| id as i32))
+                .bind(entry.item_id.map(|id| id as i32))
+                .bind(entry.amount as i32)
+                .bind(entry.outcome.map(|o| o.name()))
+                .bind(breakdown.map(|b| b.raw as i32))
+                .bind(breakdown.map(|b| b.after_outcome as i32))
+                .bind(breakdown.map(|b| b.armor as i32))
+                .bind(breakdown.map(|b| b.overkill as i32))
+                .execute(&mut *tx)
+                .await?;
+        }
+        
+        tx.commit().await?;
+        Ok(())
+    }
+    
+    /// Delete combat log events older than the given unix timestamp (ms)
+    /// Returns the number of deleted events
+    pub async fn prune_combat_log(&self, older_than_ms: u64) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM combat_log WHERE occurred_at_ms < $1")
+            .bind(older_than_ms as i64)
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(result.rows_affected())
+    }
+    
+    /// Load the most recent combat log events involving a character in a time range (unix ms)
+    /// Returns events newest first
+    pub async fn query_combat_log(
+        &self,
+        character_name: &str,
+        from_ms: u64,
+        to_ms: u64,
+        limit: u32,
+    ) -> Result<Vec<CombatLogEntry>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT l.occurred_at_ms, l.kind, l.source_name, l.source_is_player,
+                    l.target_name, l.target_is_player, l.ability_id, l.item_id, l.amount, l.outcome,
+                    l.raw_damage, l.after_outcome, l.armor_mitigated, l.overkill
+             FROM combat_log l
+             JOIN characters c ON c.id = l.source_character_id OR c.id = l.target_character_id
+             WHERE LOWER(c.name) = LOWER($1) AND l.occurred_at_ms BETWEEN $2 AND $3
+             ORDER BY l.occurred_at_ms DESC, l.id DESC
+             LIMIT $4"
+        )
+            .bind(character_name)
+            .bind(from_ms as i64)
+            .bind(to_ms as i64)
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await?;
+        
+        Ok(rows.iter().filter_map(|r| {
+            let kind = CombatLogKind::from_name(r.get::<String, _>("kind").as_str())?;
+            let outcome = r.get::<Option<String>, _>("outcome").and_then(|o| match o.as_str() {
+                "Hit" => Some(HitOutcome::Hit),
+                "Critical" => Some(HitOutcome::Critical),
+                "Miss" => Some(HitOutcome::Miss),
+                "Dodge" => Some(HitOutcome::Dodge),
+                "Block" => Some(HitOutcome::Block),
+                "Immune" => Some(HitOutcome::Immune),
+                _ => None,
+            });
+            let breakdown = match (
+                r.get::<Option<i32>, _>("raw_damage"),
+                r.get::<Option<i32>, _>("after_outcome"),
+                r.get::<Option<i32>, _>("armor_mitigated"),
+                r.get::<Option<i32>, _>("overkill"),
+            ) {
+                (Some(raw), Some(after_outcome), Some(armor), Some(overkill)) => Some(DamageBreakdown {
+                    raw: raw as u32,
+                    after_outcome: after_outcome as u32,
+                    armor: armor as u32,
+                    overkill: overkill as u32,
+                }),
+                _ => None,
+            };
+            
+            Some(CombatLogEntry {
+                timestamp_ms: r.get::<i64, _>("occurred_at_ms") as u64,
+                kind,
+                source: r.get::<Option<String>, _>("source_name").map(|name| CombatParticipant {
+                    id: 0,
+                    name,
+                    is_player: r.get("source_is_player"),
+                }),
+                target: CombatParticipant {
+                    id: 0,
+                    name: r.get("target_name"),
+                    is_player: r.get("target_is_player"),
+                },
+                ability_id: r.get::<Option<i32>, _>("ability_id").map(|id| id as u32),
+                item_id: r.get::<Option<i32>, _>("item_id").map(|id| id as u32),
+                amount: r.get::<i32, _>("amount") as u32,
+                outcome,
+                breakdown,
+            })
+        }).collect())
+    }
+    
     // =========================================================================
     // Equipment Operations
     // =========================================================================
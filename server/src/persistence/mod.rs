@@ -6,12 +6,15 @@
 mod database;
 mod cache;
 
-pub use database::{Database, PlayerData, PlayerStateData, InventorySlotData, CharacterData, CharacterEquipment};
+pub use database::{Database, PlayerData, PlayerStateData, InventorySlotData, CharacterData, CharacterEquipment, CombatLogRecord};
 pub use cache::Cache;
 
 use tokio::sync::mpsc;
 use log::{info, error, warn};
 
+/// How long combat log events are kept before being pruned
+pub const COMBAT_LOG_RETENTION_DAYS: u64 = 7;
+
 /// Commands sent to the persistence background task
 #[derive(Debug)]
 pub enum PersistenceCommand {
@@ -30,6 +33,12 @@ pub enum PersistenceCommand {
     UpdateLastLogin {
         player_id: i64,
     },
+    /// Append events to the combat history
+    RecordCombatLog {
+        records: Vec<CombatLogRecord>,
+    },
+    /// Delete combat history older than the retention window
+    PruneCombatLog,
     /// Flush all dirty data to database (called periodically)
     FlushToDatabase,
     /// Shutdown the persistence task
@@ -69,6 +78,18 @@ impl PersistenceHandle {
         let _ = self.sender.try_send(PersistenceCommand::UpdateLastLogin { player_id });
     }
     
+    /// Record combat log events (fire and forget - non-blocking)
+    pub fn record_combat_log(&self, records: Vec<CombatLogRecord>) {
+        if let Err(e) = self.sender.try_send(PersistenceCommand::RecordCombatLog { records }) {
+            warn!("Dropped combat log batch: {}", e);
+        }
+    }
+    
+    /// Prune combat history older than COMBAT_LOG_RETENTION_DAYS
+    pub fn prune_combat_log(&self) {
+        let _ = self.sender.try_send(PersistenceCommand::PruneCombatLog);
+    }
+    
     /// Request a flush to database
     pub fn flush(&self) {
         let _ = self.sender.try_send(PersistenceCommand::FlushToDatabase);
@@ -169,6 +190,25 @@ async fn persistence_task(
                 }
             }
             
+            PersistenceCommand::RecordCombatLog { records } => {
+                if let Err(e) = db.insert_combat_log(&records).await {
+                    warn!("Failed to record {} combat log events: {}", records.len(), e);
+                }
+            }
+            
+            PersistenceCommand::PruneCombatLog => {
+                let now_ms = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_millis() as u64)
+                    .unwrap_or(0);
+                let cutoff = now_ms.saturating_sub(COMBAT_LOG_RETENTION_DAYS * 24 * 60 * 60 * 1000);
+                match db.prune_combat_log(cutoff).await {
+                    Ok(0) => {}
+                    Ok(count) => info!("Pruned {} combat log events", count),
+                    Err(e) => warn!("Failed to prune combat log: {}", e),
+                }
+            }
+            
             PersistenceCommand::FlushToDatabase => {
                 // For now, we write through to database on every save
                 // In a more optimized version, we'd batch writes here
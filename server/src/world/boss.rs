@@ -0,0 +1,507 @@
+//! Scripted boss encounters.
+//!
+//! Boss templates are loaded from `bosses.json` next to the server binary. Each
+//! template places a single boss in a zone with an arena it never leaves,
+//! health-threshold phases with their own timed abilities and immunity windows,
+//! and a loot table that replaces the normal mob drops. The manager tracks
+//! respawn timers and live encounters; the world carries out the events the
+//! encounters produce. `event_only` bosses never respawn on their own and are
+//! summoned by world events instead.
+
+use std::collections::HashMap;
+use std::path::Path;
+use log::{info, debug};
+use rand::Rng;
+use serde::Deserialize;
+
+/// How often changing boss health is re-broadcast (seconds)
+const HEALTH_UPDATE_INTERVAL: f32 = 0.5;
+
+fn default_multiplier() -> f32 {
+    1.0
+}
+
+fn default_quantity() -> u32 {
+    1
+}
+
+fn default_experience_multiplier() -> f32 {
+    5.0
+}
+
+/// What a boss ability does
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type")]
+pub enum BossAbilityKind {
+    /// Damage every player within `radius` of where the boss stood when the slam started
+    AoeSlam {
+        radius: f32,
+        damage: u32,
+        /// Seconds between the telegraph and the hit
+        #[serde(default)]
+        wind_up: f32,
+    },
+    /// Spawn adds around the boss
+    SummonAdds {
+        /// Enemy template id
+        enemy_type: String,
+        count: u32,
+        level: u8,
+    },
+    /// Multiply attack power for the rest of the encounter (used once)
+    Enrage {
+        attack_multiplier: f32,
+    },
+}
+
+/// Timed ability used during a phase
+#[derive(Debug, Clone, Deserialize)]
+pub struct BossAbility {
+    pub name: String,
+    /// Seconds between uses
+    pub cooldown: f32,
+    /// Seconds after the phase starts before the first use
+    #[serde(default)]
+    pub initial_delay: f32,
+    #[serde(flatten)]
+    pub kind: BossAbilityKind,
+}
+
+/// Health-threshold phase
+#[derive(Debug, Clone, Deserialize)]
+pub struct BossPhase {
+    pub name: String,
+    /// Phase starts once boss health drops to this percent (the first phase uses 100)
+    pub health_percent: f32,
+    /// Seconds of damage immunity when the phase starts
+    #[serde(default)]
+    pub immune_duration: f32,
+    /// Attack power multiplier during this phase
+    #[serde(default = "default_multiplier")]
+    pub attack_multiplier: f32,
+    #[serde(default)]
+    pub abilities: Vec<BossAbility>,
+}
+
+/// Item a boss can drop
+#[derive(Debug, Clone, Deserialize)]
+pub struct BossLootDrop {
+    pub item_id: u32,
+    /// Drop chance (0.0 - 1.0)
+    pub chance: f64,
+    #[serde(default = "default_quantity")]
+    pub min_quantity: u32,
+    #[serde(default = "default_quantity")]
+    pub max_quantity: u32,
+}
+
+/// Boss loot (replaces the normal mob gold and drops)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct BossLootTable {
+    pub gold_min: u64,
+    pub gold_max: u64,
+    pub drops: Vec<BossLootDrop>,
+}
+
+impl BossLootTable {
+    /// Roll the gold reward
+    pub fn roll_gold(&self) -> u64 {
+        rand::thread_rng().gen_range(self.gold_min..=self.gold_max.max(self.gold_min))
+    }
+
+    /// Roll item drops as (item_id, quantity)
+    pub fn roll_items(&self) -> Vec<(u32, u32)> {
+        let mut rng = rand::thread_rng();
+        let mut items = Vec::new();
+        for drop in &self.drops {
+            if rng.gen_bool(drop.chance.clamp(0.0, 1.0)) {
+                items.push((drop.item_id, rng.gen_range(drop.min_quantity..=drop.max_quantity.max(drop.min_quantity))));
+            }
+        }
+        items
+    }
+}
+
+/// Boss definition
+#[derive(Debug, Clone, Deserialize)]
+pub struct BossTemplate {
+    pub id: String,
+    pub name: String,
+    /// Enemy template for the model, range and abilities
+    pub enemy_type: String,
+    pub level: u8,
+    pub max_health: u32,
+    pub attack_power: u32,
+    pub zone_id: u32,
+    /// Arena center in XZ world coordinates (also the spawn point)
+    pub position: [f32; 2],
+    /// The boss never leaves the arena and ignores players outside it
+    pub arena_radius: f32,
+    /// Seconds after death before the boss respawns
+    pub respawn_time: f32,
+    /// Experience compared to a normal enemy of the same level
+    #[serde(default = "default_experience_multiplier")]
+    pub experience_multiplier: f32,
+    pub phases: Vec<BossPhase>,
+    #[serde(default)]
+    pub loot: BossLootTable,
+    /// Only spawned by world events, never on a respawn timer
+    #[serde(default)]
+    pub event_only: bool,
+}
+
+/// Something an encounter wants the world to carry out
+#[derive(Debug, Clone)]
+pub enum BossEvent {
+    /// Ability started (broadcast as a telegraph/announcement)
+    AbilityUsed { name: String, radius: f32, wind_up: f32 },
+    /// A slam's wind-up finished
+    SlamLanded { position: [f32; 3], radius: f32, damage: u32 },
+    /// Spawn adds around the boss
+    SummonAdds { enemy_type: String, count: u32, level: u8 },
+}
+
+/// Status last broadcast to clients (phase, health, immune, enraged)
+type BossStatus = (usize, u32, bool, bool);
+
+/// Slam waiting for its wind-up to finish
+#[derive(Debug)]
+struct PendingSlam {
+    position: [f32; 3],
+    radius: f32,
+    damage: u32,
+    remaining: f32,
+}
+
+/// Live state of one boss
+#[derive(Debug)]
+pub struct BossEncounter {
+    /// Index into the manager's templates
+    pub template: usize,
+    /// A player in the arena has pulled the boss
+    pub engaged: bool,
+    /// Adds summoned during this encounter
+    pub adds: Vec<u64>,
+    phase: usize,
+    ability_timers: Vec<f32>,
+    immune_remaining: f32,
+    enrage_multiplier: f32,
+    pending_slams: Vec<PendingSlam>,
+    health_update_timer: f32,
+    last_sent: Option<BossStatus>,
+}
+
+impl BossEncounter {
+    fn new(template: usize) -> Self {
+        Self {
+            template,
+            engaged: false,
+            adds: Vec::new(),
+            phase: 0,
+            ability_timers: Vec::new(),
+            immune_remaining: 0.0,
+            enrage_multiplier: 1.0,
+            pending_slams: Vec::new(),
+            health_update_timer: 0.0,
+            last_sent: None,
+        }
+    }
+
+    /// Start the fight in the first phase
+    pub fn engage(&mut self, template: &BossTemplate) {
+        self.engaged = true;
+        self.enter_phase(template, 0);
+    }
+
+    /// Back to the unengaged state
+    /// Returns the adds that should be despawned
+    pub fn reset(&mut self) -> Vec<u64> {
+        let adds = std::mem::take(&mut self.adds);
+        *self = Self::new(self.template);
+        adds
+    }
+
+    fn enter_phase(&mut self, template: &BossTemplate, phase: usize) {
+        self.phase = phase;
+        let phase = &template.phases[phase];
+        self.ability_timers = phase.abilities.iter().map(|a| a.initial_delay).collect();
+        self.immune_remaining = phase.immune_duration;
+    }
+
+    pub fn is_immune(&self) -> bool {
+        self.immune_remaining > 0.0
+    }
+
+    pub fn is_enraged(&self) -> bool {
+        self.enrage_multiplier > 1.0
+    }
+
+    /// Attack power multiplier from the current phase and enrage
+    pub fn attack_multiplier(&self, template: &BossTemplate) -> f32 {
+        template.phases[self.phase].attack_multiplier * self.enrage_multiplier
+    }
+
+    /// Advance phases, ability timers and pending slams
+    pub fn update(&mut self, template: &BossTemplate, delta: f32, health_percent: f32, position: [f32; 3]) -> Vec<BossEvent> {
+        let mut events = Vec::new();
+
+        // Phases only move forward; several thresholds can be crossed in one hit
+        let target_phase = template.phases.iter()
+            .rposition(|p| health_percent <= p.health_percent)
+            .unwrap_or(0);
+        if target_phase > self.phase {
+            info!("Boss {} entering phase {} ({})", template.name, target_phase, template.phases[target_phase].name);
+            self.enter_phase(template, target_phase);
+        }
+
+        self.immune_remaining = (self.immune_remaining - delta).max(0.0);
+
+        let abilities = &template.phases[self.phase].abilities;
+        for (ability, timer) in abilities.iter().zip(self.ability_timers.iter_mut()) {
+            *timer -= delta;
+            if *timer > 0.0 {
+                continue;
+            }
+            *timer = ability.cooldown;
+
+            match &ability.kind {
+                BossAbilityKind::AoeSlam { radius, damage, wind_up } => {
+                    self.pending_slams.push(PendingSlam {
+                        position,
+                        radius: *radius,
+                        damage: *damage,
+                        remaining: *wind_up,
+                    });
+                    events.push(BossEvent::AbilityUsed { name: ability.name.clone(), radius: *radius, wind_up: *wind_up });
+                }
+                BossAbilityKind::SummonAdds { enemy_type, count, level } => {
+                    events.push(BossEvent::AbilityUsed { name: ability.name.clone(), radius: 0.0, wind_up: 0.0 });
+                    events.push(BossEvent::SummonAdds { enemy_type: enemy_type.clone(), count: *count, level: *level });
+                }
+                BossAbilityKind::Enrage { attack_multiplier } => {
+                    if self.enrage_multiplier > 1.0 {
+                        continue;
+                    }
+                    self.enrage_multiplier = *attack_multiplier;
+                    events.push(BossEvent::AbilityUsed { name: ability.name.clone(), radius: 0.0, wind_up: 0.0 });
+                }
+            }
+            debug!("Boss {} used {}", template.name, ability.name);
+        }
+
+        // Land slams whose wind-up has finished
+        for slam in &mut self.pending_slams {
+            slam.remaining -= delta;
+        }
+        self.pending_slams.retain(|slam| {
+            if slam.remaining > 0.0 {
+                return true;
+            }
+            events.push(BossEvent::SlamLanded { position: slam.position, radius: slam.radius, damage: slam.damage });
+            false
+        });
+
+        events
+    }
+
+    /// Status to broadcast, if it changed (health changes are throttled)
+    pub fn take_status_update(&mut self, delta: f32, health: u32) -> Option<BossStatus> {
+        self.health_update_timer -= delta;
+        let status = (self.phase, health, self.is_immune(), self.is_enraged());
+        let last = self.last_sent?;
+
+        let state_changed = (last.0, last.2, last.3) != (status.0, status.2, status.3);
+        let health_due = last.1 != status.1 && self.health_update_timer <= 0.0;
+        if !state_changed && !health_due {
+            return None;
+        }
+        self.health_update_timer = HEALTH_UPDATE_INTERVAL;
+        self.last_sent = Some(status);
+        Some(status)
+    }
+
+    /// Status to broadcast when the encounter starts
+    pub fn initial_status(&mut self, health: u32) -> BossStatus {
+        let status = (self.phase, health, self.is_immune(), self.is_enraged());
+        self.health_update_timer = HEALTH_UPDATE_INTERVAL;
+        self.last_sent = Some(status);
+        status
+    }
+}
+
+/// Loads boss templates and tracks boss respawns and live encounters
+#[derive(Default)]
+pub struct BossManager {
+    templates: Vec<BossTemplate>,
+    /// Seconds until a template's boss spawns (only for bosses that aren't alive)
+    respawn_timers: HashMap<usize, f32>,
+    /// Live encounters keyed by the boss's enemy id
+    encounters: HashMap<u64, BossEncounter>,
+}
+
+impl BossManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load boss templates from a JSON file (bosses spawn on the next update)
+    pub fn load_from_json<P: AsRef<Path>>(&mut self, path: P) -> Result<(), String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read bosses.json: {}", e))?;
+
+        let mut templates: Vec<BossTemplate> = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse bosses.json: {}", e))?;
+
+        for template in &mut templates {
+            if template.phases.is_empty() {
+                return Err(format!("Boss {} has no phases", template.id));
+            }
+            template.phases.sort_by(|a, b| b.health_percent.total_cmp(&a.health_percent));
+        }
+
+        info!("Loaded {} boss templates", templates.len());
+        self.respawn_timers = (0..templates.len())
+            .filter(|i| !templates[*i].event_only)
+            .map(|i| (i, 0.0))
+            .collect();
+        self.encounters.clear();
+        self.templates = templates;
+        Ok(())
+    }
+
+    pub fn template(&self, index: usize) -> &BossTemplate {
+        &self.templates[index]
+    }
+
+    /// Template index for a boss id
+    pub fn find(&self, id: &str) -> Option<usize> {
+        self.templates.iter().position(|t| t.id == id)
+    }
+
+    /// Whether a template's boss is currently alive
+    pub fn is_alive(&self, index: usize) -> bool {
+        self.encounters.values().any(|e| e.template == index)
+    }
+
+    /// Tick respawn timers
+    /// Returns the templates whose boss should spawn now
+    pub fn update_respawns(&mut self, delta: f32) -> Vec<usize> {
+        let mut ready = Vec::new();
+        self.respawn_timers.retain(|index, remaining| {
+            *remaining -= delta;
+            if *remaining > 0.0 {
+                return true;
+            }
+            ready.push(*index);
+            false
+        });
+        ready
+    }
+
+    /// Track a freshly spawned boss
+    pub fn register(&mut self, enemy_id: u64, template: usize) {
+        self.encounters.insert(enemy_id, BossEncounter::new(template));
+    }
+
+    /// Arena radius if the enemy is a boss
+    pub fn arena_radius(&self, enemy_id: u64) -> Option<f32> {
+        let encounter = self.encounters.get(&enemy_id)?;
+        Some(self.templates[encounter.template].arena_radius)
+    }
+
+    /// Enemy ids of all live bosses
+    pub fn boss_ids(&self) -> Vec<u64> {
+        self.encounters.keys().copied().collect()
+    }
+
+    /// Template and encounter state for a live boss
+    pub fn encounter_mut(&mut self, enemy_id: u64) -> Option<(&BossTemplate, &mut BossEncounter)> {
+        let encounter = self.encounters.get_mut(&enemy_id)?;
+        Some((&self.templates[encounter.template], encounter))
+    }
+
+    /// Remember a summoned add so it is cleaned up with the encounter
+    pub fn add_summon(&mut self, boss_id: u64, add_id: u64) {
+        if let Some(encounter) = self.encounters.get_mut(&boss_id) {
+            encounter.adds.push(add_id);
+        }
+    }
+
+    /// Boss died: end the encounter and queue its respawn
+    /// Returns (template index, adds to despawn)
+    pub fn on_boss_death(&mut self, enemy_id: u64) -> Option<(usize, Vec<u64>)> {
+        let encounter = self.encounters.remove(&enemy_id)?;
+        let template = &self.templates[encounter.template];
+        if template.event_only {
+            info!("Boss {} defeated", template.name);
+        } else {
+            info!("Boss {} defeated, respawning in {:.0}s", template.name, template.respawn_time);
+            self.respawn_timers.insert(encounter.template, template.respawn_time);
+        }
+        Some((encounter.template, encounter.adds))
+    }
+
+    /// Boss removed without being defeated (an event ran out): no respawn
+    /// Returns the adds to despawn
+    pub fn remove(&mut self, enemy_id: u64) -> Option<Vec<u64>> {
+        self.encounters.remove(&enemy_id).map(|encounter| encounter.adds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template() -> BossTemplate {
+        serde_json::from_str(r#"{
+            "id": "test", "name": "Test Boss", "enemy_type": "Mutant", "level": 10,
+            "max_health": 1000, "attack_power": 40, "zone_id": 1, "position": [0.0, 0.0],
+            "arena_radius": 20.0, "respawn_time": 60.0,
+            "phases": [
+                { "name": "Enraged", "health_percent": 30.0, "immune_duration": 2.0, "attack_multiplier": 2.0 },
+                { "name": "Opening", "health_percent": 100.0, "abilities": [
+                    { "name": "Slam", "type": "AoeSlam", "radius": 5.0, "damage": 50, "wind_up": 1.0, "cooldown": 10.0 }
+                ] }
+            ]
+        }"#).unwrap()
+    }
+
+    fn sorted_template() -> BossTemplate {
+        let mut template = template();
+        template.phases.sort_by(|a, b| b.health_percent.total_cmp(&a.health_percent));
+        template
+    }
+
+    #[test]
+    fn test_slam_lands_after_wind_up() {
+        let template = sorted_template();
+        let mut encounter = BossEncounter::new(0);
+        encounter.engage(&template);
+
+        let events = encounter.update(&template, 0.1, 100.0, [0.0; 3]);
+        assert!(matches!(events.as_slice(), [BossEvent::AbilityUsed { .. }]));
+
+        assert!(encounter.update(&template, 0.5, 100.0, [0.0; 3]).is_empty());
+        let events = encounter.update(&template, 0.5, 100.0, [0.0; 3]);
+        assert!(matches!(events.as_slice(), [BossEvent::SlamLanded { damage: 50, .. }]));
+    }
+
+    #[test]
+    fn test_phase_change_grants_immunity() {
+        let template = sorted_template();
+        let mut encounter = BossEncounter::new(0);
+        encounter.engage(&template);
+        assert!(!encounter.is_immune());
+
+        encounter.update(&template, 0.1, 25.0, [0.0; 3]);
+        assert_eq!(encounter.phase, 1);
+        assert!(encounter.is_immune());
+        assert_eq!(encounter.attack_multiplier(&template), 2.0);
+
+        // Healing back up never returns to an earlier phase
+        encounter.update(&template, 2.0, 80.0, [0.0; 3]);
+        assert_eq!(encounter.phase, 1);
+        assert!(!encounter.is_immune());
+    }
+}
@@ -0,0 +1,224 @@
+//! Structured combat event log.
+//!
+//! The world records every damage, heal, buff, miss, death and XP award here.
+//! The server drains the pending events once per tick to stream them to
+//! players who opted in and to append them to the persisted combat history.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use mmo_shared::{CombatLogEntry, CombatLogKind, CombatParticipant, DamageBreakdown, HitOutcome};
+
+use crate::combat::HitResult;
+use crate::entities::{ServerEnemy, ServerMetinStone, ServerPlayer};
+
+/// Current unix time in milliseconds
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Combat log participant for a player
+pub fn player_participant(player: &ServerPlayer) -> CombatParticipant {
+    CombatParticipant {
+        id: player.id,
+        name: player.name.clone(),
+        is_player: true,
+    }
+}
+
+/// Combat log participant for an enemy
+pub fn enemy_participant(enemy: &ServerEnemy) -> CombatParticipant {
+    CombatParticipant {
+        id: enemy.id,
+        name: format!("{} (Lv.{})", enemy.name(), enemy.level),
+        is_player: false,
+    }
+}
+
+/// Combat log participant for a Metin stone
+pub fn metin_stone_participant(stone: &ServerMetinStone) -> CombatParticipant {
+    CombatParticipant {
+        id: stone.id,
+        name: format!("{} (Lv.{})", stone.name, stone.level),
+        is_player: false,
+    }
+}
+
+/// Combat events recorded since the last drain
+#[derive(Debug, Default)]
+pub struct CombatLog {
+    pending: Vec<CombatLogEntry>,
+}
+
+impl CombatLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a resolved attack as a damage or miss event
+    /// `dealt` is the damage actually applied and `health_before` the target's health before it
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_attack(
+        &mut self,
+        source: CombatParticipant,
+        target: CombatParticipant,
+        ability_id: Option<u32>,
+        raw: u32,
+        hit: HitResult,
+        dealt: u32,
+        health_before: u32,
+    ) {
+        let kind = if hit.outcome.is_avoided() { CombatLogKind::Miss } else { CombatLogKind::Damage };
+        let breakdown = (kind == CombatLogKind::Damage).then(|| DamageBreakdown {
+            raw,
+            after_outcome: hit.damage,
+            armor: hit.damage.saturating_sub(dealt),
+            overkill: dealt.saturating_sub(health_before),
+        });
+
+        self.pending.push(CombatLogEntry {
+            timestamp_ms: now_ms(),
+            kind,
+            source: Some(source),
+            target,
+            ability_id,
+            item_id: None,
+            amount: dealt,
+            outcome: Some(hit.outcome),
+            breakdown,
+        });
+    }
+
+    /// Record damage that bypasses the attack table (e.g. damage over time)
+    pub fn record_direct_damage(
+        &mut self,
+        source: CombatParticipant,
+        target: CombatParticipant,
+        ability_id: Option<u32>,
+        damage: u32,
+        health_before: u32,
+    ) {
+        self.record_attack(
+            source,
+            target,
+            ability_id,
+            damage,
+            HitResult { outcome: HitOutcome::Hit, damage },
+            damage,
+            health_before,
+        );
+    }
+
+    /// Record health restored by an ability, item or regeneration
+    pub fn record_heal(
+        &mut self,
+        source: Option<CombatParticipant>,
+        target: CombatParticipant,
+        ability_id: Option<u32>,
+        item_id: Option<u32>,
+        amount: u32,
+    ) {
+        self.pending.push(CombatLogEntry {
+            timestamp_ms: now_ms(),
+            kind: CombatLogKind::Heal,
+            source,
+            target,
+            ability_id,
+            item_id,
+            amount,
+            outcome: None,
+            breakdown: None,
+        });
+    }
+
+    /// Record a buff applied by an ability (amount is the duration in ms)
+    pub fn record_buff(&mut self, source: CombatParticipant, target: CombatParticipant, ability_id: u32, duration: f32) {
+        self.pending.push(CombatLogEntry {
+            timestamp_ms: now_ms(),
+            kind: CombatLogKind::Buff,
+            source: Some(source),
+            target,
+            ability_id: Some(ability_id),
+            item_id: None,
+            amount: (duration * 1000.0) as u32,
+            outcome: None,
+            breakdown: None,
+        });
+    }
+
+    /// Record a death (source is the killer, if any)
+    pub fn record_death(&mut self, killer: Option<CombatParticipant>, target: CombatParticipant) {
+        self.pending.push(CombatLogEntry {
+            timestamp_ms: now_ms(),
+            kind: CombatLogKind::Death,
+            source: killer,
+            target,
+            ability_id: None,
+            item_id: None,
+            amount: 0,
+            outcome: None,
+            breakdown: None,
+        });
+    }
+
+    /// Record experience awarded to a player (source is the slain enemy)
+    pub fn record_experience(&mut self, source: CombatParticipant, player: CombatParticipant, amount: u32) {
+        self.pending.push(CombatLogEntry {
+            timestamp_ms: now_ms(),
+            kind: CombatLogKind::Experience,
+            source: Some(source),
+            target: player,
+            ability_id: None,
+            item_id: None,
+            amount,
+            outcome: None,
+            breakdown: None,
+        });
+    }
+
+    /// Take all events recorded since the last drain
+    pub fn drain(&mut self) -> Vec<CombatLogEntry> {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn participant(name: &str) -> CombatParticipant {
+        CombatParticipant { id: 1, name: name.to_string(), is_player: true }
+    }
+
+    #[test]
+    fn test_record_attack_breaks_down_damage() {
+        let mut log = CombatLog::new();
+        // Crit doubled 30 to 60, armor took 15, the target only had 40 health
+        let hit = HitResult { outcome: HitOutcome::Critical, damage: 60 };
+        log.record_attack(participant("Aria"), participant("Wolf"), Some(21), 30, hit, 45, 40);
+
+        let entries = log.drain();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].kind, CombatLogKind::Damage);
+        assert_eq!(entries[0].amount, 45);
+        assert_eq!(entries[0].outcome, Some(HitOutcome::Critical));
+        assert_eq!(entries[0].breakdown, Some(DamageBreakdown { raw: 30, after_outcome: 60, armor: 15, overkill: 5 }));
+        assert!(log.drain().is_empty());
+    }
+
+    #[test]
+    fn test_record_attack_logs_avoided_hits_as_misses() {
+        let mut log = CombatLog::new();
+        for outcome in [HitOutcome::Miss, HitOutcome::Dodge, HitOutcome::Immune] {
+            log.record_attack(participant("Wolf"), participant("Aria"), None, 12, HitResult { outcome, damage: 0 }, 0, 100);
+        }
+        let entries = log.drain();
+        assert!(entries.iter().all(|e| e.kind == CombatLogKind::Miss && e.breakdown.is_none() && e.amount == 0));
+
+        // Blocks still land, just for less
+        log.record_attack(participant("Wolf"), participant("Aria"), None, 12, HitResult { outcome: HitOutcome::Block, damage: 6 }, 4, 100);
+        assert_eq!(log.drain()[0].kind, CombatLogKind::Damage);
+    }
+}
@@ -0,0 +1,32 @@
+//! Small JSON settings files with built-in defaults.
+//!
+//! Each config lives in its own file next to the server binary and derives
+//! `Default` plus `#[serde(default)]`, so partial files only override what
+//! they list.
+
+use std::fmt::Debug;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+
+/// A settings struct loaded from a JSON file next to the server binary
+pub trait JsonConfig: DeserializeOwned + Default + Debug {
+    /// File name the config is read from
+    const FILE: &'static str;
+
+    /// Reject values the server can't work with
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Load and validate the config from a JSON file
+    fn load_from_json<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", Self::FILE, e))?;
+
+        let config: Self = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {}", Self::FILE, e))?;
+        config.validate().map_err(|e| format!("{}: {}", Self::FILE, e))?;
+        Ok(config)
+    }
+}
@@ -0,0 +1,45 @@
+//! Death penalty configuration.
+//!
+//! Loaded from `death_penalty.json` next to the server binary. Missing fields
+//! (or a missing file) fall back to the defaults below.
+
+use serde::Deserialize;
+
+use super::config::JsonConfig;
+
+/// Penalties and timers applied when a player dies
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct DeathPenaltyConfig {
+    /// Experience lost on death, as percent of the XP needed for the next level
+    /// (never drops the player below their current level)
+    pub experience_loss_percent: f32,
+    /// Durability lost by equipped gear on death
+    pub durability_loss: u32,
+    /// Seconds until a dead player is released to the empire spawn automatically
+    pub auto_release_time: f32,
+    /// Health restored when releasing at the death spot (fraction of max health)
+    pub death_spot_health_percent: f32,
+    /// Resurrection sickness duration after releasing (seconds, 0 = disabled)
+    /// Only releasing pays this; being resurrected by another player is how to avoid it
+    pub sickness_duration: f32,
+    /// Damage and defense multiplier while resurrection sickness lasts
+    pub sickness_stat_multiplier: f32,
+}
+
+impl Default for DeathPenaltyConfig {
+    fn default() -> Self {
+        Self {
+            experience_loss_percent: 5.0,
+            durability_loss: 10,
+            auto_release_time: 300.0,
+            death_spot_health_percent: 0.2,
+            sickness_duration: 60.0,
+            sickness_stat_multiplier: 0.75,
+        }
+    }
+}
+
+impl JsonConfig for DeathPenaltyConfig {
+    const FILE: &'static str = "death_penalty.json";
+}
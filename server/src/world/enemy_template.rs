@@ -0,0 +1,313 @@
+//! Enemy templates.
+//!
+//! Loaded from `enemy_templates.json` next to the server binary, keyed by
+//! template id. A template defines everything about a mob type: the model the
+//! client renders, base stats and how they scale with level, perception and
+//! leash ranges, movement, how far it attacks from and the distance it tries to
+//! hold, how it behaves in a fight, the abilities it casts and what it drops. Abilities use the same
+//! `AbilityEffect` vocabulary as player abilities. Adding a new mob type only
+//! needs a new entry here (plus a spawn area or boss that references it).
+
+use std::collections::HashMap;
+use std::path::Path;
+use log::info;
+use rand::Rng;
+use serde::Deserialize;
+use mmo_shared::{AbilityEffect, EnemyType, LootEntry, TargetType};
+use crate::ai::Behaviour;
+use crate::navigation::ENEMY_RADIUS;
+
+/// Default melee attack range
+pub const DEFAULT_ATTACK_RANGE: f32 = 2.0;
+
+fn default_level_scaling() -> f32 {
+    0.15
+}
+
+fn default_crit_chance() -> f32 {
+    0.05
+}
+
+fn default_dodge_chance() -> f32 {
+    0.05
+}
+
+fn default_aggro_range() -> f32 {
+    10.0
+}
+
+fn default_leash_range() -> f32 {
+    25.0
+}
+
+fn default_attack_range() -> f32 {
+    DEFAULT_ATTACK_RANGE
+}
+
+fn default_attack_cooldown() -> f32 {
+    2.0
+}
+
+fn default_move_speed() -> f32 {
+    3.0
+}
+
+fn default_radius() -> f32 {
+    ENEMY_RADIUS
+}
+
+fn default_health_threshold() -> f32 {
+    1.0
+}
+
+/// Ability an enemy casts on a cooldown during combat
+#[derive(Debug, Clone, Deserialize)]
+pub struct EnemyAbility {
+    pub name: String,
+    /// Seconds between casts
+    pub cooldown: f32,
+    /// Seconds after entering combat before the first cast
+    #[serde(default)]
+    pub initial_delay: f32,
+    /// Maximum distance to the target (units)
+    pub range: f32,
+    /// `Enemy` = the enemy's current target, `Ally` = a wounded nearby enemy (including itself),
+    /// `SelfOnly` = itself
+    pub target_type: TargetType,
+    /// Only cast on allies or self below this fraction of max health
+    #[serde(default = "default_health_threshold")]
+    pub health_threshold: f32,
+    pub effects: Vec<AbilityEffect>,
+}
+
+/// Everything that defines a mob type
+#[derive(Debug, Clone, Deserialize)]
+pub struct EnemyTemplate {
+    /// Display name
+    pub name: String,
+    /// Model and animations the client renders
+    pub model: EnemyType,
+    /// Max health at level 1
+    pub health: u32,
+    /// Attack power at level 1
+    pub attack: u32,
+    /// Level range when spawned without an explicit level
+    pub min_level: u8,
+    pub max_level: u8,
+    /// Health and attack gained per level above 1 (fraction of the level 1 value)
+    #[serde(default = "default_level_scaling")]
+    pub level_scaling: f32,
+    #[serde(default = "default_crit_chance")]
+    pub crit_chance: f32,
+    #[serde(default = "default_dodge_chance")]
+    pub dodge_chance: f32,
+    /// How far the enemy notices players (units)
+    #[serde(default = "default_aggro_range")]
+    pub aggro_range: f32,
+    /// How far from spawn the enemy chases before giving up (units)
+    #[serde(default = "default_leash_range")]
+    pub leash_range: f32,
+    /// How close the enemy needs to be to auto-attack (units)
+    #[serde(default = "default_attack_range")]
+    pub attack_range: f32,
+    /// Seconds between auto-attacks
+    #[serde(default = "default_attack_cooldown")]
+    pub attack_cooldown: f32,
+    /// Distance the enemy backs off to between attacks (0 = stays in its target's face)
+    #[serde(default)]
+    pub preferred_distance: f32,
+    /// Auto-attacks fire a projectile at this speed (0 = melee hit)
+    #[serde(default)]
+    pub projectile_speed: f32,
+    /// Movement speed while chasing or patrolling (units per second)
+    #[serde(default = "default_move_speed")]
+    pub move_speed: f32,
+    /// Collision radius for navigation (units)
+    #[serde(default = "default_radius")]
+    pub radius: f32,
+    /// AI settings (fleeing, how far it wanders from spawn)
+    #[serde(default)]
+    pub behaviour: Behaviour,
+    #[serde(default)]
+    pub abilities: Vec<EnemyAbility>,
+    /// Items dropped on death
+    #[serde(default)]
+    pub loot: Vec<LootEntry>,
+}
+
+impl EnemyTemplate {
+    /// Health and attack power at a level
+    pub fn stats_for_level(&self, level: u8) -> (u32, u32) {
+        let multiplier = 1.0 + (level.max(1) as f32 - 1.0) * self.level_scaling;
+        ((self.health as f32 * multiplier) as u32, (self.attack as f32 * multiplier) as u32)
+    }
+
+    /// Pick a random level in the template's range
+    pub fn roll_level(&self) -> u8 {
+        rand::thread_rng().gen_range(self.min_level..=self.max_level)
+    }
+
+    /// Roll the loot table
+    /// Returns (item_id, quantity) for each drop
+    pub fn roll_loot(&self) -> Vec<(u32, u32)> {
+        let mut rng = rand::thread_rng();
+        let mut drops = Vec::new();
+        for entry in &self.loot {
+            if rng.gen_bool(entry.drop_chance.clamp(0.0, 1.0) as f64) {
+                drops.push((entry.item_id, rng.gen_range(entry.min_quantity..=entry.max_quantity)));
+            }
+        }
+        drops
+    }
+
+    /// Check the template for values the AI can't work with
+    fn validate(&self) -> Result<(), String> {
+        if self.min_level == 0 || self.min_level > self.max_level {
+            return Err(format!("invalid level range {}-{}", self.min_level, self.max_level));
+        }
+        if self.preferred_distance > self.attack_range {
+            return Err(format!(
+                "prefers to stand at {:.1} but only attacks up to {:.1}",
+                self.preferred_distance, self.attack_range,
+            ));
+        }
+        self.behaviour.validate()?;
+        if let Some(entry) = self.loot.iter().find(|e| e.min_quantity == 0 || e.min_quantity > e.max_quantity) {
+            return Err(format!("invalid quantity range for item {}", entry.item_id));
+        }
+        if let Some(ability) = self.abilities.iter().find(|a| {
+            !matches!(a.target_type, TargetType::Enemy | TargetType::Ally | TargetType::SelfOnly)
+        }) {
+            return Err(format!("ability {} must target Enemy, Ally or SelfOnly", ability.name));
+        }
+        Ok(())
+    }
+}
+
+/// Normalize a template id for lookups ("Goblin_Shaman" and "goblinshaman" are the same mob)
+fn normalize_id(id: &str) -> String {
+    id.to_lowercase().replace('_', "")
+}
+
+/// Enemy templates by id
+#[derive(Debug, Default)]
+pub struct EnemyTemplates {
+    templates: HashMap<String, EnemyTemplate>,
+}
+
+impl EnemyTemplates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load templates from a JSON file (object keyed by template id)
+    pub fn load_from_json<P: AsRef<Path>>(&mut self, path: P) -> Result<(), String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read enemy_templates.json: {}", e))?;
+
+        let templates: HashMap<String, EnemyTemplate> = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse enemy_templates.json: {}", e))?;
+
+        self.templates = Self::build(templates)?;
+        for (id, template) in &self.templates {
+            info!("Loaded enemy template {} ({}, levels {}-{}, {} abilities, {} loot entries)",
+                id, template.name, template.min_level, template.max_level,
+                template.abilities.len(), template.loot.len());
+        }
+
+        Ok(())
+    }
+
+    /// Validate parsed templates and key them by normalized id
+    /// Fails on the first bad template, so nothing is half-loaded
+    fn build(templates: HashMap<String, EnemyTemplate>) -> Result<HashMap<String, EnemyTemplate>, String> {
+        // Sorted so the same file always reports the same error
+        let mut templates: Vec<_> = templates.into_iter().collect();
+        templates.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut built = HashMap::with_capacity(templates.len());
+        for (id, template) in templates {
+            template.validate().map_err(|e| format!("Enemy template {}: {}", id, e))?;
+            let key = normalize_id(&id);
+            if built.contains_key(&key) {
+                return Err(format!("Enemy template {} clashes with another id that normalizes to {}", id, key));
+            }
+            built.insert(key, template);
+        }
+        Ok(built)
+    }
+
+    /// Get a template by id (case and underscores are ignored)
+    pub fn get(&self, id: &str) -> Option<&EnemyTemplate> {
+        self.templates.get(&normalize_id(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_templates() {
+        let json = r#"{
+            "Goblin_Shaman": {
+                "name": "Goblin Shaman",
+                "model": "GoblinShaman",
+                "health": 40,
+                "attack": 6,
+                "min_level": 2,
+                "max_level": 4,
+                "attack_range": 10.0,
+                "preferred_distance": 6.0,
+                "abilities": [{
+                    "name": "Mend",
+                    "cooldown": 8.0,
+                    "range": 10.0,
+                    "target_type": "Ally",
+                    "health_threshold": 0.5,
+                    "effects": [{ "Heal": { "base": 10, "health_scaling": 0.2 } }]
+                }],
+                "loot": [{ "item_id": 3, "drop_chance": 1.0, "min_quantity": 2, "max_quantity": 2 }]
+            }
+        }"#;
+        let parsed: HashMap<String, EnemyTemplate> = serde_json::from_str(json).unwrap();
+        let loaded = EnemyTemplates {
+            templates: parsed.into_iter().map(|(id, t)| (normalize_id(&id), t)).collect(),
+        };
+
+        // Lookups ignore case and underscores
+        let shaman = loaded.get("goblinshaman").unwrap();
+        assert!(loaded.get("GOBLIN_SHAMAN").is_some());
+        assert!(shaman.validate().is_ok());
+
+        // Unset fields fall back to defaults
+        assert_eq!(shaman.attack_range, 10.0);
+        assert_eq!(shaman.projectile_speed, 0.0);
+        assert_eq!(shaman.leash_range, default_leash_range());
+        assert_eq!(shaman.abilities[0].target_type, TargetType::Ally);
+        assert!(matches!(shaman.abilities[0].effects[0], AbilityEffect::Heal { base: 10, .. }));
+
+        // Level 3 = +30% over level 1
+        assert_eq!(shaman.stats_for_level(3), (52, 7));
+        assert_eq!(shaman.roll_loot(), vec![(3, 2)]);
+    }
+
+    #[test]
+    fn test_bad_template_rejects_the_whole_file() {
+        let mob = |min_level: u8| format!(
+            r#"{{ "name": "Wolf", "model": "Wolf", "health": 10, "attack": 1, "min_level": {}, "max_level": 3 }}"#,
+            min_level,
+        );
+        let parse = |json: String| serde_json::from_str::<HashMap<String, EnemyTemplate>>(&json).unwrap();
+
+        let built = EnemyTemplates::build(parse(format!(r#"{{ "Wolf": {}, "Boar": {} }}"#, mob(1), mob(2)))).unwrap();
+        assert_eq!(built.len(), 2);
+
+        // One invalid entry fails the load instead of dropping just that mob
+        assert!(EnemyTemplates::build(parse(format!(r#"{{ "Wolf": {}, "Boar": {} }}"#, mob(1), mob(0)))).is_err());
+
+        // Ids that only differ by case or underscores would overwrite each other
+        let err = EnemyTemplates::build(parse(format!(r#"{{ "Dire_Wolf": {}, "direwolf": {} }}"#, mob(1), mob(1)))).unwrap_err();
+        assert!(err.contains("direwolf"));
+    }
+}
@@ -8,6 +8,7 @@ use std::io::{BufReader, Read};
 use std::path::Path;
 use log::{info, warn, error};
 use serde::Deserialize;
+use crate::navigation::Fingerprint;
 
 /// Heightmap metadata structure (matches Godot export format)
 #[derive(Debug, Deserialize)]
@@ -23,7 +24,7 @@ struct HeightmapMetadata {
 }
 
 /// Heightmap data for a single zone
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Heightmap {
     /// Width of the heightmap in pixels
     width: u32,
@@ -142,6 +143,25 @@ impl Heightmap {
         self.heights.get(index).copied().unwrap_or(0.0)
     }
     
+    /// World-space corners covered by the heightmap ([min_x, min_z], [max_x, max_z])
+    pub fn bounds(&self) -> ([f32; 2], [f32; 2]) {
+        ([self.world_min_x, self.world_min_z], [self.world_max_x, self.world_max_z])
+    }
+    
+    /// Hash of the height data (detects when derived data needs rebuilding)
+    pub fn fingerprint(&self) -> u64 {
+        let mut hash = Fingerprint::new();
+        hash.write_u32(self.width);
+        hash.write_u32(self.height);
+        for value in [self.world_min_x, self.world_max_x, self.world_min_z, self.world_max_z] {
+            hash.write_f32(value);
+        }
+        for height in &self.heights {
+            hash.write_f32(*height);
+        }
+        hash.finish()
+    }
+    
     /// Check if a world position is within the heightmap bounds
     pub fn contains(&self, world_x: f32, world_z: f32) -> bool {
         world_x >= self.world_min_x && world_x <= self.world_max_x &&
@@ -0,0 +1,170 @@
+//! Metin stones.
+//!
+//! Stone templates are loaded from `metin_stones.json` next to the server
+//! binary. Each template keeps a number of stones standing at random points in
+//! a zone's spawn areas. A stone has health and defense but never fights back;
+//! while it is attacked it calls waves of linked enemies every few seconds.
+//! Destroying it pays out its loot table and queues a new stone somewhere else
+//! in the zone. The manager only tracks templates and respawn timers; the world
+//! places stones and carries out their waves.
+
+use std::path::Path;
+use log::info;
+use serde::Deserialize;
+use super::boss::BossLootTable;
+use super::world_event::WaveEnemy;
+
+/// Seconds before trying again when no spawn point was found
+const RETRY_DELAY: f32 = 30.0;
+
+fn default_count() -> u32 {
+    1
+}
+
+fn default_max_linked() -> u32 {
+    12
+}
+
+fn default_wave_radius() -> f32 {
+    4.0
+}
+
+/// Metin stone definition
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetinStoneTemplate {
+    pub id: String,
+    pub name: String,
+    pub zone_id: u32,
+    /// Spawn areas stones are placed in (empty = any area in the zone)
+    #[serde(default)]
+    pub spawn_areas: Vec<String>,
+    /// Stones of this kind standing at once
+    #[serde(default = "default_count")]
+    pub count: u32,
+    pub level: u8,
+    pub max_health: u32,
+    #[serde(default)]
+    pub defense: u32,
+    /// Seconds after destruction before a new stone appears
+    pub respawn_time: f32,
+    /// Seconds between waves while the stone is under attack
+    pub wave_interval: f32,
+    /// Enemies called per wave
+    pub wave: Vec<WaveEnemy>,
+    /// How far from the stone wave enemies appear
+    #[serde(default = "default_wave_radius")]
+    pub wave_radius: f32,
+    /// No new waves while this many linked enemies are alive
+    #[serde(default = "default_max_linked")]
+    pub max_linked: u32,
+    /// Experience for the player who destroys the stone
+    #[serde(default)]
+    pub experience: u32,
+    #[serde(default)]
+    pub loot: BossLootTable,
+}
+
+/// Loads stone templates and tracks stone respawns
+#[derive(Default)]
+pub struct MetinStoneManager {
+    templates: Vec<MetinStoneTemplate>,
+    /// Stones waiting to appear (template index, seconds left)
+    respawns: Vec<(usize, f32)>,
+}
+
+impl MetinStoneManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load stone templates from a JSON file (stones appear on the next update)
+    pub fn load_from_json<P: AsRef<Path>>(&mut self, path: P) -> Result<(), String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read metin_stones.json: {}", e))?;
+        let templates: Vec<MetinStoneTemplate> = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse metin_stones.json: {}", e))?;
+
+        for template in &templates {
+            if template.max_health == 0 {
+                return Err(format!("Metin stone {} needs health", template.id));
+            }
+            if template.wave_interval <= 0.0 {
+                return Err(format!("Metin stone {} needs a positive wave_interval", template.id));
+            }
+        }
+
+        info!("Loaded {} metin stone templates", templates.len());
+        self.respawns = templates.iter().enumerate()
+            .flat_map(|(index, template)| std::iter::repeat_n((index, 0.0), template.count as usize))
+            .collect();
+        self.templates = templates;
+        Ok(())
+    }
+
+    pub fn template(&self, index: usize) -> &MetinStoneTemplate {
+        &self.templates[index]
+    }
+
+    /// Check that every spawn area a template names exists in its zone
+    pub fn validate_spawn_areas(&self, area_exists: impl Fn(u32, &str) -> bool) -> Result<(), String> {
+        for template in &self.templates {
+            if let Some(area) = template.spawn_areas.iter().find(|area| !area_exists(template.zone_id, area)) {
+                return Err(format!("Metin stone {} uses unknown spawn area {} in zone {}", template.id, area, template.zone_id));
+            }
+        }
+        Ok(())
+    }
+
+    /// Tick respawn timers
+    /// Returns the templates a stone should be placed for now
+    pub fn update(&mut self, delta: f32) -> Vec<usize> {
+        let mut ready = Vec::new();
+        self.respawns.retain_mut(|(index, remaining)| {
+            *remaining -= delta;
+            if *remaining > 0.0 {
+                return true;
+            }
+            ready.push(*index);
+            false
+        });
+        ready
+    }
+
+    /// No spawn point was found: try again later
+    pub fn retry(&mut self, index: usize) {
+        self.respawns.push((index, RETRY_DELAY));
+    }
+
+    /// A stone was removed without being destroyed: place a new one right away
+    pub fn replace(&mut self, index: usize) {
+        self.respawns.push((index, 0.0));
+    }
+
+    /// A stone was destroyed: queue its replacement
+    pub fn on_destroyed(&mut self, index: usize) {
+        let template = &self.templates[index];
+        info!("Metin stone {} destroyed, a new one appears in {:.0}s", template.name, template.respawn_time);
+        self.respawns.push((index, template.respawn_time));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_spawn_areas() {
+        let template: MetinStoneTemplate = serde_json::from_str(r#"{
+            "id": "test", "name": "Stone of Battle", "zone_id": 1, "level": 5,
+            "max_health": 100, "defense": 10, "respawn_time": 60.0, "wave_interval": 5.0,
+            "spawn_areas": ["meadow"], "wave": [{ "enemy_type": "Goblin", "count": 2 }]
+        }"#).unwrap();
+        let mut manager = MetinStoneManager::new();
+        manager.templates.push(template);
+
+        assert!(manager.validate_spawn_areas(|zone_id, id| zone_id == 1 && id == "meadow").is_ok());
+        // Renamed area, or an area with the same id in another zone
+        assert!(manager.validate_spawn_areas(|_, id| id == "meadows").is_err());
+        assert!(manager.validate_spawn_areas(|zone_id, _| zone_id == 2).is_err());
+    }
+}
@@ -3,19 +3,44 @@
 mod zone_manager;
 pub mod heightmap;
 pub mod spawn_area;
+pub mod combat_log;
+pub mod config;
+pub mod death;
+pub mod specialization;
+pub mod boss;
+pub mod enemy_template;
+pub mod patrol;
+pub mod npc_schedule;
+pub mod world_clock;
+pub mod world_event;
+pub mod metin_stone;
+#[cfg(test)]
+mod tick_bench;
 
 pub use zone_manager::{ZoneManager, ZoneDefinition, ZoneSpawnPoint, ZoneNpcSpawn};
-pub use spawn_area::{SpawnArea, SpawnAreaManager, EnemySpawnConfig};
+pub use spawn_area::{SpawnArea, SpawnAreaManager, SpawnRequest, EnemySpawnConfig};
 pub use heightmap::Heightmap;
+pub use combat_log::CombatLog;
+pub use config::JsonConfig;
+pub use death::DeathPenaltyConfig;
+pub use specialization::SpecializationConfig;
+pub use boss::BossManager;
+pub use enemy_template::EnemyTemplates;
+pub use patrol::PatrolManager;
+pub use npc_schedule::NpcSchedules;
+pub use world_clock::WorldClock;
+pub use world_event::EventManager;
+pub use metin_stone::MetinStoneManager;
 
 use std::collections::HashMap;
-use log::{info, debug};
+use log::{info, debug, warn, error};
 use rand::Rng;
 
 use mmo_shared::{
-    ServerMessage, AnimationState, EnemyType, NpcType, NpcState, InventorySlot, ItemDef, ItemType,
-    CharacterClass, Gender, Empire, AbilityEffect, TargetType,
-    get_ability_by_id, get_item_slot_size,
+    ServerMessage, AnimationState, NpcType, NpcState, InventorySlot, ItemDef, ItemType,
+    CharacterClass, Gender, Empire, AbilityDef, AbilityEffect, TargetType, HitOutcome, SkillRank, Specialization,
+    Attributes, PrimaryStat, ItemEffect, CombatLogEntry, RespawnType, RESPEC_GOLD_PER_LEVEL, MAX_DURABILITY,
+    get_item_slot_size, skill_rank_label,
 };
 
 /// Result of equipping an item
@@ -24,11 +49,38 @@ pub enum EquipResult {
     Armor(Option<u32>),
 }
 
-use crate::entities::player::BuffEffect;
+use crate::entities::player::{BuffEffect, ResourceSnapshot};
+use enemy_template::EnemyAbility;
 
+use crate::ai::{self, AiState, Roam};
+use crate::combat;
 use crate::persistence::InventorySlotData;
 
-use crate::entities::{ServerPlayer, ServerEnemy, ServerNpc, WorldItem};
+use crate::entities::{
+    ServerPlayer, ServerEnemy, ServerNpc, ServerMetinStone, WorldItem, CastEvent, ServerProjectile, ProjectilePayload,
+    ProjectileImpact, ARROW_SPEED, PROJECTILE_HEIGHT,
+};
+use world_clock::WorldConditions;
+use world_event::{EventOutcome, EventSpawn, FinishedEvent};
+use crate::navigation::{self, NavigationState, Obstacle, SpatialGrid, Vec2, LOCAL_NAV_RADIUS, SPATIAL_CELL_SIZE};
+
+/// Extra search radius covering movement since the spatial index was built (units)
+const INDEX_SLACK: f32 = 4.0;
+
+/// How far past its own radius an enemy looks for others to keep apart from (units)
+const CROWD_RADIUS: f32 = 2.0;
+
+/// Seconds between checks for area enemies whose time of day or weather has passed
+const SPAWN_CONDITION_INTERVAL: f32 = 5.0;
+
+/// Sender id of server announcements in chat
+const ANNOUNCEMENT_SENDER_ID: u64 = 0;
+
+/// How close a player must stand to pick up a world item (units)
+const PICKUP_RANGE: f32 = 5.0;
+
+/// How far from the boss summoned adds appear (units)
+const BOSS_ADD_SPAWN_DISTANCE: f32 = 3.0;
 
 /// The game world containing all entities
 pub struct GameWorld {
@@ -36,39 +88,101 @@ pub struct GameWorld {
     enemies: HashMap<u64, ServerEnemy>,
     npcs: HashMap<u64, ServerNpc>,
     world_items: HashMap<u64, WorldItem>,
+    projectiles: HashMap<u64, ServerProjectile>,
+    metin_stones: HashMap<u64, ServerMetinStone>,
+    /// Shared by enemies and Metin stones, so an attack target id never means both
     next_enemy_id: u64,
     next_npc_id: u64,
     next_item_id: u64,
+    next_projectile_id: u64,
     /// Item definitions loaded from database
     pub items: HashMap<u32, ItemDef>,
+    /// Ability definitions loaded from database
+    pub abilities: HashMap<u32, AbilityDef>,
     /// Zone manager
     pub zone_manager: ZoneManager,
     /// Spawn area manager for enemy spawning
     spawn_area_manager: SpawnAreaManager,
+    /// Combat events recorded since the server last drained them
+    combat_log: CombatLog,
+    /// Penalties applied when players die
+    death_penalty: DeathPenaltyConfig,
+    /// When class specializations unlock
+    specialization_config: SpecializationConfig,
+    /// Boss templates, respawns and live encounters
+    bosses: BossManager,
+    /// Attack ranges, spacing and abilities per enemy type
+    enemy_templates: EnemyTemplates,
+    /// Enemies hit by players since the last enemy update (enemy_id, player_id)
+    enemy_pulls: Vec<(u64, u64)>,
+    /// Patrol routes and their members
+    patrols: PatrolManager,
+    /// Players per zone by position (rebuilt at the end of every update)
+    player_index: HashMap<u32, SpatialGrid<u64>>,
+    /// Enemies per zone by position (rebuilt at the end of every update)
+    enemy_index: HashMap<u32, SpatialGrid<u64>>,
+    /// Time of day and weather
+    clock: WorldClock,
+    /// Conditions spawn areas pick enemies for (refreshed every few seconds)
+    spawn_conditions: WorldConditions,
+    /// Seconds until spawn conditions are checked again
+    spawn_condition_timer: f32,
+    /// Scheduled world events
+    events: EventManager,
+    /// Messages for everyone in a zone (zone_id, message), drained by the server
+    zone_messages: Vec<(u32, ServerMessage)>,
+    /// Messages for single players, drained by the server
+    player_messages: Vec<(u64, Vec<ServerMessage>)>,
+    /// Metin stone templates and respawns
+    metin_stone_manager: MetinStoneManager,
 }
 
 impl GameWorld {
-    pub fn new(items: HashMap<u32, ItemDef>, zone_manager: ZoneManager) -> Self {
-        Self::with_spawn_areas(items, zone_manager, SpawnAreaManager::new())
+    pub fn new(items: HashMap<u32, ItemDef>, abilities: HashMap<u32, AbilityDef>, zone_manager: ZoneManager) -> Self {
+        Self::with_spawn_areas(items, abilities, zone_manager, SpawnAreaManager::new(), EnemyTemplates::new())
     }
     
-    /// Create a new game world with spawn areas
+    /// Create a new game world with spawn areas and the enemy templates they spawn from
     pub fn with_spawn_areas(
         items: HashMap<u32, ItemDef>,
+        abilities: HashMap<u32, AbilityDef>,
         zone_manager: ZoneManager,
         spawn_area_manager: SpawnAreaManager,
+        enemy_templates: EnemyTemplates,
     ) -> Self {
+        let clock = WorldClock::default();
+        let spawn_conditions = clock.conditions();
         let mut world = Self {
             players: HashMap::new(),
             enemies: HashMap::new(),
             npcs: HashMap::new(),
             world_items: HashMap::new(),
+            projectiles: HashMap::new(),
+            metin_stones: HashMap::new(),
             next_enemy_id: 10000, // Start enemy IDs high to avoid confusion with player IDs
             next_npc_id: 30000,   // NPCs start at 30000
             next_item_id: 20000,
+            next_projectile_id: 40000,
             items,
+            abilities,
             zone_manager,
             spawn_area_manager,
+            combat_log: CombatLog::new(),
+            death_penalty: DeathPenaltyConfig::default(),
+            specialization_config: SpecializationConfig::default(),
+            bosses: BossManager::new(),
+            enemy_templates,
+            enemy_pulls: Vec::new(),
+            patrols: PatrolManager::new(),
+            player_index: HashMap::new(),
+            enemy_index: HashMap::new(),
+            clock,
+            spawn_conditions,
+            spawn_condition_timer: SPAWN_CONDITION_INTERVAL,
+            events: EventManager::new(),
+            zone_messages: Vec::new(),
+            player_messages: Vec::new(),
+            metin_stone_manager: MetinStoneManager::new(),
         };
         
         // Spawn enemies for all zones using spawn areas
@@ -84,27 +198,8 @@ impl GameWorld {
     fn spawn_all_zone_enemies(&mut self) {
         // Try spawn areas first
         if self.spawn_area_manager.has_spawn_areas() {
-            let spawns = self.spawn_area_manager.get_initial_spawns();
-            let total_spawned = spawns.len();
-            
-            // We need to collect area_ids before modifying spawn_area_manager
-            // The spawns are generated in order by area, so we can track which area each belongs to
-            let mut enemy_area_assignments: Vec<(u64, String)> = Vec::new();
-            
-            for (zone_id, pos_xz, enemy_type, level) in &spawns {
-                let position = [pos_xz[0], 0.0, pos_xz[1]];
-                let enemy_id = self.spawn_enemy_with_level(*zone_id, position, *enemy_type, *level);
-                
-                // Find which area this enemy belongs to
-                if let Some(area_id) = self.spawn_area_manager.find_area_at(*zone_id, pos_xz[0], pos_xz[1]) {
-                    enemy_area_assignments.push((enemy_id, area_id.to_string()));
-                }
-            }
-            
-            // Now register all enemies with their areas
-            for (enemy_id, area_id) in enemy_area_assignments {
-                self.spawn_area_manager.register_enemy(enemy_id, &area_id);
-            }
+            let spawns = self.spawn_area_manager.get_initial_spawns(self.spawn_conditions);
+            let total_spawned = self.spawn_area_enemies(spawns).len();
             
             info!("Spawned {} enemies using spawn areas", total_spawned);
         } else {
@@ -112,8 +207,55 @@ impl GameWorld {
         }
     }
     
-    /// Spawn an enemy with a specific level
-    pub fn spawn_enemy_with_level(&mut self, zone_id: u32, position: [f32; 3], enemy_type: EnemyType, level: u8) -> u64 {
+    /// Place the enemies spawn areas asked for and register them with their area
+    /// Requests that fail (unknown template) give their slot back to the area
+    /// Returns spawn messages for the enemies placed
+    fn spawn_area_enemies(&mut self, spawns: Vec<SpawnRequest>) -> Vec<ServerMessage> {
+        let mut messages = Vec::new();
+        for request in spawns {
+            let position = [request.position[0], 0.0, request.position[1]];
+            let Some(enemy_id) = self.spawn_enemy_with_level(request.zone_id, position, &request.template_id, request.level) else {
+                self.spawn_area_manager.spawn_failed(&request);
+                continue;
+            };
+            self.register_area_enemy(enemy_id, &request.area_id);
+            
+            if let Some(enemy) = self.enemies.get(&enemy_id) {
+                messages.push(ServerMessage::EnemySpawn {
+                    id: enemy_id,
+                    zone_id: request.zone_id,
+                    enemy_type: enemy.enemy_type,
+                    name: enemy.name().to_string(),
+                    position: enemy.position,
+                    health: enemy.health,
+                    max_health: enemy.max_health,
+                    level: enemy.level,
+                });
+            }
+        }
+        messages
+    }
+    
+    /// Register an enemy with the spawn area it came from and let it wander there
+    fn register_area_enemy(&mut self, enemy_id: u64, area_id: &str) {
+        self.spawn_area_manager.register_enemy(enemy_id, area_id);
+        let area = self.spawn_area_manager.find_area(area_id);
+        if let (Some(area), Some(enemy)) = (area, self.enemies.get_mut(&enemy_id)) {
+            enemy.ai.roam = Roam::Wander { polygon: area.polygon.clone() };
+        }
+    }
+    
+    /// Spawn an enemy from its template at a specific level
+    /// Returns None if there is no template with that id
+    pub fn spawn_enemy_with_level(&mut self, zone_id: u32, position: [f32; 3], template_id: &str, level: u8) -> Option<u64> {
+        let template = match self.enemy_templates.get(template_id) {
+            Some(template) => template.clone(),
+            None => {
+                warn!("Cannot spawn enemy: unknown enemy template {}", template_id);
+                return None;
+            }
+        };
+        
         let id = self.next_enemy_id;
         self.next_enemy_id += 1;
         
@@ -121,28 +263,14 @@ impl GameWorld {
         let terrain_height = self.zone_manager.get_terrain_height(zone_id, position[0], position[2]);
         let adjusted_position = [position[0], terrain_height + Self::ENTITY_GROUND_OFFSET, position[2]];
         
-        debug!("Spawning enemy {} (level {}) at ({:.1}, {:.1}, {:.1}) -> adjusted Y to {:.1}",
-            id, level, position[0], position[1], position[2], adjusted_position[1]);
-        
-        let mut enemy = crate::entities::ServerEnemy::new(id, zone_id, enemy_type, adjusted_position);
-        enemy.level = level;
-        
-        // Recalculate stats based on level
-        let (base_health, base_attack) = match enemy_type {
-            EnemyType::Goblin => (50, 8),
-            EnemyType::Wolf => (65, 10),
-            EnemyType::Skeleton => (80, 14),
-            EnemyType::Mutant => (150, 25),
-        };
-        
-        let level_multiplier = 1.0 + (level as f32 - 1.0) * 0.15;
-        enemy.max_health = (base_health as f32 * level_multiplier) as u32;
-        enemy.health = enemy.max_health;
-        enemy.attack_power = (base_attack as f32 * level_multiplier) as u32;
+        debug!("Spawning {} {} (level {}) at ({:.1}, {:.1}, {:.1}) -> adjusted Y to {:.1}",
+            template.name, id, level, position[0], position[1], position[2], adjusted_position[1]);
         
+        let mut enemy = ServerEnemy::new(id, zone_id, template_id, template, adjusted_position, level);
+        enemy.navmesh = self.zone_manager.get_navmesh(zone_id);
         self.enemies.insert(id, enemy);
         
-        id
+        Some(id)
     }
     
     /// Spawn NPCs for all zones using zone_manager data
@@ -151,17 +279,20 @@ impl GameWorld {
         let mut total_spawned = 0;
         
         // Collect spawn data first to avoid borrow issues
-        let mut spawn_data: Vec<(u32, [f32; 3], f32, NpcType)> = Vec::new();
+        let mut spawn_data: Vec<(i32, u32, [f32; 3], f32, NpcType)> = Vec::new();
         for zone_id in zone_ids {
             let spawns = self.zone_manager.get_npc_spawns(zone_id);
             for spawn in spawns {
-                spawn_data.push((zone_id, spawn.position, spawn.rotation, spawn.npc_type));
+                spawn_data.push((spawn.id, zone_id, spawn.position, spawn.rotation, spawn.npc_type));
             }
         }
         
         // Now spawn NPCs
-        for (zone_id, position, rotation, npc_type) in spawn_data {
-            self.spawn_npc(zone_id, position, rotation, npc_type);
+        for (spawn_id, zone_id, position, rotation, npc_type) in spawn_data {
+            let npc_id = self.spawn_npc(zone_id, position, rotation, npc_type);
+            if let Some(npc) = self.npcs.get_mut(&npc_id) {
+                npc.spawn_id = Some(spawn_id);
+            }
             total_spawned += 1;
         }
         
@@ -181,12 +312,32 @@ impl GameWorld {
         debug!("Spawning NPC {} at ({:.1}, {:.1}, {:.1}) -> adjusted Y to {:.1} (terrain: {:.1})",
             id, position[0], position[1], position[2], adjusted_position[1], terrain_height);
         
-        let npc = ServerNpc::new(id, zone_id, npc_type, adjusted_position, rotation);
+        let mut npc = ServerNpc::new(id, zone_id, npc_type, adjusted_position, rotation);
+        npc.navmesh = self.zone_manager.get_navmesh(zone_id);
         self.npcs.insert(id, npc);
         
         id
     }
     
+    /// Give NPCs their schedules (matched by spawn id)
+    pub fn set_npc_schedules(&mut self, schedules: &NpcSchedules) {
+        for npc in self.npcs.values_mut() {
+            npc.schedule = npc.spawn_id.and_then(|id| schedules.get(id)).cloned();
+        }
+        let scheduled = self.npcs.values().filter(|n| n.is_roaming()).count();
+        info!("{} NPCs follow a schedule", scheduled);
+    }
+    
+    /// Walk NPCs along their schedules
+    fn update_npcs(&mut self, delta: f32) {
+        for npc in self.npcs.values_mut().filter(|n| n.is_roaming()) {
+            let obstacles = self.zone_manager.get_obstacles(npc.zone_id);
+            npc.update(delta, obstacles);
+            npc.position[1] = self.zone_manager.get_terrain_height(npc.zone_id, npc.position[0], npc.position[2])
+                + Self::ENTITY_GROUND_OFFSET;
+        }
+    }
+    
     /// Get all NPCs
     pub fn get_npcs(&self) -> Vec<&ServerNpc> {
         self.npcs.values().collect()
@@ -214,20 +365,23 @@ impl GameWorld {
             
             for _ in 0..spawns_needed {
                 if let Some(pos_xz) = area.get_random_point() {
-                    if let Some(config) = area.select_enemy_type() {
+                    if let Some(config) = area.select_enemy_type(self.spawn_conditions) {
                         let level = rng.gen_range(config.min_level..=config.max_level);
                         let position = [pos_xz[0], 0.0, pos_xz[1]];
-                        let enemy_id = self.spawn_enemy_with_level(zone_id, position, config.enemy_type, level);
+                        let Some(enemy_id) = self.spawn_enemy_with_level(zone_id, position, &config.template_id, level) else {
+                            continue;
+                        };
                         
                         // Register the enemy with its area
                         let area_id = area.id.clone();
-                        self.spawn_area_manager.register_enemy(enemy_id, &area_id);
+                        self.register_area_enemy(enemy_id, &area_id);
                         
                         if let Some(enemy) = self.enemies.get(&enemy_id) {
                             messages.push(ServerMessage::EnemySpawn {
                                 id: enemy_id,
                                 zone_id,
                                 enemy_type: enemy.enemy_type,
+                                name: enemy.name().to_string(),
                                 position: enemy.position,
                                 health: enemy.health,
                                 max_health: enemy.max_health,
@@ -248,23 +402,11 @@ impl GameWorld {
     /// Terrain3D heights, so only a minimal offset is needed.
     const ENTITY_GROUND_OFFSET: f32 = 0.1;
     
-    /// Spawn a new enemy in a zone
+    /// Spawn a new enemy in a zone at a random level from its template
     /// Automatically adjusts Y position based on terrain heightmap
-    pub fn spawn_enemy(&mut self, zone_id: u32, position: [f32; 3], enemy_type: EnemyType) -> u64 {
-        let id = self.next_enemy_id;
-        self.next_enemy_id += 1;
-        
-        // Adjust Y position based on terrain height plus ground offset
-        let terrain_height = self.zone_manager.get_terrain_height(zone_id, position[0], position[2]);
-        let adjusted_position = [position[0], terrain_height + Self::ENTITY_GROUND_OFFSET, position[2]];
-        
-        info!("Spawning enemy {} at ({:.1}, {:.1}, {:.1}) -> adjusted Y to {:.1} (terrain: {:.1}, offset: {:.1})",
-            id, position[0], position[1], position[2], adjusted_position[1], terrain_height, Self::ENTITY_GROUND_OFFSET);
-        
-        let enemy = ServerEnemy::new(id, zone_id, enemy_type, adjusted_position);
-        self.enemies.insert(id, enemy);
-        
-        id
+    pub fn spawn_enemy(&mut self, zone_id: u32, position: [f32; 3], template_id: &str) -> Option<u64> {
+        let level = self.enemy_templates.get(template_id)?.roll_level();
+        self.spawn_enemy_with_level(zone_id, position, template_id, level)
     }
     
     /// Spawn a player with saved state (for character selection)
@@ -325,7 +467,7 @@ impl GameWorld {
             }
         }
         
-        let player = ServerPlayer::with_state_and_armor(
+        let mut player = ServerPlayer::with_state_and_armor(
             id,
             name,
             class,
@@ -347,6 +489,7 @@ impl GameWorld {
             experience,
             gold,
         );
+        player.specialization_level = self.specialization_config.unlock_level;
         self.players.insert(id, player);
     }
     
@@ -355,13 +498,167 @@ impl GameWorld {
         self.players.remove(&id);
     }
     
-    /// Respawn a player at a new position with specified health
-    pub fn respawn_player(&mut self, id: u64, position: [f32; 3], health: u32) {
-        if let Some(player) = self.players.get_mut(&id) {
-            player.position = position;
-            player.health = health;
-            player.animation_state = AnimationState::Idle;
-            player.death_announced = false;  // Reset for next death
+    /// Replace the specialization configuration (applies to players spawned afterwards)
+    pub fn set_specialization_config(&mut self, config: SpecializationConfig) {
+        self.specialization_config = config;
+    }
+    
+    /// Character level at which a specialization can be chosen
+    pub fn specialization_level(&self) -> u32 {
+        self.specialization_config.unlock_level
+    }
+    
+    /// Replace the death penalty configuration
+    pub fn set_death_penalty_config(&mut self, config: DeathPenaltyConfig) {
+        self.death_penalty = config;
+    }
+    
+    /// Current death penalty configuration
+    pub fn death_penalty_config(&self) -> &DeathPenaltyConfig {
+        &self.death_penalty
+    }
+    
+    /// Release a dead player to the empire spawn (full health) or their death spot (reduced health)
+    /// Applies resurrection sickness
+    /// Returns (messages for the player, broadcast message), or the reason it failed
+    pub fn release_player(&mut self, player_id: u64, respawn_type: RespawnType) -> Result<(Vec<ServerMessage>, ServerMessage), &'static str> {
+        let player = self.players.get_mut(&player_id).ok_or("Player not found")?;
+        if !player.is_dead() {
+            return Err("You are not dead");
+        }
+        
+        let (position, health) = match respawn_type {
+            RespawnType::Town => (player.empire.spawn_position(), player.max_health),
+            RespawnType::DeathSpot => (
+                player.position,
+                (player.max_health as f32 * self.death_penalty.death_spot_health_percent) as u32,
+            ),
+        };
+        player.revive(position, health);
+        
+        info!("Player {} released at {} {:?} with {} health",
+              player_id, respawn_type.name(), player.position, player.health);
+        
+        let mut messages = vec![ServerMessage::PlayerRespawned {
+            position: player.position,
+            health: player.health,
+            max_health: player.max_health,
+        }];
+        
+        let sickness_duration = self.death_penalty.sickness_duration;
+        if sickness_duration > 0.0 {
+            let buff_id = player.add_buff(
+                0,
+                BuffEffect::ResurrectionSickness(self.death_penalty.sickness_stat_multiplier),
+                sickness_duration,
+                true,
+            );
+            messages.push(ServerMessage::BuffApplied {
+                target_id: player_id,
+                buff_id,
+                ability_id: 0,
+                duration: sickness_duration,
+                is_debuff: true,
+            });
+        }
+        
+        let broadcast = ServerMessage::EntityRespawn {
+            entity_id: player_id,
+            position: player.position,
+            health: player.health,
+        };
+        Ok((messages, broadcast))
+    }
+    
+    /// Apply the death penalty once a player has died and release them when the timer runs out
+    /// Returns (messages for the player, messages for broadcast)
+    fn update_player_death(&mut self, player_id: u64, delta: f32) -> (Vec<ServerMessage>, Vec<ServerMessage>) {
+        let mut player_msgs = Vec::new();
+        let mut broadcast_msgs = Vec::new();
+        
+        let player = match self.players.get_mut(&player_id) {
+            Some(p) if p.is_dead() => p,
+            _ => return (player_msgs, broadcast_msgs),
+        };
+        
+        match player.release_timer.as_mut() {
+            None => {
+                // Just died: drop casts, attacks and buffs, then apply the penalty
+                player.cancel_cast();
+                player.stop_auto_attack();
+                player.is_resting = false;
+                for buff in player.active_buffs.drain(..) {
+                    player_msgs.push(ServerMessage::BuffRemoved {
+                        target_id: player_id,
+                        buff_id: buff.id,
+                    });
+                }
+                
+                let experience_lost = player.apply_death_penalty(
+                    self.death_penalty.experience_loss_percent,
+                    self.death_penalty.durability_loss,
+                );
+                player.release_timer = Some(self.death_penalty.auto_release_time);
+                
+                info!("Player {} died: lost {} XP, gear durability now {}",
+                      player.name, experience_lost, player.equipment_durability);
+                
+                player_msgs.push(ServerMessage::DeathPenalty {
+                    experience_lost,
+                    durability: player.equipment_durability,
+                    auto_release_in: self.death_penalty.auto_release_time,
+                });
+                player_msgs.push(ServerMessage::ExperienceGained {
+                    amount: 0,
+                    current_experience: player.experience,
+                    experience_to_next_level: player.get_experience_to_next_level(),
+                });
+                player_msgs.push(Self::durability_message(player));
+            }
+            Some(timer) => {
+                *timer -= delta;
+                if *timer <= 0.0 {
+                    if let Ok((msgs, broadcast)) = self.release_player(player_id, RespawnType::Town) {
+                        player_msgs.extend(msgs);
+                        broadcast_msgs.push(broadcast);
+                    }
+                }
+            }
+        }
+        
+        (player_msgs, broadcast_msgs)
+    }
+    
+    fn durability_message(player: &ServerPlayer) -> ServerMessage {
+        ServerMessage::DurabilityUpdate {
+            durability: player.equipment_durability,
+            max_durability: MAX_DURABILITY,
+            repair_cost: player.repair_cost(),
+        }
+    }
+    
+    /// Get a player's gear durability (sent on character select)
+    pub fn get_player_durability(&self, player_id: u64) -> Option<ServerMessage> {
+        self.players.get(&player_id).map(Self::durability_message)
+    }
+    
+    /// Repair a player's equipped gear for gold
+    /// Returns the messages to send to the player
+    pub fn repair_player_equipment(&mut self, player_id: u64) -> Vec<ServerMessage> {
+        let player = match self.players.get_mut(&player_id) {
+            Some(p) => p,
+            None => return Vec::new(),
+        };
+        
+        match player.repair_equipment() {
+            Ok(cost) => {
+                info!("Player {} repaired their gear for {} gold", player.name, cost);
+                vec![
+                    Self::durability_message(player),
+                    ServerMessage::GoldUpdate { gold: player.gold },
+                ]
+            }
+            Err(reason) => vec![ServerMessage::RepairFailed { reason: reason.into() }],
         }
     }
     
@@ -415,6 +712,7 @@ impl GameWorld {
     }
     
     /// Update player state from client input
+    /// Returns a position correction if the move was faster than the player's speed allows
     pub fn update_player_state(
         &mut self,
         player_id: u64,
@@ -422,49 +720,204 @@ impl GameWorld {
         rotation: f32,
         velocity: [f32; 3],
         animation_state: AnimationState,
-    ) {
-        if let Some(player) = self.players.get_mut(&player_id) {
-            // TODO: Validate position (anti-cheat)
-            player.position = position;
-            player.rotation = rotation;
-            player.velocity = velocity;
-            player.animation_state = animation_state;
+    ) -> Option<ServerMessage> {
+        let player = self.players.get_mut(&player_id)?;
+        
+        if !player.validate_movement(position, &self.items) {
+            debug!("Player {} moved too fast, correcting position", player_id);
+            return Some(ServerMessage::Teleport { position: player.position });
+        }
+        
+        player.position = position;
+        player.rotation = rotation;
+        player.velocity = velocity;
+        player.animation_state = animation_state;
+        
+        // Moving stands the player up
+        if velocity[0] != 0.0 || velocity[2] != 0.0 {
+            player.is_resting = false;
+        }
+        None
+    }
+    
+    /// Start (or switch) auto-attack from a player on a target
+    /// Re-sending the current target is a no-op, so the swing timer can't be reset
+    /// Returns (messages_for_attacker, messages_for_broadcast)
+    pub fn start_auto_attack(&mut self, attacker_id: u64, target_id: u64) -> (Vec<ServerMessage>, Vec<ServerMessage>) {
+        let mut attacker_msgs = Vec::new();
+        let mut broadcast_msgs = Vec::new();
+        
+        let attacker = match self.players.get_mut(&attacker_id) {
+            Some(p) if !p.is_dead() => p,
+            _ => return (attacker_msgs, broadcast_msgs),
+        };
+        
+        // TODO: PvP combat
+        if !self.enemies.contains_key(&target_id) && !self.metin_stones.contains_key(&target_id) {
+            attacker_msgs.push(ServerMessage::AutoAttackStopped {
+                target_id,
+                reason: "Invalid target".into(),
+            });
+            return (attacker_msgs, broadcast_msgs);
+        }
+        
+        attacker.is_resting = false;
+        if attacker.start_auto_attack(target_id) {
+            attacker_msgs.push(ServerMessage::AutoAttackStarted {
+                target_id,
+                swing_interval: attacker.get_swing_interval(&self.items),
+            });
+        }
+        
+        // Swing right away if the timer allows
+        let (swing_msgs, swing_broadcasts) = self.update_player_auto_attack(attacker_id);
+        attacker_msgs.extend(swing_msgs);
+        broadcast_msgs.extend(swing_broadcasts);
+        
+        (attacker_msgs, broadcast_msgs)
+    }
+    
+    /// Stop a player's auto-attack on request
+    /// Returns the message for the attacker, if they were auto-attacking
+    pub fn stop_auto_attack(&mut self, player_id: u64) -> Option<ServerMessage> {
+        let target_id = self.players.get_mut(&player_id)?.stop_auto_attack()?;
+        Some(ServerMessage::AutoAttackStopped {
+            target_id,
+            reason: "Stopped".into(),
+        })
+    }
+    
+    /// Fire a player's next auto-attack swing if it is ready
+    /// Stops auto-attack when the target dies or leaves range
+    /// Returns (messages_for_attacker, messages_for_broadcast)
+    fn update_player_auto_attack(&mut self, player_id: u64) -> (Vec<ServerMessage>, Vec<ServerMessage>) {
+        let mut attacker_msgs = Vec::new();
+        let mut broadcast_msgs = Vec::new();
+        
+        let player = match self.players.get_mut(&player_id) {
+            Some(p) => p,
+            None => return (attacker_msgs, broadcast_msgs),
+        };
+        let target_id = match player.auto_attack_target {
+            Some(t) => t,
+            None => return (attacker_msgs, broadcast_msgs),
+        };
+        
+        let ranged = player.has_ranged_weapon(&self.items);
+        let attack_range = player.get_attack_range(&self.items);
+        
+        // Validate target before checking timers so stop messages aren't delayed
+        let stop_reason = if player.is_dead() {
+            Some("You are dead")
+        } else {
+            let target = self.enemies.get(&target_id)
+                .map(|enemy| (enemy.position, enemy.health))
+                .or_else(|| self.metin_stones.get(&target_id).map(|stone| (stone.position, stone.health)));
+            match target {
+                Some((position, health)) if health > 0 => {
+                    let dx = position[0] - player.position[0];
+                    let dz = position[2] - player.position[2];
+                    if dx * dx + dz * dz > attack_range * attack_range {
+                        Some("Out of range")
+                    } else if ranged && !navigation::has_line_of_sight(
+                        Vec2::from_3d(player.position),
+                        Vec2::from_3d(position),
+                        self.zone_manager.get_obstacles(player.zone_id),
+                    ) {
+                        Some("No line of sight")
+                    } else {
+                        None
+                    }
+                }
+                _ => Some("Target died"),
+            }
+        };
+        
+        if let Some(reason) = stop_reason {
+            player.stop_auto_attack();
+            attacker_msgs.push(ServerMessage::AutoAttackStopped {
+                target_id,
+                reason: reason.into(),
+            });
+            return (attacker_msgs, broadcast_msgs);
+        }
+        
+        // Stuns and casts pause swings without dropping the target
+        if !player.is_swing_ready() || player.is_stunned() || player.is_casting() {
+            return (attacker_msgs, broadcast_msgs);
+        }
+        
+        player.swing_timer = player.get_swing_interval(&self.items);
+        
+        // Bows fire an arrow; the attack resolves when it lands
+        if ranged {
+            if let Some(spawn) = self.spawn_projectile(
+                player_id,
+                target_id,
+                ProjectilePayload::Attack,
+                ARROW_SPEED,
+                false,
+                attack_range,
+            ) {
+                broadcast_msgs.push(spawn);
+            }
+            return (attacker_msgs, broadcast_msgs);
+        }
+        
+        if let Some(damage_event) = self.process_attack(player_id, target_id) {
+            broadcast_msgs.push(damage_event);
         }
+        
+        (attacker_msgs, broadcast_msgs)
     }
     
-    /// Process an attack from a player to a target
-    pub fn process_attack(&mut self, attacker_id: u64, target_id: u64) -> Option<ServerMessage> {
+    /// Resolve a single attack swing from a player to a target
+    fn process_attack(&mut self, attacker_id: u64, target_id: u64) -> Option<ServerMessage> {
         let attacker = self.players.get(&attacker_id)?;
         
+        // Metin stones only soak up damage
+        if self.metin_stones.contains_key(&target_id) {
+            let base_damage = attacker.calculate_attack_damage(&self.items);
+            return self.hit_metin_stone(attacker_id, target_id, base_damage, None);
+        }
+        
         // Check if target is an enemy
         if let Some(enemy) = self.enemies.get_mut(&target_id) {
-            // Check range (simple distance check)
-            let dx = enemy.position[0] - attacker.position[0];
-            let dz = enemy.position[2] - attacker.position[2];
-            let dist_sq = dx * dx + dz * dz;
-            
-            if dist_sq > 5.0 * 5.0 {
-                debug!("Attack out of range");
-                return None;
-            }
-            
-            // Calculate damage based on equipped weapon
+            // Calculate damage based on equipped weapon, then roll the attack table
             let base_damage = attacker.calculate_attack_damage(&self.items);
-            let mut rng = rand::thread_rng();
-            let is_critical = rng.gen_bool(0.1); // 10% crit chance
-            let damage = if is_critical { base_damage * 2 } else { base_damage };
+            let hit = enemy.apply_immunity(combat::resolve_hit(
+                &attacker.combat_profile(&self.items),
+                &enemy.combat_profile(),
+                base_damage,
+            ));
             
-            // Apply damage
-            enemy.health = enemy.health.saturating_sub(damage);
+            // Apply damage (misses still pull aggro)
+            let health_before = enemy.health;
+            enemy.health = enemy.health.saturating_sub(hit.damage);
             enemy.target_id = Some(attacker_id); // Aggro
+            self.enemy_pulls.push((target_id, attacker_id));
+            
+            self.combat_log.record_attack(
+                combat_log::player_participant(attacker),
+                combat_log::enemy_participant(enemy),
+                None,
+                base_damage,
+                hit,
+                hit.damage,
+                health_before,
+            );
             
-            return Some(ServerMessage::DamageEvent {
+            let event = ServerMessage::DamageEvent {
                 attacker_id,
                 target_id,
-                damage,
+                damage: hit.damage,
                 target_new_health: enemy.health,
-                is_critical,
-            });
+                outcome: hit.outcome,
+            };
+            if let Some(attacker) = self.players.get_mut(&attacker_id) {
+                attacker.enter_combat();
+            }
+            return Some(event);
         }
         
         // TODO: PvP combat
@@ -472,10 +925,16 @@ impl GameWorld {
         None
     }
     
-    /// Pickup an item from the world
+    /// Pickup an item from the world (the player must stand within pickup range)
     pub fn pickup_item(&mut self, player_id: u64, item_entity_id: u64) -> Option<(ServerMessage, ServerMessage)> {
-        let item = self.world_items.remove(&item_entity_id)?;
         let player = self.players.get_mut(&player_id)?;
+        let item = self.world_items.get(&item_entity_id)?;
+        let dx = item.position[0] - player.position[0];
+        let dz = item.position[2] - player.position[2];
+        if (dx * dx + dz * dz).sqrt() > PICKUP_RANGE {
+            return None;
+        }
+        let item = self.world_items.remove(&item_entity_id)?;
         
         // Try to add to inventory
         player.add_to_inventory(item.item_id, item.quantity);
@@ -492,13 +951,34 @@ impl GameWorld {
     }
     
     /// Use an item from inventory
-    pub fn use_item(&mut self, player_id: u64, slot: u8) -> Option<ServerMessage> {
+    pub fn use_item(&mut self, player_id: u64, slot: u8) -> Option<Vec<ServerMessage>> {
         let player = self.players.get_mut(&player_id)?;
-        player.use_item(slot)?;
+        let item_id = player.inventory.get(slot as usize)?.as_ref()?.item_id;
+        let resets_stats = self.items.get(&item_id)
+            .is_some_and(|item| item.effects.iter().any(|e| matches!(e, ItemEffect::ResetStats)));
+        if resets_stats && !player.has_allocated_attributes() {
+            return Some(vec![ServerMessage::StatAllocationFailed { reason: "No stat points to reset".into() }]);
+        }
+        let health_before = player.health;
+        let effects = player.use_item(slot, &self.items)?;
+        
+        if player.health > health_before {
+            self.combat_log.record_heal(
+                Some(combat_log::player_participant(player)),
+                combat_log::player_participant(player),
+                None,
+                Some(item_id),
+                player.health - health_before,
+            );
+        }
         
-        Some(ServerMessage::InventoryUpdate {
+        let mut messages = vec![ServerMessage::InventoryUpdate {
             slots: player.get_inventory_slots(),
-        })
+        }];
+        if effects.iter().any(|e| matches!(e, ItemEffect::ResetStats)) {
+            messages.extend(self.get_player_attributes(player_id));
+        }
+        Some(messages)
     }
     
     /// Drop an item from inventory
@@ -613,63 +1093,212 @@ impl GameWorld {
     pub fn update(&mut self, delta: f32, _tick: u64) -> Vec<ServerMessage> {
         let mut messages = Vec::new();
         
+        // Pick up nav-meshes rebuilt in the background after a /reload
+        if self.zone_manager.poll_navmesh_rebuild() {
+            self.refresh_navmeshes();
+        }
+        
+        // Advance the weather and retire enemies whose time has passed
+        let clock_messages = self.update_world_clock(delta);
+        messages.extend(clock_messages);
+        
         // Update enemies (AI, attacks) and collect damage events
         let damage_events = self.update_enemies(delta);
         messages.extend(damage_events);
         
+        // Walk NPCs along their schedules
+        self.update_npcs(delta);
+        
+        // Run boss phases, abilities and encounter resets
+        let boss_messages = self.update_bosses(delta);
+        messages.extend(boss_messages);
+        
+        // Move projectiles and resolve impacts
+        let projectile_messages = self.update_projectiles(delta);
+        messages.extend(projectile_messages);
+        
         // Check for dead enemies and handle loot drops
         let death_messages = self.process_enemy_deaths();
         messages.extend(death_messages);
         
+        // Destroyed Metin stones pay out, the rest call waves while attacked
+        let stone_messages = self.update_metin_stones(delta);
+        messages.extend(stone_messages);
+        
+        // Start, finish and pay out world events
+        let event_messages = self.update_world_events(delta);
+        messages.extend(event_messages);
+        
         // Process spawn area respawns
         let respawn_messages = self.process_spawn_area_respawns(delta);
         messages.extend(respawn_messages);
         
+        // Spawn bosses whose respawn timer finished
+        let boss_spawn_messages = self.process_boss_spawns(delta);
+        messages.extend(boss_spawn_messages);
+        
+        // Spawn patrol members whose respawn timer finished
+        let patrol_spawn_messages = self.process_patrol_spawns(delta);
+        messages.extend(patrol_spawn_messages);
+        
+        // Place Metin stones whose respawn timer finished
+        self.process_metin_stone_spawns(delta);
+        
+        // Index where everything ended up for proximity queries
+        self.rebuild_spatial_index();
+        
         messages
     }
     
+    /// Rebuild the per-zone player and enemy grids from current positions
+    fn rebuild_spatial_index(&mut self) {
+        self.player_index.clear();
+        for player in self.players.values() {
+            self.player_index
+                .entry(player.zone_id)
+                .or_insert_with(|| SpatialGrid::new(SPATIAL_CELL_SIZE))
+                .insert(player.id, Vec2::from_3d(player.position));
+        }
+        
+        self.enemy_index.clear();
+        for enemy in self.enemies.values() {
+            self.enemy_index
+                .entry(enemy.zone_id)
+                .or_insert_with(|| SpatialGrid::new(SPATIAL_CELL_SIZE))
+                .insert(enemy.id, Vec2::from_3d(enemy.position));
+        }
+    }
+    
+    /// Players in a zone within `radius` of a position
+    /// Uses the index from the last update, so players that joined since are missing
+    pub fn get_players_near(&self, zone_id: u32, position: [f32; 3], radius: f32) -> Vec<&ServerPlayer> {
+        let center = Vec2::from_3d(position);
+        self.player_index.get(&zone_id)
+            .map(|grid| grid.query(center, radius + INDEX_SLACK)
+                .filter_map(|id| self.players.get(&id))
+                .filter(|p| p.zone_id == zone_id && Vec2::from_3d(p.position).distance_to(center) <= radius)
+                .collect())
+            .unwrap_or_default()
+    }
+    
+    /// Enemies in a zone within `radius` of a position
+    /// Uses the index from the last update, so enemies spawned since are missing
+    pub fn get_enemies_near(&self, zone_id: u32, position: [f32; 3], radius: f32) -> Vec<&ServerEnemy> {
+        let center = Vec2::from_3d(position);
+        self.enemy_index.get(&zone_id)
+            .map(|grid| grid.query(center, radius + INDEX_SLACK)
+                .filter_map(|id| self.enemies.get(&id))
+                .filter(|e| e.zone_id == zone_id && Vec2::from_3d(e.position).distance_to(center) <= radius)
+                .collect())
+            .unwrap_or_default()
+    }
+    
     /// Update enemy AI and process enemy attacks
     /// Returns damage events to broadcast
     fn update_enemies(&mut self, delta: f32) -> Vec<ServerMessage> {
         let mut damage_events = Vec::new();
         
-        // Build a map of zone_id -> player positions for that zone
-        let mut zone_player_positions: HashMap<u32, Vec<(u64, [f32; 3])>> = HashMap::new();
+        // Index living players by zone and position
+        let mut zone_players: HashMap<u32, SpatialGrid<(u64, [f32; 3])>> = HashMap::new();
         for player in self.players.values() {
             if !player.is_dead() {
-                zone_player_positions
+                zone_players
                     .entry(player.zone_id)
-                    .or_insert_with(Vec::new)
-                    .push((player.id, player.position));
+                    .or_insert_with(|| SpatialGrid::new(SPATIAL_CELL_SIZE))
+                    .insert((player.id, player.position), Vec2::from_3d(player.position));
             }
         }
         
         // Collect attacks from enemies
         let mut attacks: Vec<(u64, u64, u32)> = Vec::new(); // (enemy_id, player_id, damage)
         
+        // Enemies that were hit bring their friends before deciding what to do
+        for (enemy_id, player_id) in std::mem::take(&mut self.enemy_pulls) {
+            ai::social::pull(&mut self.enemies, enemy_id, player_id);
+        }
+        let zone_neighbours = ai::social::neighbours_by_zone(&self.enemies);
+        let players = &self.players;
+        ai::surround::assign_attack_slots(&mut self.enemies, |id| players.get(&id).map(|p| p.position));
+        let mut pulls: Vec<(u64, u64)> = Vec::new(); // (enemy_id, player_id)
+        let mut evading_packs: Vec<u64> = Vec::new();
+        
         // Log obstacle count once per update cycle (not per enemy)
         static OBSTACLE_LOG_COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
         let log_count = OBSTACLE_LOG_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
         
+        // Per-enemy scratch buffers, reused across the loop
+        let mut player_positions: Vec<(u64, [f32; 3])> = Vec::new();
+        let mut neighbours: Vec<ai::Neighbour> = Vec::new();
+        let mut obstacles: Vec<Obstacle> = Vec::new();
+        
         for enemy in self.enemies.values_mut() {
-            // Get players in the same zone as this enemy
-            let player_positions = zone_player_positions
-                .get(&enemy.zone_id)
-                .map(|v| v.as_slice())
-                .unwrap_or(&[]);
+            player_positions.clear();
+            neighbours.clear();
+            obstacles.clear();
             
-            // Get obstacles for this zone
-            let obstacles = self.zone_manager.get_obstacles(enemy.zone_id);
+            if enemy.health > 0 {
+                // Players in aggro range, plus the one the pack is after however far it ran
+                if let Some(grid) = zone_players.get(&enemy.zone_id) {
+                    let here = Vec2::from_3d(enemy.position);
+                    player_positions.extend(grid.query(here, enemy.template.aggro_range));
+                    let called = enemy.ai.called_target.filter(|id| !player_positions.iter().any(|(p, _)| p == id));
+                    if let Some(player) = called.and_then(|id| self.players.get(&id)) {
+                        if player.zone_id == enemy.zone_id && !player.is_dead() {
+                            player_positions.push((player.id, player.position));
+                        }
+                    }
+                }
+                
+                // Bosses only see players inside their arena
+                if let Some(radius) = self.bosses.arena_radius(enemy.id) {
+                    let center = Vec2::from_3d(enemy.spawn_position);
+                    player_positions.retain(|(_, pos)| Vec2::from_3d(*pos).distance_to(center) <= radius);
+                }
+                
+                // Enemies close enough to bump into, and friends to run to
+                let behaviour = &enemy.template.behaviour;
+                let mut radius = enemy.template.radius + CROWD_RADIUS;
+                if behaviour.flee_for_help {
+                    radius = radius.max(behaviour.help_radius);
+                }
+                if let Some(zone) = zone_neighbours.get(&enemy.zone_id) {
+                    zone.near(enemy.position, radius, &mut neighbours);
+                }
+                
+                // Obstacles close enough to steer around or block line of sight
+                let reach = LOCAL_NAV_RADIUS.max(enemy.template.attack_range) + enemy.template.radius;
+                self.zone_manager.get_obstacles_near(enemy.zone_id, enemy.position, reach, &mut obstacles);
+            }
             
             // Debug log every 100 updates (about every 5 seconds at 20 tick/s)
             if log_count % 100 == 0 && enemy.target_id.is_some() {
-                debug!("Enemy {} in zone {} chasing target, {} obstacles available", 
+                debug!("Enemy {} in zone {} chasing target, {} obstacles nearby", 
                     enemy.id, enemy.zone_id, obstacles.len());
             }
             
-            if let Some((target_player_id, damage)) = enemy.update(delta, player_positions, obstacles) {
+            let had_target = enemy.target_id.is_some();
+            let pack = enemy.ai.pack;
+            let was_evading = enemy.is_evading();
+            
+            if let Some((target_player_id, damage)) = enemy.update(delta, &player_positions, &neighbours, &obstacles) {
                 attacks.push((enemy.id, target_player_id, damage));
             }
+            
+            // Engaging, or running past friends for help, brings allies along
+            let fleeing_for_help = matches!(enemy.ai.state, AiState::Flee { .. }) && enemy.template.behaviour.flee_for_help;
+            if let Some(target) = enemy.target_id.filter(|_| !had_target || fleeing_for_help) {
+                pulls.push((enemy.id, target));
+            }
+            if let Some(pack) = pack.filter(|_| !was_evading && enemy.is_evading()) {
+                evading_packs.push(pack);
+            }
+        }
+        
+        for (enemy_id, player_id) in pulls {
+            ai::social::pull(&mut self.enemies, enemy_id, player_id);
+        }
+        for pack in evading_packs {
+            ai::social::evade_together(&mut self.enemies, pack);
         }
         
         // Update enemy Y positions based on terrain height
@@ -692,41 +1321,85 @@ impl GameWorld {
             }
         }
         
-        // Process attacks and apply damage to players
+        // Process attacks: melee hits land now, ranged attacks fire a projectile
         for (attacker_id, target_id, base_damage) in attacks {
-            if let Some(player) = self.players.get_mut(&target_id) {
-                // Skip if player is already dead
-                if player.is_dead() {
-                    continue;
-                }
-                
-                // Apply damage (defense reduces damage by ~50%)
-                let actual_damage = player.take_damage(base_damage);
-                
-                damage_events.push(ServerMessage::DamageEvent {
-                    attacker_id,
-                    target_id,
-                    damage: actual_damage,
-                    target_new_health: player.health,
-                    is_critical: false, // Enemies don't crit for now
-                });
-                
-                // Check if player died (and death not yet announced)
-                if player.is_dead() && !player.death_announced {
-                    info!("Player {} was killed by enemy {}", target_id, attacker_id);
-                    player.death_announced = true;
-                    // Send death message to all clients
-                    damage_events.push(ServerMessage::EntityDeath {
-                        entity_id: target_id,
-                        killer_id: Some(attacker_id),
-                    });
+            let (ranged, speed, range) = match self.enemies.get(&attacker_id) {
+                Some(enemy) => (enemy.is_ranged(), enemy.template.projectile_speed, enemy.template.attack_range),
+                None => continue,
+            };
+            
+            if ranged {
+                if let Some(spawn) = self.spawn_projectile(attacker_id, target_id, ProjectilePayload::Attack, speed, true, range) {
+                    damage_events.push(spawn);
                 }
+            } else {
+                damage_events.extend(self.process_enemy_attack(attacker_id, target_id, base_damage));
             }
         }
         
+        // Cast heals, hexes and spells
+        damage_events.extend(self.update_enemy_abilities());
+        
         damage_events
     }
     
+    /// Resolve an enemy hit on a player (auto-attack or damaging ability)
+    /// Returns damage and death events to broadcast
+    fn process_enemy_attack(&mut self, attacker_id: u64, target_id: u64, base_damage: u32) -> Vec<ServerMessage> {
+        let mut messages = Vec::new();
+        let (attacker_profile, attacker) = match self.enemies.get(&attacker_id) {
+            Some(enemy) => (enemy.combat_profile(), combat_log::enemy_participant(enemy)),
+            None => return messages,
+        };
+        
+        let player = match self.players.get_mut(&target_id) {
+            // Skip if player is already dead
+            Some(player) if !player.is_dead() => player,
+            _ => return messages,
+        };
+        
+        // Roll the attack table, then apply damage (defense reduces damage by ~50%)
+        let hit = combat::resolve_hit(&attacker_profile, &player.combat_profile(&self.items), base_damage);
+        let health_before = player.health;
+        let actual_damage = if hit.outcome.is_avoided() {
+            0
+        } else {
+            player.take_damage(hit.damage)
+        };
+        
+        self.combat_log.record_attack(
+            attacker.clone(),
+            combat_log::player_participant(player),
+            None,
+            base_damage,
+            hit,
+            actual_damage,
+            health_before,
+        );
+        
+        messages.push(ServerMessage::DamageEvent {
+            attacker_id,
+            target_id,
+            damage: actual_damage,
+            target_new_health: player.health,
+            outcome: hit.outcome,
+        });
+        
+        // Check if player died (and death not yet announced)
+        if player.is_dead() && !player.death_announced {
+            info!("Player {} was killed by enemy {}", target_id, attacker_id);
+            player.death_announced = true;
+            self.combat_log.record_death(Some(attacker), combat_log::player_participant(player));
+            // Send death message to all clients
+            messages.push(ServerMessage::EntityDeath {
+                entity_id: target_id,
+                killer_id: Some(attacker_id),
+            });
+        }
+        
+        messages
+    }
+    
     /// Process enemy deaths and spawn loot, award XP and gold
     /// Returns (broadcast_messages, player_specific_messages)
     /// player_specific_messages is a Vec of (player_id, Vec<ServerMessage>)
@@ -746,12 +1419,28 @@ impl GameWorld {
             if let Some(enemy) = self.enemies.remove(&enemy_id) {
                 info!("Enemy {} (level {}) died, killer: {:?}", enemy_id, enemy_level, killer_id);
                 
+                let killer = killer_id
+                    .and_then(|id| self.players.get(&id))
+                    .map(combat_log::player_participant);
+                self.combat_log.record_death(killer, combat_log::enemy_participant(&enemy));
+                
+                let boss = self.bosses.on_boss_death(enemy_id)
+                    .map(|(index, adds)| (self.bosses.template(index).clone(), adds));
+                
                 // Award XP and gold to killer
                 if let Some(player_id) = killer_id {
                     if let Some(player) = self.players.get_mut(&player_id) {
                         // Calculate and award XP
-                        let xp_gained = ServerPlayer::calculate_xp_for_enemy(player.level, enemy_level);
+                        let mut xp_gained = ServerPlayer::calculate_xp_for_enemy(player.level, enemy_level);
+                        if let Some((template, _)) = &boss {
+                            xp_gained = (xp_gained as f32 * template.experience_multiplier) as u32;
+                        }
                         let level_up = player.add_experience(xp_gained);
+                        self.combat_log.record_experience(
+                            combat_log::enemy_participant(&enemy),
+                            combat_log::player_participant(player),
+                            xp_gained,
+                        );
                         
                         info!("Player {} gained {} XP (total: {})", player.name, xp_gained, player.experience);
                         
@@ -771,11 +1460,16 @@ impl GameWorld {
                                 max_mana: player.max_mana,
                                 attack: player.attack_power,
                                 defense: player.defense,
+                                skill_points: player.skill_points,
+                                stat_points: player.stat_points,
                             });
                         }
                         
-                        // Award gold (enemy_level * 5-15 random)
-                        let gold_gained = (enemy_level as u64) * rng.gen_range(5..=15);
+                        // Award gold (enemy_level * 5-15 random, bosses use their loot table)
+                        let gold_gained = match &boss {
+                            Some((template, _)) => template.loot.roll_gold(),
+                            None => (enemy_level as u64) * rng.gen_range(5..=15),
+                        };
                         player.gold += gold_gained;
                         
                         info!("Player {} gained {} gold", player.name, gold_gained);
@@ -786,54 +1480,34 @@ impl GameWorld {
                 // Broadcast enemy despawn
                 messages.push(ServerMessage::EnemyDespawn { id: enemy_id });
                 
-                // Spawn loot
-                if rng.gen_bool(0.5) {
-                    let item_entity_id = self.next_item_id;
-                    self.next_item_id += 1;
-                    
-                    let item = WorldItem {
-                        entity_id: item_entity_id,
-                        item_id: 3, // Goblin Ear
-                        quantity: 1,
-                        position: enemy.position,
-                    };
-                    self.world_items.insert(item_entity_id, item.clone());
-                    
-                    messages.push(ServerMessage::ItemSpawn {
-                        entity_id: item_entity_id,
-                        item_id: item.item_id,
-                        position: item.position,
-                    });
+                // Bosses drop from their own loot table and take their adds with them
+                if let Some((template, adds)) = boss {
+                    for (item_id, quantity) in template.loot.roll_items() {
+                        let position = [
+                            enemy.position[0] + rng.gen_range(-1.5..1.5),
+                            enemy.position[1],
+                            enemy.position[2] + rng.gen_range(-1.5..1.5),
+                        ];
+                        messages.push(self.spawn_world_item(item_id, quantity, position));
+                    }
+                    messages.extend(self.despawn_boss_adds(adds));
+                    self.zone_messages.push((enemy.zone_id, ServerMessage::BossEncounterEnded { enemy_id, defeated: true }));
+                    continue;
                 }
                 
-                // Health potion drop
-                if rng.gen_bool(0.2) {
-                    let item_entity_id = self.next_item_id;
-                    self.next_item_id += 1;
-                    
+                // Spawn loot from the enemy's template
+                for (item_id, quantity) in enemy.template.roll_loot() {
                     let position = [
                         enemy.position[0] + rng.gen_range(-1.0..1.0),
                         enemy.position[1],
                         enemy.position[2] + rng.gen_range(-1.0..1.0),
                     ];
-                    
-                    let item = WorldItem {
-                        entity_id: item_entity_id,
-                        item_id: 1, // Health Potion
-                        quantity: 1,
-                        position,
-                    };
-                    self.world_items.insert(item_entity_id, item);
-                    
-                    messages.push(ServerMessage::ItemSpawn {
-                        entity_id: item_entity_id,
-                        item_id: 1,
-                        position,
-                    });
+                    messages.push(self.spawn_world_item(item_id, quantity, position));
                 }
                 
                 // Notify spawn area manager of enemy death (queues respawn timer)
                 self.spawn_area_manager.on_enemy_death(enemy_id);
+                self.patrols.on_enemy_death(enemy_id);
             }
         }
         
@@ -843,64 +1517,1451 @@ impl GameWorld {
     /// Process spawn area respawns
     /// Returns messages for newly spawned enemies
     fn process_spawn_area_respawns(&mut self, delta: f32) -> Vec<ServerMessage> {
+        let spawns = self.spawn_area_manager.update(delta, self.spawn_conditions);
+        self.spawn_area_enemies(spawns)
+    }
+    
+    // ==========================================================================
+    // World Clock
+    // ==========================================================================
+    
+    /// Replace the world clock (spawn conditions are rechecked on the next update)
+    pub fn set_world_clock(&mut self, clock: WorldClock) {
+        self.clock = clock;
+        self.spawn_condition_timer = 0.0;
+    }
+    
+    /// Time sync message with the world's time and weather
+    pub fn time_sync(&self) -> ServerMessage {
+        self.clock.time_sync()
+    }
+    
+    /// Advance the weather and despawn idle area enemies that may no longer be out
+    /// (their spawn area fills back up with what suits the new conditions)
+    /// Returns the time sync for a weather change and despawn messages
+    fn update_world_clock(&mut self, delta: f32) -> Vec<ServerMessage> {
+        let mut messages = Vec::new();
+        if self.clock.update(delta) {
+            messages.push(self.clock.time_sync());
+        }
+        
+        self.spawn_condition_timer -= delta;
+        if self.spawn_condition_timer > 0.0 {
+            return messages;
+        }
+        self.spawn_condition_timer = SPAWN_CONDITION_INTERVAL;
+        
+        let conditions = self.clock.conditions();
+        if conditions != self.spawn_conditions {
+            info!("Spawn conditions now {:?} / {}", conditions.time_of_day, conditions.weather.name());
+            self.spawn_conditions = conditions;
+        }
+        
+        let out_of_time: Vec<u64> = self.enemies.values()
+            .filter(|e| e.health > 0 && e.ai.state == AiState::Idle)
+            .filter(|e| self.spawn_area_manager.area_of_enemy(e.id)
+                .is_some_and(|area| !area.allows(&e.template_id, conditions)))
+            .map(|e| e.id)
+            .collect();
+        for id in out_of_time {
+            debug!("Despawning enemy {}, it does not belong out under {:?}", id, conditions);
+            self.enemies.remove(&id);
+            self.spawn_area_manager.on_enemy_death(id);
+            messages.push(ServerMessage::EnemyDespawn { id });
+        }
+        
+        messages
+    }
+    
+    // ==========================================================================
+    // World Events
+    // ==========================================================================
+    
+    /// Replace the world event definitions (schedules start on the next update)
+    pub fn set_event_manager(&mut self, events: EventManager) {
+        self.events = events;
+    }
+    
+    /// Queue an event to start on the next update
+    /// Returns the event's name
+    pub fn start_world_event(&mut self, id: &str) -> Result<String, String> {
+        self.events.request_start(id).map(str::to_string)
+    }
+    
+    /// Queue a running event to be called off on the next update
+    /// Returns the event's name
+    pub fn cancel_world_event(&mut self, id: &str) -> Result<String, String> {
+        self.events.request_cancel(id).map(str::to_string)
+    }
+    
+    /// One status line per world event
+    pub fn describe_world_events(&self) -> Vec<String> {
+        self.events.describe(self.clock.unix_timestamp())
+    }
+    
+    /// Messages for everyone in a zone since the last call
+    pub fn drain_zone_messages(&mut self) -> Vec<(u32, ServerMessage)> {
+        std::mem::take(&mut self.zone_messages)
+    }
+    
+    /// Messages for single players since the last call
+    pub fn drain_player_messages(&mut self) -> Vec<(u64, Vec<ServerMessage>)> {
+        std::mem::take(&mut self.player_messages)
+    }
+    
+    /// Start due events and end those that were won, lost or cancelled
+    /// Returns spawn and despawn messages
+    fn update_world_events(&mut self, delta: f32) -> Vec<ServerMessage> {
+        let mut messages = Vec::new();
+        
+        for index in self.events.due(self.clock.unix_timestamp()) {
+            messages.extend(self.begin_world_event(index));
+        }
+        
+        let enemies = &self.enemies;
+        let finished = self.events.update(delta, |id| enemies.get(&id).is_some_and(|e| e.health > 0));
+        for event in finished {
+            messages.extend(self.end_world_event(event));
+        }
+        
+        messages
+    }
+    
+    /// Spawn an event's wave or boss and announce it to the zone
+    fn begin_world_event(&mut self, index: usize) -> Vec<ServerMessage> {
+        let mut messages = Vec::new();
+        let event = self.events.event(index).clone();
+        let mut enemy_ids = Vec::new();
+        let mut boss_id = None;
+        
+        let center = match &event.spawn {
+            EventSpawn::Wave { position, radius, enemies } => {
+                let mut rng = rand::thread_rng();
+                for wave in enemies {
+                    for _ in 0..wave.count {
+                        let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+                        let distance = radius * rng.gen_range(0.0f32..1.0).sqrt();
+                        let spawn_position = [position[0] + angle.sin() * distance, 0.0, position[1] + angle.cos() * distance];
+                        let spawned = match wave.level {
+                            Some(level) => self.spawn_enemy_with_level(event.zone_id, spawn_position, &wave.enemy_type, level),
+                            None => self.spawn_enemy(event.zone_id, spawn_position, &wave.enemy_type),
+                        };
+                        let Some(enemy_id) = spawned else { break };
+                        if let Some(enemy) = self.enemies.get(&enemy_id) {
+                            messages.push(ServerMessage::EnemySpawn {
+                                id: enemy_id,
+                                zone_id: event.zone_id,
+                                enemy_type: enemy.enemy_type,
+                                name: enemy.name().to_string(),
+                                position: enemy.position,
+                                health: enemy.health,
+                                max_health: enemy.max_health,
+                                level: enemy.level,
+                            });
+                        }
+                        enemy_ids.push(enemy_id);
+                    }
+                }
+                [position[0], 0.0, position[1]]
+            }
+            EventSpawn::WorldBoss { boss_id: template_id } => {
+                let Some(template) = self.bosses.find(template_id) else {
+                    warn!("World event {} needs unknown boss {}", event.id, template_id);
+                    return messages;
+                };
+                if self.bosses.is_alive(template) {
+                    warn!("World event {}: boss {} is already out", event.id, template_id);
+                    return messages;
+                }
+                let Some((enemy_id, spawn_message)) = self.spawn_boss(template) else {
+                    return messages;
+                };
+                messages.push(spawn_message);
+                enemy_ids.push(enemy_id);
+                boss_id = Some(enemy_id);
+                let position = self.bosses.template(template).position;
+                [position[0], 0.0, position[1]]
+            }
+        };
+        
+        if enemy_ids.is_empty() {
+            warn!("World event {} spawned nothing, not starting it", event.id);
+            return messages;
+        }
+        self.events.start(index, enemy_ids, boss_id, center);
+        self.announce(event.zone_id, &event.name, event.announcement(None));
+        messages
+    }
+    
+    /// Clear up an event that ended, announce how it went and reward participants
+    fn end_world_event(&mut self, finished: FinishedEvent) -> Vec<ServerMessage> {
+        let mut messages = Vec::new();
+        let event = self.events.event(finished.event).clone();
+        
+        // Whatever is left of a lost or cancelled event leaves with it
+        if let Some(boss_id) = finished.state.boss {
+            if let Some(adds) = self.bosses.remove(boss_id) {
+                messages.extend(self.despawn_boss_adds(adds));
+                self.zone_messages.push((event.zone_id, ServerMessage::BossEncounterEnded { enemy_id: boss_id, defeated: false }));
+            }
+        }
+        for id in finished.state.enemies {
+            if self.enemies.remove(&id).is_some() {
+                messages.push(ServerMessage::EnemyDespawn { id });
+            }
+        }
+        
+        self.announce(event.zone_id, &event.name, event.announcement(Some(finished.outcome)));
+        if finished.outcome != EventOutcome::Success {
+            return messages;
+        }
+        
+        let center = Vec2::from_3d(finished.state.center);
+        let participants: Vec<u64> = self.players.values()
+            .filter(|p| p.zone_id == event.zone_id && Vec2::from_3d(p.position).distance_to(center) <= event.participant_radius)
+            .map(|p| p.id)
+            .collect();
+        info!("World event {} rewards {} participants", event.id, participants.len());
+        
+        for player_id in participants {
+            let Some(player) = self.players.get_mut(&player_id) else { continue };
+            let mut rewards = Vec::new();
+            
+            if event.rewards.experience > 0 {
+                let level_up = player.add_experience(event.rewards.experience);
+                rewards.push(ServerMessage::ExperienceGained {
+                    amount: event.rewards.experience,
+                    current_experience: player.experience,
+                    experience_to_next_level: player.get_experience_to_next_level(),
+                });
+                if let Some(new_level) = level_up {
+                    info!("Player {} leveled up to {}", player.name, new_level);
+                    rewards.push(ServerMessage::LevelUp {
+                        new_level,
+                        max_health: player.max_health,
+                        max_mana: player.max_mana,
+                        attack: player.attack_power,
+                        defense: player.defense,
+                        skill_points: player.skill_points,
+                        stat_points: player.stat_points,
+                    });
+                }
+            }
+            
+            if event.rewards.gold > 0 {
+                player.gold += event.rewards.gold;
+                rewards.push(ServerMessage::GoldUpdate { gold: player.gold });
+            }
+            
+            if !event.rewards.items.is_empty() {
+                for item in &event.rewards.items {
+                    if !player.add_to_inventory(item.item_id, item.quantity) {
+                        warn!("No room for event reward item {} for player {}", item.item_id, player.name);
+                    }
+                }
+                rewards.push(ServerMessage::InventoryUpdate { slots: player.get_inventory_slots() });
+            }
+            
+            if !rewards.is_empty() {
+                self.player_messages.push((player_id, rewards));
+            }
+        }
+        
+        messages
+    }
+    
+    /// Chat announcement for everyone in a zone
+    fn announce(&mut self, zone_id: u32, sender_name: &str, content: String) {
+        info!("[{}] zone {}: {}", sender_name, zone_id, content);
+        self.zone_messages.push((zone_id, ServerMessage::ChatBroadcast {
+            sender_id: ANNOUNCEMENT_SENDER_ID,
+            sender_name: sender_name.to_string(),
+            content,
+        }));
+    }
+    
+    // ==========================================================================
+    // Metin Stones
+    // ==========================================================================
+    
+    /// Replace the Metin stone templates (stones appear on the next update)
+    pub fn set_metin_stone_manager(&mut self, manager: MetinStoneManager) {
+        if let Err(e) = manager.validate_spawn_areas(|zone_id, id| self.spawn_area_exists(zone_id, id)) {
+            error!("{} (those stones will keep retrying)", e);
+        }
+        self.metin_stone_manager = manager;
+    }
+    
+    /// Whether a zone has a spawn area with this id
+    fn spawn_area_exists(&self, zone_id: u32, area_id: &str) -> bool {
+        self.spawn_area_manager.find_area(area_id).is_some_and(|area| area.zone_id == zone_id)
+    }
+    
+    /// Get all Metin stones in a zone
+    pub fn get_metin_stones_in_zone(&self, zone_id: u32) -> Vec<&ServerMetinStone> {
+        self.metin_stones.values()
+            .filter(|s| s.zone_id == zone_id)
+            .collect()
+    }
+    
+    /// Position and health of something players can attack (an enemy or a Metin stone)
+    fn hostile_target(&self, id: u64) -> Option<([f32; 3], u32)> {
+        if let Some(enemy) = self.enemies.get(&id) {
+            return Some((enemy.position, enemy.health));
+        }
+        self.metin_stones.get(&id).map(|stone| (stone.position, stone.health))
+    }
+    
+    /// Resolve a player's attack or damage ability against a Metin stone
+    /// Returns the damage event to broadcast
+    fn hit_metin_stone(&mut self, attacker_id: u64, stone_id: u64, damage: u32, ability_id: Option<u32>) -> Option<ServerMessage> {
+        let attacker = self.players.get(&attacker_id)?;
+        let stone = self.metin_stones.get_mut(&stone_id).filter(|s| !s.is_destroyed())?;
+        
+        let hit = combat::resolve_hit(&attacker.combat_profile(&self.items), &stone.combat_profile(), damage);
+        let health_before = stone.health;
+        let dealt = stone.take_damage(attacker_id, hit.damage);
+        
+        self.combat_log.record_attack(
+            combat_log::player_participant(attacker),
+            combat_log::metin_stone_participant(stone),
+            ability_id,
+            damage,
+            hit,
+            dealt,
+            health_before,
+        );
+        
+        let event = ServerMessage::DamageEvent {
+            attacker_id,
+            target_id: stone_id,
+            damage: dealt,
+            target_new_health: stone.health,
+            outcome: hit.outcome,
+        };
+        if let Some(attacker) = self.players.get_mut(&attacker_id) {
+            attacker.enter_combat();
+        }
+        Some(event)
+    }
+    
+    /// Place stones whose respawn timer finished at random points in their zone's spawn areas
+    /// Spawn messages go to the stone's zone
+    fn process_metin_stone_spawns(&mut self, delta: f32) {
+        for index in self.metin_stone_manager.update(delta) {
+            let template = self.metin_stone_manager.template(index);
+            let areas: Vec<&SpawnArea> = self.spawn_area_manager.get_areas(template.zone_id).iter()
+                .filter(|area| template.spawn_areas.is_empty() || template.spawn_areas.contains(&area.id))
+                .collect();
+            let point = match areas.len() {
+                0 => None,
+                count => {
+                    let area = areas[rand::thread_rng().gen_range(0..count)];
+                    area.get_random_point().map(|point| (area.id.clone(), point))
+                }
+            };
+            let Some((area_id, [x, z])) = point else {
+                warn!("No spawn area to place Metin stone {} in zone {}", template.id, template.zone_id);
+                self.metin_stone_manager.retry(index);
+                continue;
+            };
+            
+            let id = self.next_enemy_id;
+            self.next_enemy_id += 1;
+            let y = self.zone_manager.get_terrain_height(template.zone_id, x, z);
+            let stone = ServerMetinStone::new(id, template.zone_id, index, template, &area_id, [x, y, z]);
+            
+            info!("Metin stone {} ({}) placed in zone {} at ({:.1}, {:.1})", stone.name, id, stone.zone_id, x, z);
+            self.zone_messages.push((stone.zone_id, stone.spawn_message()));
+            self.metin_stones.insert(id, stone);
+        }
+    }
+    
+    /// Remove destroyed stones, call waves for stones under attack and send state changes
+    /// Stone messages go to the stone's zone, returns the rest (enemy spawns, loot) to broadcast
+    fn update_metin_stones(&mut self, delta: f32) -> Vec<ServerMessage> {
+        let mut messages = Vec::new();
+        let mut waves = Vec::new();
+        
+        let destroyed: Vec<u64> = self.metin_stones.values()
+            .filter(|s| s.is_destroyed())
+            .map(|s| s.id)
+            .collect();
+        for id in destroyed {
+            messages.extend(self.destroy_metin_stone(id));
+        }
+        
+        for stone in self.metin_stones.values_mut() {
+            let template = self.metin_stone_manager.template(stone.template);
+            stone.linked_enemies.retain(|id| self.enemies.get(id).is_some_and(|e| e.health > 0));
+            if stone.update(delta, template.wave_interval) && stone.linked_enemies.len() < template.max_linked as usize {
+                waves.push(stone.id);
+            }
+            if let Some(state) = stone.take_state_update(delta) {
+                self.zone_messages.push((stone.zone_id, state));
+            }
+        }
+        
+        for stone_id in waves {
+            messages.extend(self.spawn_metin_stone_wave(stone_id));
+        }
+        
+        messages
+    }
+    
+    /// Call a wave of enemies around a stone, going for whoever hit it last
+    fn spawn_metin_stone_wave(&mut self, stone_id: u64) -> Vec<ServerMessage> {
         let mut messages = Vec::new();
+        let Some(stone) = self.metin_stones.get(&stone_id) else {
+            return messages;
+        };
+        let (zone_id, position, target_id) = (stone.zone_id, stone.position, stone.last_attacker);
+        let template = self.metin_stone_manager.template(stone.template).clone();
+        let mut rng = rand::thread_rng();
+        let mut linked = Vec::new();
+        
+        for wave in &template.wave {
+            for _ in 0..wave.count {
+                let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+                let spawn_position = [
+                    position[0] + angle.sin() * template.wave_radius,
+                    0.0,
+                    position[2] + angle.cos() * template.wave_radius,
+                ];
+                let spawned = match wave.level {
+                    Some(level) => self.spawn_enemy_with_level(zone_id, spawn_position, &wave.enemy_type, level),
+                    None => self.spawn_enemy(zone_id, spawn_position, &wave.enemy_type),
+                };
+                let Some(enemy_id) = spawned else { break };
+                
+                if let Some(enemy) = self.enemies.get_mut(&enemy_id) {
+                    // Pulled onto the attacker however far away they stand
+                    enemy.ai.called_target = target_id;
+                    messages.push(ServerMessage::EnemySpawn {
+                        id: enemy_id,
+                        zone_id,
+                        enemy_type: enemy.enemy_type,
+                        name: enemy.name().to_string(),
+                        position: enemy.position,
+                        health: enemy.health,
+                        max_health: enemy.max_health,
+                        level: enemy.level,
+                    });
+                }
+                linked.push(enemy_id);
+            }
+        }
+        
+        debug!("Metin stone {} called {} enemies", stone_id, linked.len());
+        if let Some(stone) = self.metin_stones.get_mut(&stone_id) {
+            stone.linked_enemies.extend(linked);
+        }
+        messages
+    }
+    
+    /// Remove a destroyed stone, reward whoever broke it and drop its loot
+    /// (enemies it called stay and keep fighting)
+    fn destroy_metin_stone(&mut self, stone_id: u64) -> Vec<ServerMessage> {
+        let mut messages = Vec::new();
+        let Some(stone) = self.metin_stones.remove(&stone_id) else {
+            return messages;
+        };
+        let template = self.metin_stone_manager.template(stone.template).clone();
+        self.metin_stone_manager.on_destroyed(stone.template);
+        
+        let destroyer = stone.last_attacker.and_then(|id| self.players.get_mut(&id));
+        self.combat_log.record_death(
+            destroyer.as_deref().map(combat_log::player_participant),
+            combat_log::metin_stone_participant(&stone),
+        );
+        
+        if let Some(player) = destroyer {
+            let mut rewards = Vec::new();
+            if template.experience > 0 {
+                let level_up = player.add_experience(template.experience);
+                self.combat_log.record_experience(
+                    combat_log::metin_stone_participant(&stone),
+                    combat_log::player_participant(player),
+                    template.experience,
+                );
+                rewards.push(ServerMessage::ExperienceGained {
+                    amount: template.experience,
+                    current_experience: player.experience,
+                    experience_to_next_level: player.get_experience_to_next_level(),
+                });
+                if let Some(new_level) = level_up {
+                    info!("Player {} leveled up to {}", player.name, new_level);
+                    rewards.push(ServerMessage::LevelUp {
+                        new_level,
+                        max_health: player.max_health,
+                        max_mana: player.max_mana,
+                        attack: player.attack_power,
+                        defense: player.defense,
+                        skill_points: player.skill_points,
+                        stat_points: player.stat_points,
+                    });
+                }
+            }
+            
+            let gold = template.loot.roll_gold();
+            if gold > 0 {
+                player.gold += gold;
+                rewards.push(ServerMessage::GoldUpdate { gold: player.gold });
+            }
+            info!("Player {} destroyed Metin stone {}", player.name, stone.name);
+            if !rewards.is_empty() {
+                self.player_messages.push((player.id, rewards));
+            }
+        }
         
-        // Update spawn area manager and get any pending spawns
-        let spawns = self.spawn_area_manager.update(delta);
+        self.zone_messages.push((stone.zone_id, ServerMessage::MetinStoneDespawn { id: stone_id, destroyed: true }));
         
-        // Collect area assignments
-        let mut enemy_area_assignments: Vec<(u64, String)> = Vec::new();
+        let mut rng = rand::thread_rng();
+        for (item_id, quantity) in template.loot.roll_items() {
+            let position = [
+                stone.position[0] + rng.gen_range(-1.5..1.5),
+                stone.position[1],
+                stone.position[2] + rng.gen_range(-1.5..1.5),
+            ];
+            messages.push(self.spawn_world_item(item_id, quantity, position));
+        }
+        
+        messages
+    }
+    
+    // ==========================================================================
+    // Level Data Reload
+    // ==========================================================================
+    
+    /// Reload spawn areas, obstacles and/or heightmaps exported from Godot
+    /// Everything asked for is loaded and checked before anything is swapped in.
+    /// Enemies of removed areas despawn, idle enemies above a lowered population
+    /// despawn, emptier areas fill up on the next update and enemies, NPCs and
+    /// stones move onto the new terrain and nav-meshes.
+    /// Returns what was reloaded, for the admin who asked
+    pub fn reload_level_data(&mut self, spawn_areas: bool, obstacles: bool, heightmaps: bool) -> Result<String, String> {
+        let reloaded_areas = if spawn_areas {
+            let mut reloaded = SpawnAreaManager::new();
+            reloaded.load_from_json("spawn_areas.json")?;
+            reloaded.validate(|id| self.enemy_templates.get(id).is_some())?;
+            self.metin_stone_manager.validate_spawn_areas(|zone_id, id| {
+                reloaded.find_area(id).is_some_and(|area| area.zone_id == zone_id)
+            })?;
+            Some(reloaded)
+        } else {
+            None
+        };
+        
+        let mut summary = Vec::new();
+        if obstacles || heightmaps {
+            // Enemies and NPCs switch to the new nav-meshes once they are built
+            summary.push(self.zone_manager.reload_terrain(obstacles, heightmaps)?);
+        }
+        if heightmaps {
+            self.ground_metin_stones();
+        }
+        if let Some(reloaded) = reloaded_areas {
+            summary.push(self.replace_spawn_areas(reloaded));
+        }
+        Ok(format!("Reloaded {}", summary.join(", ")))
+    }
+    
+    /// Swap in reloaded spawn areas and reconcile the enemies they own
+    /// Returns a summary
+    fn replace_spawn_areas(&mut self, reloaded: SpawnAreaManager) -> String {
+        let area_count = reloaded.all_areas().count();
+        let mut despawned = self.spawn_area_manager.replace_areas(reloaded);
+        
+        let areas: Vec<SpawnArea> = self.spawn_area_manager.all_areas().cloned().collect();
+        for area in areas {
+            let mut enemies = self.spawn_area_manager.enemies_in_area(&area.id);
+            for id in &enemies {
+                if let Some(enemy) = self.enemies.get_mut(id) {
+                    enemy.ai.roam = Roam::Wander { polygon: area.polygon.clone() };
+                }
+            }
+            
+            // Only idle enemies leave; fighting ones stay until they die
+            let surplus = enemies.len().saturating_sub(area.max_population as usize);
+            enemies.retain(|id| self.enemies.get(id).is_some_and(|e| e.health > 0 && e.ai.state == AiState::Idle));
+            for id in enemies.into_iter().take(surplus) {
+                self.spawn_area_manager.unregister_enemy(id);
+                despawned.push(id);
+            }
+        }
+        
+        for id in &despawned {
+            if let Some(enemy) = self.enemies.remove(id) {
+                self.zone_messages.push((enemy.zone_id, ServerMessage::EnemyDespawn { id: *id }));
+            }
+        }
+        
+        // Enemy types an area no longer lists are cleared on the next update
+        self.spawn_condition_timer = 0.0;
+        
+        let moved_stones = self.replace_orphaned_metin_stones();
+        
+        info!("Reloaded {} spawn areas, despawned {} enemies, moved {} Metin stones",
+            area_count, despawned.len(), moved_stones);
+        format!("{} spawn areas ({} enemies despawned, {} Metin stones moved)",
+            area_count, despawned.len(), moved_stones)
+    }
+    
+    /// Remove stones whose spawn area is gone or no longer contains them and
+    /// place replacements on the next update (enemies they called stay)
+    /// Returns how many stones were replaced
+    fn replace_orphaned_metin_stones(&mut self) -> usize {
+        let orphaned: Vec<u64> = self.metin_stones.values()
+            .filter(|stone| {
+                let area = self.spawn_area_manager.find_area(&stone.spawn_area)
+                    .filter(|area| area.zone_id == stone.zone_id);
+                !area.is_some_and(|area| area.contains_point(stone.position[0], stone.position[2]))
+            })
+            .map(|stone| stone.id)
+            .collect();
+        
+        for id in &orphaned {
+            if let Some(stone) = self.metin_stones.remove(id) {
+                self.metin_stone_manager.replace(stone.template);
+                self.zone_messages.push((stone.zone_id, ServerMessage::MetinStoneDespawn { id: *id, destroyed: false }));
+            }
+        }
+        orphaned.len()
+    }
+    
+    /// Point enemies and NPCs at their zone's current nav-mesh
+    fn refresh_navmeshes(&mut self) {
+        for enemy in self.enemies.values_mut() {
+            enemy.navmesh = self.zone_manager.get_navmesh(enemy.zone_id);
+            enemy.nav_state = NavigationState::new();
+        }
+        for npc in self.npcs.values_mut() {
+            npc.set_navmesh(self.zone_manager.get_navmesh(npc.zone_id));
+        }
+    }
+    
+    /// Put standing stones back on the ground after the terrain changed
+    /// (clients are sent the stone again at its new height)
+    fn ground_metin_stones(&mut self) {
+        for stone in self.metin_stones.values_mut() {
+            let y = self.zone_manager.get_terrain_height(stone.zone_id, stone.position[0], stone.position[2]);
+            if (y - stone.position[1]).abs() < f32::EPSILON {
+                continue;
+            }
+            stone.position[1] = y;
+            self.zone_messages.push((stone.zone_id, ServerMessage::MetinStoneDespawn { id: stone.id, destroyed: false }));
+            self.zone_messages.push((stone.zone_id, stone.spawn_message()));
+        }
+    }
+    
+    // ==========================================================================
+    // Patrols
+    // ==========================================================================
+    
+    /// Replace the patrol routes (members spawn on the next update)
+    pub fn set_patrol_manager(&mut self, patrols: PatrolManager) {
+        self.patrols = patrols;
+    }
+    
+    /// Spawn patrol members whose respawn timer finished
+    /// Returns messages for the new members
+    fn process_patrol_spawns(&mut self, delta: f32) -> Vec<ServerMessage> {
+        let mut messages = Vec::new();
+        
+        for slot in self.patrols.update(delta) {
+            let path = self.patrols.path(slot).clone();
+            let template_id = &path.enemies[slot.member];
+            let position = path.spawn_position(slot.member);
+            let level = match self.enemy_templates.get(template_id) {
+                Some(template) => template.roll_level(),
+                None => 1,
+            };
+            let Some(enemy_id) = self.spawn_enemy_with_level(slot.zone_id, position, template_id, level) else {
+                self.patrols.retry(slot);
+                continue;
+            };
+            self.patrols.register(enemy_id, slot);
+            
+            if let Some(enemy) = self.enemies.get_mut(&enemy_id) {
+                enemy.ai.roam = Roam::Patrol {
+                    waypoints: path.waypoints.clone(),
+                    looped: path.looped,
+                    pause: path.pause,
+                };
+                debug!("Patrol {} member {} spawned as enemy {}", path.name, slot.member, enemy_id);
+                messages.push(ServerMessage::EnemySpawn {
+                    id: enemy_id,
+                    zone_id: slot.zone_id,
+                    enemy_type: enemy.enemy_type,
+                    name: enemy.name().to_string(),
+                    position: enemy.position,
+                    health: enemy.health,
+                    max_health: enemy.max_health,
+                    level: enemy.level,
+                });
+            }
+        }
+        
+        messages
+    }
+    
+    // ==========================================================================
+    // Boss Encounters
+    // ==========================================================================
+    
+    /// Replace the boss templates (bosses spawn on the next update)
+    pub fn set_boss_manager(&mut self, bosses: BossManager) {
+        self.bosses = bosses;
+    }
+    
+    /// Spawn bosses whose respawn timer finished
+    /// Returns messages for the new bosses
+    fn process_boss_spawns(&mut self, delta: f32) -> Vec<ServerMessage> {
+        let mut messages = Vec::new();
+        
+        for index in self.bosses.update_respawns(delta) {
+            if let Some((_, spawn_message)) = self.spawn_boss(index) {
+                messages.push(spawn_message);
+            }
+        }
+        
+        messages
+    }
+    
+    /// Spawn a boss from its template at the center of its arena
+    /// Returns the boss's enemy id and spawn message
+    fn spawn_boss(&mut self, index: usize) -> Option<(u64, ServerMessage)> {
+        let template = self.bosses.template(index).clone();
+        let position = [template.position[0], 0.0, template.position[1]];
+        let enemy_id = self.spawn_enemy_with_level(template.zone_id, position, &template.enemy_type, template.level)?;
+        self.bosses.register(enemy_id, index);
+        
+        let enemy = self.enemies.get_mut(&enemy_id)?;
+        enemy.max_health = template.max_health;
+        enemy.health = template.max_health;
+        enemy.attack_power = template.attack_power;
+        enemy.leash_range = template.arena_radius;
+        enemy.is_immune = true;
+        
+        info!("Boss {} spawned as enemy {} in zone {}", template.name, enemy_id, template.zone_id);
+        Some((enemy_id, ServerMessage::EnemySpawn {
+            id: enemy_id,
+            zone_id: template.zone_id,
+            enemy_type: enemy.enemy_type,
+            name: enemy.name().to_string(),
+            position: enemy.position,
+            health: enemy.health,
+            max_health: enemy.max_health,
+            level: enemy.level,
+        }))
+    }
+    
+    /// Run boss encounters: engage and reset, phases, abilities and status updates
+    /// Encounter messages go to the boss's zone; returns the combat messages to broadcast
+    fn update_bosses(&mut self, delta: f32) -> Vec<ServerMessage> {
+        let mut messages = Vec::new();
+        let mut adds_to_despawn = Vec::new();
+        let mut events = Vec::new();
+        
+        for boss_id in self.bosses.boss_ids() {
+            let enemy = match self.enemies.get_mut(&boss_id) {
+                Some(e) if e.health > 0 => e,
+                _ => continue,
+            };
+            let (template, encounter) = match self.bosses.encounter_mut(boss_id) {
+                Some(b) => b,
+                None => continue,
+            };
+            
+            let center = Vec2::from_3d(enemy.spawn_position);
+            let arena_occupied = self.players.values().any(|p| {
+                !p.is_dead()
+                    && p.zone_id == enemy.zone_id
+                    && Vec2::from_3d(p.position).distance_to(center) <= template.arena_radius
+            });
+            
+            if !encounter.engaged {
+                // Untouchable until someone inside the arena pulls it
+                enemy.is_immune = !arena_occupied;
+                if arena_occupied && enemy.target_id.is_some() && !enemy.is_evading() {
+                    encounter.engage(template);
+                    info!("Boss {} engaged", template.name);
+                    let status = encounter.initial_status(enemy.health);
+                    self.zone_messages.push((enemy.zone_id, Self::boss_update_message(boss_id, template, status, enemy.max_health)));
+                }
+                continue;
+            }
+            
+            if !arena_occupied || enemy.is_evading() {
+                // Wipe or everyone left: walk home, heal up and start over
+                info!("Boss {} reset", template.name);
+                adds_to_despawn.extend(encounter.reset());
+                enemy.start_evading();
+                enemy.is_immune = true;
+                enemy.attack_power = template.attack_power;
+                self.zone_messages.push((enemy.zone_id, ServerMessage::BossEncounterEnded { enemy_id: boss_id, defeated: false }));
+                continue;
+            }
+            
+            let health_percent = enemy.health as f32 / enemy.max_health.max(1) as f32 * 100.0;
+            for event in encounter.update(template, delta, health_percent, enemy.position) {
+                match event {
+                    boss::BossEvent::AbilityUsed { name, radius, wind_up } => {
+                        self.zone_messages.push((enemy.zone_id, ServerMessage::BossAbilityUsed {
+                            enemy_id: boss_id,
+                            ability_name: name,
+                            position: enemy.position,
+                            radius,
+                            wind_up,
+                        }));
+                    }
+                    other => events.push((boss_id, other)),
+                }
+            }
+            
+            enemy.is_immune = encounter.is_immune();
+            enemy.attack_power = (template.attack_power as f32 * encounter.attack_multiplier(template)) as u32;
+            
+            if let Some(status) = encounter.take_status_update(delta, enemy.health) {
+                self.zone_messages.push((enemy.zone_id, Self::boss_update_message(boss_id, template, status, enemy.max_health)));
+            }
+        }
+        
+        messages.extend(self.despawn_boss_adds(adds_to_despawn));
+        
+        for (boss_id, event) in events {
+            match event {
+                boss::BossEvent::SlamLanded { position, radius, damage } => {
+                    messages.extend(self.resolve_boss_slam(boss_id, position, radius, damage));
+                }
+                boss::BossEvent::SummonAdds { enemy_type, count, level } => {
+                    messages.extend(self.summon_boss_adds(boss_id, &enemy_type, count, level));
+                }
+                boss::BossEvent::AbilityUsed { .. } => {}
+            }
+        }
+        
+        messages
+    }
+    
+    fn boss_update_message(
+        enemy_id: u64,
+        template: &boss::BossTemplate,
+        (phase, health, immune, enraged): (usize, u32, bool, bool),
+        max_health: u32,
+    ) -> ServerMessage {
+        ServerMessage::BossUpdate {
+            enemy_id,
+            name: template.name.clone(),
+            phase: phase as u8,
+            phase_count: template.phases.len() as u8,
+            phase_name: template.phases[phase].name.clone(),
+            health,
+            max_health,
+            immune,
+            enraged,
+        }
+    }
+    
+    /// Damage every living player in the slam radius
+    fn resolve_boss_slam(&mut self, boss_id: u64, position: [f32; 3], radius: f32, damage: u32) -> Vec<ServerMessage> {
+        let mut messages = Vec::new();
+        let (zone_id, source) = match self.enemies.get(&boss_id) {
+            Some(enemy) => (enemy.zone_id, combat_log::enemy_participant(enemy)),
+            None => return messages,
+        };
+        let center = Vec2::from_3d(position);
+        let candidates: Vec<u64> = self.player_index.get(&zone_id)
+            .map(|grid| grid.query(center, radius + INDEX_SLACK).collect())
+            .unwrap_or_default();
+        
+        for player_id in candidates {
+            let Some(player) = self.players.get_mut(&player_id) else { continue };
+            if player.is_dead()
+                || player.zone_id != zone_id
+                || Vec2::from_3d(player.position).distance_to(center) > radius
+            {
+                continue;
+            }
+            
+            let health_before = player.health;
+            let dealt = player.take_damage(damage);
+            self.combat_log.record_direct_damage(
+                source.clone(),
+                combat_log::player_participant(player),
+                None,
+                dealt,
+                health_before,
+            );
+            messages.push(ServerMessage::DamageEvent {
+                attacker_id: boss_id,
+                target_id: player.id,
+                damage: dealt,
+                target_new_health: player.health,
+                outcome: HitOutcome::Hit,
+            });
+            
+            if player.is_dead() && !player.death_announced {
+                info!("Player {} was killed by boss {}", player.id, boss_id);
+                player.death_announced = true;
+                self.combat_log.record_death(Some(source.clone()), combat_log::player_participant(player));
+                messages.push(ServerMessage::EntityDeath {
+                    entity_id: player.id,
+                    killer_id: Some(boss_id),
+                });
+            }
+        }
+        
+        messages
+    }
+    
+    /// Spawn adds around a boss, already attacking the boss's target
+    fn summon_boss_adds(&mut self, boss_id: u64, template_id: &str, count: u32, level: u8) -> Vec<ServerMessage> {
+        let mut messages = Vec::new();
+        let (zone_id, position, target_id) = match self.enemies.get(&boss_id) {
+            Some(enemy) => (enemy.zone_id, enemy.position, enemy.target_id),
+            None => return messages,
+        };
+        let mut rng = rand::thread_rng();
+        
+        for _ in 0..count {
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            let spawn_position = [
+                position[0] + angle.sin() * BOSS_ADD_SPAWN_DISTANCE,
+                0.0,
+                position[2] + angle.cos() * BOSS_ADD_SPAWN_DISTANCE,
+            ];
+            let Some(add_id) = self.spawn_enemy_with_level(zone_id, spawn_position, template_id, level) else {
+                break;
+            };
+            self.bosses.add_summon(boss_id, add_id);
+            
+            if let Some(add) = self.enemies.get_mut(&add_id) {
+                add.target_id = target_id;
+                messages.push(ServerMessage::EnemySpawn {
+                    id: add_id,
+                    zone_id,
+                    enemy_type: add.enemy_type,
+                    name: add.name().to_string(),
+                    position: add.position,
+                    health: add.health,
+                    max_health: add.max_health,
+                    level: add.level,
+                });
+            }
+        }
+        
+        messages
+    }
+    
+    /// Remove a boss's surviving adds
+    fn despawn_boss_adds(&mut self, adds: Vec<u64>) -> Vec<ServerMessage> {
+        adds.into_iter()
+            .filter(|id| self.enemies.remove(id).is_some())
+            .map(|id| ServerMessage::EnemyDespawn { id })
+            .collect()
+    }
+    
+    /// Drop an item into the world
+    fn spawn_world_item(&mut self, item_id: u32, quantity: u32, position: [f32; 3]) -> ServerMessage {
+        let entity_id = self.next_item_id;
+        self.next_item_id += 1;
+        
+        self.world_items.insert(entity_id, WorldItem {
+            entity_id,
+            item_id,
+            quantity,
+            position,
+        });
+        
+        ServerMessage::ItemSpawn {
+            entity_id,
+            item_id,
+            position,
+        }
+    }
+    
+    /// Award XP to a player (for commands)
+    pub fn add_experience_to_player(&mut self, player_id: u64, amount: u32) -> Option<(u32, Option<u32>)> {
+        let player = self.players.get_mut(&player_id)?;
+        let level_up = player.add_experience(amount);
+        Some((player.experience, level_up))
+    }
+    
+    /// Set XP for a player (for commands)
+    pub fn set_player_experience(&mut self, player_id: u64, experience: u32) -> Option<(u32, u32, Option<u32>)> {
+        let player = self.players.get_mut(&player_id)?;
+        let level_changed = player.set_experience(experience);
+        Some((player.experience, player.level, level_changed))
+    }
+
+    // ==========================================================================
+    // Enemy Abilities
+    // ==========================================================================
+    
+    /// Cast ready enemy abilities (at most one per enemy per tick)
+    /// Returns messages to broadcast
+    fn update_enemy_abilities(&mut self) -> Vec<ServerMessage> {
+        let mut casts = Vec::new();
+        
+        for enemy in self.enemies.values() {
+            if enemy.health == 0 || enemy.is_evading() || enemy.target_id.is_none() {
+                continue;
+            }
+            
+            let ready = enemy.template.abilities.iter().enumerate()
+                .filter(|(index, _)| enemy.ability_cooldowns[*index] <= 0.0);
+            for (index, ability) in ready {
+                if let Some(target_id) = self.select_enemy_ability_target(enemy, ability) {
+                    casts.push((enemy.id, index, target_id));
+                    break;
+                }
+            }
+        }
+        
+        let mut messages = Vec::new();
+        for (enemy_id, index, target_id) in casts {
+            let ability = match self.enemies.get_mut(&enemy_id) {
+                Some(enemy) => {
+                    enemy.ability_cooldowns[index] = enemy.template.abilities[index].cooldown;
+                    enemy.template.abilities[index].clone()
+                }
+                None => continue,
+            };
+            
+            debug!("[ENEMY {}] Casting {} on {}", enemy_id, ability.name, target_id);
+            messages.push(ServerMessage::EnemyAbilityUsed {
+                enemy_id,
+                ability_name: ability.name.clone(),
+                target_id: Some(target_id),
+            });
+            messages.extend(self.apply_enemy_ability_effects(enemy_id, &ability, target_id));
+        }
+        
+        messages
+    }
+    
+    /// Pick a target for an enemy ability in range and line of sight
+    /// Allies and self are only picked when wounded below the ability's health threshold
+    fn select_enemy_ability_target(&self, caster: &ServerEnemy, ability: &EnemyAbility) -> Option<u64> {
+        let obstacles = self.zone_manager.get_obstacles(caster.zone_id);
+        let caster_position = Vec2::from_3d(caster.position);
+        let reachable = |position: [f32; 3]| {
+            caster_position.distance_to(Vec2::from_3d(position)) <= ability.range
+                && caster.can_see(position, obstacles)
+        };
+        let health_fraction = |enemy: &ServerEnemy| enemy.health as f32 / enemy.max_health.max(1) as f32;
+        let wounded = |enemy: &ServerEnemy| enemy.health > 0 && health_fraction(enemy) < ability.health_threshold;
+        
+        match ability.target_type {
+            TargetType::Enemy => {
+                let target_id = caster.target_id?;
+                let player = self.players.get(&target_id)
+                    .filter(|p| !p.is_dead() && p.zone_id == caster.zone_id)?;
+                reachable(player.position).then_some(target_id)
+            }
+            TargetType::Ally => self.get_enemies_near(caster.zone_id, caster.position, ability.range)
+                .into_iter()
+                .filter(|e| !e.is_evading() && wounded(e) && reachable(e.position))
+                .min_by(|a, b| health_fraction(a).total_cmp(&health_fraction(b)))
+                .map(|e| e.id),
+            TargetType::SelfOnly => wounded(caster).then_some(caster.id),
+            _ => None,
+        }
+    }
+    
+    /// Apply an enemy ability: damage and debuffs land on players, heals on enemies
+    /// Returns messages to broadcast
+    fn apply_enemy_ability_effects(&mut self, caster_id: u64, ability: &EnemyAbility, target_id: u64) -> Vec<ServerMessage> {
+        let mut messages = Vec::new();
+        let (attack_power, source) = match self.enemies.get(&caster_id) {
+            Some(enemy) => (enemy.attack_power, combat_log::enemy_participant(enemy)),
+            None => return messages,
+        };
+        
+        for effect in &ability.effects {
+            let debuff = match effect {
+                AbilityEffect::Damage { base, attack_scaling } => {
+                    let damage = base + (attack_power as f32 * attack_scaling) as u32;
+                    messages.extend(self.process_enemy_attack(caster_id, target_id, damage));
+                    None
+                }
+                AbilityEffect::Heal { base, health_scaling } => {
+                    if let Some(enemy) = self.enemies.get_mut(&target_id).filter(|e| e.health > 0) {
+                        let old_health = enemy.health;
+                        let heal_amount = base + (enemy.max_health as f32 * health_scaling) as u32;
+                        enemy.health = (enemy.health + heal_amount).min(enemy.max_health);
+                        let actual_heal = enemy.health - old_health;
+                        
+                        if actual_heal > 0 {
+                            self.combat_log.record_heal(
+                                Some(source.clone()),
+                                combat_log::enemy_participant(enemy),
+                                None,
+                                None,
+                                actual_heal,
+                            );
+                            messages.push(ServerMessage::HealEvent {
+                                healer_id: caster_id,
+                                target_id,
+                                amount: actual_heal,
+                                target_new_health: enemy.health,
+                            });
+                        }
+                    }
+                    None
+                }
+                AbilityEffect::DamageOverTime { damage_per_tick, interval, duration } => Some((
+                    BuffEffect::DamageOverTime {
+                        source_id: caster_id,
+                        damage_per_tick: *damage_per_tick,
+                        interval: *interval,
+                        next_tick: *interval,
+                    },
+                    *duration,
+                )),
+                AbilityEffect::DebuffAttack { amount, duration } => Some((BuffEffect::AttackBonus(-amount), *duration)),
+                AbilityEffect::DebuffDefense { amount, duration } => Some((BuffEffect::DefenseBonus(-amount), *duration)),
+                AbilityEffect::Slow { multiplier, duration } => Some((BuffEffect::SpeedMultiplier(*multiplier), *duration)),
+                AbilityEffect::Stun { duration } => Some((BuffEffect::Stunned, *duration)),
+                // Enemies don't track buffs yet, and can't resurrect
+                _ => None,
+            };
+            
+            // Debuffs go on the player as timed buff effects (ability 0 = enemy ability)
+            if let Some((effect, duration)) = debuff {
+                if let Some(player) = self.players.get_mut(&target_id).filter(|p| !p.is_dead()) {
+                    let buff_id = player.add_buff(0, effect, duration, true);
+                    self.combat_log.record_buff(source.clone(), combat_log::player_participant(player), 0, duration);
+                    messages.push(ServerMessage::BuffApplied {
+                        target_id,
+                        buff_id,
+                        ability_id: 0,
+                        duration,
+                        is_debuff: true,
+                    });
+                }
+            }
+        }
+        
+        messages
+    }
+    
+    // ==========================================================================
+    // Projectiles
+    // ==========================================================================
+
+    /// Get the position a projectile should aim at for an entity (None if gone or dead)
+    fn projectile_target_position(&self, entity_id: u64) -> Option<[f32; 3]> {
+        let position = match self.hostile_target(entity_id) {
+            Some((position, health)) if health > 0 => position,
+            Some(_) => return None,
+            None => {
+                let player = self.players.get(&entity_id).filter(|p| !p.is_dead())?;
+                player.position
+            }
+        };
+        Some([position[0], position[1] + PROJECTILE_HEIGHT, position[2]])
+    }
+
+    /// Launch a projectile from an entity at a target
+    /// Returns the spawn message to broadcast
+    fn spawn_projectile(
+        &mut self,
+        owner_id: u64,
+        target_id: u64,
+        payload: ProjectilePayload,
+        speed: f32,
+        homing: bool,
+        range: f32,
+    ) -> Option<ServerMessage> {
+        let (zone_id, owner_position) = match self.players.get(&owner_id) {
+            Some(player) => (player.zone_id, player.position),
+            None => {
+                let enemy = self.enemies.get(&owner_id)?;
+                (enemy.zone_id, enemy.position)
+            }
+        };
+        let origin = [owner_position[0], owner_position[1] + PROJECTILE_HEIGHT, owner_position[2]];
+        let aim = self.projectile_target_position(target_id)?;
+
+        let id = self.next_projectile_id;
+        self.next_projectile_id += 1;
+
+        // Allow some overshoot so straight shots can still catch a moving target
+        let projectile = ServerProjectile::new(
+            id, zone_id, owner_id, Some(target_id), payload, origin, aim, speed, homing, range * 1.5,
+        );
+
+        let message = ServerMessage::ProjectileSpawn {
+            id,
+            zone_id,
+            owner_id,
+            target_id: Some(target_id),
+            ability_id: match payload {
+                ProjectilePayload::Ability(ability_id) => Some(ability_id),
+                ProjectilePayload::Attack => None,
+            },
+            position: origin,
+            velocity: projectile.velocity(),
+            homing,
+        };
+
+        self.projectiles.insert(id, projectile);
+        Some(message)
+    }
+
+    /// Move all projectiles and resolve the ones that hit something
+    /// Returns messages to broadcast
+    fn update_projectiles(&mut self, delta: f32) -> Vec<ServerMessage> {
+        let mut messages = Vec::new();
+        let mut impacts = Vec::new();
+
+        // Look up target positions first; projectiles whose target is gone fly on until they expire
+        let target_positions: HashMap<u64, [f32; 3]> = self.projectiles.values()
+            .filter_map(|p| p.target_id)
+            .filter_map(|tid| Some((tid, self.projectile_target_position(tid)?)))
+            .collect();
+
+        for projectile in self.projectiles.values_mut() {
+            let target_position = projectile.target_id.and_then(|tid| target_positions.get(&tid).copied());
+            let obstacles = self.zone_manager.get_obstacles(projectile.zone_id);
+
+            if let Some(impact) = projectile.update(delta, target_position, obstacles) {
+                impacts.push((projectile.id, impact));
+            }
+        }
+
+        for (id, impact) in impacts {
+            let projectile = match self.projectiles.remove(&id) {
+                Some(p) => p,
+                None => continue,
+            };
+
+            let hit_target = match impact {
+                ProjectileImpact::Target => projectile.target_id,
+                ProjectileImpact::Obstacle | ProjectileImpact::Expired => None,
+            };
+
+            messages.push(ServerMessage::ProjectileImpact {
+                id,
+                target_id: hit_target,
+                position: projectile.position,
+            });
+
+            let target_id = match hit_target {
+                Some(tid) => tid,
+                None => continue,
+            };
+
+            // Enemy arrows and bolts carry the enemy's auto-attack
+            if let Some(enemy) = self.enemies.get(&projectile.owner_id).filter(|e| e.health > 0) {
+                let attack_power = enemy.attack_power;
+                messages.extend(self.process_enemy_attack(projectile.owner_id, target_id, attack_power));
+                continue;
+            }
+            
+            // Effects only apply if the owner is still around
+            if !self.players.contains_key(&projectile.owner_id) {
+                continue;
+            }
+
+            match projectile.payload {
+                ProjectilePayload::Attack => {
+                    if let Some(damage_event) = self.process_attack(projectile.owner_id, target_id) {
+                        messages.push(damage_event);
+                    }
+                }
+                ProjectilePayload::Ability(ability_id) => {
+                    if let Some(ability) = self.ability_for_player(projectile.owner_id, ability_id) {
+                        messages.extend(self.apply_ability_effects(projectile.owner_id, &ability, Some(target_id)));
+                    }
+                }
+            }
+        }
+
+        messages
+    }
+
+    // ==========================================================================
+    // Ability System
+    // ==========================================================================
+    
+    /// Get all ability definitions, sorted by ID (synced to clients at login)
+    pub fn get_ability_definitions(&self) -> Vec<AbilityDef> {
+        let mut abilities: Vec<AbilityDef> = self.abilities.values().cloned().collect();
+        abilities.sort_by_key(|a| a.id);
+        abilities
+    }
+    
+    /// Get an ability scaled for a player's skill rank
+    fn ability_for_player(&self, player_id: u64, ability_id: u32) -> Option<AbilityDef> {
+        let rank = self.players.get(&player_id).map(|p| p.get_skill_rank(ability_id)).unwrap_or(0);
+        Some(self.abilities.get(&ability_id)?.scaled_for_rank(rank))
+    }
+    
+    /// Set a player's learned skills and skill points after loading them
+    /// Characters without any skills are granted their class's level 1 skills
+    /// Returns newly granted skills (ability_id, rank) that should be persisted
+    pub fn load_player_skills(&mut self, player_id: u64, skill_ranks: HashMap<u32, u8>, skill_points: u32) -> Vec<(u32, u8)> {
+        let player = match self.players.get_mut(&player_id) {
+            Some(p) => p,
+            None => return Vec::new(),
+        };
+        
+        player.skill_ranks = skill_ranks;
+        player.skill_points = skill_points;
+        
+        if !player.skill_ranks.is_empty() {
+            return Vec::new();
+        }
+        
+        let starter_skills: Vec<(u32, u8)> = self.abilities
+            .values()
+            .filter(|a| a.level_requirement <= 1)
+            .filter(|a| a.is_available_to(player.class, player.specialization))
+            .map(|a| (a.id, 1))
+            .collect();
+        player.skill_ranks.extend(starter_skills.iter().copied());
+        starter_skills
+    }
+    
+    /// Learn a skill or train it to the next rank
+    /// Returns (messages_for_player, new_rank if it changed)
+    pub fn upgrade_player_skill(&mut self, player_id: u64, ability_id: u32) -> (Vec<ServerMessage>, Option<u8>) {
+        let mut messages = Vec::new();
+        
+        let player = match self.players.get_mut(&player_id) {
+            Some(p) => p,
+            None => return (messages, None),
+        };
+        let ability = match self.abilities.get(&ability_id) {
+            Some(a) => a,
+            None => {
+                messages.push(ServerMessage::SkillUpgradeFailed {
+                    ability_id,
+                    reason: "Unknown skill".into(),
+                });
+                return (messages, None);
+            }
+        };
         
-        for (zone_id, pos_xz, enemy_type, level) in &spawns {
-            let position = [pos_xz[0], 0.0, pos_xz[1]];
-            let enemy_id = self.spawn_enemy_with_level(*zone_id, position, *enemy_type, *level);
-            
-            // Find which area this enemy belongs to
-            if let Some(area_id) = self.spawn_area_manager.find_area_at(*zone_id, pos_xz[0], pos_xz[1]) {
-                enemy_area_assignments.push((enemy_id, area_id.to_string()));
+        let new_rank = match player.try_upgrade_skill(ability) {
+            Ok(true) => {
+                info!("Player {} trained {} to {}", player.name, ability.name,
+                    skill_rank_label(player.get_skill_rank(ability_id)));
+                Some(player.get_skill_rank(ability_id))
             }
-            
-            // Broadcast new enemy spawn
-            if let Some(enemy) = self.enemies.get(&enemy_id) {
-                messages.push(ServerMessage::EnemySpawn {
-                    id: enemy_id,
-                    zone_id: *zone_id,
-                    enemy_type: enemy.enemy_type,
-                    position: enemy.position,
-                    health: enemy.health,
-                    max_health: enemy.max_health,
-                    level: enemy.level,
+            Ok(false) => {
+                messages.push(ServerMessage::SkillUpgradeFailed {
+                    ability_id,
+                    reason: "Training failed".into(),
+                });
+                None
+            }
+            Err(reason) => {
+                messages.push(ServerMessage::SkillUpgradeFailed {
+                    ability_id,
+                    reason: reason.into(),
                 });
+                return (messages, None);
             }
+        };
+        
+        // Points are spent on failed training too
+        messages.push(ServerMessage::SkillUpdate {
+            ability_id,
+            rank: player.get_skill_rank(ability_id),
+            skill_points: player.skill_points,
+        });
+        
+        (messages, new_rank)
+    }
+    
+    /// Get a player's attributes and derived stats
+    pub fn get_player_attributes(&self, player_id: u64) -> Option<ServerMessage> {
+        let player = self.players.get(&player_id)?;
+        Some(ServerMessage::AttributesUpdate {
+            attributes: player.attributes,
+            stat_points: player.stat_points,
+            max_health: player.max_health,
+            max_mana: player.max_mana,
+            attack: player.attack_power,
+            defense: player.defense,
+            attack_speed: player.get_attack_speed(&self.items),
+            crit_chance: player.get_crit_chance(),
+            health: player.health,
+            mana: player.mana,
+        })
+    }
+    
+    /// Spend a player's stat points on a primary attribute
+    /// Returns the message for the player (updated attributes or failure)
+    pub fn allocate_player_stat(&mut self, player_id: u64, stat: PrimaryStat, amount: u32) -> Option<ServerMessage> {
+        let player = self.players.get_mut(&player_id)?;
+        if let Err(reason) = player.allocate_stat(stat, amount) {
+            return Some(ServerMessage::StatAllocationFailed { reason: reason.into() });
         }
+        self.get_player_attributes(player_id)
+    }
+    
+    /// Reset a player's attributes for gold (RESPEC_GOLD_PER_LEVEL per level)
+    /// Returns messages for the player
+    pub fn reset_player_stats(&mut self, player_id: u64) -> Vec<ServerMessage> {
+        let player = match self.players.get_mut(&player_id) {
+            Some(p) => p,
+            None => return Vec::new(),
+        };
         
-        // Register all enemies with their areas
-        for (enemy_id, area_id) in enemy_area_assignments {
-            self.spawn_area_manager.register_enemy(enemy_id, &area_id);
+        let cost = RESPEC_GOLD_PER_LEVEL * player.level as u64;
+        if player.attributes == Attributes::starting_for_class(player.class) {
+            return vec![ServerMessage::StatAllocationFailed { reason: "No stat points to reset".into() }];
+        }
+        if player.gold < cost {
+            return vec![ServerMessage::StatAllocationFailed {
+                reason: format!("Resetting stats costs {} gold", cost),
+            }];
         }
         
+        player.gold -= cost;
+        let refunded = player.reset_attributes();
+        info!("Player {} reset {} stat points for {} gold", player.name, refunded, cost);
+        
+        let mut messages = vec![ServerMessage::GoldUpdate { gold: player.gold }];
+        messages.extend(self.get_player_attributes(player_id));
         messages
     }
     
-    /// Award XP to a player (for commands)
-    pub fn add_experience_to_player(&mut self, player_id: u64, amount: u32) -> Option<(u32, Option<u32>)> {
+    /// Choose a player's class specialization
+    /// Returns the message for the player (chosen or failed)
+    pub fn choose_player_specialization(&mut self, player_id: u64, specialization: Specialization) -> Option<ServerMessage> {
         let player = self.players.get_mut(&player_id)?;
-        let level_up = player.add_experience(amount);
-        Some((player.experience, level_up))
+        
+        match player.choose_specialization(specialization) {
+            Ok(()) => {
+                info!("Player {} chose the {} specialization", player.name, specialization.name());
+                Some(ServerMessage::SpecializationChosen {
+                    specialization,
+                    max_health: player.max_health,
+                    max_mana: player.max_mana,
+                    attack: player.attack_power,
+                    defense: player.defense,
+                })
+            }
+            Err(reason) => Some(ServerMessage::SpecializationFailed {
+                reason: reason.into(),
+            }),
+        }
     }
     
-    /// Set XP for a player (for commands)
-    pub fn set_player_experience(&mut self, player_id: u64, experience: u32) -> Option<(u32, u32, Option<u32>)> {
-        let player = self.players.get_mut(&player_id)?;
-        let level_changed = player.set_experience(experience);
-        Some((player.experience, player.level, level_changed))
+    /// Get a player's learned skills and unspent skill points
+    pub fn get_player_skills(&self, player_id: u64) -> Option<(Vec<SkillRank>, u32)> {
+        let player = self.players.get(&player_id)?;
+        Some((player.get_skill_list(), player.skill_points))
     }
     
-    // ==========================================================================
-    // Ability System
-    // ==========================================================================
-    
     /// Process an ability use request
+    /// Abilities with a cast time start casting here and resolve in update_player_abilities
     /// Returns (messages_for_caster, messages_for_broadcast)
     pub fn process_ability(
         &mut self,
@@ -911,8 +2972,8 @@ impl GameWorld {
         let mut caster_msgs = Vec::new();
         let mut broadcast_msgs = Vec::new();
         
-        // Get ability definition
-        let ability = match get_ability_by_id(ability_id) {
+        // Get ability definition, scaled for the caster's skill rank
+        let ability = match self.ability_for_player(caster_id, ability_id) {
             Some(a) => a,
             None => {
                 caster_msgs.push(ServerMessage::AbilityFailed {
@@ -929,61 +2990,110 @@ impl GameWorld {
             None => return (caster_msgs, broadcast_msgs),
         };
         
-        // Check if dead
-        if caster.is_dead() {
+        // Only one cast at a time
+        if caster.is_casting() {
             caster_msgs.push(ServerMessage::AbilityFailed {
                 ability_id,
-                reason: "You are dead".into(),
+                reason: "Already casting".into(),
             });
             return (caster_msgs, broadcast_msgs);
         }
         
-        // Check if stunned
-        if caster.is_stunned() {
+        // Global cooldown is shared between abilities and auto-attack
+        if caster.is_on_global_cooldown() {
             caster_msgs.push(ServerMessage::AbilityFailed {
                 ability_id,
-                reason: "You are stunned".into(),
+                reason: "Global cooldown".into(),
+            });
+            return (caster_msgs, broadcast_msgs);
+        }
+        
+        let validated_target = match self.validate_ability(caster_id, &ability, target_id) {
+            Ok(target) => target,
+            Err(reason) => {
+                caster_msgs.push(ServerMessage::AbilityFailed { ability_id, reason });
+                return (caster_msgs, broadcast_msgs);
+            }
+        };
+        
+        let caster = self.players.get_mut(&caster_id).unwrap();
+        caster.start_global_cooldown();
+        caster.is_resting = false;
+        
+        // Abilities with a cast time resolve when the cast finishes
+        if ability.cast_time > 0.0 {
+            let caster = self.players.get_mut(&caster_id).unwrap();
+            caster.start_cast(ability_id, validated_target, ability.cast_time, false);
+            
+            broadcast_msgs.push(ServerMessage::CastStarted {
+                caster_id,
+                ability_id,
+                target_id: validated_target,
+                duration: ability.cast_time,
+                is_channel: false,
             });
             return (caster_msgs, broadcast_msgs);
         }
         
+        self.resolve_ability(caster_id, &ability, validated_target, &mut caster_msgs, &mut broadcast_msgs);
+        
+        (caster_msgs, broadcast_msgs)
+    }
+    
+    /// Check whether a player can use an ability on the requested target
+    /// Returns the validated target, or the failure reason
+    fn validate_ability(
+        &self,
+        caster_id: u64,
+        ability: &AbilityDef,
+        target_id: Option<u64>,
+    ) -> Result<Option<u64>, String> {
+        let ability_id = ability.id;
+        let caster = self.players.get(&caster_id).ok_or("Unknown caster")?;
+        
+        // Check if dead
+        if caster.is_dead() {
+            return Err("You are dead".into());
+        }
+        
+        // Check if stunned
+        if caster.is_stunned() {
+            return Err("You are stunned".into());
+        }
+        
         // Check class restriction
         if let Some(required_class) = ability.class_restriction {
             if caster.class != required_class {
-                caster_msgs.push(ServerMessage::AbilityFailed {
-                    ability_id,
-                    reason: format!("Requires {} class", required_class.name()),
-                });
-                return (caster_msgs, broadcast_msgs);
+                return Err(format!("Requires {} class", required_class.name()));
+            }
+        }
+        
+        // Check specialization restriction
+        if let Some(required_spec) = ability.specialization {
+            if caster.specialization != Some(required_spec) {
+                return Err(format!("Requires {} specialization", required_spec.name()));
             }
         }
         
         // Check level requirement
         if caster.level < ability.level_requirement {
-            caster_msgs.push(ServerMessage::AbilityFailed {
-                ability_id,
-                reason: format!("Requires level {}", ability.level_requirement),
-            });
-            return (caster_msgs, broadcast_msgs);
+            return Err(format!("Requires level {}", ability.level_requirement));
+        }
+        
+        // Check the skill has been learned
+        if caster.get_skill_rank(ability_id) == 0 {
+            return Err("Skill not learned".into());
         }
         
         // Check cooldown
         if caster.is_ability_on_cooldown(ability_id) {
             let remaining = caster.get_ability_cooldown(ability_id);
-            caster_msgs.push(ServerMessage::AbilityFailed {
-                ability_id,
-                reason: format!("On cooldown ({:.1}s)", remaining),
-            });
-            return (caster_msgs, broadcast_msgs);
+            return Err(format!("On cooldown ({:.1}s)", remaining));
         }
         
         // Check mana
         if caster.mana < ability.mana_cost {
-            caster_msgs.push(ServerMessage::AbilityFailed {
-                ability_id,
-                reason: "Not enough mana".into(),
-            });
-            return (caster_msgs, broadcast_msgs);
+            return Err("Not enough mana".into());
         }
         
         // Validate target based on ability type
@@ -992,47 +3102,75 @@ impl GameWorld {
             TargetType::Enemy => {
                 match target_id {
                     Some(tid) => {
-                        // Check if target exists and is an enemy
-                        if !self.enemies.contains_key(&tid) {
-                            caster_msgs.push(ServerMessage::AbilityFailed {
-                                ability_id,
-                                reason: "Invalid target".into(),
-                            });
-                            return (caster_msgs, broadcast_msgs);
-                        }
+                        // Check if target exists and is an enemy or Metin stone
+                        let Some((position, _)) = self.hostile_target(tid) else {
+                            return Err("Invalid target".into());
+                        };
                         // Check range
-                        if let Some(enemy) = self.enemies.get(&tid) {
-                            let dx = enemy.position[0] - caster.position[0];
-                            let dz = enemy.position[2] - caster.position[2];
-                            let dist = (dx * dx + dz * dz).sqrt();
-                            if dist > ability.range {
-                                caster_msgs.push(ServerMessage::AbilityFailed {
-                                    ability_id,
-                                    reason: "Out of range".into(),
-                                });
-                                return (caster_msgs, broadcast_msgs);
-                            }
+                        let dx = position[0] - caster.position[0];
+                        let dz = position[2] - caster.position[2];
+                        let dist = (dx * dx + dz * dz).sqrt();
+                        if dist > ability.range {
+                            return Err("Out of range".into());
+                        }
+                        if !navigation::has_line_of_sight(
+                            Vec2::from_3d(caster.position),
+                            Vec2::from_3d(position),
+                            self.zone_manager.get_obstacles(caster.zone_id),
+                        ) {
+                            return Err("No line of sight".into());
                         }
                         Some(tid)
                     }
                     None => {
-                        caster_msgs.push(ServerMessage::AbilityFailed {
-                            ability_id,
-                            reason: "No target".into(),
-                        });
-                        return (caster_msgs, broadcast_msgs);
+                        return Err("No target".into());
                     }
                 }
             }
             TargetType::Ally => {
-                // For now, ally abilities only work on self
-                Some(caster_id)
+                match target_id.filter(|tid| *tid != caster_id) {
+                    Some(tid) => {
+                        let target = self.players.get(&tid).ok_or("Invalid target")?;
+                        let dx = target.position[0] - caster.position[0];
+                        let dz = target.position[2] - caster.position[2];
+                        if target.zone_id != caster.zone_id || (dx * dx + dz * dz).sqrt() > ability.range {
+                            return Err("Out of range".into());
+                        }
+                        if ability.revives_target() && !target.is_dead() {
+                            return Err("Target is not dead".into());
+                        }
+                        if !ability.revives_target() && target.is_dead() {
+                            return Err("Target is dead".into());
+                        }
+                        Some(tid)
+                    }
+                    // Without another player targeted, ally abilities go on the caster
+                    None if ability.revives_target() => return Err("No target".into()),
+                    None => Some(caster_id),
+                }
             }
             TargetType::None | TargetType::AreaAroundSelf | TargetType::AreaAroundTarget => None,
         };
         
-        // All checks passed - consume mana and start cooldown
-        let caster = self.players.get_mut(&caster_id).unwrap();
+        
+        Ok(validated_target)
+    }
+    
+    /// Consume mana, start the cooldown and apply the ability (or start its channel)
+    fn resolve_ability(
+        &mut self,
+        caster_id: u64,
+        ability: &AbilityDef,
+        validated_target: Option<u64>,
+        caster_msgs: &mut Vec<ServerMessage>,
+        broadcast_msgs: &mut Vec<ServerMessage>,
+    ) {
+        let ability_id = ability.id;
+        let caster = match self.players.get_mut(&caster_id) {
+            Some(p) => p,
+            None => return,
+        };
+        
         caster.consume_mana(ability.mana_cost);
         caster.start_ability_cooldown(ability_id, ability.cooldown);
         
@@ -1050,7 +3188,52 @@ impl GameWorld {
             target_id: validated_target,
         });
         
-        // Apply effects
+        // Channeled abilities apply their effects in pulses while channeling
+        if ability.channel_duration > 0.0 {
+            caster.start_cast(ability_id, validated_target, ability.channel_duration, true);
+            
+            broadcast_msgs.push(ServerMessage::CastStarted {
+                caster_id,
+                ability_id,
+                target_id: validated_target,
+                duration: ability.channel_duration,
+                is_channel: true,
+            });
+            return;
+        }
+        
+        // Projectile abilities apply their effects on impact
+        if ability.projectile_speed > 0.0 {
+            if let Some(tid) = validated_target.filter(|tid| self.hostile_target(*tid).is_some()) {
+                if let Some(spawn) = self.spawn_projectile(
+                    caster_id,
+                    tid,
+                    ProjectilePayload::Ability(ability_id),
+                    ability.projectile_speed,
+                    true,
+                    ability.range,
+                ) {
+                    broadcast_msgs.push(spawn);
+                }
+                return;
+            }
+        }
+        
+        broadcast_msgs.extend(self.apply_ability_effects(caster_id, ability, validated_target));
+    }
+    
+    /// Apply an ability's effects to its target
+    /// Returns messages to broadcast
+    fn apply_ability_effects(
+        &mut self,
+        caster_id: u64,
+        ability: &AbilityDef,
+        validated_target: Option<u64>,
+    ) -> Vec<ServerMessage> {
+        let mut messages = Vec::new();
+        let ability_id = ability.id;
+        let source = self.players.get(&caster_id).map(combat_log::player_participant);
+        
         for effect in &ability.effects {
             match effect {
                 AbilityEffect::Damage { base, attack_scaling } => {
@@ -1058,30 +3241,61 @@ impl GameWorld {
                         if let Some(enemy) = self.enemies.get_mut(&tid) {
                             let caster = self.players.get(&caster_id).unwrap();
                             let damage = caster.calculate_ability_damage(*base, *attack_scaling, &self.items);
+                            let hit = enemy.apply_immunity(combat::resolve_hit(
+                                &caster.combat_profile(&self.items),
+                                &enemy.combat_profile(),
+                                damage,
+                            ));
                             
-                            enemy.health = enemy.health.saturating_sub(damage);
+                            let health_before = enemy.health;
+                            enemy.health = enemy.health.saturating_sub(hit.damage);
                             enemy.target_id = Some(caster_id); // Aggro
+                            self.enemy_pulls.push((tid, caster_id));
                             
-                            broadcast_msgs.push(ServerMessage::DamageEvent {
+                            self.combat_log.record_attack(
+                                combat_log::player_participant(caster),
+                                combat_log::enemy_participant(enemy),
+                                Some(ability_id),
+                                damage,
+                                hit,
+                                hit.damage,
+                                health_before,
+                            );
+                            
+                            messages.push(ServerMessage::DamageEvent {
                                 attacker_id: caster_id,
                                 target_id: tid,
-                                damage,
+                                damage: hit.damage,
                                 target_new_health: enemy.health,
-                                is_critical: false,
+                                outcome: hit.outcome,
                             });
+                            if let Some(caster) = self.players.get_mut(&caster_id) {
+                                caster.enter_combat();
+                            }
+                        } else if self.metin_stones.contains_key(&tid) {
+                            let caster = self.players.get(&caster_id).unwrap();
+                            let damage = caster.calculate_ability_damage(*base, *attack_scaling, &self.items);
+                            messages.extend(self.hit_metin_stone(caster_id, tid, damage, Some(ability_id)));
                         }
                     }
                 }
                 AbilityEffect::Heal { base, health_scaling } => {
                     let target = validated_target.unwrap_or(caster_id);
-                    if let Some(player) = self.players.get_mut(&target) {
+                    if let Some(player) = self.players.get_mut(&target).filter(|p| !p.is_dead()) {
                         let heal_amount = player.calculate_heal_amount(*base, *health_scaling);
                         let old_health = player.health;
                         player.health = (player.health + heal_amount).min(player.max_health);
                         let actual_heal = player.health - old_health;
                         
                         if actual_heal > 0 {
-                            broadcast_msgs.push(ServerMessage::HealEvent {
+                            self.combat_log.record_heal(
+                                source.clone(),
+                                combat_log::player_participant(player),
+                                Some(ability_id),
+                                None,
+                                actual_heal,
+                            );
+                            messages.push(ServerMessage::HealEvent {
                                 healer_id: caster_id,
                                 target_id: target,
                                 amount: actual_heal,
@@ -1095,21 +3309,40 @@ impl GameWorld {
                         if let Some(enemy) = self.enemies.get_mut(&tid) {
                             // For enemies, we'll track DOT separately
                             // For now, just apply first tick immediately
-                            enemy.health = enemy.health.saturating_sub(*damage_per_tick);
-                            broadcast_msgs.push(ServerMessage::DamageEvent {
+                            let hit = enemy.apply_immunity(combat::HitResult {
+                                outcome: HitOutcome::Hit,
+                                damage: *damage_per_tick,
+                            });
+                            let health_before = enemy.health;
+                            enemy.health = enemy.health.saturating_sub(hit.damage);
+                            if let Some(source) = source.clone() {
+                                self.combat_log.record_attack(
+                                    source,
+                                    combat_log::enemy_participant(enemy),
+                                    Some(ability_id),
+                                    *damage_per_tick,
+                                    hit,
+                                    hit.damage,
+                                    health_before,
+                                );
+                            }
+                            messages.push(ServerMessage::DamageEvent {
                                 attacker_id: caster_id,
                                 target_id: tid,
-                                damage: *damage_per_tick,
+                                damage: hit.damage,
                                 target_new_health: enemy.health,
-                                is_critical: false,
+                                outcome: hit.outcome,
                             });
+                            if let Some(caster) = self.players.get_mut(&caster_id) {
+                                caster.enter_combat();
+                            }
                             // TODO: Track DOT on enemies properly
                         }
                     }
                 }
                 AbilityEffect::HealOverTime { heal_per_tick, interval, duration } => {
                     let target = validated_target.unwrap_or(caster_id);
-                    if let Some(player) = self.players.get_mut(&target) {
+                    if let Some(player) = self.players.get_mut(&target).filter(|p| !p.is_dead()) {
                         // Calculate heal per tick (if 0, use 2% of max health)
                         let tick_heal = if *heal_per_tick == 0 {
                             (player.max_health as f32 * 0.02) as u32
@@ -1128,7 +3361,11 @@ impl GameWorld {
                             false,
                         );
                         
-                        broadcast_msgs.push(ServerMessage::BuffApplied {
+                        if let Some(source) = source.clone() {
+                            self.combat_log.record_buff(source, combat_log::player_participant(player), ability_id, *duration);
+                        }
+                        
+                        messages.push(ServerMessage::BuffApplied {
                             target_id: target,
                             buff_id,
                             ability_id,
@@ -1147,7 +3384,11 @@ impl GameWorld {
                             false,
                         );
                         
-                        broadcast_msgs.push(ServerMessage::BuffApplied {
+                        if let Some(source) = source.clone() {
+                            self.combat_log.record_buff(source, combat_log::player_participant(player), ability_id, *duration);
+                        }
+                        
+                        messages.push(ServerMessage::BuffApplied {
                             target_id: target,
                             buff_id,
                             ability_id,
@@ -1166,7 +3407,11 @@ impl GameWorld {
                             false,
                         );
                         
-                        broadcast_msgs.push(ServerMessage::BuffApplied {
+                        if let Some(source) = source.clone() {
+                            self.combat_log.record_buff(source, combat_log::player_participant(player), ability_id, *duration);
+                        }
+                        
+                        messages.push(ServerMessage::BuffApplied {
                             target_id: target,
                             buff_id,
                             ability_id,
@@ -1185,7 +3430,11 @@ impl GameWorld {
                             false,
                         );
                         
-                        broadcast_msgs.push(ServerMessage::BuffApplied {
+                        if let Some(source) = source.clone() {
+                            self.combat_log.record_buff(source, combat_log::player_participant(player), ability_id, *duration);
+                        }
+                        
+                        messages.push(ServerMessage::BuffApplied {
                             target_id: target,
                             buff_id,
                             ability_id,
@@ -1202,21 +3451,187 @@ impl GameWorld {
                     // TODO: Implement enemy debuff tracking
                 }
                 AbilityEffect::Slow { multiplier, duration } => {
-                    // TODO: Implement enemy slow
+                    if let Some(tid) = validated_target {
+                        if let Some(enemy) = self.enemies.get_mut(&tid) {
+                            enemy.apply_slow(*multiplier, *duration);
+                            if let Some(source) = source.clone() {
+                                self.combat_log.record_buff(source, combat_log::enemy_participant(enemy), ability_id, *duration);
+                            }
+                        }
+                    }
                 }
                 AbilityEffect::Stun { duration } => {
                     // TODO: Implement enemy stun
                 }
+                AbilityEffect::Resurrect { health_percent } => {
+                    if let Some(tid) = validated_target {
+                        if let Some(player) = self.players.get_mut(&tid).filter(|p| p.is_dead()) {
+                            // No resurrection sickness: that is the price of releasing instead
+                            let position = player.position;
+                            player.revive(position, (player.max_health as f32 * health_percent) as u32);
+                            info!("Player {} was resurrected by {}", player.name, caster_id);
+                            
+                            if let Some(source) = source.clone() {
+                                self.combat_log.record_heal(
+                                    Some(source),
+                                    combat_log::player_participant(player),
+                                    Some(ability_id),
+                                    None,
+                                    player.health,
+                                );
+                            }
+                            
+                            // The revived player picks this up as their own respawn
+                            messages.push(ServerMessage::EntityRespawn {
+                                entity_id: tid,
+                                position,
+                                health: player.health,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        
+        
+        messages
+    }
+    
+    /// Advance a player's cast or channel, handling interrupts, completion and pulses
+    /// Returns (messages_for_caster, messages_for_broadcast)
+    fn update_player_cast(&mut self, player_id: u64, delta: f32) -> (Vec<ServerMessage>, Vec<ServerMessage>) {
+        let mut caster_msgs = Vec::new();
+        let mut broadcast_msgs = Vec::new();
+        
+        let player = match self.players.get_mut(&player_id) {
+            Some(p) if p.is_casting() => p,
+            _ => return (caster_msgs, broadcast_msgs),
+        };
+        
+        // Movement, stuns, death and heavy damage interrupt the cast
+        if let Some(reason) = player.check_cast_interrupt() {
+            if let Some(cast) = player.cancel_cast() {
+                debug!("Player {} cast of ability {} interrupted: {}", player_id, cast.ability_id, reason);
+                broadcast_msgs.push(ServerMessage::CastInterrupted {
+                    caster_id: player_id,
+                    ability_id: cast.ability_id,
+                    reason: reason.into(),
+                });
+            }
+            return (caster_msgs, broadcast_msgs);
+        }
+        
+        let events = player.update_cast(delta);
+        let (ability_id, target_id) = match &player.active_cast {
+            Some(cast) => (cast.ability_id, cast.target_id),
+            None => return (caster_msgs, broadcast_msgs),
+        };
+        
+        let rank = player.get_skill_rank(ability_id);
+        let ability = match self.abilities.get(&ability_id) {
+            Some(a) => a.scaled_for_rank(rank),
+            None => {
+                player.cancel_cast();
+                return (caster_msgs, broadcast_msgs);
+            }
+        };
+        
+        for event in events {
+            match event {
+                CastEvent::Progress(elapsed, duration) => {
+                    broadcast_msgs.push(ServerMessage::CastProgress {
+                        caster_id: player_id,
+                        ability_id,
+                        elapsed,
+                        duration,
+                    });
+                }
+                CastEvent::ChannelPulse => {
+                    broadcast_msgs.extend(self.apply_ability_effects(player_id, &ability, target_id));
+                }
+                CastEvent::ChannelFinished => {
+                    if let Some(player) = self.players.get_mut(&player_id) {
+                        player.cancel_cast();
+                    }
+                    broadcast_msgs.push(ServerMessage::CastCompleted {
+                        caster_id: player_id,
+                        ability_id,
+                    });
+                }
+                CastEvent::CastFinished => {
+                    if let Some(player) = self.players.get_mut(&player_id) {
+                        player.cancel_cast();
+                    }
+                    
+                    // Target may have died or moved out of range during the cast
+                    match self.validate_ability(player_id, &ability, target_id) {
+                        Ok(target) => {
+                            broadcast_msgs.push(ServerMessage::CastCompleted {
+                                caster_id: player_id,
+                                ability_id,
+                            });
+                            self.resolve_ability(player_id, &ability, target, &mut caster_msgs, &mut broadcast_msgs);
+                        }
+                        Err(reason) => {
+                            broadcast_msgs.push(ServerMessage::CastInterrupted {
+                                caster_id: player_id,
+                                ability_id,
+                                reason,
+                            });
+                        }
+                    }
+                }
             }
         }
         
         (caster_msgs, broadcast_msgs)
     }
     
-    /// Update player cooldowns and buffs
-    /// Returns messages to send to individual players (player_id, messages)
-    pub fn update_player_abilities(&mut self, delta: f32) -> Vec<(u64, Vec<ServerMessage>)> {
+    /// Cancel a player's cast or channel on request
+    /// Returns the interrupt message to broadcast, if the player was casting
+    pub fn cancel_player_cast(&mut self, player_id: u64) -> Option<ServerMessage> {
+        let cast = self.players.get_mut(&player_id)?.cancel_cast()?;
+        Some(ServerMessage::CastInterrupted {
+            caster_id: player_id,
+            ability_id: cast.ability_id,
+            reason: "Cancelled".into(),
+        })
+    }
+    
+    /// Advance a player's combat timer and regeneration
+    /// Returns a throttled ResourceUpdate when health, mana or combat/rest state changed
+    fn update_player_regeneration(&mut self, player_id: u64, delta: f32) -> Option<ServerMessage> {
+        let player = self.players.get_mut(&player_id)?;
+        player.update_regeneration(delta, &self.items);
+        player.take_resource_update().map(Self::resource_update_message)
+    }
+    
+    fn resource_update_message(snapshot: ResourceSnapshot) -> ServerMessage {
+        ServerMessage::ResourceUpdate {
+            health: snapshot.health,
+            max_health: snapshot.max_health,
+            mana: snapshot.mana,
+            max_mana: snapshot.max_mana,
+            in_combat: snapshot.in_combat,
+            resting: snapshot.resting,
+        }
+    }
+    
+    /// Sit down to rest or stand up
+    /// Returns the resource update to send, or the reason it failed
+    pub fn set_player_resting(&mut self, player_id: u64, resting: bool) -> Result<ServerMessage, &'static str> {
+        let player = self.players.get_mut(&player_id).ok_or("Player not found")?;
+        player.set_resting(resting)?;
+        let snapshot = player.resource_snapshot();
+        player.last_sent_resources = Some(snapshot);
+        Ok(Self::resource_update_message(snapshot))
+    }
+    
+    /// Update player cooldowns, buffs, casts, auto-attacks, deaths and regeneration
+    /// Returns (messages for individual players as (player_id, messages), messages for broadcast)
+    pub fn update_player_abilities(&mut self, delta: f32) -> (Vec<(u64, Vec<ServerMessage>)>, Vec<ServerMessage>) {
         let mut player_messages = Vec::new();
+        let mut broadcast_msgs = Vec::new();
         
         // Collect player IDs first to avoid borrow issues
         let player_ids: Vec<u64> = self.players.keys().copied().collect();
@@ -1229,7 +3644,7 @@ impl GameWorld {
                 player.update_cooldowns(delta);
                 
                 // Update buffs and get events
-                let (expired_buffs, dot_damage, hot_heal) = player.update_buffs(delta);
+                let (expired_buffs, dot_ticks, hot_heal) = player.update_buffs(delta);
                 
                 // Send buff removed messages
                 for buff_id in expired_buffs {
@@ -1239,11 +3654,17 @@ impl GameWorld {
                     });
                 }
                 
-                // DOT damage is handled separately (on enemies)
                 // HOT heal already applied in update_buffs
                 
                 // Send heal event for HOT if any
                 if hot_heal > 0 {
+                    self.combat_log.record_heal(
+                        Some(combat_log::player_participant(player)),
+                        combat_log::player_participant(player),
+                        None,
+                        None,
+                        hot_heal,
+                    );
                     messages.push(ServerMessage::HealEvent {
                         healer_id: player_id,
                         target_id: player_id,
@@ -1251,6 +3672,67 @@ impl GameWorld {
                         target_new_health: player.health,
                     });
                 }
+                
+                // Apply damage over time (enemy hexes), attributed to whoever applied it
+                for (source_id, damage) in dot_ticks {
+                    if player.is_dead() {
+                        break;
+                    }
+                    let source = self.enemies.get(&source_id).map(combat_log::enemy_participant);
+                    let health_before = player.health;
+                    let dealt = player.take_damage(damage);
+                    if let Some(source) = source.clone() {
+                        self.combat_log.record_direct_damage(
+                            source,
+                            combat_log::player_participant(player),
+                            None,
+                            dealt,
+                            health_before,
+                        );
+                    }
+                    broadcast_msgs.push(ServerMessage::DamageEvent {
+                        attacker_id: source_id,
+                        target_id: player_id,
+                        damage: dealt,
+                        target_new_health: player.health,
+                        outcome: HitOutcome::Hit,
+                    });
+                    
+                    if player.is_dead() && !player.death_announced {
+                        info!("Player {} died to damage over time from {}", player_id, source_id);
+                        player.death_announced = true;
+                        self.combat_log.record_death(source, combat_log::player_participant(player));
+                        broadcast_msgs.push(ServerMessage::EntityDeath {
+                            entity_id: player_id,
+                            killer_id: Some(source_id),
+                        });
+                    }
+                }
+            }
+            
+            // Advance casts and channels
+            let (cast_msgs, cast_broadcasts) = self.update_player_cast(player_id, delta);
+            messages.extend(cast_msgs);
+            broadcast_msgs.extend(cast_broadcasts);
+            
+            // Fire auto-attack swings
+            let (swing_msgs, swing_broadcasts) = self.update_player_auto_attack(player_id);
+            messages.extend(swing_msgs);
+            broadcast_msgs.extend(swing_broadcasts);
+            
+            // Apply death penalties and auto-release dead players
+            let (death_msgs, death_broadcasts) = self.update_player_death(player_id, delta);
+            messages.extend(death_msgs);
+            broadcast_msgs.extend(death_broadcasts);
+            
+            // Regenerate out of combat and report health/mana
+            messages.extend(self.update_player_regeneration(player_id, delta));
+            
+            // Report movement speed changes from buffs, slows and gear
+            if let Some(player) = self.players.get_mut(&player_id) {
+                if let Some(speed) = player.take_movement_speed_update(&self.items) {
+                    messages.push(ServerMessage::MovementSpeedUpdate { speed });
+                }
             }
             
             if !messages.is_empty() {
@@ -1258,7 +3740,12 @@ impl GameWorld {
             }
         }
         
-        player_messages
+        (player_messages, broadcast_msgs)
+    }
+    
+    /// Take all combat log events recorded since the last call
+    pub fn drain_combat_log(&mut self) -> Vec<CombatLogEntry> {
+        self.combat_log.drain()
     }
     
     /// Get a player's action bar
@@ -0,0 +1,92 @@
+//! NPC schedules.
+//!
+//! Loaded from `npc_schedules.json` next to the server binary, keyed by NPC
+//! spawn id. A schedule is a loop of stops: the NPC walks to each one, faces the
+//! given direction and stays there for a while before moving on. NPCs without a
+//! schedule stand at their spawn.
+
+use std::collections::HashMap;
+use std::path::Path;
+use log::info;
+use serde::Deserialize;
+
+/// A place an NPC spends some time at
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScheduleStop {
+    pub position: [f32; 3],
+    /// Direction to face while waiting (radians)
+    #[serde(default)]
+    pub rotation: f32,
+    /// Seconds to stay
+    pub wait: f32,
+}
+
+/// Stops an NPC cycles through
+#[derive(Debug, Clone, Deserialize)]
+pub struct NpcSchedule {
+    pub stops: Vec<ScheduleStop>,
+}
+
+/// Schedules by NPC spawn id
+#[derive(Debug, Default)]
+pub struct NpcSchedules {
+    schedules: HashMap<i32, NpcSchedule>,
+}
+
+impl NpcSchedules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load schedules from a JSON file (object keyed by NPC spawn id)
+    pub fn load_from_json<P: AsRef<Path>>(&mut self, path: P) -> Result<(), String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read npc_schedules.json: {}", e))?;
+
+        let schedules: HashMap<String, NpcSchedule> = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse npc_schedules.json: {}", e))?;
+
+        self.schedules = Self::build(schedules)?;
+        info!("Loaded {} NPC schedules", self.schedules.len());
+        Ok(())
+    }
+
+    /// Check parsed schedules and key them by spawn id
+    /// Fails on the first bad entry, so nothing is half-loaded
+    fn build(schedules: HashMap<String, NpcSchedule>) -> Result<HashMap<i32, NpcSchedule>, String> {
+        let mut built = HashMap::with_capacity(schedules.len());
+        for (id_str, schedule) in schedules {
+            let spawn_id: i32 = id_str.parse()
+                .map_err(|_| format!("Invalid NPC spawn ID in npc_schedules.json: {}", id_str))?;
+            if schedule.stops.is_empty() {
+                return Err(format!("Schedule for NPC spawn {} has no stops", spawn_id));
+            }
+            built.insert(spawn_id, schedule);
+        }
+        Ok(built)
+    }
+
+    /// Get the schedule for an NPC spawn
+    pub fn get(&self, spawn_id: i32) -> Option<&NpcSchedule> {
+        self.schedules.get(&spawn_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> HashMap<String, NpcSchedule> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_bad_schedule_rejects_the_whole_file() {
+        let stop = r#"{ "stops": [{ "position": [0.0, 0.0, 0.0], "wait": 5.0 }] }"#;
+        let built = NpcSchedules::build(parse(&format!(r#"{{ "1": {}, "2": {} }}"#, stop, stop))).unwrap();
+        assert_eq!(built.len(), 2);
+
+        assert!(NpcSchedules::build(parse(&format!(r#"{{ "1": {}, "guard": {} }}"#, stop, stop))).is_err());
+        assert!(NpcSchedules::build(parse(&format!(r#"{{ "1": {}, "2": {{ "stops": [] }} }}"#, stop))).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+//! Patrol routes.
+//!
+//! Loaded from `patrol_paths.json` next to the server binary, exported from
+//! Godot `Path3D` nodes named `PatrolPath...`. Each route spawns its enemies in
+//! single file at the first waypoint; they walk the route out of combat and
+//! respawn at its start after dying.
+
+use std::collections::HashMap;
+use std::path::Path;
+use log::info;
+use serde::Deserialize;
+
+/// Gap between patrol members when they spawn (units)
+const SPAWN_SPACING: f32 = 1.5;
+
+fn default_looped() -> bool {
+    true
+}
+
+fn default_respawn_time() -> f32 {
+    60.0
+}
+
+/// An authored patrol route
+#[derive(Debug, Clone, Deserialize)]
+pub struct PatrolPath {
+    pub name: String,
+    /// Route points in world coordinates
+    pub waypoints: Vec<[f32; 3]>,
+    /// Walk back to the first waypoint after the last (otherwise turn around)
+    #[serde(default = "default_looped")]
+    pub looped: bool,
+    /// Seconds to stand at each waypoint
+    #[serde(default)]
+    pub pause: f32,
+    /// Enemy template ids of the members, front to back
+    pub enemies: Vec<String>,
+    /// Seconds after a member dies before it respawns
+    #[serde(default = "default_respawn_time")]
+    pub respawn_time: f32,
+}
+
+impl PatrolPath {
+    /// Spawn position for a member, lined up behind the first waypoint
+    pub fn spawn_position(&self, slot: usize) -> [f32; 3] {
+        let start = self.waypoints[0];
+        let Some(next) = self.waypoints.get(1) else {
+            return start;
+        };
+        let (dx, dz) = (start[0] - next[0], start[2] - next[2]);
+        let length = (dx * dx + dz * dz).sqrt().max(0.001);
+        let offset = slot as f32 * SPAWN_SPACING;
+        [start[0] + dx / length * offset, start[1], start[2] + dz / length * offset]
+    }
+}
+
+/// A patrol member waiting to (re)spawn
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PatrolSlot {
+    /// Zone and index of the route
+    pub zone_id: u32,
+    pub path: usize,
+    /// Position in the route's member list
+    pub member: usize,
+}
+
+/// Loads patrol routes and keeps their members spawned
+#[derive(Debug, Default)]
+pub struct PatrolManager {
+    paths: HashMap<u32, Vec<PatrolPath>>,
+    /// Enemy id -> the slot it fills
+    members: HashMap<u64, PatrolSlot>,
+    /// Slots waiting to spawn, with seconds remaining
+    respawns: Vec<(PatrolSlot, f32)>,
+}
+
+impl PatrolManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load routes from a JSON file (object keyed by zone id); members spawn on the next update
+    pub fn load_from_json<P: AsRef<Path>>(&mut self, path: P) -> Result<(), String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read patrol_paths.json: {}", e))?;
+
+        let zones: HashMap<String, Vec<PatrolPath>> = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse patrol_paths.json: {}", e))?;
+
+        self.paths.clear();
+        self.members.clear();
+        self.respawns.clear();
+
+        for (zone_str, paths) in zones {
+            let zone_id: u32 = zone_str.parse()
+                .map_err(|_| format!("Invalid zone ID in patrol_paths.json: {}", zone_str))?;
+            for (index, path) in paths.iter().enumerate() {
+                if path.waypoints.is_empty() {
+                    return Err(format!("Patrol path {} has no waypoints", path.name));
+                }
+                for member in 0..path.enemies.len() {
+                    self.respawns.push((PatrolSlot { zone_id, path: index, member }, 0.0));
+                }
+            }
+            info!("Loaded {} patrol paths for zone {}", paths.len(), zone_id);
+            self.paths.insert(zone_id, paths);
+        }
+
+        Ok(())
+    }
+
+    pub fn path(&self, slot: PatrolSlot) -> &PatrolPath {
+        &self.paths[&slot.zone_id][slot.path]
+    }
+
+    /// Tick respawn timers
+    /// Returns the slots that should spawn now
+    pub fn update(&mut self, delta: f32) -> Vec<PatrolSlot> {
+        let mut ready = Vec::new();
+        self.respawns.retain_mut(|(slot, remaining)| {
+            *remaining -= delta;
+            if *remaining > 0.0 {
+                return true;
+            }
+            ready.push(*slot);
+            false
+        });
+        ready
+    }
+
+    /// Track a freshly spawned member
+    pub fn register(&mut self, enemy_id: u64, slot: PatrolSlot) {
+        self.members.insert(enemy_id, slot);
+    }
+
+    /// Queue a slot for another try (spawning failed)
+    pub fn retry(&mut self, slot: PatrolSlot) {
+        let respawn_time = self.path(slot).respawn_time;
+        self.respawns.push((slot, respawn_time));
+    }
+
+    /// A member died: queue its respawn
+    pub fn on_enemy_death(&mut self, enemy_id: u64) {
+        if let Some(slot) = self.members.remove(&enemy_id) {
+            self.retry(slot);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_members_line_up_behind_start() {
+        let path: PatrolPath = serde_json::from_str(r#"{
+            "name": "Graveyard", "waypoints": [[0.0, 0.0, 0.0], [10.0, 0.0, 0.0]],
+            "enemies": ["Skeleton", "Skeleton"]
+        }"#).unwrap();
+
+        assert!(path.looped);
+        assert_eq!(path.spawn_position(0), [0.0, 0.0, 0.0]);
+        assert_eq!(path.spawn_position(1), [-SPAWN_SPACING, 0.0, 0.0]);
+    }
+}
@@ -2,20 +2,59 @@
 //!
 //! Spawn areas are polygon-based regions where enemies can spawn. Each area
 //! has configurable enemy types, population limits, and respawn timers.
+//! Enemy types can be limited to times of day and weather (see `world_clock`).
 
 use std::collections::HashMap;
 use std::path::Path;
 use log::{info, warn, error, debug};
 use rand::Rng;
-use mmo_shared::EnemyType;
+use mmo_shared::Weather;
+use super::world_clock::{TimeOfDay, WorldConditions};
+
+/// Check if a point is inside a polygon (XZ coordinates, ray casting)
+pub fn point_in_polygon(polygon: &[[f32; 2]], x: f32, z: f32) -> bool {
+    if polygon.len() < 3 {
+        return false;
+    }
+    
+    let mut inside = false;
+    let mut j = polygon.len() - 1;
+    
+    for i in 0..polygon.len() {
+        let xi = polygon[i][0];
+        let zi = polygon[i][1];
+        let xj = polygon[j][0];
+        let zj = polygon[j][1];
+        
+        if ((zi > z) != (zj > z)) && (x < (xj - xi) * (z - zi) / (zj - zi) + xi) {
+            inside = !inside;
+        }
+        j = i;
+    }
+    
+    inside
+}
 
 /// Configuration for a single enemy type within a spawn area
 #[derive(Debug, Clone)]
 pub struct EnemySpawnConfig {
-    pub enemy_type: EnemyType,
+    /// Enemy template id (see `enemy_templates.json`)
+    pub template_id: String,
     pub weight: f32,
     pub min_level: u8,
     pub max_level: u8,
+    /// Times of day it spawns at (empty = any time)
+    pub time_of_day: Vec<TimeOfDay>,
+    /// Weather it spawns in (empty = any weather)
+    pub weather: Vec<Weather>,
+}
+
+impl EnemySpawnConfig {
+    /// Whether this enemy type may spawn under the current conditions
+    pub fn allowed(&self, conditions: WorldConditions) -> bool {
+        (self.time_of_day.is_empty() || self.time_of_day.contains(&conditions.time_of_day))
+            && (self.weather.is_empty() || self.weather.contains(&conditions.weather))
+    }
 }
 
 /// A spawn area defining where and what enemies can spawn
@@ -38,27 +77,7 @@ pub struct SpawnArea {
 impl SpawnArea {
     /// Check if a point is inside the polygon (XZ coordinates)
     pub fn contains_point(&self, x: f32, z: f32) -> bool {
-        if self.polygon.len() < 3 {
-            return false;
-        }
-        
-        // Ray casting algorithm
-        let mut inside = false;
-        let mut j = self.polygon.len() - 1;
-        
-        for i in 0..self.polygon.len() {
-            let xi = self.polygon[i][0];
-            let zi = self.polygon[i][1];
-            let xj = self.polygon[j][0];
-            let zj = self.polygon[j][1];
-            
-            if ((zi > z) != (zj > z)) && (x < (xj - xi) * (z - zi) / (zj - zi) + xi) {
-                inside = !inside;
-            }
-            j = i;
-        }
-        
-        inside
+        point_in_polygon(&self.polygon, x, z)
     }
     
     /// Get a random point inside the polygon
@@ -103,29 +122,37 @@ impl SpawnArea {
         Some([cx / n, cz / n])
     }
     
-    /// Select a random enemy type based on weights
-    pub fn select_enemy_type(&self) -> Option<&EnemySpawnConfig> {
-        if self.enemy_configs.is_empty() {
+    /// Select a random enemy type based on weights, among those allowed right now
+    pub fn select_enemy_type(&self, conditions: WorldConditions) -> Option<&EnemySpawnConfig> {
+        let allowed: Vec<&EnemySpawnConfig> = self.enemy_configs.iter()
+            .filter(|c| c.allowed(conditions))
+            .collect();
+        if allowed.is_empty() {
             return None;
         }
         
-        let total_weight: f32 = self.enemy_configs.iter().map(|c| c.weight).sum();
+        let total_weight: f32 = allowed.iter().map(|c| c.weight).sum();
         if total_weight <= 0.0 {
-            return Some(&self.enemy_configs[0]);
+            return Some(allowed[0]);
         }
         
         let mut rng = rand::thread_rng();
         let roll = rng.gen_range(0.0..total_weight);
         
         let mut cumulative = 0.0;
-        for config in &self.enemy_configs {
+        for config in &allowed {
             cumulative += config.weight;
             if roll < cumulative {
                 return Some(config);
             }
         }
         
-        self.enemy_configs.last()
+        allowed.last().copied()
+    }
+    
+    /// Whether an enemy type from this area may still be around under the current conditions
+    pub fn allows(&self, template_id: &str, conditions: WorldConditions) -> bool {
+        self.enemy_configs.iter().any(|c| c.template_id == template_id && c.allowed(conditions))
     }
     
     /// Calculate the area of the polygon
@@ -147,6 +174,17 @@ impl SpawnArea {
     }
 }
 
+/// An enemy a spawn area wants placed
+#[derive(Debug, Clone)]
+pub struct SpawnRequest {
+    pub area_id: String,
+    pub zone_id: u32,
+    /// Position in XZ world coordinates
+    pub position: [f32; 2],
+    pub template_id: String,
+    pub level: u8,
+}
+
 /// Pending respawn entry
 #[derive(Debug)]
 struct PendingRespawn {
@@ -286,17 +324,8 @@ impl SpawnAreaManager {
     fn parse_enemy_config(&self, value: &serde_json::Value) -> Option<EnemySpawnConfig> {
         let obj = value.as_object()?;
         
-        let enemy_type_str = obj.get("enemy_type")?.as_str()?;
-        let enemy_type = match enemy_type_str.to_lowercase().as_str() {
-            "goblin" => EnemyType::Goblin,
-            "wolf" => EnemyType::Wolf,
-            "skeleton" => EnemyType::Skeleton,
-            "mutant" => EnemyType::Mutant,
-            _ => {
-                warn!("Unknown enemy type: {}", enemy_type_str);
-                return None;
-            }
-        };
+        // Checked against the enemy templates when the enemy spawns
+        let template_id = obj.get("enemy_type")?.as_str()?.to_string();
         
         let weight = obj.get("weight")
             .and_then(|v| v.as_f64())
@@ -310,11 +339,31 @@ impl SpawnAreaManager {
             .and_then(|v| v.as_u64())
             .unwrap_or(5) as u8;
         
+        let time_of_day = match obj.get("time_of_day").map(|v| serde_json::from_value(v.clone())) {
+            None => Vec::new(),
+            Some(Ok(times)) => times,
+            Some(Err(e)) => {
+                warn!("Spawn config {} has invalid time_of_day: {}", template_id, e);
+                return None;
+            }
+        };
+        
+        let weather = match obj.get("weather").map(|v| serde_json::from_value(v.clone())) {
+            None => Vec::new(),
+            Some(Ok(weather)) => weather,
+            Some(Err(e)) => {
+                warn!("Spawn config {} has invalid weather: {}", template_id, e);
+                return None;
+            }
+        };
+        
         Some(EnemySpawnConfig {
-            enemy_type,
+            template_id,
             weight,
             min_level,
             max_level,
+            time_of_day,
+            weather,
         })
     }
     
@@ -334,8 +383,8 @@ impl SpawnAreaManager {
     }
     
     /// Get spawn requests for initial population
-    /// Returns: Vec<(zone_id, position_xz, enemy_type, level)>
-    pub fn get_initial_spawns(&mut self) -> Vec<(u32, [f32; 2], EnemyType, u8)> {
+    /// Slots nothing may fill under the current conditions are retried after the respawn time
+    pub fn get_initial_spawns(&mut self, conditions: WorldConditions) -> Vec<SpawnRequest> {
         let mut spawns = Vec::new();
         let mut rng = rand::thread_rng();
         
@@ -344,12 +393,24 @@ impl SpawnAreaManager {
                 // Spawn up to max_population
                 for _ in 0..area.max_population {
                     if let Some(pos) = area.get_random_point() {
-                        if let Some(config) = area.select_enemy_type() {
+                        if let Some(config) = area.select_enemy_type(conditions) {
                             let level = rng.gen_range(config.min_level..=config.max_level);
-                            spawns.push((*zone_id, pos, config.enemy_type, level));
+                            spawns.push(SpawnRequest {
+                                area_id: area.id.clone(),
+                                zone_id: *zone_id,
+                                position: pos,
+                                template_id: config.template_id.clone(),
+                                level,
+                            });
                             
                             // Track population
                             *self.area_population.entry(area.id.clone()).or_insert(0) += 1;
+                        } else {
+                            self.respawn_queue.push(PendingRespawn {
+                                time_remaining: area.respawn_time_secs,
+                                area_id: area.id.clone(),
+                                zone_id: *zone_id,
+                            });
                         }
                     }
                 }
@@ -364,6 +425,30 @@ impl SpawnAreaManager {
         self.enemy_to_area.insert(enemy_id, area_id.to_string());
     }
     
+    /// A requested enemy could not be placed: free its slot and try again after the respawn time
+    pub fn spawn_failed(&mut self, request: &SpawnRequest) {
+        if let Some(pop) = self.area_population.get_mut(&request.area_id) {
+            *pop = pop.saturating_sub(1);
+        }
+        let respawn_time = self.find_area(&request.area_id)
+            .map(|a| a.respawn_time_secs)
+            .unwrap_or(60.0);
+        self.respawn_queue.push(PendingRespawn {
+            time_remaining: respawn_time,
+            area_id: request.area_id.clone(),
+            zone_id: request.zone_id,
+        });
+    }
+    
+    /// Forget an enemy without queueing a respawn (its area has enough)
+    pub fn unregister_enemy(&mut self, enemy_id: u64) {
+        if let Some(area_id) = self.enemy_to_area.remove(&enemy_id) {
+            if let Some(pop) = self.area_population.get_mut(&area_id) {
+                *pop = pop.saturating_sub(1);
+            }
+        }
+    }
+    
     /// Called when an enemy dies - queues respawn
     pub fn on_enemy_death(&mut self, enemy_id: u64) {
         if let Some(area_id) = self.enemy_to_area.remove(&enemy_id) {
@@ -392,8 +477,13 @@ impl SpawnAreaManager {
         }
     }
     
+    /// The area an enemy spawned from
+    pub fn area_of_enemy(&self, enemy_id: u64) -> Option<&SpawnArea> {
+        self.find_area(self.enemy_to_area.get(&enemy_id)?)
+    }
+    
     /// Find an area by ID
-    fn find_area(&self, area_id: &str) -> Option<&SpawnArea> {
+    pub fn find_area(&self, area_id: &str) -> Option<&SpawnArea> {
         for areas in self.areas.values() {
             for area in areas {
                 if area.id == area_id {
@@ -405,8 +495,8 @@ impl SpawnAreaManager {
     }
     
     /// Update respawn timers and return spawn requests
-    /// Returns: Vec<(zone_id, position_xz, enemy_type, level)>
-    pub fn update(&mut self, delta: f32) -> Vec<(u32, [f32; 2], EnemyType, u8)> {
+    /// Respawns nothing may fill under the current conditions wait another respawn time
+    pub fn update(&mut self, delta: f32, conditions: WorldConditions) -> Vec<SpawnRequest> {
         let mut spawns = Vec::new();
         let mut rng = rand::thread_rng();
         
@@ -420,26 +510,37 @@ impl SpawnAreaManager {
         }
         
         // Process completed respawns (in reverse order to avoid index issues)
+        let mut waiting = Vec::new();
         for i in completed.into_iter().rev() {
-            let respawn = self.respawn_queue.remove(i);
+            let mut respawn = self.respawn_queue.remove(i);
             
             // Find the area and check population
             if let Some(area) = self.find_area(&respawn.area_id) {
                 let current_pop = self.area_population.get(&area.id).copied().unwrap_or(0);
                 
                 if current_pop < area.max_population {
+                    let Some(config) = area.select_enemy_type(conditions) else {
+                        debug!("Nothing in area {} spawns right now, retrying later", area.id);
+                        respawn.time_remaining = area.respawn_time_secs;
+                        waiting.push(respawn);
+                        continue;
+                    };
                     if let Some(pos) = area.get_random_point() {
-                        if let Some(config) = area.select_enemy_type() {
-                            let level = rng.gen_range(config.min_level..=config.max_level);
-                            
-                            debug!("Respawning enemy in area {} at ({:.1}, {:.1})", 
-                                area.id, pos[0], pos[1]);
-                            
-                            spawns.push((respawn.zone_id, pos, config.enemy_type, level));
-                            
-                            // Track population
-                            *self.area_population.entry(area.id.clone()).or_insert(0) += 1;
-                        }
+                        let level = rng.gen_range(config.min_level..=config.max_level);
+                        
+                        debug!("Respawning enemy in area {} at ({:.1}, {:.1})", 
+                            area.id, pos[0], pos[1]);
+                        
+                        spawns.push(SpawnRequest {
+                            area_id: area.id.clone(),
+                            zone_id: respawn.zone_id,
+                            position: pos,
+                            template_id: config.template_id.clone(),
+                            level,
+                        });
+                        
+                        // Track population
+                        *self.area_population.entry(area.id.clone()).or_insert(0) += 1;
                     }
                 } else {
                     debug!("Area {} at max population {}, skipping respawn", 
@@ -447,10 +548,90 @@ impl SpawnAreaManager {
                 }
             }
         }
+        self.respawn_queue.extend(waiting);
         
         spawns
     }
     
+    /// Check freshly loaded areas before they replace the current ones
+    /// Area ids must be unique and every enemy type must have a template
+    pub fn validate(&self, template_exists: impl Fn(&str) -> bool) -> Result<(), String> {
+        let mut seen = std::collections::HashSet::new();
+        for area in self.areas.values().flatten() {
+            if !seen.insert(area.id.as_str()) {
+                return Err(format!("Spawn area id {} is used twice", area.id));
+            }
+            if let Some(config) = area.enemy_configs.iter().find(|c| !template_exists(&c.template_id)) {
+                return Err(format!("Spawn area {} uses unknown enemy type {}", area.id, config.template_id));
+            }
+        }
+        Ok(())
+    }
+    
+    /// Swap in reloaded areas, keeping the enemies of areas that still exist
+    /// An area that moved to another zone counts as removed (its enemies can't follow)
+    /// Areas below their new population are filled right away
+    /// Returns the enemies whose area was removed (already unregistered)
+    pub fn replace_areas(&mut self, reloaded: SpawnAreaManager) -> Vec<u64> {
+        let old_zones: HashMap<String, u32> = self.areas.values().flatten()
+            .map(|a| (a.id.clone(), a.zone_id))
+            .collect();
+        self.areas = reloaded.areas;
+        
+        let mut orphaned = Vec::new();
+        let areas = &self.areas;
+        let exists = |area_id: &str| areas.values().flatten()
+            .any(|a| a.id == area_id && old_zones.get(area_id) == Some(&a.zone_id));
+        self.enemy_to_area.retain(|enemy_id, area_id| {
+            let keep = exists(area_id);
+            if !keep {
+                orphaned.push(*enemy_id);
+            }
+            keep
+        });
+        self.respawn_queue.retain_mut(|respawn| {
+            match areas.values().flatten().find(|a| a.id == respawn.area_id) {
+                Some(area) => {
+                    respawn.zone_id = area.zone_id;
+                    true
+                }
+                None => false,
+            }
+        });
+        
+        self.area_population.clear();
+        for area_id in self.enemy_to_area.values() {
+            *self.area_population.entry(area_id.clone()).or_insert(0) += 1;
+        }
+        
+        for area in self.areas.values().flatten() {
+            let population = self.area_population.get(&area.id).copied().unwrap_or(0);
+            let queued = self.respawn_queue.iter().filter(|r| r.area_id == area.id).count() as u32;
+            for _ in (population + queued)..area.max_population {
+                self.respawn_queue.push(PendingRespawn {
+                    time_remaining: 0.0,
+                    area_id: area.id.clone(),
+                    zone_id: area.zone_id,
+                });
+            }
+        }
+        
+        orphaned
+    }
+    
+    /// Every loaded area
+    pub fn all_areas(&self) -> impl Iterator<Item = &SpawnArea> {
+        self.areas.values().flatten()
+    }
+    
+    /// Enemies of an area
+    pub fn enemies_in_area(&self, area_id: &str) -> Vec<u64> {
+        self.enemy_to_area.iter()
+            .filter(|(_, area)| *area == area_id)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+    
     /// Get the area ID for a given enemy position (for assigning enemies to areas)
     pub fn find_area_at(&self, zone_id: u32, x: f32, z: f32) -> Option<&str> {
         if let Some(areas) = self.areas.get(&zone_id) {
@@ -484,3 +665,99 @@ impl std::fmt::Debug for SpawnAreaManager {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(id: &str, max_population: u32) -> SpawnArea {
+        SpawnArea {
+            id: id.to_string(),
+            zone_id: 1,
+            polygon: vec![[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]],
+            enemy_configs: vec![EnemySpawnConfig {
+                template_id: "Goblin".to_string(),
+                weight: 1.0,
+                min_level: 1,
+                max_level: 1,
+                time_of_day: Vec::new(),
+                weather: Vec::new(),
+            }],
+            max_population,
+            respawn_time_secs: 60.0,
+            min_spawn_distance: 2.0,
+        }
+    }
+
+    fn manager(areas: Vec<SpawnArea>) -> SpawnAreaManager {
+        let mut manager = SpawnAreaManager::new();
+        manager.areas.insert(1, areas);
+        manager
+    }
+
+    #[test]
+    fn test_replace_areas_reconciles_enemies() {
+        let mut current = manager(vec![area("meadow", 2), area("cave", 1)]);
+        for (enemy_id, area_id) in [(1, "meadow"), (2, "meadow"), (3, "cave")] {
+            current.register_enemy(enemy_id, area_id);
+            *current.area_population.entry(area_id.to_string()).or_insert(0) += 1;
+        }
+
+        // The cave is gone, the meadow holds more and a new area appears
+        let orphaned = current.replace_areas(manager(vec![area("meadow", 4), area("ridge", 1)]));
+        assert_eq!(orphaned, vec![3]);
+        assert_eq!(current.get_area_population("meadow"), 2);
+        assert!(current.area_of_enemy(3).is_none());
+
+        let conditions = WorldConditions { time_of_day: TimeOfDay::Day, weather: Weather::Clear };
+        let spawns = current.update(0.0, conditions);
+        assert_eq!(spawns.len(), 3);
+        assert_eq!(current.get_area_population("meadow"), 4);
+        assert_eq!(current.get_area_population("ridge"), 1);
+
+        // Lowering a population leaves the rest to the world to despawn
+        current.replace_areas(manager(vec![area("meadow", 1)]));
+        assert_eq!(current.enemies_in_area("meadow").len(), 2);
+        current.unregister_enemy(1);
+        assert_eq!(current.get_area_population("meadow"), 1);
+    }
+
+    #[test]
+    fn test_replace_areas_drops_enemies_of_moved_area() {
+        let mut current = manager(vec![area("meadow", 1)]);
+        current.register_enemy(1, "meadow");
+        *current.area_population.entry("meadow".to_string()).or_insert(0) += 1;
+
+        // Same id, other zone: the enemy stays behind in zone 1
+        let mut reloaded = SpawnAreaManager::new();
+        reloaded.areas.insert(2, vec![SpawnArea { zone_id: 2, ..area("meadow", 1) }]);
+        assert_eq!(current.replace_areas(reloaded), vec![1]);
+        assert_eq!(current.get_area_population("meadow"), 0);
+
+        let conditions = WorldConditions { time_of_day: TimeOfDay::Day, weather: Weather::Clear };
+        let spawns = current.update(0.0, conditions);
+        assert_eq!(spawns.len(), 1);
+        assert_eq!(spawns[0].zone_id, 2);
+    }
+
+    #[test]
+    fn test_failed_spawn_frees_slot_and_retries() {
+        let mut current = manager(vec![area("meadow", 1)]);
+        let conditions = WorldConditions { time_of_day: TimeOfDay::Day, weather: Weather::Clear };
+        let spawns = current.get_initial_spawns(conditions);
+        assert_eq!(spawns.len(), 1);
+        assert_eq!(spawns[0].area_id, "meadow");
+
+        current.spawn_failed(&spawns[0]);
+        assert_eq!(current.get_area_population("meadow"), 0);
+        assert!(current.update(59.0, conditions).is_empty());
+        assert_eq!(current.update(1.0, conditions).len(), 1);
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_templates_and_duplicate_ids() {
+        assert!(manager(vec![area("meadow", 1)]).validate(|id| id == "Goblin").is_ok());
+        assert!(manager(vec![area("meadow", 1)]).validate(|_| false).is_err());
+        assert!(manager(vec![area("meadow", 1), area("meadow", 2)]).validate(|_| true).is_err());
+    }
+}
@@ -0,0 +1,40 @@
+//! Class specialization settings.
+//!
+//! `specialization.json` sets the level at which players pick a specialization.
+//! The same level is where stat growth switches from class to specialization
+//! values, and it is sent to the client with the selected character.
+
+use serde::Deserialize;
+
+use super::config::JsonConfig;
+
+/// Character level at which a specialization can be chosen unless configured
+pub const DEFAULT_SPECIALIZATION_LEVEL: u32 = 5;
+
+/// When class specializations unlock
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SpecializationConfig {
+    /// Character level at which a specialization can be chosen (and its stat growth starts)
+    pub unlock_level: u32,
+}
+
+impl Default for SpecializationConfig {
+    fn default() -> Self {
+        Self {
+            unlock_level: DEFAULT_SPECIALIZATION_LEVEL,
+        }
+    }
+}
+
+impl JsonConfig for SpecializationConfig {
+    const FILE: &'static str = "specialization.json";
+
+    fn validate(&self) -> Result<(), String> {
+        // Level 1 characters have no growth of their own to replace
+        if self.unlock_level < 2 {
+            return Err("unlock_level must be at least 2".into());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+//! Tick timing with thousands of enemies.
+//!
+//! Ignored by default; run with
+//! `cargo test --release tick_bench -- --ignored --test-threads=1`
+//! Measured numbers are in the README (Server Tick Rate).
+
+use std::collections::HashMap;
+use std::time::Instant;
+use rand::Rng;
+use mmo_shared::{CharacterClass, Gender, Empire, SERVER_TICK_RATE};
+use super::{GameWorld, ZoneManager, SpawnAreaManager, EnemyTemplates};
+
+/// Zone with exported obstacles to steer around
+const BENCH_ZONE: u32 = 200;
+/// Half the side of the square the hotspots are scattered over (units)
+const BENCH_AREA: f32 = 1000.0;
+/// Enemies per fight; players and enemies are clustered into fights so most
+/// enemies aggro, chase and crowd (more enemies = more fights, not denser ones)
+const ENEMIES_PER_HOTSPOT: usize = 100;
+/// How far from its hotspot a player stands (inside enemy aggro range)
+const PLAYER_SPREAD: f32 = 8.0;
+/// How far from its hotspot an enemy spawns
+const ENEMY_SPREAD: f32 = 25.0;
+const BENCH_PLAYERS: u64 = 200;
+const BENCH_TICKS: u64 = 100;
+const TICK_DELTA: f32 = 0.05;
+
+fn random_position(rng: &mut impl Rng) -> [f32; 3] {
+    [rng.gen_range(-BENCH_AREA..BENCH_AREA), 0.0, rng.gen_range(-BENCH_AREA..BENCH_AREA)]
+}
+
+fn near(center: [f32; 3], spread: f32, rng: &mut impl Rng) -> [f32; 3] {
+    [center[0] + rng.gen_range(-spread..spread), 0.0, center[2] + rng.gen_range(-spread..spread)]
+}
+
+fn bench_world(enemy_count: usize) -> GameWorld {
+    let mut zone_manager = ZoneManager::with_defaults();
+    zone_manager.init_obstacles();
+    let mut templates = EnemyTemplates::new();
+    templates.load_from_json("enemy_templates.json").expect("enemy_templates.json");
+    let mut world = GameWorld::with_spawn_areas(
+        HashMap::new(),
+        HashMap::new(),
+        zone_manager,
+        SpawnAreaManager::new(),
+        templates,
+    );
+
+    let mut rng = rand::thread_rng();
+    let hotspots: Vec<[f32; 3]> = (0..enemy_count.div_ceil(ENEMIES_PER_HOTSPOT))
+        .map(|_| random_position(&mut rng))
+        .collect();
+    let template_ids = ["Goblin", "Wolf", "Skeleton", "SkeletonArcher", "GoblinShaman"];
+    for i in 0..enemy_count {
+        let position = near(hotspots[i % hotspots.len()], ENEMY_SPREAD, &mut rng);
+        world.spawn_enemy(BENCH_ZONE, position, template_ids[i % template_ids.len()]);
+    }
+    for id in 1..=BENCH_PLAYERS {
+        let position = near(hotspots[id as usize % hotspots.len()], PLAYER_SPREAD, &mut rng);
+        world.spawn_player_with_state(
+            id, format!("Bench{}", id), CharacterClass::Warrior, Gender::Male, Empire::Blue,
+            BENCH_ZONE, position, 0.0,
+            100_000, 100_000, 100, 100, 10, 10, &[], None, None, 1, 0, 0,
+        );
+    }
+    world
+}
+
+/// Run the ticks and check the world update and the broadcast's interest
+/// queries each fit in one tick
+fn time_ticks(enemy_count: usize) {
+    let budget_ms = 1000.0 / SERVER_TICK_RATE as f64;
+    let mut world = bench_world(enemy_count);
+    let started = Instant::now();
+    for tick in 0..BENCH_TICKS {
+        world.update(TICK_DELTA, tick);
+    }
+    let per_tick = started.elapsed().as_secs_f64() * 1000.0 / BENCH_TICKS as f64;
+    println!("{} enemies: world update {:.2} ms/tick", enemy_count, per_tick);
+    assert!(per_tick < budget_ms, "{} enemies: {:.2} ms/tick", enemy_count, per_tick);
+
+    // The hotspots must actually fight, or the timing says nothing about combat
+    let wounded = (1..=BENCH_PLAYERS)
+        .filter(|id| world.get_player(*id).is_some_and(|p| p.health < p.max_health))
+        .count();
+    assert!(wounded as u64 > BENCH_PLAYERS / 2, "only {} players were attacked", wounded);
+
+    // What the broadcast asks for every connected player
+    let started = Instant::now();
+    let mut visible = 0;
+    for id in 1..=BENCH_PLAYERS {
+        let position = world.get_player(id).unwrap().position;
+        visible += world.get_players_near(BENCH_ZONE, position, 150.0).len();
+        visible += world.get_enemies_near(BENCH_ZONE, position, 150.0).len();
+    }
+    let interest = started.elapsed().as_secs_f64() * 1000.0;
+    println!("{} enemies: interest queries {:.2} ms", enemy_count, interest);
+    assert!(visible > 0);
+    assert!(interest < budget_ms, "{} enemies: interest queries took {:.2} ms", enemy_count, interest);
+}
+
+#[test]
+#[ignore]
+fn tick_bench_1000_enemies() {
+    time_ticks(1000);
+}
+
+#[test]
+#[ignore]
+fn tick_bench_2000_enemies() {
+    time_ticks(2000);
+}
+
+#[test]
+#[ignore]
+fn tick_bench_5000_enemies() {
+    time_ticks(5000);
+}
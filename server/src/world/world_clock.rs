@@ -0,0 +1,251 @@
+//! World clock and weather.
+//!
+//! Time of day follows the real sun at the server's location, worked out with
+//! the same solar formula as the client's day/night controller, so night on the
+//! server is night on screen. An optional offset shifts the clock (e.g. to try
+//! night spawns at noon). Weather rolls a new state from weighted options every
+//! few minutes. Both go out in `TimeSync` and decide which spawn area entries
+//! may spawn.
+
+use std::path::Path;
+use log::info;
+use rand::Rng;
+use serde::Deserialize;
+use mmo_shared::{ServerMessage, Weather};
+
+/// Length of a year in days, for the day of year from a timestamp
+const DAYS_PER_YEAR: f64 = 365.2425;
+
+/// Sun elevation below which it counts as night (degrees)
+const NIGHT_ELEVATION: f32 = -6.0;
+
+/// Sun elevation above which dawn and dusk give way to day (degrees)
+const DAY_ELEVATION: f32 = 6.0;
+
+/// Part of the solar day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum TimeOfDay {
+    /// Sun rising, between -6° and 6°
+    Dawn,
+    /// Sun above 6°
+    Day,
+    /// Sun setting, between 6° and -6°
+    Dusk,
+    /// Sun below -6°
+    Night,
+}
+
+/// Everything spawn rules can depend on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorldConditions {
+    pub time_of_day: TimeOfDay,
+    pub weather: Weather,
+}
+
+/// Chance of a weather state when the weather changes
+#[derive(Debug, Clone, Deserialize)]
+pub struct WeatherChance {
+    pub weather: Weather,
+    pub weight: f32,
+}
+
+fn default_latitude() -> f32 {
+    52.5
+}
+
+fn default_longitude() -> f32 {
+    13.4
+}
+
+fn default_weather_duration() -> [f32; 2] {
+    [600.0, 1800.0]
+}
+
+/// World clock settings (world_clock.json)
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorldClockConfig {
+    /// Server location for solar calculations (Berlin by default)
+    #[serde(default = "default_latitude")]
+    pub latitude: f32,
+    #[serde(default = "default_longitude")]
+    pub longitude: f32,
+    /// Shift the world clock against real time (hours)
+    #[serde(default)]
+    pub time_offset_hours: f32,
+    /// Weather states to roll from (empty = always clear)
+    #[serde(default)]
+    pub weather: Vec<WeatherChance>,
+    /// How long a weather state lasts, min and max (seconds)
+    #[serde(default = "default_weather_duration")]
+    pub weather_duration: [f32; 2],
+}
+
+impl Default for WorldClockConfig {
+    fn default() -> Self {
+        Self {
+            latitude: default_latitude(),
+            longitude: default_longitude(),
+            time_offset_hours: 0.0,
+            weather: Vec::new(),
+            weather_duration: default_weather_duration(),
+        }
+    }
+}
+
+/// Server-side time of day and weather
+#[derive(Debug)]
+pub struct WorldClock {
+    config: WorldClockConfig,
+    weather: Weather,
+    /// Seconds until the weather rolls again
+    weather_remaining: f32,
+}
+
+impl WorldClock {
+    pub fn new(config: WorldClockConfig) -> Self {
+        let mut clock = Self { config, weather: Weather::Clear, weather_remaining: 0.0 };
+        clock.roll_weather();
+        clock
+    }
+
+    /// Load clock settings from a JSON file
+    pub fn load_from_json<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| format!("Failed to read world_clock.json: {}", e))?;
+        let config: WorldClockConfig = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse world_clock.json: {}", e))?;
+
+        if config.weather.iter().any(|w| w.weight < 0.0) {
+            return Err("weather weights must not be negative".to_string());
+        }
+        let [min, max] = config.weather_duration;
+        if min <= 0.0 || max < min {
+            return Err(format!("weather_duration [{}, {}] must be positive and ascending", min, max));
+        }
+        Ok(Self::new(config))
+    }
+
+    /// World time as a Unix timestamp (seconds, UTC)
+    pub fn unix_timestamp(&self) -> i64 {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        now + (self.config.time_offset_hours * 3600.0) as i64
+    }
+
+    pub fn time_of_day(&self) -> TimeOfDay {
+        time_of_day_at(self.unix_timestamp(), self.config.latitude, self.config.longitude)
+    }
+
+    pub fn weather(&self) -> Weather {
+        self.weather
+    }
+
+    pub fn conditions(&self) -> WorldConditions {
+        WorldConditions { time_of_day: self.time_of_day(), weather: self.weather }
+    }
+
+    /// Count down to the next weather change
+    /// Returns true if the weather changed
+    pub fn update(&mut self, delta: f32) -> bool {
+        self.weather_remaining -= delta;
+        if self.weather_remaining > 0.0 {
+            return false;
+        }
+        let previous = self.weather;
+        self.roll_weather();
+        if self.weather != previous {
+            info!("Weather changed from {} to {}", previous.name(), self.weather.name());
+        }
+        self.weather != previous
+    }
+
+    /// Time sync message for clients
+    pub fn time_sync(&self) -> ServerMessage {
+        ServerMessage::TimeSync {
+            unix_timestamp: self.unix_timestamp(),
+            latitude: self.config.latitude,
+            longitude: self.config.longitude,
+            weather: self.weather,
+        }
+    }
+
+    fn roll_weather(&mut self) {
+        let mut rng = rand::thread_rng();
+        let [min, max] = self.config.weather_duration;
+        self.weather_remaining = if max > min { rng.gen_range(min..max) } else { min };
+
+        let total: f32 = self.config.weather.iter().map(|w| w.weight).sum();
+        if total <= 0.0 {
+            self.weather = Weather::Clear;
+            return;
+        }
+        let mut roll = rng.gen_range(0.0..total);
+        for chance in &self.config.weather {
+            if roll < chance.weight {
+                self.weather = chance.weather;
+                return;
+            }
+            roll -= chance.weight;
+        }
+    }
+}
+
+impl Default for WorldClock {
+    fn default() -> Self {
+        Self::new(WorldClockConfig::default())
+    }
+}
+
+/// Sun elevation (degrees) and whether it is before solar noon
+/// Mirrors `_calculate_sun_position` in the client's day_night_controller.gd
+fn sun_position(timestamp: i64, latitude: f32, longitude: f32) -> (f32, bool) {
+    let days = timestamp.div_euclid(86400);
+    let utc_hour = timestamp.rem_euclid(86400) as f32 / 3600.0;
+    // Epoch day 0 is January 1st; close enough for the sun's declination
+    let day_of_year = (days as f64 % DAYS_PER_YEAR) as f32 + 1.0;
+
+    let declination = -23.45 * (360.0 / 365.0 * (day_of_year + 10.0)).to_radians().cos();
+    let b = (360.0 / 365.0 * (day_of_year - 81.0)).to_radians();
+    let equation_of_time = 9.87 * (2.0 * b).sin() - 7.53 * b.cos() - 1.5 * b.sin();
+    let solar_time = utc_hour + longitude / 15.0 + equation_of_time / 60.0;
+    let hour_angle = 15.0 * (solar_time - 12.0);
+
+    let (lat, dec, ha) = (latitude.to_radians(), declination.to_radians(), hour_angle.to_radians());
+    let sin_elevation = lat.sin() * dec.sin() + lat.cos() * dec.cos() * ha.cos();
+    let morning = hour_angle.rem_euclid(360.0) > 180.0;
+    (sin_elevation.clamp(-1.0, 1.0).asin().to_degrees(), morning)
+}
+
+/// Part of the day at a timestamp and location
+pub fn time_of_day_at(timestamp: i64, latitude: f32, longitude: f32) -> TimeOfDay {
+    let (elevation, morning) = sun_position(timestamp, latitude, longitude);
+    if elevation < NIGHT_ELEVATION {
+        TimeOfDay::Night
+    } else if elevation >= DAY_ELEVATION {
+        TimeOfDay::Day
+    } else if morning {
+        TimeOfDay::Dawn
+    } else {
+        TimeOfDay::Dusk
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_berlin_time_of_day_over_a_summer_day() {
+        // 2025-06-21 00:00 UTC
+        let midnight = 1_750_464_000;
+        let at = |hour: i64| time_of_day_at(midnight + hour * 3600, 52.5, 13.4);
+
+        assert_eq!(at(0), TimeOfDay::Night);
+        assert_eq!(at(3), TimeOfDay::Dawn);
+        assert_eq!(at(11), TimeOfDay::Day);
+        assert_eq!(at(19), TimeOfDay::Dusk);
+        assert_eq!(at(22), TimeOfDay::Night);
+    }
+}
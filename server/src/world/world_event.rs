@@ -0,0 +1,444 @@
+//! Scheduled world events.
+//!
+//! Event definitions are loaded from `world_events.json` next to the server
+//! binary. An event starts on a schedule (every few minutes or at fixed times
+//! on the world clock) or when an admin triggers it, announces itself to its
+//! zone, and spawns either a wave of enemies or an event-only world boss. It
+//! succeeds once enough of them are dead and fails when its time runs out;
+//! players taking part share the rewards. The manager only keeps schedules and
+//! event state; the world spawns, announces and pays out.
+
+use std::collections::HashMap;
+use std::path::Path;
+use log::{info, warn};
+use serde::Deserialize;
+
+const SECONDS_PER_DAY: i64 = 86400;
+
+fn default_wave_radius() -> f32 {
+    8.0
+}
+
+fn default_participant_radius() -> f32 {
+    80.0
+}
+
+fn default_quantity() -> u32 {
+    1
+}
+
+/// When an event starts on its own
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(tag = "type")]
+pub enum EventSchedule {
+    /// Only started by an admin
+    #[default]
+    Manual,
+    /// Repeats every `every_minutes`, the first time `first_after_minutes` after startup
+    Interval {
+        every_minutes: f32,
+        #[serde(default)]
+        first_after_minutes: f32,
+    },
+    /// Fixed times of day on the world clock ("HH:MM", UTC plus the clock offset)
+    Daily {
+        times: Vec<String>,
+    },
+}
+
+impl EventSchedule {
+    /// Next start after `now` (unix seconds), None for manual events
+    /// `first` is set when nothing has been scheduled yet
+    fn next_after(&self, now: i64, first: bool) -> Option<i64> {
+        match self {
+            EventSchedule::Manual => None,
+            EventSchedule::Interval { every_minutes, first_after_minutes } => {
+                let minutes = if first { *first_after_minutes } else { *every_minutes };
+                Some(now + (minutes * 60.0) as i64)
+            }
+            EventSchedule::Daily { times } => {
+                let day_start = now - now.rem_euclid(SECONDS_PER_DAY);
+                times.iter()
+                    .filter_map(|time| parse_time_of_day(time))
+                    .map(|offset| {
+                        let start = day_start + offset;
+                        if start > now { start } else { start + SECONDS_PER_DAY }
+                    })
+                    .min()
+            }
+        }
+    }
+}
+
+/// Seconds since midnight for "HH:MM"
+fn parse_time_of_day(time: &str) -> Option<i64> {
+    let (hours, minutes) = time.split_once(':')?;
+    let hours: i64 = hours.trim().parse().ok().filter(|h| (0..24).contains(h))?;
+    let minutes: i64 = minutes.trim().parse().ok().filter(|m| (0..60).contains(m))?;
+    Some(hours * 3600 + minutes * 60)
+}
+
+/// Enemies of one type in a wave
+#[derive(Debug, Clone, Deserialize)]
+pub struct WaveEnemy {
+    /// Enemy template id
+    pub enemy_type: String,
+    pub count: u32,
+    /// Fixed level (random from the template if unset)
+    #[serde(default)]
+    pub level: Option<u8>,
+}
+
+/// What an event puts into the world
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type")]
+pub enum EventSpawn {
+    /// Enemies scattered around a point
+    Wave {
+        /// Center in XZ world coordinates
+        position: [f32; 2],
+        #[serde(default = "default_wave_radius")]
+        radius: f32,
+        enemies: Vec<WaveEnemy>,
+    },
+    /// A boss from bosses.json (should be `event_only`)
+    WorldBoss {
+        boss_id: String,
+    },
+}
+
+/// When an event counts as won
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(tag = "type")]
+pub enum EventGoal {
+    /// Everything the event spawned is dead
+    #[default]
+    KillAll,
+    /// At least `count` of the event's enemies are dead
+    KillCount { count: u32 },
+}
+
+/// Chat lines sent to the event's zone (empty = generic text)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EventAnnouncements {
+    #[serde(default)]
+    pub start: String,
+    #[serde(default)]
+    pub success: String,
+    #[serde(default)]
+    pub failure: String,
+}
+
+/// Item handed to every participant
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventItemReward {
+    pub item_id: u32,
+    #[serde(default = "default_quantity")]
+    pub quantity: u32,
+}
+
+/// What participants get when the event succeeds
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EventRewards {
+    #[serde(default)]
+    pub experience: u32,
+    #[serde(default)]
+    pub gold: u64,
+    #[serde(default)]
+    pub items: Vec<EventItemReward>,
+}
+
+/// World event definition
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorldEvent {
+    pub id: String,
+    pub name: String,
+    pub zone_id: u32,
+    #[serde(default)]
+    pub schedule: EventSchedule,
+    #[serde(default)]
+    pub announcements: EventAnnouncements,
+    pub spawn: EventSpawn,
+    #[serde(default)]
+    pub goal: EventGoal,
+    /// Seconds until the event fails
+    pub duration: f32,
+    #[serde(default)]
+    pub rewards: EventRewards,
+    /// Players this close to the event when it succeeds take part in the rewards
+    #[serde(default = "default_participant_radius")]
+    pub participant_radius: f32,
+}
+
+impl WorldEvent {
+    /// Zone announcement for the start or the end of the event
+    pub fn announcement(&self, outcome: Option<EventOutcome>) -> String {
+        let (text, fallback) = match outcome {
+            None => (self.announcements.start.as_str(), "has begun!"),
+            Some(EventOutcome::Success) => (self.announcements.success.as_str(), "is over. Victory!"),
+            Some(EventOutcome::Failure) => (self.announcements.failure.as_str(), "is over. It was not stopped in time."),
+            Some(EventOutcome::Cancelled) => ("", "was called off."),
+        };
+        if text.is_empty() { format!("{} {}", self.name, fallback) } else { text.to_string() }
+    }
+}
+
+/// How an event ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventOutcome {
+    Success,
+    Failure,
+    Cancelled,
+}
+
+/// A running event
+#[derive(Debug)]
+pub struct ActiveEvent {
+    /// Enemy ids the event spawned that are still alive
+    pub enemies: Vec<u64>,
+    /// Boss enemy id for world boss events
+    pub boss: Option<u64>,
+    /// Where participants are counted from
+    pub center: [f32; 3],
+    spawned: usize,
+    remaining: f32,
+}
+
+/// An event that just ended
+#[derive(Debug)]
+pub struct FinishedEvent {
+    pub event: usize,
+    pub outcome: EventOutcome,
+    pub state: ActiveEvent,
+}
+
+/// Loads event definitions and tracks schedules and running events
+#[derive(Default)]
+pub struct EventManager {
+    events: Vec<WorldEvent>,
+    /// Next scheduled start per event (unix seconds on the world clock)
+    next_start: Vec<Option<i64>>,
+    /// Running events keyed by event index
+    active: HashMap<usize, ActiveEvent>,
+    /// Admin-triggered starts and cancels, carried out on the next update
+    start_requests: Vec<usize>,
+    cancel_requests: Vec<usize>,
+}
+
+impl EventManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load event definitions from a JSON file (schedules start on the next update)
+    pub fn load_from_json<P: AsRef<Path>>(&mut self, path: P) -> Result<(), String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read world_events.json: {}", e))?;
+        let events: Vec<WorldEvent> = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse world_events.json: {}", e))?;
+        self.set_events(events)
+    }
+
+    fn set_events(&mut self, events: Vec<WorldEvent>) -> Result<(), String> {
+        for (i, event) in events.iter().enumerate() {
+            if events[..i].iter().any(|e| e.id == event.id) {
+                return Err(format!("Duplicate world event id {}", event.id));
+            }
+            if event.duration <= 0.0 {
+                return Err(format!("World event {} needs a positive duration", event.id));
+            }
+            match &event.schedule {
+                EventSchedule::Interval { every_minutes, .. } if *every_minutes <= 0.0 => {
+                    return Err(format!("World event {} needs a positive interval", event.id));
+                }
+                EventSchedule::Daily { times } => {
+                    if let Some(time) = times.iter().find(|t| parse_time_of_day(t).is_none()) {
+                        return Err(format!("World event {} has an invalid time {:?} (expected HH:MM)", event.id, time));
+                    }
+                }
+                _ => {}
+            }
+            if let EventSpawn::Wave { enemies, .. } = &event.spawn {
+                if enemies.iter().all(|e| e.count == 0) {
+                    return Err(format!("World event {} has an empty wave", event.id));
+                }
+            }
+        }
+
+        info!("Loaded {} world events", events.len());
+        self.next_start = vec![None; events.len()];
+        self.active.clear();
+        self.start_requests.clear();
+        self.cancel_requests.clear();
+        self.events = events;
+        Ok(())
+    }
+
+    pub fn event(&self, index: usize) -> &WorldEvent {
+        &self.events[index]
+    }
+
+    fn find(&self, id: &str) -> Result<usize, String> {
+        self.events.iter()
+            .position(|e| e.id == id)
+            .ok_or_else(|| format!("Unknown event: {}", id))
+    }
+
+    /// Start an event on the next update
+    /// Returns the event's name
+    pub fn request_start(&mut self, id: &str) -> Result<&str, String> {
+        let index = self.find(id)?;
+        if self.active.contains_key(&index) || self.start_requests.contains(&index) {
+            return Err(format!("{} is already running", self.events[index].name));
+        }
+        self.start_requests.push(index);
+        Ok(&self.events[index].name)
+    }
+
+    /// End a running event on the next update
+    /// Returns the event's name
+    pub fn request_cancel(&mut self, id: &str) -> Result<&str, String> {
+        let index = self.find(id)?;
+        if !self.active.contains_key(&index) {
+            return Err(format!("{} is not running", self.events[index].name));
+        }
+        if !self.cancel_requests.contains(&index) {
+            self.cancel_requests.push(index);
+        }
+        Ok(&self.events[index].name)
+    }
+
+    /// Events that should start now: admin requests and schedules that came due
+    /// Schedules that come due while their event is still running are skipped
+    pub fn due(&mut self, now: i64) -> Vec<usize> {
+        let mut due = std::mem::take(&mut self.start_requests);
+
+        for (index, event) in self.events.iter().enumerate() {
+            let next_start = &mut self.next_start[index];
+            match *next_start {
+                None => *next_start = event.schedule.next_after(now, true),
+                Some(start) if now >= start => {
+                    *next_start = event.schedule.next_after(now, false);
+                    if self.active.contains_key(&index) || due.contains(&index) {
+                        warn!("World event {} is still running, skipping its scheduled start", event.id);
+                    } else {
+                        due.push(index);
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+        due
+    }
+
+    /// Track an event whose enemies have been spawned
+    pub fn start(&mut self, index: usize, enemies: Vec<u64>, boss: Option<u64>, center: [f32; 3]) {
+        info!("World event {} started with {} enemies", self.events[index].id, enemies.len());
+        self.active.insert(index, ActiveEvent {
+            spawned: enemies.len(),
+            remaining: self.events[index].duration,
+            enemies,
+            boss,
+            center,
+        });
+    }
+
+    /// Count down running events and check their goals
+    /// `is_alive` says whether an event enemy is still fighting
+    /// Returns the events that ended, including admin cancels
+    pub fn update(&mut self, delta: f32, is_alive: impl Fn(u64) -> bool) -> Vec<FinishedEvent> {
+        let mut outcomes: Vec<(usize, EventOutcome)> = self.cancel_requests.drain(..)
+            .filter(|index| self.active.contains_key(index))
+            .map(|index| (index, EventOutcome::Cancelled))
+            .collect();
+
+        for (index, state) in &mut self.active {
+            if outcomes.iter().any(|(i, _)| i == index) {
+                continue;
+            }
+            state.enemies.retain(|id| is_alive(*id));
+            state.remaining -= delta;
+
+            let killed = state.spawned - state.enemies.len();
+            let won = match self.events[*index].goal {
+                EventGoal::KillAll => state.enemies.is_empty(),
+                EventGoal::KillCount { count } => killed >= (count as usize).min(state.spawned),
+            };
+            if won {
+                outcomes.push((*index, EventOutcome::Success));
+            } else if state.remaining <= 0.0 {
+                outcomes.push((*index, EventOutcome::Failure));
+            }
+        }
+
+        outcomes.into_iter()
+            .filter_map(|(event, outcome)| {
+                let state = self.active.remove(&event)?;
+                info!("World event {} ended: {:?}", self.events[event].id, outcome);
+                Some(FinishedEvent { event, outcome, state })
+            })
+            .collect()
+    }
+
+    /// One line per event for the admin event list
+    pub fn describe(&self, now: i64) -> Vec<String> {
+        self.events.iter().enumerate()
+            .map(|(index, event)| {
+                let status = match (self.active.get(&index), self.next_start[index]) {
+                    (Some(state), _) => format!(
+                        "running, {} enemies left, {:.0}s to go",
+                        state.enemies.len(), state.remaining,
+                    ),
+                    (None, Some(start)) => format!("next in {} min", (start - now).max(0) / 60),
+                    (None, None) => "manual".to_string(),
+                };
+                format!("{} - {} (zone {}): {}", event.id, event.name, event.zone_id, status)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager(json: &str) -> EventManager {
+        let mut manager = EventManager::new();
+        manager.set_events(serde_json::from_str(json).unwrap()).unwrap();
+        manager
+    }
+
+    #[test]
+    fn test_daily_schedule_starts_at_the_next_time() {
+        let schedule = EventSchedule::Daily { times: vec!["20:00".into(), "08:30".into()] };
+        // 2025-06-21 12:00 UTC
+        let noon = 1_750_507_200;
+        assert_eq!(schedule.next_after(noon, true), Some(noon + 8 * 3600));
+        assert_eq!(schedule.next_after(noon + 9 * 3600, false), Some(noon + 20 * 3600 + 1800));
+    }
+
+    #[test]
+    fn test_wave_succeeds_when_the_goal_is_met_and_fails_on_time() {
+        let mut events = manager(r#"[
+            { "id": "raid", "name": "Goblin Raid", "zone_id": 1, "duration": 60.0,
+              "schedule": { "type": "Interval", "every_minutes": 30, "first_after_minutes": 1 },
+              "spawn": { "type": "Wave", "position": [0.0, 0.0], "enemies": [{ "enemy_type": "Goblin", "count": 3 }] },
+              "goal": { "type": "KillCount", "count": 2 } }
+        ]"#);
+
+        assert!(events.due(0).is_empty());
+        assert_eq!(events.due(60), vec![0]);
+        events.start(0, vec![1, 2, 3], None, [0.0; 3]);
+        assert!(events.request_start("raid").is_err());
+
+        // One kill is not enough
+        assert!(events.update(1.0, |id| id != 1).is_empty());
+        let finished = events.update(1.0, |id| id == 3);
+        assert_eq!(finished[0].outcome, EventOutcome::Success);
+        assert_eq!(finished[0].state.enemies, vec![3]);
+
+        events.start(0, vec![1, 2, 3], None, [0.0; 3]);
+        let finished = events.update(61.0, |_| true);
+        assert_eq!(finished[0].outcome, EventOutcome::Failure);
+    }
+}
@@ -8,12 +8,22 @@
 
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::oneshot;
 use log::{info, warn, error, debug};
 use mmo_shared::{Empire, NpcType};
 
-use crate::navigation::{Obstacle, CircleObstacle, BoxObstacle};
+use crate::navigation::{Obstacle, CircleObstacle, BoxObstacle, NavMesh, Fingerprint, ObstacleGrid, Vec2, ENEMY_RADIUS, SPATIAL_CELL_SIZE};
 use super::heightmap::Heightmap;
 
+/// Zone IDs and the empire names used in exported file names
+const ZONE_EMPIRES: [(u32, &str); 3] = [
+    (1, "shinsoo"),
+    (100, "chunjo"),
+    (200, "jinno"),
+];
+
 /// Zone definition loaded from database
 #[derive(Debug, Clone)]
 pub struct ZoneDefinition {
@@ -56,8 +66,16 @@ pub struct ZoneManager {
     default_zones: HashMap<Empire, u32>,
     /// Obstacles per zone (for enemy navigation)
     obstacles: HashMap<u32, Vec<Obstacle>>,
+    /// Spatial index over each zone's obstacles
+    obstacle_grids: HashMap<u32, ObstacleGrid>,
     /// Heightmaps per zone (for terrain height queries)
     heightmaps: HashMap<u32, Heightmap>,
+    /// Nav-meshes per zone (for long enemy routes)
+    navmeshes: HashMap<u32, Arc<NavMesh>>,
+    /// Where nav-meshes are cached (kept for rebuilds after a reload)
+    navmesh_cache: Option<String>,
+    /// Nav-meshes being rebuilt in the background after a reload
+    navmesh_rebuild: Option<oneshot::Receiver<HashMap<u32, Arc<NavMesh>>>>,
 }
 
 impl std::fmt::Debug for ZoneManager {
@@ -69,6 +87,7 @@ impl std::fmt::Debug for ZoneManager {
             .field("default_zones", &self.default_zones)
             .field("obstacles", &self.obstacles.keys().collect::<Vec<_>>())
             .field("heightmaps", &self.heightmaps.keys().collect::<Vec<_>>())
+            .field("navmeshes", &self.navmeshes.keys().collect::<Vec<_>>())
             .finish()
     }
 }
@@ -82,7 +101,11 @@ impl ZoneManager {
             npc_spawns: HashMap::new(),
             default_zones: HashMap::new(),
             obstacles: HashMap::new(),
+            obstacle_grids: HashMap::new(),
             heightmaps: HashMap::new(),
+            navmeshes: HashMap::new(),
+            navmesh_cache: None,
+            navmesh_rebuild: None,
         };
         
         // Always initialize hardcoded spawn points
@@ -302,6 +325,7 @@ impl ZoneManager {
         manager.obstacles.insert(1, village_obstacles.clone());
         manager.obstacles.insert(100, village_obstacles.clone());
         manager.obstacles.insert(200, village_obstacles);
+        manager.index_obstacles();
         
         info!("ZoneManager initialized with {} zones (hardcoded defaults)", manager.zones.len());
         
@@ -435,6 +459,15 @@ impl ZoneManager {
         self.obstacles.get(&zone_id).map(|v| v.as_slice()).unwrap_or(&[])
     }
     
+    /// Copy the obstacles of a zone that may come within `radius` of a position into `out`
+    pub fn get_obstacles_near(&self, zone_id: u32, position: [f32; 3], radius: f32, out: &mut Vec<Obstacle>) {
+        let obstacles = self.get_obstacles(zone_id);
+        match self.obstacle_grids.get(&zone_id) {
+            Some(grid) => grid.query(obstacles, Vec2::from_3d(position), radius, out),
+            None => out.extend_from_slice(obstacles),
+        }
+    }
+    
     /// Get terrain height at a world position in a zone
     /// Returns the Y coordinate for the terrain surface at (x, z)
     /// Falls back to 0.0 if no heightmap is loaded for the zone
@@ -462,15 +495,8 @@ impl ZoneManager {
     pub fn init_heightmaps(&mut self) {
         self.heightmaps.clear();
         
-        // Map zone IDs to empire names for file lookup
-        let zone_empire_map: Vec<(u32, &str)> = vec![
-            (1, "shinsoo"),
-            (100, "chunjo"),
-            (200, "jinno"),
-        ];
-        
-        for (zone_id, empire_name) in zone_empire_map {
-            let json_path = format!("heightmaps/{}_heightmap.json", empire_name);
+        for (zone_id, empire_name) in ZONE_EMPIRES {
+            let json_path = heightmap_path(empire_name);
             
             match Heightmap::load(&json_path) {
                 Ok(heightmap) => {
@@ -487,6 +513,108 @@ impl ZoneManager {
         info!("Loaded {} heightmaps", self.heightmaps.len());
     }
     
+    /// Reload obstacles.json and/or the heightmaps, then start rebuilding the nav-meshes
+    /// Nothing is replaced unless everything asked for loads: obstacles.json must
+    /// parse without invalid entries and zones that have terrain must keep it
+    /// Returns what was loaded, for the admin who asked
+    pub fn reload_terrain(&mut self, obstacles: bool, heightmaps: bool) -> Result<String, String> {
+        let mut loaded = Vec::new();
+        
+        let new_obstacles = if obstacles {
+            let (obstacles, skipped) = read_obstacles(Path::new("obstacles.json"))?;
+            if skipped > 0 {
+                return Err(format!("obstacles.json has {} invalid entries", skipped));
+            }
+            let total: usize = obstacles.values().map(|v| v.len()).sum();
+            loaded.push(format!("{} obstacles", total));
+            Some(obstacles)
+        } else {
+            None
+        };
+        
+        let new_heightmaps = if heightmaps {
+            let mut loaded_maps = HashMap::new();
+            for (zone_id, empire_name) in ZONE_EMPIRES {
+                match Heightmap::load(heightmap_path(empire_name)) {
+                    Ok(heightmap) => {
+                        loaded_maps.insert(zone_id, heightmap);
+                    }
+                    Err(e) if self.heightmaps.contains_key(&zone_id) => {
+                        return Err(format!("Heightmap for zone {} ({}) did not load: {}", zone_id, empire_name, e));
+                    }
+                    Err(_) => {}
+                }
+            }
+            loaded.push(format!("{} heightmaps", loaded_maps.len()));
+            Some(loaded_maps)
+        } else {
+            None
+        };
+        
+        if let Some(obstacles) = new_obstacles {
+            self.obstacles = obstacles;
+            self.index_obstacles();
+        }
+        if let Some(heightmaps) = new_heightmaps {
+            self.heightmaps = heightmaps;
+        }
+        let summary = format!("{} (nav-meshes rebuilding in the background)", loaded.join(", "));
+        info!("Reloaded {}", summary);
+        self.rebuild_navmeshes();
+        Ok(summary)
+    }
+    
+    /// Build nav-meshes for all zones with a heightmap
+    /// Call after init_obstacles and init_heightmaps. With a cache directory,
+    /// meshes are loaded from there when still up to date and saved after building.
+    pub fn init_navmeshes(&mut self, cache_dir: Option<&str>) {
+        self.navmesh_cache = cache_dir.map(str::to_string);
+        self.navmesh_rebuild = None;
+        self.navmeshes = build_navmeshes(&self.heightmaps, &self.obstacles, cache_dir);
+    }
+    
+    /// Rebuild the nav-meshes after obstacles or heightmaps changed
+    /// Builds off the game tick; the current meshes stay in use until
+    /// poll_navmesh_rebuild swaps the new ones in. Cached meshes whose inputs
+    /// still match are reused, and a newer rebuild replaces one still running.
+    fn rebuild_navmeshes(&mut self) {
+        let heightmaps = self.heightmaps.clone();
+        let obstacles = self.obstacles.clone();
+        let cache_dir = self.navmesh_cache.clone();
+        let (tx, rx) = oneshot::channel();
+        tokio::task::spawn_blocking(move || {
+            let _ = tx.send(build_navmeshes(&heightmaps, &obstacles, cache_dir.as_deref()));
+        });
+        self.navmesh_rebuild = Some(rx);
+    }
+    
+    /// Swap in nav-meshes from a finished background rebuild
+    /// Returns true when new meshes were installed
+    pub fn poll_navmesh_rebuild(&mut self) -> bool {
+        let Some(rx) = &mut self.navmesh_rebuild else {
+            return false;
+        };
+        match rx.try_recv() {
+            Ok(navmeshes) => {
+                self.navmeshes = navmeshes;
+                self.navmesh_rebuild = None;
+                info!("Swapped in {} rebuilt nav-meshes", self.navmeshes.len());
+                true
+            }
+            Err(oneshot::error::TryRecvError::Empty) => false,
+            Err(oneshot::error::TryRecvError::Closed) => {
+                error!("Nav-mesh rebuild failed, keeping the current nav-meshes");
+                self.navmesh_rebuild = None;
+                false
+            }
+        }
+    }
+    
+    /// Get the nav-mesh for a zone, if one was built
+    pub fn get_navmesh(&self, zone_id: u32) -> Option<Arc<NavMesh>> {
+        self.navmeshes.get(&zone_id).cloned()
+    }
+    
     /// Initialize obstacles for all zones
     /// 
     /// First tries to load from obstacles.json (exported from Godot).
@@ -496,74 +624,37 @@ impl ZoneManager {
         self.obstacles.clear();
         
         // Try to load from JSON file first
-        if self.load_obstacles_from_json("obstacles.json") {
-            return;
+        if !self.load_obstacles_from_json("obstacles.json") {
+            info!("No obstacles.json found, using hardcoded fallback obstacles");
+            self.init_hardcoded_obstacles();
         }
         
-        info!("No obstacles.json found, using hardcoded fallback obstacles");
-        self.init_hardcoded_obstacles();
+        self.index_obstacles();
+    }
+    
+    /// Rebuild the spatial index over every zone's obstacles
+    fn index_obstacles(&mut self) {
+        self.obstacle_grids = self.obstacles.iter()
+            .map(|(zone_id, obstacles)| (*zone_id, ObstacleGrid::new(obstacles, SPATIAL_CELL_SIZE)))
+            .collect();
     }
     
     /// Load obstacles from a JSON file exported by Godot
     /// Returns true if successful, false if file not found or parse error
     fn load_obstacles_from_json<P: AsRef<Path>>(&mut self, path: P) -> bool {
         let path = path.as_ref();
+        if !path.exists() {
+            return false;
+        }
         
-        let content = match std::fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(e) => {
-                if e.kind() != std::io::ErrorKind::NotFound {
-                    warn!("Failed to read obstacles file {:?}: {}", path, e);
-                }
-                return false;
-            }
-        };
-        
-        let json: serde_json::Value = match serde_json::from_str(&content) {
-            Ok(v) => v,
+        let obstacles = match read_obstacles(path) {
+            Ok((obstacles, _)) => obstacles,
             Err(e) => {
-                error!("Failed to parse obstacles.json: {}", e);
+                error!("{}", e);
                 return false;
             }
         };
-        
-        let obj = match json.as_object() {
-            Some(o) => o,
-            None => {
-                error!("obstacles.json root is not an object");
-                return false;
-            }
-        };
-        
-        for (zone_id_str, obstacles_array) in obj {
-            let zone_id: u32 = match zone_id_str.parse() {
-                Ok(id) => id,
-                Err(_) => {
-                    warn!("Invalid zone ID in obstacles.json: {}", zone_id_str);
-                    continue;
-                }
-            };
-            
-            let obstacles = match obstacles_array.as_array() {
-                Some(arr) => arr,
-                None => {
-                    warn!("Zone {} obstacles is not an array", zone_id);
-                    continue;
-                }
-            };
-            
-            let mut zone_obstacles = Vec::new();
-            
-            for obs in obstacles {
-                if let Some(obstacle) = self.parse_obstacle(obs) {
-                    zone_obstacles.push(obstacle);
-                }
-            }
-            
-            if !zone_obstacles.is_empty() {
-                self.obstacles.insert(zone_id, zone_obstacles);
-            }
-        }
+        self.obstacles = obstacles;
         
         let total_obstacles: usize = self.obstacles.values().map(|v| v.len()).sum();
         info!("Loaded {} obstacles from {:?} across {} zones", 
@@ -577,32 +668,6 @@ impl ZoneManager {
         true
     }
     
-    /// Parse a single obstacle from JSON
-    fn parse_obstacle(&self, value: &serde_json::Value) -> Option<Obstacle> {
-        let obj = value.as_object()?;
-        let obstacle_type = obj.get("type")?.as_str()?;
-        
-        match obstacle_type {
-            "circle" => {
-                let x = obj.get("center_x")?.as_f64()? as f32;
-                let z = obj.get("center_z")?.as_f64()? as f32;
-                let radius = obj.get("radius")?.as_f64()? as f32;
-                Some(Obstacle::Circle(CircleObstacle::new(x, z, radius)))
-            }
-            "box" => {
-                let x = obj.get("center_x")?.as_f64()? as f32;
-                let z = obj.get("center_z")?.as_f64()? as f32;
-                let half_w = obj.get("half_width")?.as_f64()? as f32;
-                let half_d = obj.get("half_depth")?.as_f64()? as f32;
-                Some(Obstacle::Box(BoxObstacle::from_center(x, z, half_w, half_d)))
-            }
-            _ => {
-                warn!("Unknown obstacle type: {}", obstacle_type);
-                None
-            }
-        }
-    }
-    
     /// Fallback: hardcoded obstacles matching the Godot scene layouts
     fn init_hardcoded_obstacles(&mut self) {
         // Common village obstacles (same layout for all empires)
@@ -653,3 +718,163 @@ impl Default for ZoneManager {
         Self::new()
     }
 }
+
+/// Path of a zone's exported heightmap metadata
+fn heightmap_path(empire_name: &str) -> String {
+    format!("heightmaps/{}_heightmap.json", empire_name)
+}
+
+/// Read obstacles per zone from a JSON file exported by Godot
+/// Entries that do not parse are skipped (with a warning) and counted
+/// Returns the obstacles and the number of skipped entries
+fn read_obstacles(path: &Path) -> Result<(HashMap<u32, Vec<Obstacle>>, usize), String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read obstacles file {:?}: {}", path, e))?;
+    
+    let json: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse obstacles.json: {}", e))?;
+    
+    let obj = json.as_object()
+        .ok_or("obstacles.json root is not an object")?;
+    
+    let mut obstacles = HashMap::new();
+    let mut skipped = 0;
+    
+    for (zone_id_str, obstacles_array) in obj {
+        let zone_id: u32 = match zone_id_str.parse() {
+            Ok(id) => id,
+            Err(_) => {
+                warn!("Invalid zone ID in obstacles.json: {}", zone_id_str);
+                skipped += 1;
+                continue;
+            }
+        };
+        
+        let Some(entries) = obstacles_array.as_array() else {
+            warn!("Zone {} obstacles is not an array", zone_id);
+            skipped += 1;
+            continue;
+        };
+        
+        let mut zone_obstacles = Vec::new();
+        for obs in entries {
+            match parse_obstacle(obs) {
+                Some(obstacle) => zone_obstacles.push(obstacle),
+                None => skipped += 1,
+            }
+        }
+        
+        if !zone_obstacles.is_empty() {
+            obstacles.insert(zone_id, zone_obstacles);
+        }
+    }
+    
+    Ok((obstacles, skipped))
+}
+
+/// Parse a single obstacle from JSON
+fn parse_obstacle(value: &serde_json::Value) -> Option<Obstacle> {
+    let obj = value.as_object()?;
+    let obstacle_type = obj.get("type")?.as_str()?;
+    
+    match obstacle_type {
+        "circle" => {
+            let x = obj.get("center_x")?.as_f64()? as f32;
+            let z = obj.get("center_z")?.as_f64()? as f32;
+            let radius = obj.get("radius")?.as_f64()? as f32;
+            Some(Obstacle::Circle(CircleObstacle::new(x, z, radius)))
+        }
+        "box" => {
+            let x = obj.get("center_x")?.as_f64()? as f32;
+            let z = obj.get("center_z")?.as_f64()? as f32;
+            let half_w = obj.get("half_width")?.as_f64()? as f32;
+            let half_d = obj.get("half_depth")?.as_f64()? as f32;
+            Some(Obstacle::Box(BoxObstacle::from_center(x, z, half_w, half_d)))
+        }
+        _ => {
+            warn!("Unknown obstacle type: {}", obstacle_type);
+            None
+        }
+    }
+}
+
+/// Build (or load from the cache directory) the nav-mesh of every zone with a heightmap
+fn build_navmeshes(
+    heightmaps: &HashMap<u32, Heightmap>,
+    obstacles: &HashMap<u32, Vec<Obstacle>>,
+    cache_dir: Option<&str>,
+) -> HashMap<u32, Arc<NavMesh>> {
+    let mut navmeshes = HashMap::new();
+    
+    for (zone_id, empire_name) in ZONE_EMPIRES {
+        let Some(heightmap) = heightmaps.get(&zone_id) else {
+            continue;
+        };
+        let obstacles = obstacles.get(&zone_id).map(|v| v.as_slice()).unwrap_or(&[]);
+        let fingerprint = navmesh_fingerprint(heightmap, obstacles);
+        let cache_path = cache_dir.map(|dir| format!("{}/{}_navmesh.json", dir, empire_name));
+        
+        let cached = match &cache_path {
+            Some(path) => NavMesh::load_cached(path, fingerprint)
+                .map_err(|e| debug!("Not using cached nav-mesh for zone {}: {}", zone_id, e))
+                .ok(),
+            None => None,
+        };
+        let mesh = match cached {
+            Some(mesh) => {
+                info!("Loaded cached nav-mesh for zone {} ({} polygons)", zone_id, mesh.poly_count());
+                mesh
+            }
+            None => {
+                let started = Instant::now();
+                let (min, max) = heightmap.bounds();
+                let mesh = NavMesh::build(
+                    Vec2::new(min[0], min[1]),
+                    Vec2::new(max[0], max[1]),
+                    |x, z| heightmap.get_height(x, z),
+                    obstacles,
+                    ENEMY_RADIUS,
+                );
+                info!("Built nav-mesh for zone {} ({} polygons) in {:.1}s",
+                    zone_id, mesh.poly_count(), started.elapsed().as_secs_f32());
+                if let Some(path) = &cache_path {
+                    if let Err(e) = mesh.save(path, fingerprint) {
+                        warn!("Could not cache nav-mesh for zone {}: {}", zone_id, e);
+                    }
+                }
+                mesh
+            }
+        };
+        navmeshes.insert(zone_id, Arc::new(mesh));
+    }
+    
+    info!("Loaded {} nav-meshes", navmeshes.len());
+    navmeshes
+}
+
+/// Hash of everything a nav-mesh is built from (detects stale caches)
+fn navmesh_fingerprint(heightmap: &Heightmap, obstacles: &[Obstacle]) -> u64 {
+    let mut hash = Fingerprint::new();
+    hash.write_u64(heightmap.fingerprint());
+    hash.write_obstacles(obstacles);
+    hash.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_navmesh_rebuild_is_swapped_in_when_done() {
+        let mut manager = ZoneManager::new();
+        assert!(!manager.poll_navmesh_rebuild());
+
+        manager.rebuild_navmeshes();
+        let started = Instant::now();
+        while !manager.poll_navmesh_rebuild() {
+            assert!(started.elapsed().as_secs() < 5, "rebuild never finished");
+            tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+        }
+        assert!(manager.navmesh_rebuild.is_none());
+    }
+}
@@ -1,7 +1,7 @@
 //! Ability definitions shared between client and server.
 
 use serde::{Deserialize, Serialize};
-use crate::CharacterClass;
+use crate::{CharacterClass, Specialization};
 
 // =============================================================================
 // Ability Types
@@ -9,19 +9,38 @@ use crate::CharacterClass;
 
 /// Target type for abilities
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
 pub enum TargetType {
     /// Targets self only
-    SelfOnly,
+    SelfOnly = 0,
     /// Targets a single enemy
-    Enemy,
+    Enemy = 1,
     /// Targets a single ally (including self)
-    Ally,
+    Ally = 2,
     /// No target needed (instant effect)
-    None,
+    None = 3,
     /// Area around self
-    AreaAroundSelf,
+    AreaAroundSelf = 4,
     /// Area around target
-    AreaAroundTarget,
+    AreaAroundTarget = 5,
+}
+
+impl TargetType {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::SelfOnly),
+            1 => Some(Self::Enemy),
+            2 => Some(Self::Ally),
+            3 => Some(Self::None),
+            4 => Some(Self::AreaAroundSelf),
+            5 => Some(Self::AreaAroundTarget),
+            _ => None,
+        }
+    }
+    
+    pub fn as_u8(&self) -> u8 {
+        *self as u8
+    }
 }
 
 /// Effect types that abilities can apply
@@ -49,6 +68,8 @@ pub enum AbilityEffect {
     Slow { multiplier: f32, duration: f32 },
     /// Stun target (duration in seconds)
     Stun { duration: f32 },
+    /// Revive a dead ally in place (fraction of max health restored)
+    Resurrect { health_percent: f32 },
 }
 
 /// Ability definition
@@ -64,12 +85,20 @@ pub struct AbilityDef {
     pub mana_cost: u32,
     /// Cooldown in seconds
     pub cooldown: f32,
+    /// Cast time in seconds before the ability resolves (0 = instant)
+    pub cast_time: f32,
+    /// Channel duration in seconds, effects pulse while channeling (0 = not channeled)
+    pub channel_duration: f32,
+    /// Projectile speed in units per second; effects apply on impact (0 = hits instantly)
+    pub projectile_speed: f32,
     /// Range in units (0 = melee/self)
     pub range: f32,
     /// Target type
     pub target_type: TargetType,
     /// Class restriction (None = all classes)
     pub class_restriction: Option<CharacterClass>,
+    /// Specialization restriction (None = whole class)
+    pub specialization: Option<Specialization>,
     /// Minimum level required
     pub level_requirement: u32,
     /// Effects applied when ability is used
@@ -79,197 +108,246 @@ pub struct AbilityDef {
 }
 
 // =============================================================================
-// Ability Definitions
+// Skill Ranks
 // =============================================================================
 
-/// Get all ability definitions
-pub fn get_ability_definitions() -> Vec<AbilityDef> {
-    vec![
-        // =====================================================================
-        // Universal Abilities (ID 1-10)
-        // =====================================================================
-        AbilityDef {
-            id: 1,
-            name: "Power Strike".into(),
-            description: "A powerful melee attack dealing 150% weapon damage.".into(),
-            mana_cost: 10,
-            cooldown: 6.0,
-            range: 3.0,
-            target_type: TargetType::Enemy,
-            class_restriction: None,
-            level_requirement: 1,
-            effects: vec![AbilityEffect::Damage { base: 5, attack_scaling: 1.5 }],
-            icon: "power_strike.png".into(),
-        },
-        AbilityDef {
-            id: 2,
-            name: "Recuperate".into(),
-            description: "Restore 20% of your maximum health over 10 seconds.".into(),
-            mana_cost: 20,
-            cooldown: 30.0,
-            range: 0.0,
-            target_type: TargetType::SelfOnly,
-            class_restriction: None,
-            level_requirement: 1,
-            effects: vec![AbilityEffect::HealOverTime { 
-                heal_per_tick: 0, // Will be calculated as 2% per tick
-                interval: 1.0, 
-                duration: 10.0 
-            }],
-            icon: "recuperate.png".into(),
-        },
+/// Highest skill rank (Perfect Master)
+pub const MAX_SKILL_RANK: u8 = 40;
+
+/// Skill points awarded per character level gained
+pub const SKILL_POINTS_PER_LEVEL: u32 = 1;
+
+/// Skill grade tiers (Metin2-style)
+/// Ranks 1-19 are Normal, 20-29 Master (M1-M10), 30-39 Grand Master (G1-G10), 40 Perfect Master
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum SkillGrade {
+    Normal = 0,
+    Master = 1,
+    GrandMaster = 2,
+    PerfectMaster = 3,
+}
+
+/// Rules for training a skill out of its current grade
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkillUpgradeRule {
+    /// Skill points spent per attempt
+    pub point_cost: u32,
+    /// Chance the attempt raises the rank (points are spent either way)
+    pub success_chance: f32,
+    /// Minimum character level to train at this grade
+    pub min_level: u32,
+}
+
+impl SkillGrade {
+    /// Get the grade of a skill rank (rank 0 = not learned, treated as Normal)
+    pub fn from_rank(rank: u8) -> Self {
+        match rank {
+            0..=19 => Self::Normal,
+            20..=29 => Self::Master,
+            30..=39 => Self::GrandMaster,
+            _ => Self::PerfectMaster,
+        }
+    }
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Normal => "Normal",
+            Self::Master => "Master",
+            Self::GrandMaster => "Grand Master",
+            Self::PerfectMaster => "Perfect Master",
+        }
+    }
+    
+    /// Training rules for raising a skill out of this grade (None = already maxed)
+    pub fn upgrade_rule(&self) -> Option<SkillUpgradeRule> {
+        match self {
+            // Normal ranks always succeed
+            Self::Normal => Some(SkillUpgradeRule { point_cost: 1, success_chance: 1.0, min_level: 1 }),
+            // Master training can fail
+            Self::Master => Some(SkillUpgradeRule { point_cost: 1, success_chance: 0.5, min_level: 20 }),
+            // Grand Master training is expensive and unreliable
+            Self::GrandMaster => Some(SkillUpgradeRule { point_cost: 2, success_chance: 0.3, min_level: 30 }),
+            Self::PerfectMaster => None,
+        }
+    }
+}
+
+/// Display label for a rank ("5", "M3", "G10", "P"; "-" if not learned)
+pub fn skill_rank_label(rank: u8) -> String {
+    if rank == 0 {
+        return "-".into();
+    }
+    match SkillGrade::from_rank(rank) {
+        SkillGrade::Normal => rank.to_string(),
+        SkillGrade::Master => format!("M{}", rank - 19),
+        SkillGrade::GrandMaster => format!("G{}", rank - 29),
+        SkillGrade::PerfectMaster => "P".into(),
+    }
+}
+
+/// Damage/heal/buff strength multiplier for a rank (+5% per rank above 1)
+pub fn skill_power_multiplier(rank: u8) -> f32 {
+    1.0 + rank.saturating_sub(1) as f32 * 0.05
+}
+
+/// Effect duration multiplier for a rank (+2% per rank above 1)
+pub fn skill_duration_multiplier(rank: u8) -> f32 {
+    1.0 + rank.saturating_sub(1) as f32 * 0.02
+}
+
+/// Cooldown multiplier for a rank (-1% per rank above 1)
+pub fn skill_cooldown_multiplier(rank: u8) -> f32 {
+    1.0 - rank.saturating_sub(1) as f32 * 0.01
+}
+
+/// A learned skill and its rank
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillRank {
+    pub ability_id: u32,
+    pub rank: u8,
+}
+
+impl AbilityDef {
+    /// Whether a character of this class and specialization may use this ability
+    pub fn is_available_to(&self, class: CharacterClass, specialization: Option<Specialization>) -> bool {
+        let class_ok = self.class_restriction.is_none() || self.class_restriction == Some(class);
+        let spec_ok = self.specialization.is_none() || self.specialization == specialization;
+        class_ok && spec_ok
+    }
+    
+    /// Whether this ability targets dead players (resurrection)
+    pub fn revives_target(&self) -> bool {
+        self.effects.iter().any(|e| matches!(e, AbilityEffect::Resurrect { .. }))
+    }
+    
+    /// Get a copy of this ability with effects and cooldown scaled for a skill rank
+    pub fn scaled_for_rank(&self, rank: u8) -> AbilityDef {
+        let power = skill_power_multiplier(rank);
+        let duration_mult = skill_duration_multiplier(rank);
+        let scale = |value: u32| (value as f32 * power).round() as u32;
+        let scale_i = |value: i32| (value as f32 * power).round() as i32;
         
-        // =====================================================================
-        // Ninja Abilities (ID 11-20)
-        // =====================================================================
-        AbilityDef {
-            id: 11,
-            name: "Shadow Strike".into(),
-            description: "Strike from the shadows, dealing 200% weapon damage with increased critical chance.".into(),
-            mana_cost: 15,
-            cooldown: 8.0,
-            range: 3.0,
-            target_type: TargetType::Enemy,
-            class_restriction: Some(CharacterClass::Ninja),
-            level_requirement: 1,
-            effects: vec![AbilityEffect::Damage { base: 8, attack_scaling: 2.0 }],
-            icon: "shadow_strike.png".into(),
-        },
-        AbilityDef {
-            id: 12,
-            name: "Poison Blade".into(),
-            description: "Coat your weapon in poison, causing the target to take damage over 8 seconds.".into(),
-            mana_cost: 20,
-            cooldown: 12.0,
-            range: 3.0,
-            target_type: TargetType::Enemy,
-            class_restriction: Some(CharacterClass::Ninja),
-            level_requirement: 5,
-            effects: vec![
-                AbilityEffect::Damage { base: 5, attack_scaling: 0.5 },
-                AbilityEffect::DamageOverTime { damage_per_tick: 8, interval: 2.0, duration: 8.0 },
-            ],
-            icon: "poison_blade.png".into(),
-        },
+        let mut ability = self.clone();
+        ability.cooldown *= skill_cooldown_multiplier(rank);
         
-        // =====================================================================
-        // Warrior Abilities (ID 21-30)
-        // =====================================================================
-        AbilityDef {
-            id: 21,
-            name: "Crushing Blow".into(),
-            description: "A devastating attack dealing 180% weapon damage and reducing enemy defense.".into(),
-            mana_cost: 20,
-            cooldown: 10.0,
-            range: 3.0,
-            target_type: TargetType::Enemy,
-            class_restriction: Some(CharacterClass::Warrior),
-            level_requirement: 1,
-            effects: vec![
+        for effect in &mut ability.effects {
+            match effect {
+                AbilityEffect::Damage { base, attack_scaling } => {
+                    *base = scale(*base);
+                    *attack_scaling *= power;
+                }
+                AbilityEffect::Heal { base, health_scaling } => {
+                    *base = scale(*base);
+                    *health_scaling *= power;
+                }
+                AbilityEffect::DamageOverTime { damage_per_tick, duration, .. } => {
+                    *damage_per_tick = scale(*damage_per_tick);
+                    *duration *= duration_mult;
+                }
+                AbilityEffect::HealOverTime { heal_per_tick, duration, .. } => {
+                    *heal_per_tick = scale(*heal_per_tick);
+                    *duration *= duration_mult;
+                }
+                AbilityEffect::BuffAttack { amount, duration }
+                | AbilityEffect::BuffDefense { amount, duration }
+                | AbilityEffect::DebuffAttack { amount, duration }
+                | AbilityEffect::DebuffDefense { amount, duration } => {
+                    *amount = scale_i(*amount);
+                    *duration *= duration_mult;
+                }
+                AbilityEffect::BuffAttackSpeed { duration, .. }
+                | AbilityEffect::Slow { duration, .. }
+                | AbilityEffect::Stun { duration } => {
+                    *duration *= duration_mult;
+                }
+                AbilityEffect::Resurrect { .. } => {}
+            }
+        }
+        
+        ability
+    }
+}
+
+// =============================================================================
+// Ability Queries
+// =============================================================================
+// Definitions live in the `abilities` table (see server/migrations); the server
+// loads them at startup and sends them to clients at login.
+
+/// Starter abilities for a server running without a database
+/// NOTE: A copy of the level 1 rows seeded by 011_abilities.sql, only used as a fallback.
+/// Everything else (and any rebalancing) comes from the `abilities` table.
+pub fn get_fallback_abilities() -> Vec<AbilityDef> {
+    let ability = |id: u32, name: &str, description: &str, mana_cost: u32, cooldown: f32, range: f32,
+                   target_type: TargetType, class_restriction: Option<CharacterClass>,
+                   effects: Vec<AbilityEffect>, icon: &str| AbilityDef {
+        id,
+        name: name.to_string(),
+        description: description.to_string(),
+        mana_cost,
+        cooldown,
+        cast_time: 0.0,
+        channel_duration: 0.0,
+        projectile_speed: 0.0,
+        range,
+        target_type,
+        class_restriction,
+        specialization: None,
+        level_requirement: 1,
+        effects,
+        icon: icon.to_string(),
+    };
+    
+    vec![
+        ability(1, "Power Strike", "A powerful melee attack dealing 150% weapon damage.", 10, 6.0, 3.0,
+            TargetType::Enemy, None,
+            vec![AbilityEffect::Damage { base: 5, attack_scaling: 1.5 }], "power_strike.png"),
+        ability(2, "Recuperate", "Restore 20% of your maximum health over 10 seconds.", 20, 30.0, 0.0,
+            TargetType::SelfOnly, None,
+            vec![AbilityEffect::HealOverTime { heal_per_tick: 0, interval: 1.0, duration: 10.0 }], "recuperate.png"),
+        ability(11, "Shadow Strike", "Strike from the shadows, dealing 200% weapon damage with increased critical chance.", 15, 8.0, 3.0,
+            TargetType::Enemy, Some(CharacterClass::Ninja),
+            vec![AbilityEffect::Damage { base: 8, attack_scaling: 2.0 }], "shadow_strike.png"),
+        ability(21, "Crushing Blow", "A devastating attack dealing 180% weapon damage and reducing enemy defense.", 20, 10.0, 3.0,
+            TargetType::Enemy, Some(CharacterClass::Warrior),
+            vec![
                 AbilityEffect::Damage { base: 10, attack_scaling: 1.8 },
                 AbilityEffect::DebuffDefense { amount: 5, duration: 10.0 },
-            ],
-            icon: "crushing_blow.png".into(),
-        },
+            ], "crushing_blow.png"),
+        ability(31, "Dark Slash", "Channel dark energy into your blade, dealing 170% weapon damage.", 15, 7.0, 3.0,
+            TargetType::Enemy, Some(CharacterClass::Sura),
+            vec![AbilityEffect::Damage { base: 8, attack_scaling: 1.7 }], "dark_slash.png"),
         AbilityDef {
-            id: 22,
-            name: "Battle Cry".into(),
-            description: "Let out a battle cry, increasing your attack by 20% for 15 seconds.".into(),
-            mana_cost: 25,
-            cooldown: 45.0,
-            range: 0.0,
-            target_type: TargetType::SelfOnly,
-            class_restriction: Some(CharacterClass::Warrior),
-            level_requirement: 5,
-            effects: vec![AbilityEffect::BuffAttack { amount: 10, duration: 15.0 }],
-            icon: "battle_cry.png".into(),
-        },
-        
-        // =====================================================================
-        // Sura Abilities (ID 31-40)
-        // =====================================================================
-        AbilityDef {
-            id: 31,
-            name: "Dark Slash".into(),
-            description: "Channel dark energy into your blade, dealing 170% weapon damage.".into(),
-            mana_cost: 15,
-            cooldown: 7.0,
-            range: 3.0,
-            target_type: TargetType::Enemy,
-            class_restriction: Some(CharacterClass::Sura),
-            level_requirement: 1,
-            effects: vec![AbilityEffect::Damage { base: 8, attack_scaling: 1.7 }],
-            icon: "dark_slash.png".into(),
-        },
-        AbilityDef {
-            id: 32,
-            name: "Life Drain".into(),
-            description: "Drain life from your enemy, dealing damage and healing yourself.".into(),
-            mana_cost: 30,
-            cooldown: 15.0,
-            range: 5.0,
-            target_type: TargetType::Enemy,
-            class_restriction: Some(CharacterClass::Sura),
-            level_requirement: 5,
-            effects: vec![
-                AbilityEffect::Damage { base: 15, attack_scaling: 1.0 },
-                AbilityEffect::Heal { base: 20, health_scaling: 0.1 },
-            ],
-            icon: "life_drain.png".into(),
-        },
-        
-        // =====================================================================
-        // Shaman Abilities (ID 41-50)
-        // =====================================================================
-        AbilityDef {
-            id: 41,
-            name: "Lightning Bolt".into(),
-            description: "Call down lightning on your enemy, dealing magic damage.".into(),
-            mana_cost: 20,
-            cooldown: 5.0,
-            range: 15.0,
-            target_type: TargetType::Enemy,
-            class_restriction: Some(CharacterClass::Shaman),
-            level_requirement: 1,
-            effects: vec![AbilityEffect::Damage { base: 25, attack_scaling: 0.8 }],
-            icon: "lightning_bolt.png".into(),
-        },
-        AbilityDef {
-            id: 42,
-            name: "Healing Wave".into(),
-            description: "Channel healing energy to restore health.".into(),
-            mana_cost: 35,
-            cooldown: 12.0,
-            range: 0.0,
-            target_type: TargetType::SelfOnly,
-            class_restriction: Some(CharacterClass::Shaman),
-            level_requirement: 5,
-            effects: vec![AbilityEffect::Heal { base: 50, health_scaling: 0.15 }],
-            icon: "healing_wave.png".into(),
+            cast_time: 1.5,
+            projectile_speed: 30.0,
+            ..ability(41, "Lightning Bolt", "Hurl a bolt of lightning at your enemy, dealing magic damage.", 20, 5.0, 15.0,
+                TargetType::Enemy, Some(CharacterClass::Shaman),
+                vec![AbilityEffect::Damage { base: 25, attack_scaling: 0.8 }], "lightning_bolt.png")
         },
     ]
 }
 
-/// Get ability by ID
-pub fn get_ability_by_id(id: u32) -> Option<AbilityDef> {
-    get_ability_definitions().into_iter().find(|a| a.id == id)
-}
-
-/// Get abilities available for a class at a given level
-pub fn get_abilities_for_class(class: CharacterClass, level: u32) -> Vec<AbilityDef> {
-    get_ability_definitions()
+/// Get abilities available for a class and specialization at a given level
+pub fn get_abilities_for_class<'a>(
+    abilities: impl IntoIterator<Item = &'a AbilityDef>,
+    class: CharacterClass,
+    specialization: Option<Specialization>,
+    level: u32,
+) -> Vec<&'a AbilityDef> {
+    abilities
         .into_iter()
         .filter(|a| {
             // Must meet level requirement
             a.level_requirement <= level &&
-            // Must be universal or match class
-            (a.class_restriction.is_none() || a.class_restriction == Some(class))
+            // Must be universal or match class and specialization
+            a.is_available_to(class, specialization)
         })
         .collect()
 }
 
-/// Get default action bar ability IDs for a class (slots 1-4)
-pub fn get_default_action_bar(class: CharacterClass) -> [Option<u32>; 8] {
+/// Get default action bar ability IDs for a class (slots 1-4, plus 5 once specialized)
+pub fn get_default_action_bar(class: CharacterClass, specialization: Option<Specialization>) -> [Option<u32>; 8] {
     let mut bar = [None; 8];
     
     // Slot 0: Power Strike (universal)
@@ -294,7 +372,111 @@ pub fn get_default_action_bar(class: CharacterClass) -> [Option<u32>; 8] {
     // Slot 3: Recuperate (universal)
     bar[3] = Some(2);
     
-    // Slots 4-7: Empty for future abilities
+    // Slot 4: Specialization ability (once chosen)
+    bar[4] = specialization.map(|spec| match spec {
+        Specialization::Blade => 13,      // Ambush
+        Specialization::Archery => 14,    // Fire Arrow
+        Specialization::Body => 23,       // Sword Spin
+        Specialization::Mental => 24,     // Strong Body
+        Specialization::Weaponry => 33,   // Enchanted Blade
+        Specialization::BlackMagic => 34, // Dark Orb
+        Specialization::Dragon => 43,     // Dragon's Roar
+        Specialization::Healing => 44,    // Blessing
+    });
+    
+    // Slot 5: Class utility (Shaman resurrect)
+    bar[5] = match class {
+        CharacterClass::Shaman => Some(45), // Spirit Recall
+        _ => None,
+    };
+    
+    // Slots 6-7: Empty for future abilities
     
     bar
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ability(id: u32, class: Option<CharacterClass>, specialization: Option<Specialization>, level: u32) -> AbilityDef {
+        AbilityDef {
+            id,
+            name: format!("Ability {}", id),
+            description: String::new(),
+            mana_cost: 10,
+            cooldown: 10.0,
+            cast_time: 0.0,
+            channel_duration: 0.0,
+            projectile_speed: 0.0,
+            range: 3.0,
+            target_type: TargetType::Enemy,
+            class_restriction: class,
+            specialization,
+            level_requirement: level,
+            effects: vec![AbilityEffect::Damage { base: 10, attack_scaling: 1.5 }],
+            icon: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_abilities_for_class_respect_class_specialization_and_level() {
+        let abilities = vec![
+            ability(1, None, None, 1),
+            ability(21, Some(CharacterClass::Warrior), None, 1),
+            ability(23, Some(CharacterClass::Warrior), Some(Specialization::Body), 5),
+            ability(24, Some(CharacterClass::Warrior), Some(Specialization::Mental), 5),
+            ability(41, Some(CharacterClass::Shaman), None, 1),
+        ];
+        let ids = |spec, level| -> Vec<u32> {
+            get_abilities_for_class(&abilities, CharacterClass::Warrior, spec, level)
+                .iter()
+                .map(|a| a.id)
+                .collect()
+        };
+
+        assert_eq!(ids(None, 10), vec![1, 21]);
+        assert_eq!(ids(Some(Specialization::Body), 4), vec![1, 21]);
+        assert_eq!(ids(Some(Specialization::Body), 5), vec![1, 21, 23]);
+    }
+
+    #[test]
+    fn test_skill_grades_and_labels() {
+        let grades = [(1, SkillGrade::Normal, "1"), (19, SkillGrade::Normal, "19"), (20, SkillGrade::Master, "M1"),
+            (29, SkillGrade::Master, "M10"), (30, SkillGrade::GrandMaster, "G1"), (39, SkillGrade::GrandMaster, "G10"),
+            (MAX_SKILL_RANK, SkillGrade::PerfectMaster, "P")];
+        for (rank, grade, label) in grades {
+            assert_eq!(SkillGrade::from_rank(rank), grade);
+            assert_eq!(skill_rank_label(rank), label);
+        }
+        assert_eq!(skill_rank_label(0), "-");
+        assert!(SkillGrade::PerfectMaster.upgrade_rule().is_none());
+    }
+
+    #[test]
+    fn test_skill_rank_multipliers() {
+        // Rank 0 (not learned) and rank 1 are both unscaled
+        for rank in [0, 1] {
+            assert_eq!(skill_power_multiplier(rank), 1.0);
+            assert_eq!(skill_duration_multiplier(rank), 1.0);
+            assert_eq!(skill_cooldown_multiplier(rank), 1.0);
+        }
+        assert!((skill_power_multiplier(MAX_SKILL_RANK) - 2.95).abs() < 0.001);
+        assert!((skill_duration_multiplier(MAX_SKILL_RANK) - 1.78).abs() < 0.001);
+        assert!((skill_cooldown_multiplier(MAX_SKILL_RANK) - 0.61).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_fallback_gives_every_class_its_starter_abilities() {
+        let fallback = get_fallback_abilities();
+        for class in [CharacterClass::Ninja, CharacterClass::Warrior, CharacterClass::Sura, CharacterClass::Shaman] {
+            let available = get_abilities_for_class(&fallback, class, None, 1);
+            let bar = get_default_action_bar(class, None);
+            // Power Strike, the class attack and Recuperate
+            for id in [bar[0], bar[1], bar[3]].into_iter().flatten() {
+                assert!(available.iter().any(|a| a.id == id), "{:?} is missing ability {}", class, id);
+            }
+        }
+    }
+
+}
@@ -1,6 +1,25 @@
 //! Shared entity definitions.
 
 use serde::{Deserialize, Serialize};
+use crate::CharacterClass;
+
+/// Stat points awarded per character level gained
+pub const STAT_POINTS_PER_LEVEL: u32 = 3;
+
+/// Highest value a single primary attribute can reach
+pub const MAX_ATTRIBUTE_VALUE: u32 = 90;
+
+/// Gold cost per character level for a paid attribute reset
+pub const RESPEC_GOLD_PER_LEVEL: u64 = 100;
+
+/// Base player movement speed (units per second)
+pub const BASE_MOVE_SPEED: f32 = 5.0;
+
+/// Speed multiplier while sprinting
+pub const SPRINT_MULTIPLIER: f32 = 1.5;
+
+/// Durability of fully repaired gear
+pub const MAX_DURABILITY: u32 = 100;
 
 /// Stats shared between client and server
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,11 +42,96 @@ impl Default for EntityStats {
             max_mana: 50,
             attack_power: 10,
             defense: 5,
-            speed: 5.0,
+            speed: BASE_MOVE_SPEED,
         }
     }
 }
 
+/// Primary attribute that stat points can be spent on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum PrimaryStat {
+    /// Max health and defense
+    Vitality = 0,
+    /// Max mana
+    Intelligence = 1,
+    /// Attack
+    Strength = 2,
+    /// Attack speed and critical chance
+    Dexterity = 3,
+}
+
+impl PrimaryStat {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Vitality),
+            1 => Some(Self::Intelligence),
+            2 => Some(Self::Strength),
+            3 => Some(Self::Dexterity),
+            _ => None,
+        }
+    }
+    
+    pub fn as_u8(&self) -> u8 {
+        *self as u8
+    }
+    
+    /// Short display name (VIT, INT, STR, DEX)
+    pub fn short_name(&self) -> &'static str {
+        match self {
+            Self::Vitality => "VIT",
+            Self::Intelligence => "INT",
+            Self::Strength => "STR",
+            Self::Dexterity => "DEX",
+        }
+    }
+}
+
+/// Primary attributes of a character
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attributes {
+    pub vitality: u32,
+    pub intelligence: u32,
+    pub strength: u32,
+    pub dexterity: u32,
+}
+
+impl Attributes {
+    /// Attributes a new character of this class starts with
+    pub fn starting_for_class(class: CharacterClass) -> Self {
+        let (vitality, intelligence, strength, dexterity) = match class {
+            CharacterClass::Ninja => (4, 3, 4, 6),
+            CharacterClass::Warrior => (4, 3, 6, 3),
+            CharacterClass::Sura => (3, 5, 5, 3),
+            CharacterClass::Shaman => (3, 6, 3, 4),
+        };
+        Self { vitality, intelligence, strength, dexterity }
+    }
+    
+    pub fn get(&self, stat: PrimaryStat) -> u32 {
+        match stat {
+            PrimaryStat::Vitality => self.vitality,
+            PrimaryStat::Intelligence => self.intelligence,
+            PrimaryStat::Strength => self.strength,
+            PrimaryStat::Dexterity => self.dexterity,
+        }
+    }
+    
+    pub fn get_mut(&mut self, stat: PrimaryStat) -> &mut u32 {
+        match stat {
+            PrimaryStat::Vitality => &mut self.vitality,
+            PrimaryStat::Intelligence => &mut self.intelligence,
+            PrimaryStat::Strength => &mut self.strength,
+            PrimaryStat::Dexterity => &mut self.dexterity,
+        }
+    }
+    
+    /// Sum of all four attributes
+    pub fn total(&self) -> u32 {
+        self.vitality + self.intelligence + self.strength + self.dexterity
+    }
+}
+
 /// Player data that persists
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlayerData {
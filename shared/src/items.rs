@@ -41,6 +41,11 @@ impl WeaponVisualType {
     pub fn as_u8(&self) -> u8 {
         *self as u8
     }
+    
+    /// Whether this weapon attacks with projectiles
+    pub fn is_ranged(&self) -> bool {
+        matches!(self, Self::Bow)
+    }
 }
 
 /// Weapon-specific stats
@@ -125,6 +130,12 @@ pub enum ItemEffect {
     IncreaseAttack(u32),
     IncreaseDefense(u32),
     IncreaseSpeed(f32),
+    /// Refund all spent stat points
+    ResetStats,
+    /// Passive health regeneration per second while equipped (out of combat)
+    HealthRegen(u32),
+    /// Passive mana regeneration per second while equipped (out of combat)
+    ManaRegen(u32),
 }
 
 /// Built-in item definitions for the prototype
@@ -166,6 +177,17 @@ pub fn get_item_definitions() -> Vec<ItemDef> {
             weapon_stats: None,
             armor_stats: None,
         },
+        ItemDef {
+            id: 101,
+            name: "Scroll of Forgetting".into(),
+            description: "Refunds all spent stat points.".into(),
+            item_type: ItemType::Consumable,
+            rarity: ItemRarity::Rare,
+            max_stack: 5,
+            effects: vec![ItemEffect::ResetStats],
+            weapon_stats: None,
+            armor_stats: None,
+        },
         // Universal Weapons (IDs 4-5)
         ItemDef {
             id: 4,
@@ -442,7 +464,7 @@ pub fn get_item_definitions() -> Vec<ItemDef> {
             item_type: ItemType::Armor,
             rarity: ItemRarity::Epic,
             max_stack: 1,
-            effects: vec![],
+            effects: vec![ItemEffect::HealthRegen(1)],
             weapon_stats: None,
             armor_stats: Some(ArmorStats {
                 defense: 50,
@@ -458,7 +480,7 @@ pub fn get_item_definitions() -> Vec<ItemDef> {
             item_type: ItemType::Armor,
             rarity: ItemRarity::Legendary,
             max_stack: 1,
-            effects: vec![],
+            effects: vec![ItemEffect::HealthRegen(2)],
             weapon_stats: None,
             armor_stats: Some(ArmorStats {
                 defense: 70,
@@ -542,7 +564,7 @@ pub fn get_item_definitions() -> Vec<ItemDef> {
             item_type: ItemType::Armor,
             rarity: ItemRarity::Epic,
             max_stack: 1,
-            effects: vec![],
+            effects: vec![ItemEffect::HealthRegen(2)],
             weapon_stats: None,
             armor_stats: Some(ArmorStats {
                 defense: 65,
@@ -558,7 +580,7 @@ pub fn get_item_definitions() -> Vec<ItemDef> {
             item_type: ItemType::Armor,
             rarity: ItemRarity::Legendary,
             max_stack: 1,
-            effects: vec![],
+            effects: vec![ItemEffect::HealthRegen(3)],
             weapon_stats: None,
             armor_stats: Some(ArmorStats {
                 defense: 90,
@@ -642,7 +664,7 @@ pub fn get_item_definitions() -> Vec<ItemDef> {
             item_type: ItemType::Armor,
             rarity: ItemRarity::Epic,
             max_stack: 1,
-            effects: vec![],
+            effects: vec![ItemEffect::ManaRegen(1)],
             weapon_stats: None,
             armor_stats: Some(ArmorStats {
                 defense: 55,
@@ -658,7 +680,7 @@ pub fn get_item_definitions() -> Vec<ItemDef> {
             item_type: ItemType::Armor,
             rarity: ItemRarity::Legendary,
             max_stack: 1,
-            effects: vec![],
+            effects: vec![ItemEffect::ManaRegen(2)],
             weapon_stats: None,
             armor_stats: Some(ArmorStats {
                 defense: 75,
@@ -742,7 +764,7 @@ pub fn get_item_definitions() -> Vec<ItemDef> {
             item_type: ItemType::Armor,
             rarity: ItemRarity::Epic,
             max_stack: 1,
-            effects: vec![],
+            effects: vec![ItemEffect::ManaRegen(2)],
             weapon_stats: None,
             armor_stats: Some(ArmorStats {
                 defense: 48,
@@ -758,7 +780,7 @@ pub fn get_item_definitions() -> Vec<ItemDef> {
             item_type: ItemType::Armor,
             rarity: ItemRarity::Legendary,
             max_stack: 1,
-            effects: vec![],
+            effects: vec![ItemEffect::ManaRegen(3)],
             weapon_stats: None,
             armor_stats: Some(ArmorStats {
                 defense: 68,
@@ -1,9 +1,10 @@
 //! Network protocol definitions shared between client and server.
 
 use serde::{Deserialize, Serialize};
+use crate::{AbilityDef, SkillRank, Attributes, PrimaryStat};
 
 /// Protocol version for compatibility checking
-pub const PROTOCOL_VERSION: u32 = 11;
+pub const PROTOCOL_VERSION: u32 = 28;
 
 /// Server tick rate in Hz
 pub const SERVER_TICK_RATE: u32 = 20;
@@ -62,6 +63,136 @@ impl CharacterClass {
             Self::Shaman => 0.9,   // Shamans attack slightly slower (magic focused)
         }
     }
+    
+    /// Get base critical hit chance for this class (0.0 - 1.0)
+    pub fn base_crit_chance(&self) -> f32 {
+        match self {
+            Self::Ninja => 0.15,   // Ninjas rely on precise strikes
+            Self::Warrior => 0.08,
+            Self::Sura => 0.10,
+            Self::Shaman => 0.06,
+        }
+    }
+    
+    /// Get base dodge chance for this class (0.0 - 1.0)
+    pub fn base_dodge_chance(&self) -> f32 {
+        match self {
+            Self::Ninja => 0.10,   // Light armor, agile
+            Self::Warrior => 0.03,
+            Self::Sura => 0.05,
+            Self::Shaman => 0.04,
+        }
+    }
+    
+    /// Get out-of-combat health regeneration as a fraction of max health per second
+    pub fn base_health_regen(&self) -> f32 {
+        match self {
+            Self::Ninja => 0.012,
+            Self::Warrior => 0.015,  // Warriors recover fastest
+            Self::Sura => 0.010,
+            Self::Shaman => 0.008,
+        }
+    }
+    
+    /// Get out-of-combat mana regeneration as a fraction of max mana per second
+    pub fn base_mana_regen(&self) -> f32 {
+        match self {
+            Self::Ninja => 0.008,
+            Self::Warrior => 0.005,
+            Self::Sura => 0.012,
+            Self::Shaman => 0.015,   // Shamans recover mana fastest
+        }
+    }
+}
+
+/// Class specialization (Metin2-style doctrine), two per class
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum Specialization {
+    /// Ninja - close combat assassin
+    Blade = 0,
+    /// Ninja - ranged bow fighter
+    Archery = 1,
+    /// Warrior - offensive melee
+    Body = 2,
+    /// Warrior - defensive tank
+    Mental = 3,
+    /// Sura - enchanted weapon fighter
+    Weaponry = 4,
+    /// Sura - dark spellcaster
+    BlackMagic = 5,
+    /// Shaman - offensive dragon magic
+    Dragon = 6,
+    /// Shaman - support and healing
+    Healing = 7,
+}
+
+impl Specialization {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Blade),
+            1 => Some(Self::Archery),
+            2 => Some(Self::Body),
+            3 => Some(Self::Mental),
+            4 => Some(Self::Weaponry),
+            5 => Some(Self::BlackMagic),
+            6 => Some(Self::Dragon),
+            7 => Some(Self::Healing),
+            _ => None,
+        }
+    }
+    
+    pub fn as_u8(&self) -> u8 {
+        *self as u8
+    }
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Blade => "Blade Fight",
+            Self::Archery => "Archery",
+            Self::Body => "Body Warrior",
+            Self::Mental => "Mental Warrior",
+            Self::Weaponry => "Weaponry",
+            Self::BlackMagic => "Black Magic",
+            Self::Dragon => "Dragon Force",
+            Self::Healing => "Healing Force",
+        }
+    }
+    
+    /// Class this specialization belongs to
+    pub fn class(&self) -> CharacterClass {
+        match self {
+            Self::Blade | Self::Archery => CharacterClass::Ninja,
+            Self::Body | Self::Mental => CharacterClass::Warrior,
+            Self::Weaponry | Self::BlackMagic => CharacterClass::Sura,
+            Self::Dragon | Self::Healing => CharacterClass::Shaman,
+        }
+    }
+    
+    /// Both specializations available to a class
+    pub fn for_class(class: CharacterClass) -> [Self; 2] {
+        match class {
+            CharacterClass::Ninja => [Self::Blade, Self::Archery],
+            CharacterClass::Warrior => [Self::Body, Self::Mental],
+            CharacterClass::Sura => [Self::Weaponry, Self::BlackMagic],
+            CharacterClass::Shaman => [Self::Dragon, Self::Healing],
+        }
+    }
+    
+    /// Stat growth per level after specializing
+    /// Returns (health, mana, attack, defense) gained per level
+    pub fn stat_growth_per_level(&self) -> (u32, u32, u32, u32) {
+        match self {
+            Self::Blade => (5, 2, 3, 1),
+            Self::Archery => (4, 3, 3, 1),
+            Self::Body => (7, 2, 3, 1),
+            Self::Mental => (8, 2, 1, 3),
+            Self::Weaponry => (6, 2, 3, 1),
+            Self::BlackMagic => (4, 6, 2, 1),
+            Self::Dragon => (4, 6, 2, 1),
+            Self::Healing => (5, 7, 1, 2),
+        }
+    }
 }
 
 /// Character gender
@@ -196,11 +327,14 @@ pub enum ClientMessage {
         content: String,
     },
     
-    /// Attack request
+    /// Start (or switch) auto-attack on a target
     Attack {
         target_id: u64,
     },
     
+    /// Stop auto-attacking
+    StopAttack,
+    
     /// Pick up item request
     PickupItem {
         item_entity_id: u64,
@@ -216,12 +350,14 @@ pub enum ClientMessage {
         slot: u8,
     },
     
-    /// Request respawn after death
-    /// respawn_type: 0 = at empire spawn (full health), 1 = at death location (20% health)
+    /// Release after death and respawn at the chosen location
     RespawnRequest {
-        respawn_type: u8,
+        respawn_type: RespawnType,
     },
     
+    /// Repair all equipped gear for gold
+    RepairEquipment,
+    
     /// Equip an item from inventory
     EquipItem {
         inventory_slot: u8,
@@ -257,6 +393,38 @@ pub enum ClientMessage {
         /// Target entity ID (for targeted abilities)
         target_id: Option<u64>,
     },
+    
+    /// Cancel the ability currently being cast or channeled
+    CancelCast,
+    
+    /// Learn a skill or train it to the next rank (costs skill points)
+    UpgradeSkill {
+        ability_id: u32,
+    },
+    
+    /// Choose a class specialization (once, from the level in CharacterSelected)
+    ChooseSpecialization {
+        specialization: Specialization,
+    },
+    
+    /// Spend stat points on a primary attribute
+    AllocateStat {
+        stat: PrimaryStat,
+        amount: u32,
+    },
+    
+    /// Reset all spent stat points, paid with gold
+    ResetStats,
+    
+    /// Opt in or out of streaming combat log events for this character
+    SetCombatLogStreaming {
+        enabled: bool,
+    },
+    
+    /// Sit down to rest (faster regeneration) or stand up
+    SetResting {
+        resting: bool,
+    },
 }
 
 // =============================================================================
@@ -330,6 +498,10 @@ pub enum ServerMessage {
         equipped_armor_id: Option<u32>,
         /// Player's gold currency
         gold: u64,
+        /// Chosen class specialization (None = not chosen yet)
+        specialization: Option<Specialization>,
+        /// Character level at which a specialization can be chosen
+        specialization_level: u32,
     },
     
     /// Character selection failed
@@ -379,13 +551,13 @@ pub enum ServerMessage {
         content: String,
     },
     
-    /// Damage was dealt
+    /// Damage was dealt (or avoided - see outcome)
     DamageEvent {
         attacker_id: u64,
         target_id: u64,
         damage: u32,
         target_new_health: u32,
-        is_critical: bool,
+        outcome: HitOutcome,
     },
     
     /// Entity died
@@ -394,7 +566,8 @@ pub enum ServerMessage {
         killer_id: Option<u64>,
     },
     
-    /// Entity respawned (for other entities)
+    /// Entity respawned or was resurrected in place
+    /// The local player is revived when this names them while dead (resurrection by another player)
     EntityRespawn {
         entity_id: u64,
         position: [f32; 3],
@@ -429,7 +602,10 @@ pub enum ServerMessage {
     EnemySpawn {
         id: u64,
         zone_id: u32,
+        /// Model the client renders
         enemy_type: EnemyType,
+        /// Display name from the enemy's template
+        name: String,
         position: [f32; 3],
         health: u32,
         max_health: u32,
@@ -450,7 +626,7 @@ pub enum ServerMessage {
     },
     
     /// Time synchronization for day/night cycle
-    /// Sent on character select and every 60 seconds
+    /// Sent on character select, every 60 seconds and when the weather changes
     TimeSync {
         /// Unix timestamp in seconds (server's current UTC time)
         unix_timestamp: i64,
@@ -458,6 +634,8 @@ pub enum ServerMessage {
         latitude: f32,
         /// Server longitude for solar calculations (e.g., 13.4 for Berlin)
         longitude: f32,
+        /// Current world weather
+        weather: Weather,
     },
     
     /// Zone change notification
@@ -517,6 +695,10 @@ pub enum ServerMessage {
         max_mana: u32,
         attack: u32,
         defense: u32,
+        /// Unspent skill points after the level up
+        skill_points: u32,
+        /// Unspent stat points after the level up
+        stat_points: u32,
     },
     
     /// Gold update (when gold changes from loot, trade, or commands)
@@ -524,6 +706,29 @@ pub enum ServerMessage {
         gold: u64,
     },
     
+    /// Death penalty applied to the local player
+    DeathPenalty {
+        /// Experience lost (never drops a level)
+        experience_lost: u32,
+        /// Gear durability after the loss
+        durability: u32,
+        /// Seconds until the player is released to the empire spawn automatically
+        auto_release_in: f32,
+    },
+    
+    /// Durability of the local player's equipped gear
+    DurabilityUpdate {
+        durability: u32,
+        max_durability: u32,
+        /// Gold needed to fully repair
+        repair_cost: u64,
+    },
+    
+    /// Repair failed (only sent to requester)
+    RepairFailed {
+        reason: String,
+    },
+    
     /// Ability used successfully (for visual feedback)
     AbilityUsed {
         /// Player who used the ability
@@ -582,6 +787,203 @@ pub enum ServerMessage {
         /// 8 slots, each containing an optional ability ID
         slots: [Option<u32>; 8],
     },
+    
+    /// Player started casting or channeling an ability (for cast bars)
+    CastStarted {
+        caster_id: u64,
+        ability_id: u32,
+        target_id: Option<u64>,
+        /// Cast or channel duration in seconds
+        duration: f32,
+        /// True if this is a channel (effects pulse while channeling)
+        is_channel: bool,
+    },
+    
+    /// Periodic cast/channel progress update
+    CastProgress {
+        caster_id: u64,
+        ability_id: u32,
+        /// Time elapsed in seconds
+        elapsed: f32,
+        /// Total duration in seconds
+        duration: f32,
+    },
+    
+    /// Cast or channel finished successfully
+    CastCompleted {
+        caster_id: u64,
+        ability_id: u32,
+    },
+    
+    /// Cast or channel was interrupted (movement, stun, damage, cancel)
+    CastInterrupted {
+        caster_id: u64,
+        ability_id: u32,
+        reason: String,
+    },
+    
+    /// Auto-attack started on a target (sent to attacker only)
+    AutoAttackStarted {
+        target_id: u64,
+        /// Seconds between swings at current attack speed
+        swing_interval: f32,
+    },
+    
+    /// Auto-attack stopped (sent to attacker only)
+    AutoAttackStopped {
+        target_id: u64,
+        /// Why it stopped (e.g. "Target died", "Out of range", "Stopped")
+        reason: String,
+    },
+    /// Projectile launched by a ranged ability or bow attack
+    ProjectileSpawn {
+        id: u64,
+        zone_id: u32,
+        owner_id: u64,
+        target_id: Option<u64>,
+        /// Ability that launched it (None = weapon attack)
+        ability_id: Option<u32>,
+        position: [f32; 3],
+        /// Initial velocity in units per second
+        velocity: [f32; 3],
+        /// Follows its target (otherwise flies straight)
+        homing: bool,
+    },
+    /// Projectile stopped (hit its target, an obstacle, or flew out of range)
+    ProjectileImpact {
+        id: u64,
+        /// Entity that was hit (None = obstacle or expired)
+        target_id: Option<u64>,
+        position: [f32; 3],
+    },
+    /// All ability definitions (sent on character select)
+    AbilityDefinitions {
+        abilities: Vec<AbilityDef>,
+    },
+    /// Learned skills and unspent skill points (sent on character select)
+    SkillList {
+        skills: Vec<SkillRank>,
+        skill_points: u32,
+    },
+    /// A skill's rank or the unspent skill points changed
+    SkillUpdate {
+        ability_id: u32,
+        rank: u8,
+        skill_points: u32,
+    },
+    /// Skill learn/upgrade failed (only sent to requester)
+    SkillUpgradeFailed {
+        ability_id: u32,
+        reason: String,
+    },
+    /// Specialization chosen, with stats recalculated for the new growth
+    SpecializationChosen {
+        specialization: Specialization,
+        max_health: u32,
+        max_mana: u32,
+        attack: u32,
+        defense: u32,
+    },
+    /// Specialization choice failed (only sent to requester)
+    SpecializationFailed {
+        reason: String,
+    },
+    /// Primary attributes and the stats derived from them (on select, allocation and reset)
+    AttributesUpdate {
+        attributes: Attributes,
+        /// Unspent stat points
+        stat_points: u32,
+        max_health: u32,
+        max_mana: u32,
+        attack: u32,
+        defense: u32,
+        attack_speed: f32,
+        /// Critical hit chance (0.0 - 1.0)
+        crit_chance: f32,
+        health: u32,
+        mana: u32,
+    },
+    /// Stat allocation or reset failed (only sent to requester)
+    StatAllocationFailed {
+        reason: String,
+    },
+    /// A combat log event involving this player (only sent when streaming is enabled)
+    CombatLogEvent {
+        entry: CombatLogEntry,
+    },
+    /// Current health/mana and combat/resting state (throttled, only sent to the player)
+    ResourceUpdate {
+        health: u32,
+        max_health: u32,
+        mana: u32,
+        max_mana: u32,
+        in_combat: bool,
+        resting: bool,
+    },
+    /// Local player's current movement speed (units per second, before sprinting)
+    /// Sent whenever buffs, slows or equipment change it
+    MovementSpeedUpdate {
+        speed: f32,
+    },
+    /// Boss encounter state (on engage, phase/immunity/enrage changes and throttled health changes)
+    BossUpdate {
+        enemy_id: u64,
+        name: String,
+        /// Current phase (0-based)
+        phase: u8,
+        phase_count: u8,
+        phase_name: String,
+        health: u32,
+        max_health: u32,
+        /// Boss takes no damage
+        immune: bool,
+        enraged: bool,
+    },
+    /// Boss used a scripted ability
+    /// AoE abilities land at `position` within `radius` after `wind_up` seconds (radius 0 = no area)
+    BossAbilityUsed {
+        enemy_id: u64,
+        ability_name: String,
+        position: [f32; 3],
+        radius: f32,
+        wind_up: f32,
+    },
+    /// Boss encounter ended (boss killed, or reset after the group wiped or left the arena)
+    BossEncounterEnded {
+        enemy_id: u64,
+        defeated: bool,
+    },
+    /// Enemy used one of its template abilities (heal, hex, spell)
+    EnemyAbilityUsed {
+        enemy_id: u64,
+        ability_name: String,
+        target_id: Option<u64>,
+    },
+    /// Metin stone appeared (also sent for existing stones on entering a zone)
+    /// Stones are attacked like enemies and share their id space
+    MetinStoneSpawn {
+        id: u64,
+        zone_id: u32,
+        name: String,
+        position: [f32; 3],
+        health: u32,
+        max_health: u32,
+        level: u8,
+    },
+    /// Metin stone health or combat state changed (health changes are throttled)
+    MetinStoneState {
+        id: u64,
+        health: u32,
+        max_health: u32,
+        /// Being attacked and calling enemies to its defense
+        under_attack: bool,
+    },
+    /// Metin stone removed
+    MetinStoneDespawn {
+        id: u64,
+        /// Destroyed by players (false = removed by the server)
+        destroyed: bool,
+    },
 }
 
 // =============================================================================
@@ -645,13 +1047,248 @@ pub enum AnimationState {
     Dead,
 }
 
-/// Enemy type enum
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// Enemy model (stats and behaviour come from the server's enemy templates)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum EnemyType {
     Goblin,
     Skeleton,
     Mutant,
     Wolf,
+    /// Ranged skeleton that keeps its distance
+    SkeletonArcher,
+    /// Goblin caster that heals allies and hexes players
+    GoblinShaman,
+}
+
+impl EnemyType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Goblin => "Goblin",
+            Self::Skeleton => "Skeleton",
+            Self::Mutant => "Mutant",
+            Self::Wolf => "Wolf",
+            Self::SkeletonArcher => "Skeleton Archer",
+            Self::GoblinShaman => "Goblin Shaman",
+        }
+    }
+}
+
+/// Where a dead player is released to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum RespawnType {
+    /// Empire spawn point with full health
+    Town = 0,
+    /// Where the player died, with reduced health
+    DeathSpot = 1,
+}
+
+impl RespawnType {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Town),
+            1 => Some(Self::DeathSpot),
+            _ => None,
+        }
+    }
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Town => "town",
+            Self::DeathSpot => "death spot",
+        }
+    }
+}
+
+/// World weather (sent with TimeSync, some enemies only spawn in certain weather)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[repr(u8)]
+pub enum Weather {
+    #[default]
+    Clear = 0,
+    Rain = 1,
+    Fog = 2,
+    Storm = 3,
+}
+
+impl Weather {
+    pub fn as_u8(&self) -> u8 {
+        *self as u8
+    }
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Clear => "Clear",
+            Self::Rain => "Rain",
+            Self::Fog => "Fog",
+            Self::Storm => "Storm",
+        }
+    }
+}
+
+/// Outcome of an attack roll
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum HitOutcome {
+    Hit = 0,
+    Critical = 1,
+    Miss = 2,
+    Dodge = 3,
+    /// Blocked by a shield (reduced damage)
+    Block = 4,
+    /// Target is immune (no damage)
+    Immune = 5,
+}
+
+impl HitOutcome {
+    pub fn as_u8(&self) -> u8 {
+        *self as u8
+    }
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Hit => "Hit",
+            Self::Critical => "Critical",
+            Self::Miss => "Miss",
+            Self::Dodge => "Dodge",
+            Self::Block => "Block",
+            Self::Immune => "Immune",
+        }
+    }
+    
+    /// Whether the attack was avoided entirely (no damage)
+    pub fn is_avoided(&self) -> bool {
+        matches!(self, Self::Miss | Self::Dodge | Self::Immune)
+    }
+}
+
+/// Kind of event recorded in the combat log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum CombatLogKind {
+    Damage = 0,
+    Heal = 1,
+    Buff = 2,
+    /// Attack missed or was dodged
+    Miss = 3,
+    Death = 4,
+    Experience = 5,
+}
+
+impl CombatLogKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Damage => "Damage",
+            Self::Heal => "Heal",
+            Self::Buff => "Buff",
+            Self::Miss => "Miss",
+            Self::Death => "Death",
+            Self::Experience => "Experience",
+        }
+    }
+    
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Damage" => Some(Self::Damage),
+            "Heal" => Some(Self::Heal),
+            "Buff" => Some(Self::Buff),
+            "Miss" => Some(Self::Miss),
+            "Death" => Some(Self::Death),
+            "Experience" => Some(Self::Experience),
+            _ => None,
+        }
+    }
+}
+
+/// An entity taking part in a combat log event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CombatParticipant {
+    /// Runtime entity ID (0 for entries loaded from history)
+    pub id: u64,
+    pub name: String,
+    pub is_player: bool,
+}
+
+/// How raw damage turned into the damage actually dealt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DamageBreakdown {
+    /// Damage before the attack table and armor
+    pub raw: u32,
+    /// Damage after crit/block was applied
+    pub after_outcome: u32,
+    /// Damage removed by the target's defense
+    pub armor: u32,
+    /// Damage beyond the target's remaining health
+    pub overkill: u32,
+}
+
+/// A single combat log event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CombatLogEntry {
+    /// Unix timestamp in milliseconds
+    pub timestamp_ms: u64,
+    pub kind: CombatLogKind,
+    /// Who caused the event (None for environmental events)
+    pub source: Option<CombatParticipant>,
+    pub target: CombatParticipant,
+    /// Ability that caused the event, if any
+    pub ability_id: Option<u32>,
+    /// Item that caused the event, if any
+    pub item_id: Option<u32>,
+    /// Damage dealt, health restored, buff duration in ms or experience gained
+    pub amount: u32,
+    /// Attack table outcome for damage and miss events
+    pub outcome: Option<HitOutcome>,
+    /// Mitigation breakdown for damage events
+    pub breakdown: Option<DamageBreakdown>,
+}
+
+impl CombatLogEntry {
+    /// One-line human readable description (used by the client log and admin queries)
+    pub fn describe(&self) -> String {
+        let source = self.source.as_ref().map(|s| s.name.as_str()).unwrap_or("Unknown");
+        let target = self.target.name.as_str();
+        
+        let mut text = match self.kind {
+            CombatLogKind::Damage => {
+                let outcome = self.outcome.map(|o| o.name()).unwrap_or("Hit");
+                format!("{} hit {} for {} ({})", source, target, self.amount, outcome)
+            }
+            CombatLogKind::Miss => {
+                let outcome = self.outcome.map(|o| o.name()).unwrap_or("Miss");
+                format!("{} attacked {}: {}", source, target, outcome)
+            }
+            CombatLogKind::Heal => match &self.source {
+                Some(s) if s.name != self.target.name => format!("{} healed {} for {}", s.name, target, self.amount),
+                _ => format!("{} recovered {} health", target, self.amount),
+            },
+            CombatLogKind::Buff => {
+                format!("{} buffed {} for {:.1}s", source, target, self.amount as f32 / 1000.0)
+            }
+            CombatLogKind::Death => match &self.source {
+                Some(s) => format!("{} was killed by {}", target, s.name),
+                None => format!("{} died", target),
+            },
+            CombatLogKind::Experience => {
+                format!("{} gained {} XP from {}", target, self.amount, source)
+            }
+        };
+        
+        if let Some(ability_id) = self.ability_id {
+            text.push_str(&format!(" [ability {}]", ability_id));
+        }
+        if let Some(item_id) = self.item_id {
+            text.push_str(&format!(" [item {}]", item_id));
+        }
+        if let Some(b) = self.breakdown {
+            text.push_str(&format!(
+                " - raw {}, after roll {}, armor -{}, overkill {}",
+                b.raw, b.after_outcome, b.armor, b.overkill
+            ));
+        }
+        
+        text
+    }
 }
 
 /// NPC type enum
@@ -730,3 +1367,61 @@ impl ServerMessage {
         bincode::deserialize(data)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn participant(name: &str) -> CombatParticipant {
+        CombatParticipant { id: 0, name: name.to_string(), is_player: true }
+    }
+
+    fn entry(kind: CombatLogKind, source: Option<&str>, target: &str, amount: u32) -> CombatLogEntry {
+        CombatLogEntry {
+            timestamp_ms: 0,
+            kind,
+            source: source.map(participant),
+            target: participant(target),
+            ability_id: None,
+            item_id: None,
+            amount,
+            outcome: None,
+            breakdown: None,
+        }
+    }
+
+    #[test]
+    fn test_combat_log_kind_names_round_trip() {
+        let kinds = [CombatLogKind::Damage, CombatLogKind::Heal, CombatLogKind::Buff,
+            CombatLogKind::Miss, CombatLogKind::Death, CombatLogKind::Experience];
+        for kind in kinds {
+            assert_eq!(CombatLogKind::from_name(kind.name()), Some(kind));
+        }
+        assert_eq!(CombatLogKind::from_name("damage"), None);
+    }
+
+    #[test]
+    fn test_describe_combat_log_entries() {
+        let mut hit = entry(CombatLogKind::Damage, Some("Aria"), "Wolf", 42);
+        hit.outcome = Some(HitOutcome::Critical);
+        hit.ability_id = Some(21);
+        hit.breakdown = Some(DamageBreakdown { raw: 30, after_outcome: 60, armor: 18, overkill: 2 });
+        assert_eq!(hit.describe(), "Aria hit Wolf for 42 (Critical) [ability 21] - raw 30, after roll 60, armor -18, overkill 2");
+
+        let mut miss = entry(CombatLogKind::Miss, Some("Wolf"), "Aria", 0);
+        miss.outcome = Some(HitOutcome::Dodge);
+        assert_eq!(miss.describe(), "Wolf attacked Aria: Dodge");
+
+        // Healing yourself (or regenerating) reads as recovering
+        assert_eq!(entry(CombatLogKind::Heal, Some("Aria"), "Aria", 15).describe(), "Aria recovered 15 health");
+        assert_eq!(entry(CombatLogKind::Heal, Some("Bo"), "Aria", 15).describe(), "Bo healed Aria for 15");
+        let mut potion = entry(CombatLogKind::Heal, None, "Aria", 50);
+        potion.item_id = Some(1);
+        assert_eq!(potion.describe(), "Aria recovered 50 health [item 1]");
+
+        assert_eq!(entry(CombatLogKind::Buff, Some("Bo"), "Aria", 15000).describe(), "Bo buffed Aria for 15.0s");
+        assert_eq!(entry(CombatLogKind::Death, None, "Aria", 0).describe(), "Aria died");
+        assert_eq!(entry(CombatLogKind::Death, Some("Wolf"), "Aria", 0).describe(), "Aria was killed by Wolf");
+        assert_eq!(entry(CombatLogKind::Experience, Some("Wolf"), "Aria", 30).describe(), "Aria gained 30 XP from Wolf");
+    }
+}